@@ -32,6 +32,11 @@ pub(crate) fn impl_ClientStateCommon(
         enum_variants.iter(),
         quote! {validate_proof_height(cs, proof_height)},
     );
+    let header_height_impl = delegate_call_in_match(
+        client_state_enum_name,
+        enum_variants.iter(),
+        quote! {header_height(cs, header)},
+    );
     let verify_upgrade_client_impl = delegate_call_in_match(
         client_state_enum_name,
         enum_variants.iter(),
@@ -85,6 +90,12 @@ pub(crate) fn impl_ClientStateCommon(
                 }
             }
 
+            fn header_height(&self, header: #Any) -> core::result::Result<#Height, #ClientError> {
+                match self {
+                    #(#header_height_impl),*
+                }
+            }
+
             fn verify_upgrade_client(
                 &self,
                 upgraded_client_state: #Any,
@@ -42,6 +42,13 @@ pub(crate) fn impl_ClientStateExecution(
         quote! { update_state_on_upgrade(cs, ctx, client_id, upgraded_client_state, upgraded_consensus_state) },
     );
 
+    let update_state_on_verified_proofs_impl = delegate_call_in_match(
+        client_state_enum_name,
+        enum_variants.iter(),
+        opts,
+        quote! { update_state_on_verified_proofs(cs, ctx, client_id) },
+    );
+
     let HostClientState = client_state_enum_name;
     let ClientExecutionContext = &opts.client_execution_context;
 
@@ -99,6 +106,16 @@ pub(crate) fn impl_ClientStateExecution(
                     #(#update_state_with_upgrade_client_impl),*
                 }
             }
+
+            fn update_state_on_verified_proofs(
+                &self,
+                ctx: &mut #ClientExecutionContext,
+                client_id: &#ClientId,
+            ) -> core::result::Result<(), #ClientError> {
+                match self {
+                    #(#update_state_on_verified_proofs_impl),*
+                }
+            }
         }
 
     }
@@ -37,12 +37,30 @@ pub(crate) fn impl_ClientStateValidation(
         quote! { status(cs, ctx, client_id) },
     );
 
+    let verify_membership_with_context_impl = delegate_call_in_match(
+        client_state_enum_name,
+        enum_variants.iter(),
+        opts,
+        quote! { verify_membership_with_context(cs, ctx, prefix, proof, root, path, value) },
+    );
+
+    let verify_non_membership_with_context_impl = delegate_call_in_match(
+        client_state_enum_name,
+        enum_variants.iter(),
+        opts,
+        quote! { verify_non_membership_with_context(cs, ctx, prefix, proof, root, path) },
+    );
+
     let HostClientState = client_state_enum_name;
     let ClientValidationContext = &opts.client_validation_context;
 
     let Any = Imports::Any();
     let ClientId = Imports::ClientId();
     let ClientError = Imports::ClientError();
+    let CommitmentRoot = Imports::CommitmentRoot();
+    let CommitmentPrefix = Imports::CommitmentPrefix();
+    let CommitmentProofBytes = Imports::CommitmentProofBytes();
+    let Path = Imports::Path();
     let ClientStateValidation = Imports::ClientStateValidation();
     let Status = Imports::Status();
     let UpdateKind = Imports::UpdateKind();
@@ -83,6 +101,33 @@ pub(crate) fn impl_ClientStateValidation(
                 }
 
             }
+
+            fn verify_membership_with_context(
+                &self,
+                ctx: &#ClientValidationContext,
+                prefix: &#CommitmentPrefix,
+                proof: &#CommitmentProofBytes,
+                root: &#CommitmentRoot,
+                path: #Path,
+                value: Vec<u8>,
+            ) -> core::result::Result<(), #ClientError> {
+                match self {
+                    #(#verify_membership_with_context_impl),*
+                }
+            }
+
+            fn verify_non_membership_with_context(
+                &self,
+                ctx: &#ClientValidationContext,
+                prefix: &#CommitmentPrefix,
+                proof: &#CommitmentProofBytes,
+                root: &#CommitmentRoot,
+                path: #Path,
+            ) -> core::result::Result<(), #ClientError> {
+                match self {
+                    #(#verify_non_membership_with_context_impl),*
+                }
+            }
         }
 
     }
@@ -0,0 +1,64 @@
+//! Defines the interchain accounts error type
+
+use displaydoc::Display;
+
+use crate::core::ics04_channel::acknowledgement::StatusValue;
+use crate::core::ics04_channel::channel::Order;
+use crate::core::ics04_channel::error::{ChannelError, PacketError};
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::PortId;
+use crate::prelude::*;
+
+#[derive(Display, Debug)]
+pub enum InterchainAccountError {
+    /// failed to decode the packet data
+    PacketDataDeserialization,
+    /// failed to decode the transaction of the packet data: `{0}`
+    CosmosTxDeserialization(prost::DecodeError),
+    /// unsupported packet data type `{packet_data_type}`
+    UnsupportedPacketDataType { packet_data_type: String },
+    /// executing interchain account transactions is not supported
+    ExecutionNotSupported,
+    /// channel handshakes can only be initiated by controller chains
+    ChannelOpenInitNotAllowed,
+    /// expected an ordered channel, got `{order}`
+    ChannelNotOrdered { order: Order },
+    /// counterparty port `{port_id}` is not an interchain accounts controller port
+    InvalidControllerPort { port_id: PortId },
+    /// unsupported version `{version}`
+    UnsupportedVersion { version: Version },
+    /// host chains do not send packets
+    PacketsNotSent,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InterchainAccountError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            Self::CosmosTxDeserialization(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<InterchainAccountError> for StatusValue {
+    fn from(err: InterchainAccountError) -> Self {
+        StatusValue::new(err.to_string()).expect("error message must not be empty")
+    }
+}
+
+impl From<InterchainAccountError> for ChannelError {
+    fn from(err: InterchainAccountError) -> Self {
+        ChannelError::AppModule {
+            description: err.to_string(),
+        }
+    }
+}
+
+impl From<InterchainAccountError> for PacketError {
+    fn from(err: InterchainAccountError) -> Self {
+        PacketError::AppModule {
+            description: err.to_string(),
+        }
+    }
+}
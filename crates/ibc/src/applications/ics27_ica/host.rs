@@ -0,0 +1,267 @@
+//! Callbacks of the interchain accounts application on host chains
+
+use crate::prelude::*;
+
+use super::error::InterchainAccountError;
+use super::packet::InterchainAccountPacketData;
+use super::{CONTROLLER_PORT_PREFIX, VERSION};
+use crate::core::ics04_channel::acknowledgement::{Acknowledgement, AcknowledgementStatus};
+use crate::core::ics04_channel::channel::{Counterparty, Order};
+use crate::core::ics04_channel::error::{ChannelError, PacketError};
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::router::{Module, ModuleExtras};
+use crate::signer::Signer;
+
+/// The interchain accounts application of a host chain, to be bound to the
+/// [`HOST_PORT_ID_STR`](super::HOST_PORT_ID_STR) port.
+///
+/// Host chains neither initiate channel handshakes nor send packets, so the
+/// corresponding callbacks fail.
+#[derive(Clone, Debug, Default)]
+pub struct InterchainAccountHostModule;
+
+impl InterchainAccountHostModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Module for InterchainAccountHostModule {
+    fn on_chan_open_init_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Err(InterchainAccountError::ChannelOpenInitNotAllowed.into())
+    }
+
+    fn on_chan_open_init_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Err(InterchainAccountError::ChannelOpenInitNotAllowed.into())
+    }
+
+    fn on_chan_open_try_validate(
+        &self,
+        order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        on_chan_open_try_validate(order, counterparty, counterparty_version)?;
+
+        Ok(counterparty_version.clone())
+    }
+
+    fn on_chan_open_try_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Ok((ModuleExtras::empty(), counterparty_version.clone()))
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        on_recv_packet_execute(packet)
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Err(InterchainAccountError::PacketsNotSent.into())
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (
+            ModuleExtras::empty(),
+            Err(InterchainAccountError::PacketsNotSent.into()),
+        )
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Err(InterchainAccountError::PacketsNotSent.into())
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (
+            ModuleExtras::empty(),
+            Err(InterchainAccountError::PacketsNotSent.into()),
+        )
+    }
+}
+
+/// Checks that a controller chain opens an ordered channel from one of its
+/// controller ports, with the supported version.
+pub fn on_chan_open_try_validate(
+    order: Order,
+    counterparty: &Counterparty,
+    counterparty_version: &Version,
+) -> Result<(), InterchainAccountError> {
+    if order != Order::Ordered {
+        return Err(InterchainAccountError::ChannelNotOrdered { order });
+    }
+
+    if !counterparty
+        .port_id()
+        .as_str()
+        .starts_with(CONTROLLER_PORT_PREFIX)
+    {
+        return Err(InterchainAccountError::InvalidControllerPort {
+            port_id: counterparty.port_id().clone(),
+        });
+    }
+
+    if counterparty_version != &Version::new(VERSION.to_string()) {
+        return Err(InterchainAccountError::UnsupportedVersion {
+            version: counterparty_version.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Decodes the JSON packet data that a controller chain sent to its
+/// interchain account.
+///
+/// Executing the decoded transaction is not supported yet, so every packet is
+/// acknowledged with an error. Packets that fail to decode are acknowledged
+/// with the decoding error.
+pub fn on_recv_packet_execute(packet: &Packet) -> (ModuleExtras, Acknowledgement) {
+    let err = match InterchainAccountPacketData::from_json_bytes(&packet.data) {
+        Ok(_packet_data) => InterchainAccountError::ExecutionNotSupported,
+        Err(err) => err,
+    };
+    let ack = AcknowledgementStatus::error(err.into());
+
+    (ModuleExtras::empty(), ack.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ibc_proto::google::protobuf::Any;
+
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::test_utils::get_dummy_account_id;
+
+    #[test]
+    fn on_recv_packet_acknowledges_with_error() {
+        let ack_of = |data: Vec<u8>| {
+            let mut packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+            packet.data = data;
+            on_recv_packet_execute(&packet).1
+        };
+        let error_ack = |err: InterchainAccountError| -> Acknowledgement {
+            AcknowledgementStatus::error(err.into()).into()
+        };
+
+        let msg = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![1, 2, 3],
+        };
+        let packet_data = InterchainAccountPacketData::new(vec![msg], String::new());
+
+        assert_eq!(
+            ack_of(packet_data.to_json_bytes()),
+            error_ack(InterchainAccountError::ExecutionNotSupported)
+        );
+        assert_eq!(
+            ack_of(b"not json".to_vec()),
+            error_ack(InterchainAccountError::PacketDataDeserialization)
+        );
+    }
+
+    #[test]
+    fn host_module_delegates_recv_packet() {
+        let mut module = InterchainAccountHostModule::new();
+        let packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+
+        let (_, ack) = module.on_recv_packet_execute(&packet, &get_dummy_account_id());
+
+        assert_eq!(ack, on_recv_packet_execute(&packet).1);
+    }
+
+    #[test]
+    fn host_module_channel_handshake() {
+        let module = InterchainAccountHostModule::new();
+        let connection_hops = [ConnectionId::default()];
+        let port_id: PortId = super::super::HOST_PORT_ID_STR.parse().unwrap();
+        let controller_port_id: PortId = format!("{CONTROLLER_PORT_PREFIX}owner").parse().unwrap();
+        let version = Version::new(VERSION.to_string());
+        let open_try = |order, counterparty: &Counterparty, version: &Version| {
+            module.on_chan_open_try_validate(
+                order,
+                &connection_hops,
+                &port_id,
+                &ChannelId::default(),
+                counterparty,
+                version,
+            )
+        };
+
+        let counterparty = Counterparty::new(controller_port_id, Some(ChannelId::default()));
+        assert_eq!(
+            open_try(Order::Ordered, &counterparty, &version).unwrap(),
+            version
+        );
+        assert!(open_try(Order::Unordered, &counterparty, &version).is_err());
+        assert!(open_try(
+            Order::Ordered,
+            &counterparty,
+            &Version::new("ics20-1".to_string())
+        )
+        .is_err());
+
+        let counterparty = Counterparty::new(PortId::transfer(), Some(ChannelId::default()));
+        assert!(open_try(Order::Ordered, &counterparty, &version).is_err());
+
+        assert!(module
+            .on_chan_open_init_validate(
+                Order::Ordered,
+                &connection_hops,
+                &port_id,
+                &ChannelId::default(),
+                &counterparty,
+                &version,
+            )
+            .is_err());
+    }
+}
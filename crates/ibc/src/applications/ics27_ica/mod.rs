@@ -0,0 +1,16 @@
+//! Implementation of the [interchain accounts](https://github.com/cosmos/ibc/blob/main/spec/app/ics-027-interchain-accounts/README.md)
+//! (ICS-27) packet data, along with the host chain callbacks.
+
+pub mod error;
+pub mod host;
+pub mod packet;
+
+/// ICS27 application current version.
+pub const VERSION: &str = "ics27-1";
+
+/// The port identifier that ICS27 host chains bind with.
+pub const HOST_PORT_ID_STR: &str = "icahost";
+
+/// The prefix of the port identifiers that ICS27 controller chains bind
+/// with, followed by the address of the account owner.
+pub const CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
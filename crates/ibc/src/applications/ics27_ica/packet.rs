@@ -0,0 +1,194 @@
+//! Contains the `InterchainAccountPacketData` type that defines the structure
+//! of interchain accounts' packet bytes
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::interchain_accounts::v1::{
+    CosmosTx as RawCosmosTx, InterchainAccountPacketData as RawInterchainAccountPacketData,
+    Type as RawType,
+};
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use subtle_encoding::base64;
+
+use super::error::InterchainAccountError;
+
+/// The messages that an interchain account executes on the host chain as a
+/// single transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CosmosTx {
+    pub messages: Vec<Any>,
+}
+
+impl Protobuf<RawCosmosTx> for CosmosTx {}
+
+impl From<RawCosmosTx> for CosmosTx {
+    fn from(raw_tx: RawCosmosTx) -> Self {
+        Self {
+            messages: raw_tx.messages,
+        }
+    }
+}
+
+impl From<CosmosTx> for RawCosmosTx {
+    fn from(tx: CosmosTx) -> Self {
+        Self {
+            messages: tx.messages,
+        }
+    }
+}
+
+/// Defines the structure of interchain accounts' packet bytes, which carry a
+/// transaction to execute from the controller chain to the host chain.
+///
+/// Only the `TYPE_EXECUTE_TX` packet type is defined by the protocol. The
+/// transaction is protobuf encoded in the `data` field of the raw packet data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterchainAccountPacketData {
+    pub tx: CosmosTx,
+    pub memo: String,
+}
+
+impl InterchainAccountPacketData {
+    /// Builds the packet data that a controller chain sends to have its
+    /// interchain account execute the given messages.
+    pub fn new(messages: Vec<Any>, memo: String) -> Self {
+        Self {
+            tx: CosmosTx { messages },
+            memo,
+        }
+    }
+
+    /// Encodes the packet data as JSON, with sorted keys and all fields
+    /// present, as ibc-go controller chains do.
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        let raw = RawInterchainAccountPacketData::from(self.clone());
+        let json = JsonPacketData {
+            data: String::from_utf8(base64::encode(raw.data))
+                .expect("base64 encoding is always valid UTF-8"),
+            memo: raw.memo,
+            r#type: RawType::ExecuteTx.as_str_name().to_string(),
+        };
+        serde_json::to_vec(&json).expect("packet data serialization never fails")
+    }
+
+    /// Decodes packet data encoded as JSON by a controller chain.
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, InterchainAccountError> {
+        let json: JsonPacketData = serde_json::from_slice(bytes)
+            .map_err(|_| InterchainAccountError::PacketDataDeserialization)?;
+        let packet_data_type = RawType::from_str_name(&json.r#type).ok_or(
+            InterchainAccountError::UnsupportedPacketDataType {
+                packet_data_type: json.r#type,
+            },
+        )?;
+        let data = base64::decode(json.data)
+            .map_err(|_| InterchainAccountError::PacketDataDeserialization)?;
+
+        RawInterchainAccountPacketData {
+            r#type: packet_data_type as i32,
+            data,
+            memo: json.memo,
+        }
+        .try_into()
+    }
+}
+
+impl Protobuf<RawInterchainAccountPacketData> for InterchainAccountPacketData {}
+
+impl TryFrom<RawInterchainAccountPacketData> for InterchainAccountPacketData {
+    type Error = InterchainAccountError;
+
+    fn try_from(raw_pkt_data: RawInterchainAccountPacketData) -> Result<Self, Self::Error> {
+        match RawType::from_i32(raw_pkt_data.r#type) {
+            Some(RawType::ExecuteTx) => {}
+            Some(packet_data_type) => {
+                return Err(InterchainAccountError::UnsupportedPacketDataType {
+                    packet_data_type: packet_data_type.as_str_name().to_string(),
+                })
+            }
+            None => {
+                return Err(InterchainAccountError::UnsupportedPacketDataType {
+                    packet_data_type: raw_pkt_data.r#type.to_string(),
+                })
+            }
+        }
+        let tx = RawCosmosTx::decode(raw_pkt_data.data.as_slice())
+            .map_err(InterchainAccountError::CosmosTxDeserialization)?;
+
+        Ok(Self {
+            tx: tx.into(),
+            memo: raw_pkt_data.memo,
+        })
+    }
+}
+
+impl From<InterchainAccountPacketData> for RawInterchainAccountPacketData {
+    fn from(pkt_data: InterchainAccountPacketData) -> Self {
+        Self {
+            r#type: RawType::ExecuteTx as i32,
+            data: RawCosmosTx::from(pkt_data.tx).encode_to_vec(),
+            memo: pkt_data.memo,
+        }
+    }
+}
+
+/// The JSON representation of the packet data, with the fields in sorted
+/// order and the transaction bytes base64 encoded.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct JsonPacketData {
+    data: String,
+    memo: String,
+    r#type: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_packet_data() -> InterchainAccountPacketData {
+        let msg = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![1, 2, 3],
+        };
+        InterchainAccountPacketData::new(vec![msg], "memo".to_string())
+    }
+
+    #[test]
+    fn packet_data_json_roundtrip() {
+        let packet_data = dummy_packet_data();
+
+        let json = packet_data.to_json_bytes();
+        assert_eq!(
+            core::str::from_utf8(&json).unwrap(),
+            r#"{"data":"CiMKHC9jb3Ntb3MuYmFuay52MWJldGExLk1zZ1NlbmQSAwECAw==","memo":"memo","type":"TYPE_EXECUTE_TX"}"#
+        );
+        assert_eq!(
+            InterchainAccountPacketData::from_json_bytes(&json).unwrap(),
+            packet_data
+        );
+    }
+
+    #[test]
+    fn packet_data_proto_roundtrip() {
+        let packet_data = dummy_packet_data();
+
+        let bytes = packet_data.clone().encode_vec();
+        let decoded =
+            <InterchainAccountPacketData as Protobuf<RawInterchainAccountPacketData>>::decode_vec(
+                &bytes,
+            )
+            .unwrap();
+        assert_eq!(decoded, packet_data);
+    }
+
+    #[test]
+    fn packet_data_unspecified_type() {
+        let json = br#"{"data":"","memo":"","type":"TYPE_UNSPECIFIED"}"#;
+
+        assert!(matches!(
+            InterchainAccountPacketData::from_json_bytes(json),
+            Err(InterchainAccountError::UnsupportedPacketDataType { .. })
+        ));
+    }
+}
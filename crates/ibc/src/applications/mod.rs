@@ -1,4 +1,6 @@
 //! Implementation of IBC applications
 
+#[cfg(feature = "serde")]
+pub mod ics27_ica;
 #[cfg(feature = "serde")]
 pub mod transfer;
@@ -0,0 +1,217 @@
+//! Escrow-conservation invariant for the token transfer module.
+//!
+//! Every token escrowed on this chain is backed by a voucher minted on a
+//! counterparty chain, so for every denomination the two totals must match.
+
+use alloc::collections::BTreeMap;
+
+use super::amount::Amount;
+use super::coin::PrefixedCoin;
+use super::context::TokenTransferValidationContext;
+use super::denom::PrefixedDenom;
+use super::error::TokenTransferError;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+
+/// Read access to the local state that the conservation check compares, on
+/// top of the escrow balances tracked by the transfer module.
+pub trait EscrowConservationContext: TokenTransferValidationContext {
+    /// Returns every port and channel combination whose escrow account holds
+    /// tokens, or has an escrow balance stored.
+    fn escrow_channels(&self) -> Vec<(PortId, ChannelId)>;
+
+    /// Returns the coins held by `account`.
+    fn account_coins(&self, account: &Self::AccountId) -> Vec<PrefixedCoin>;
+}
+
+/// A denomination whose escrowed total differs from its voucher total.
+///
+/// A total is `None` if it overflows, in which case it is always reported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Imbalance {
+    pub denom: PrefixedDenom,
+    pub escrowed: Option<Amount>,
+    pub vouchers: Option<Amount>,
+}
+
+/// Checks that, for every denomination, the total amount held by the escrow
+/// accounts of this chain equals the total amount of vouchers minted on
+/// counterparty chains, as recorded by the escrow balances (i.e. tokens sent
+/// out minus tokens refunded or received back).
+///
+/// Fails with [`TokenTransferError::EscrowImbalance`], listing every
+/// denomination for which the totals differ, ordered by denomination.
+pub fn check_conservation<Ctx>(ctx: &Ctx) -> Result<(), TokenTransferError>
+where
+    Ctx: EscrowConservationContext,
+{
+    let zero = Some(Amount::from(0u64));
+    let mut totals: BTreeMap<PrefixedDenom, (Option<Amount>, Option<Amount>)> = BTreeMap::new();
+
+    for (port_id, channel_id) in ctx.escrow_channels() {
+        let escrow_account = ctx.get_escrow_account(&port_id, &channel_id)?;
+
+        let mut denoms = ctx.escrowed_denoms(&port_id, &channel_id);
+        for coin in ctx.account_coins(&escrow_account) {
+            let (escrowed, _) = totals.entry(coin.denom.clone()).or_insert((zero, zero));
+            *escrowed = escrowed.and_then(|total| total.checked_add(coin.amount));
+            denoms.push(coin.denom);
+        }
+
+        denoms.sort();
+        denoms.dedup();
+        for denom in denoms {
+            let balance = ctx.get_escrow_balance(&port_id, &channel_id, &denom)?;
+            let (_, vouchers) = totals.entry(denom).or_insert((zero, zero));
+            *vouchers = vouchers.and_then(|total| total.checked_add(balance));
+        }
+    }
+
+    let imbalances: Vec<Imbalance> = totals
+        .into_iter()
+        .filter(|(_, (escrowed, vouchers))| escrowed.is_none() || escrowed != vouchers)
+        .map(|(denom, (escrowed, vouchers))| Imbalance {
+            denom,
+            escrowed,
+            vouchers,
+        })
+        .collect();
+
+    if imbalances.is_empty() {
+        Ok(())
+    } else {
+        Err(TokenTransferError::EscrowImbalance { imbalances })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::applications::transfer::context::TokenTransferExecutionContext;
+    use crate::applications::transfer::msgs::transfer::MsgTransfer;
+    use crate::applications::transfer::send_transfer;
+    use crate::core::ics02_client::height::Height;
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::core::timestamp::ZERO_DURATION;
+    use crate::mock::context::MockContext;
+    use crate::test_utils::{get_dummy_account_id, DummyTransferModule};
+
+    fn context() -> MockContext {
+        let chan_end_on_a = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::new("ics20-1".to_string()),
+        )
+        .unwrap();
+
+        let conn_end_on_a = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        MockContext::default()
+            .with_client(&ClientId::default(), Height::new(0, 5).unwrap())
+            .with_connection(ConnectionId::default(), conn_end_on_a)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end_on_a)
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into())
+    }
+
+    /// Escrows the tokens of a dummy transfer, returning them.
+    fn transfer(token_ctx: &mut DummyTransferModule) -> PrefixedCoin {
+        let msg = MsgTransfer::new_dummy(Height::new(0, 10).unwrap().into(), None);
+        let token = msg.packet_data.token.clone();
+
+        send_transfer(&mut context(), token_ctx, msg).unwrap();
+
+        token
+    }
+
+    #[test]
+    fn conservation_holds_after_transfer() {
+        let mut token_ctx = DummyTransferModule::new();
+        let token = transfer(&mut token_ctx);
+
+        assert_eq!(
+            token_ctx
+                .get_escrow_balance(&PortId::default(), &ChannelId::default(), &token.denom)
+                .unwrap(),
+            token.amount
+        );
+        assert!(check_conservation(&token_ctx).is_ok());
+    }
+
+    #[test]
+    fn conservation_flags_untracked_escrow() {
+        let mut token_ctx = DummyTransferModule::new();
+        let token = transfer(&mut token_ctx);
+
+        // tokens sent to the escrow account outside of a transfer
+        let escrow_account = token_ctx
+            .get_escrow_account(&PortId::default(), &ChannelId::default())
+            .unwrap();
+        let extra = PrefixedCoin {
+            denom: token.denom.clone(),
+            amount: 5u64.into(),
+        };
+        token_ctx
+            .send_coins_execute(&get_dummy_account_id(), &escrow_account, &extra)
+            .unwrap();
+
+        let res = check_conservation(&token_ctx);
+
+        let escrowed = token.amount.checked_add(extra.amount);
+        assert!(
+            matches!(
+                &res,
+                Err(TokenTransferError::EscrowImbalance { imbalances })
+                    if imbalances == &vec![Imbalance {
+                        denom: token.denom.clone(),
+                        escrowed,
+                        vouchers: Some(token.amount),
+                    }]
+            ),
+            "unexpected result: {res:?}"
+        );
+    }
+
+    #[test]
+    fn conservation_flags_overflow() {
+        let denom: PrefixedDenom = "uatom".parse().unwrap();
+        let max = Amount::from([u64::MAX; 4]);
+        let token_ctx = DummyTransferModule::new()
+            .with_escrow_balance(PortId::transfer(), ChannelId::new(0), denom.clone(), max)
+            .with_escrow_balance(PortId::transfer(), ChannelId::new(1), denom.clone(), max);
+
+        let res = check_conservation(&token_ctx);
+
+        assert!(
+            matches!(
+                &res,
+                Err(TokenTransferError::EscrowImbalance { imbalances })
+                    if imbalances == &vec![Imbalance {
+                        denom,
+                        escrowed: Some(0u64.into()),
+                        vouchers: None,
+                    }]
+            ),
+            "unexpected result: {res:?}"
+        );
+    }
+}
@@ -2,16 +2,22 @@
 use crate::prelude::*;
 
 use sha2::{Digest, Sha256};
+use subtle_encoding::bech32;
 
 use super::ack_success_b64;
 use super::error::TokenTransferError;
 use crate::applications::transfer::events::{AckEvent, AckStatusEvent, RecvEvent, TimeoutEvent};
-use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::is_sender_chain_source;
+use crate::applications::transfer::packet::{
+    decode_and_validate_transfer, PacketData, PacketDataEncoding,
+};
 use crate::applications::transfer::relay::refund_packet_token_execute;
 use crate::applications::transfer::relay::{
     on_recv_packet::process_recv_packet_execute, refund_packet_token_validate,
 };
-use crate::applications::transfer::{PrefixedCoin, PrefixedDenom, VERSION};
+use crate::applications::transfer::{
+    Amount, DenomMetadata, Memo, PrefixedCoin, PrefixedDenom, DEFAULT_MAX_MEMO_BYTES, VERSION,
+};
 use crate::core::ics04_channel::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::acknowledgement::AcknowledgementStatus;
 use crate::core::ics04_channel::channel::{Counterparty, Order};
@@ -19,7 +25,6 @@ use crate::core::ics04_channel::packet::Packet;
 use crate::core::ics04_channel::Version;
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::core::router::ModuleExtras;
-use crate::core::ContextError;
 use crate::signer::Signer;
 
 /// Methods required in token transfer validation, to be implemented by the host
@@ -36,6 +41,24 @@ pub trait TokenTransferValidationContext {
         channel_id: &ChannelId,
     ) -> Result<Self::AccountId, TokenTransferError>;
 
+    /// Returns the amount of `denom` held in escrow for the given port and
+    /// channel combination, i.e. sent out and not yet refunded or received
+    /// back. Returns zero if nothing was ever escrowed, which includes funds
+    /// escrowed before the balances were tracked unless they were seeded with
+    /// [`TokenTransferExecutionContext::seed_escrow_balances`].
+    fn get_escrow_balance(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        denom: &PrefixedDenom,
+    ) -> Result<Amount, TokenTransferError>;
+
+    /// Returns the display metadata stored for `denom`, if any.
+    fn get_denom_metadata(
+        &self,
+        denom: &PrefixedDenom,
+    ) -> Result<Option<DenomMetadata>, TokenTransferError>;
+
     /// Returns Ok() if the host chain supports sending coins.
     fn can_send_coins(&self) -> Result<(), TokenTransferError>;
 
@@ -43,6 +66,10 @@ pub trait TokenTransferValidationContext {
     fn can_receive_coins(&self) -> Result<(), TokenTransferError>;
 
     /// Validates the sender and receiver accounts and the coin inputs
+    ///
+    /// Should return [`TokenTransferError::InsufficientFunds`] if the sender
+    /// does not hold `coin`, and [`TokenTransferError::ModuleNotReady`] if
+    /// the accounts cannot be checked yet.
     fn send_coins_validate(
         &self,
         from_account: &Self::AccountId,
@@ -58,6 +85,9 @@ pub trait TokenTransferValidationContext {
     ) -> Result<(), TokenTransferError>;
 
     /// Validates the sender account and the coin input
+    ///
+    /// See [`TokenTransferValidationContext::send_coins_validate`] for the
+    /// errors to return.
     fn burn_coins_validate(
         &self,
         account: &Self::AccountId,
@@ -69,6 +99,69 @@ pub trait TokenTransferValidationContext {
     fn denom_hash_string(&self, _denom: &PrefixedDenom) -> Option<String> {
         None
     }
+
+    /// Returns the denomination trace registered under the given hash, if any.
+    /// Implement only if the host chain supports hashed denominations.
+    fn denom_trace(&self, _denom_hash: &str) -> Option<PrefixedDenom> {
+        None
+    }
+
+    /// Returns every denomination trace registered by the host.
+    /// Implement only if the host chain supports hashed denominations.
+    fn denom_traces(&self) -> Vec<PrefixedDenom> {
+        Vec::new()
+    }
+
+    /// Returns every denomination with an escrow balance stored for the given
+    /// port and channel combination. Implement only if the host renumbers
+    /// channels with [`TokenTransferExecutionContext::remap_channel_in_denom_traces`].
+    fn escrowed_denoms(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Vec<PrefixedDenom> {
+        Vec::new()
+    }
+
+    /// Returns the channel versions supported by the transfer module, in
+    /// order of preference. Defaults to `ics20-1` only.
+    fn supported_versions(&self) -> Vec<Version> {
+        vec![Version::new(VERSION.to_string())]
+    }
+
+    /// Returns the version negotiated for the given channel.
+    fn channel_version(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Version, TokenTransferError>;
+
+    /// Returns the encoding of the packet data sent and received on the given
+    /// channel. Defaults to the encoding of the version of the channel, as
+    /// resolved by [`PacketDataEncoding::from_version`].
+    fn packet_data_encoding(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<PacketDataEncoding, TokenTransferError> {
+        PacketDataEncoding::from_version(&self.channel_version(port_id, channel_id)?)
+    }
+
+    /// Returns the maximum size in bytes of the memo of sent and received
+    /// transfers. Memos are otherwise passed through unchanged, for the use of
+    /// middleware such as packet forwarding and IBC hooks.
+    fn max_memo_bytes(&self) -> usize {
+        DEFAULT_MAX_MEMO_BYTES
+    }
+}
+
+/// Checks that `memo` does not exceed the host's [`max_memo_bytes`](TokenTransferValidationContext::max_memo_bytes).
+pub(crate) fn validate_memo(
+    ctx: &impl TokenTransferValidationContext,
+    memo: &Memo,
+) -> Result<(), TokenTransferError> {
+    let size = memo.as_ref().len();
+    let max = ctx.max_memo_bytes();
+    if size > max {
+        return Err(TokenTransferError::MemoTooLarge { size, max });
+    }
+    Ok(())
 }
 
 /// Methods required in token transfer execution, to be implemented by the host
@@ -81,6 +174,20 @@ pub trait TokenTransferExecutionContext: TokenTransferValidationContext {
         coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError>;
 
+    /// Stores the amount of `denom` held in escrow for the given port and
+    /// channel combination
+    fn store_escrow_balance(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        denom: &PrefixedDenom,
+        amount: Amount,
+    ) -> Result<(), TokenTransferError>;
+
+    /// Stores the display metadata of `metadata.base`. Called when a voucher
+    /// is received for the first time.
+    fn store_denom_metadata(&mut self, metadata: DenomMetadata) -> Result<(), TokenTransferError>;
+
     /// This function to enable minting ibc tokens to a user account
     fn mint_coins_execute(
         &mut self,
@@ -94,6 +201,105 @@ pub trait TokenTransferExecutionContext: TokenTransferValidationContext {
         account: &Self::AccountId,
         coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError>;
+
+    /// Registers the denomination trace `denom` under its hash.
+    /// Implement only if the host chain supports hashed denominations.
+    fn store_denom_trace(&mut self, _denom: PrefixedDenom) -> Result<(), TokenTransferError> {
+        Ok(())
+    }
+
+    /// Removes the denomination trace `denom` registered under its hash.
+    /// Implement only if the host chain supports hashed denominations.
+    fn delete_denom_trace(&mut self, _denom: &PrefixedDenom) -> Result<(), TokenTransferError> {
+        Ok(())
+    }
+
+    /// Moves every account balance held in `old_denom` to `new_denom`.
+    /// Implement only if the host chain supports hashed denominations.
+    fn migrate_denom_balances(
+        &mut self,
+        _old_denom: &PrefixedDenom,
+        _new_denom: &PrefixedDenom,
+    ) -> Result<(), TokenTransferError> {
+        Ok(())
+    }
+
+    /// Renumbers the local channel `old_channel` on `port_id` to
+    /// `new_channel`, returning the number of rewritten denomination traces.
+    /// Meant for channel-migration tooling.
+    ///
+    /// Every stored trace whose leading prefix references the old channel
+    /// end is registered under its new hash, and the voucher balances held in
+    /// it are moved along with [`Self::migrate_denom_balances`]. The escrow
+    /// balances of the old channel, as listed by
+    /// [`TokenTransferValidationContext::escrowed_denoms`], are moved to the
+    /// new channel together with the coins held by its escrow account.
+    fn remap_channel_in_denom_traces(
+        &mut self,
+        port_id: &PortId,
+        old_channel: &ChannelId,
+        new_channel: &ChannelId,
+    ) -> Result<usize, TokenTransferError> {
+        let mut remapped = 0;
+        for old_denom in self.denom_traces() {
+            let mut new_denom = old_denom.clone();
+            if new_denom.remap_trace_channel(port_id, old_channel, new_channel) {
+                self.migrate_denom_balances(&old_denom, &new_denom)?;
+                self.delete_denom_trace(&old_denom)?;
+                self.store_denom_trace(new_denom)?;
+                remapped += 1;
+            }
+        }
+
+        // Tokens escrowed on a channel never carry its prefix, so the escrowed
+        // denominations themselves are unaffected by the remapping above.
+        let old_escrow = self.get_escrow_account(port_id, old_channel)?;
+        let new_escrow = self.get_escrow_account(port_id, new_channel)?;
+        for denom in self.escrowed_denoms(port_id, old_channel) {
+            let amount = self.get_escrow_balance(port_id, old_channel, &denom)?;
+            if amount.is_zero() {
+                continue;
+            }
+            let balance = self
+                .get_escrow_balance(port_id, new_channel, &denom)?
+                .checked_add(amount)
+                .ok_or_else(|| TokenTransferError::EscrowBalanceOverflow {
+                    port_id: port_id.clone(),
+                    channel_id: new_channel.clone(),
+                    denom: denom.clone(),
+                })?;
+
+            let coin = PrefixedCoin {
+                denom: denom.clone(),
+                amount,
+            };
+            self.send_coins_execute(&old_escrow, &new_escrow, &coin)?;
+            self.store_escrow_balance(port_id, old_channel, &denom, Amount::from(0))?;
+            self.store_escrow_balance(port_id, new_channel, &denom, balance)?;
+        }
+
+        Ok(remapped)
+    }
+
+    /// Seeds the escrow balances of the given port and channel combination
+    /// from `holdings`, the coins held by its escrow account, overwriting any
+    /// balance already stored for their denominations.
+    ///
+    /// Hosts upgrading from a version that did not track escrow balances must
+    /// call this once per channel as part of the upgrade, otherwise funds
+    /// escrowed before the upgrade read as a zero balance and can no longer
+    /// be unescrowed.
+    fn seed_escrow_balances(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        holdings: &[PrefixedCoin],
+    ) -> Result<(), TokenTransferError> {
+        for coin in holdings {
+            self.store_escrow_balance(port_id, channel_id, &coin.denom, coin.amount)?;
+        }
+        Ok(())
+    }
 }
 
 // https://github.com/cosmos/cosmos-sdk/blob/master/docs/architecture/adr-028-public-key-addresses.md
@@ -110,6 +316,46 @@ pub fn cosmos_adr028_escrow_address(port_id: &PortId, channel_id: &ChannelId) ->
     hash
 }
 
+/// Returns the bech32 encoded escrow address for a port and channel
+/// combination, derived exactly as the Cosmos SDK's `GetEscrowAddress` does.
+///
+/// Hosts that need their escrow accounts to match those of Go chains can
+/// return this from [`TokenTransferValidationContext::get_escrow_account`].
+pub fn get_escrow_address(bech32_prefix: &str, port_id: &PortId, channel_id: &ChannelId) -> Signer {
+    let addr = cosmos_adr028_escrow_address(port_id, channel_id);
+    bech32::encode(bech32_prefix, addr).into()
+}
+
+/// Checks that `version` is one of the versions supported by the transfer
+/// module, once unwrapped from the fee metadata of fee-enabled channels.
+fn verify_version_supported(
+    ctx: &impl TokenTransferValidationContext,
+    version: &Version,
+) -> Result<(), TokenTransferError> {
+    if !ctx.supported_versions().contains(&version.app_version()) {
+        return Err(TokenTransferError::UnsupportedVersion {
+            version: version.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the version to open a channel with, given the version proposed
+/// by the relayer. An empty version lets us pick our most preferred one.
+fn init_version(ctx: &impl TokenTransferValidationContext, version: &Version) -> Version {
+    if version.is_empty() {
+        ctx.supported_versions()
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| Version::new(VERSION.to_string()))
+    } else {
+        version.clone()
+    }
+}
+
+/// Validates the opening of a channel, and returns the version the channel
+/// would be opened with, as [`on_chan_open_init_execute`] does.
 pub fn on_chan_open_init_validate(
     ctx: &impl TokenTransferValidationContext,
     order: Order,
@@ -118,7 +364,7 @@ pub fn on_chan_open_init_validate(
     _channel_id: &ChannelId,
     _counterparty: &Counterparty,
     version: &Version,
-) -> Result<(), TokenTransferError> {
+) -> Result<Version, TokenTransferError> {
     if order != Order::Unordered {
         return Err(TokenTransferError::ChannelNotUnordered {
             expect_order: Order::Unordered,
@@ -134,28 +380,26 @@ pub fn on_chan_open_init_validate(
     }
 
     if !version.is_empty() {
-        version
-            .verify_is_expected(Version::new(VERSION.to_string()))
-            .map_err(ContextError::from)?;
+        verify_version_supported(ctx, version)?;
     }
 
-    Ok(())
+    Ok(init_version(ctx, version))
 }
 
 pub fn on_chan_open_init_execute(
-    _ctx: &mut impl TokenTransferExecutionContext,
+    ctx: &mut impl TokenTransferExecutionContext,
     _order: Order,
     _connection_hops: &[ConnectionId],
     _port_id: &PortId,
     _channel_id: &ChannelId,
     _counterparty: &Counterparty,
-    _version: &Version,
+    version: &Version,
 ) -> Result<(ModuleExtras, Version), TokenTransferError> {
-    Ok((ModuleExtras::empty(), Version::new(VERSION.to_string())))
+    Ok((ModuleExtras::empty(), init_version(ctx, version)))
 }
 
 pub fn on_chan_open_try_validate(
-    _ctx: &impl TokenTransferValidationContext,
+    ctx: &impl TokenTransferValidationContext,
     order: Order,
     _connection_hops: &[ConnectionId],
     _port_id: &PortId,
@@ -170,9 +414,7 @@ pub fn on_chan_open_try_validate(
         });
     }
 
-    counterparty_version
-        .verify_is_expected(Version::new(VERSION.to_string()))
-        .map_err(ContextError::from)?;
+    verify_version_supported(ctx, counterparty_version)?;
 
     Ok(())
 }
@@ -184,20 +426,18 @@ pub fn on_chan_open_try_execute(
     _port_id: &PortId,
     _channel_id: &ChannelId,
     _counterparty: &Counterparty,
-    _counterparty_version: &Version,
+    counterparty_version: &Version,
 ) -> Result<(ModuleExtras, Version), TokenTransferError> {
-    Ok((ModuleExtras::empty(), Version::new(VERSION.to_string())))
+    Ok((ModuleExtras::empty(), counterparty_version.clone()))
 }
 
 pub fn on_chan_open_ack_validate(
-    _ctx: &impl TokenTransferValidationContext,
+    ctx: &impl TokenTransferValidationContext,
     _port_id: &PortId,
     _channel_id: &ChannelId,
     counterparty_version: &Version,
 ) -> Result<(), TokenTransferError> {
-    counterparty_version
-        .verify_is_expected(Version::new(VERSION.to_string()))
-        .map_err(ContextError::from)?;
+    verify_version_supported(ctx, counterparty_version)?;
 
     Ok(())
 }
@@ -263,16 +503,20 @@ pub fn on_recv_packet_execute(
     ctx_b: &mut impl TokenTransferExecutionContext,
     packet: &Packet,
 ) -> (ModuleExtras, Acknowledgement) {
-    let data = match serde_json::from_slice::<PacketData>(&packet.data) {
-        Ok(data) => data,
-        Err(_) => {
-            let ack =
-                AcknowledgementStatus::error(TokenTransferError::PacketDataDeserialization.into());
+    let transfer = match ctx_b
+        .packet_data_encoding(&packet.port_id_on_b, &packet.chan_id_on_b)
+        .and_then(|encoding| decode_and_validate_transfer(packet, encoding))
+        .and_then(|transfer| validate_memo(ctx_b, &transfer.data.memo).map(|_| transfer))
+    {
+        Ok(transfer) => transfer,
+        Err(err) => {
+            let ack = AcknowledgementStatus::error(err.into());
             return (ModuleExtras::empty(), ack.into());
         }
     };
+    let data = transfer.data.clone();
 
-    let (mut extras, ack) = match process_recv_packet_execute(ctx_b, packet, data.clone()) {
+    let (mut extras, ack) = match process_recv_packet_execute(ctx_b, packet, transfer) {
         Ok(extras) => (extras, AcknowledgementStatus::success(ack_success_b64())),
         Err((extras, error)) => (extras, AcknowledgementStatus::error(error.into())),
     };
@@ -299,8 +543,7 @@ pub fn on_acknowledgement_packet_validate<Ctx>(
 where
     Ctx: TokenTransferValidationContext,
 {
-    let data = serde_json::from_slice::<PacketData>(&packet.data)
-        .map_err(|_| TokenTransferError::PacketDataDeserialization)?;
+    let data = decode_sent_packet_data(ctx, packet)?;
 
     let acknowledgement = serde_json::from_slice::<AcknowledgementStatus>(acknowledgement.as_ref())
         .map_err(|_| TokenTransferError::AckDeserialization)?;
@@ -318,14 +561,9 @@ pub fn on_acknowledgement_packet_execute(
     acknowledgement: &Acknowledgement,
     _relayer: &Signer,
 ) -> (ModuleExtras, Result<(), TokenTransferError>) {
-    let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+    let data = match decode_sent_packet_data(ctx, packet) {
         Ok(data) => data,
-        Err(_) => {
-            return (
-                ModuleExtras::empty(),
-                Err(TokenTransferError::PacketDataDeserialization),
-            );
-        }
+        Err(err) => return (ModuleExtras::empty(), Err(err)),
     };
 
     let acknowledgement =
@@ -370,8 +608,7 @@ pub fn on_timeout_packet_validate<Ctx>(
 where
     Ctx: TokenTransferValidationContext,
 {
-    let data = serde_json::from_slice::<PacketData>(&packet.data)
-        .map_err(|_| TokenTransferError::PacketDataDeserialization)?;
+    let data = decode_sent_packet_data(ctx, packet)?;
 
     refund_packet_token_validate(ctx, packet, &data)?;
 
@@ -383,14 +620,9 @@ pub fn on_timeout_packet_execute(
     packet: &Packet,
     _relayer: &Signer,
 ) -> (ModuleExtras, Result<(), TokenTransferError>) {
-    let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+    let data = match decode_sent_packet_data(ctx, packet) {
         Ok(data) => data,
-        Err(_) => {
-            return (
-                ModuleExtras::empty(),
-                Err(TokenTransferError::PacketDataDeserialization),
-            );
-        }
+        Err(err) => return (ModuleExtras::empty(), Err(err)),
     };
 
     if let Err(err) = refund_packet_token_execute(ctx, packet, &data) {
@@ -412,16 +644,80 @@ pub fn on_timeout_packet_execute(
     (extras, Ok(()))
 }
 
+/// Decodes the packet data of `packet`, sent by this chain, with the encoding
+/// of its source channel.
+fn decode_sent_packet_data(
+    ctx: &impl TokenTransferValidationContext,
+    packet: &Packet,
+) -> Result<PacketData, TokenTransferError> {
+    ctx.packet_data_encoding(&packet.port_id_on_a, &packet.chan_id_on_a)?
+        .decode(&packet.data)
+}
+
+/// How a refund returns the tokens of a packet to its sender.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefundKind {
+    /// The tokens are native to this chain and are released from escrow.
+    Unescrow,
+    /// The tokens are vouchers, which are minted back.
+    Mint,
+}
+
+/// The tokens that a refund returns to the sender of a packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefundEffect {
+    pub receiver: Signer,
+    pub coin: PrefixedCoin,
+    pub kind: RefundKind,
+}
+
+/// Returns the refund that timing out `packet` would make, without mutating any state.
+///
+/// Fails if the refund could not be made, as `on_timeout_packet_validate` would.
+pub fn simulate_timeout_refund<Ctx>(
+    ctx: &Ctx,
+    packet: &Packet,
+) -> Result<RefundEffect, TokenTransferError>
+where
+    Ctx: TokenTransferValidationContext,
+{
+    let data = decode_sent_packet_data(ctx, packet)?;
+
+    refund_packet_token_validate(ctx, packet, &data)?;
+
+    let kind = if is_sender_chain_source(
+        packet.port_id_on_a.clone(),
+        packet.chan_id_on_a.clone(),
+        &data.token.denom,
+    ) {
+        RefundKind::Unescrow
+    } else {
+        RefundKind::Mint
+    };
+
+    Ok(RefundEffect {
+        receiver: data.sender,
+        coin: data.token,
+        kind,
+    })
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
-    use subtle_encoding::bech32;
+    use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+    use prost::Message;
 
-    use crate::applications::transfer::context::cosmos_adr028_escrow_address;
+    use crate::applications::transfer::context::{
+        cosmos_adr028_escrow_address, get_escrow_address,
+    };
+    use crate::applications::transfer::{TracePrefix, PROTOBUF_VERSION};
+    use crate::core::ics04_channel::acknowledgement::StatusValue;
     use crate::core::ics04_channel::channel::{Counterparty, Order};
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
-    use crate::test_utils::DummyTransferModule;
+    use crate::test_utils::{get_dummy_account_id, DummyTransferModule};
 
     fn get_defaults() -> (
         DummyTransferModule,
@@ -438,7 +734,7 @@ pub(crate) mod test {
         let counterparty = Counterparty::new(port_id.clone(), Some(channel_id.clone()));
 
         (
-            DummyTransferModule,
+            DummyTransferModule::new(),
             order,
             connection_hops,
             port_id,
@@ -447,6 +743,71 @@ pub(crate) mod test {
         )
     }
 
+    #[test]
+    fn test_remap_channel_in_denom_traces() {
+        let port_id = PortId::transfer();
+        let old_channel = ChannelId::new(0);
+        let new_channel = ChannelId::new(5);
+        let migrated: PrefixedDenom = "transfer/channel-0/uatom".parse().unwrap();
+        let untouched: PrefixedDenom = "transfer/channel-1/uatom".parse().unwrap();
+        let other_port: PrefixedDenom = "other/channel-0/uatom".parse().unwrap();
+        let escrowed: PrefixedDenom = "uosmo".parse().unwrap();
+        let mut ctx = DummyTransferModule::new()
+            .with_denom_trace(migrated.clone())
+            .with_denom_trace(untouched.clone())
+            .with_denom_trace(other_port.clone())
+            .with_escrow_balance(
+                port_id.clone(),
+                old_channel.clone(),
+                escrowed.clone(),
+                40u64.into(),
+            );
+
+        let holder = get_dummy_account_id();
+        ctx.mint_coins_execute(
+            &holder,
+            &PrefixedCoin {
+                denom: migrated.clone(),
+                amount: 100u64.into(),
+            },
+        )
+        .unwrap();
+
+        let remapped = ctx
+            .remap_channel_in_denom_traces(&port_id, &old_channel, &new_channel)
+            .unwrap();
+        assert_eq!(remapped, 1);
+
+        // the voucher balance resolves under the new trace and its hash
+        let remapped_denom: PrefixedDenom = "transfer/channel-5/uatom".parse().unwrap();
+        let new_hash = ctx.denom_hash_string(&remapped_denom).unwrap();
+        let resolved = ctx.denom_trace(&new_hash).unwrap();
+        assert_eq!(resolved, remapped_denom);
+        assert_eq!(ctx.minted_balance(&holder, &resolved), 100u64.into());
+        assert_eq!(ctx.minted_balance(&holder, &migrated), 0u64.into());
+
+        let old_hash = ctx.denom_hash_string(&migrated).unwrap();
+        assert_eq!(ctx.denom_trace(&old_hash), None);
+
+        let untouched_hash = ctx.denom_hash_string(&untouched).unwrap();
+        assert_eq!(ctx.denom_trace(&untouched_hash), Some(untouched));
+
+        let other_port_hash = ctx.denom_hash_string(&other_port).unwrap();
+        assert_eq!(ctx.denom_trace(&other_port_hash), Some(other_port));
+
+        // the escrow balance follows the channel
+        assert_eq!(
+            ctx.get_escrow_balance(&port_id, &old_channel, &escrowed)
+                .unwrap(),
+            0u64.into()
+        );
+        assert_eq!(
+            ctx.get_escrow_balance(&port_id, &new_channel, &escrowed)
+                .unwrap(),
+            40u64.into()
+        );
+    }
+
     #[test]
     fn test_cosmos_escrow_address() {
         fn assert_eq_escrow_address(port_id: &str, channel_id: &str, address: &str) {
@@ -456,7 +817,11 @@ pub(crate) mod test {
                 let addr = cosmos_adr028_escrow_address(&port_id, &channel_id);
                 bech32::encode("cosmos", addr)
             };
-            assert_eq!(gen_address, address.to_owned())
+            assert_eq!(gen_address, address.to_owned());
+            assert_eq!(
+                get_escrow_address("cosmos", &port_id, &channel_id),
+                Signer::from(address.to_owned())
+            );
         }
 
         // addresses obtained using `gaiad query ibc-transfer escrow-address [port-id] [channel-id]`
@@ -519,6 +884,34 @@ pub(crate) mod test {
         assert_eq!(out_version, Version::new(VERSION.to_string()));
     }
 
+    /// Validation accepts an empty version as a request for the default one,
+    /// and returns the version the channel would be opened with
+    #[test]
+    fn test_on_chan_open_init_validate_version_negotiation() {
+        let (ctx, order, connection_hops, port_id, channel_id, counterparty) = get_defaults();
+        let validate = |version: &str| {
+            on_chan_open_init_validate(
+                &ctx,
+                order,
+                &connection_hops,
+                &port_id,
+                &channel_id,
+                &counterparty,
+                &Version::new(version.to_string()),
+            )
+        };
+
+        assert_eq!(validate("").unwrap(), Version::new(VERSION.to_string()));
+        assert_eq!(
+            validate(VERSION).unwrap(),
+            Version::new(VERSION.to_string())
+        );
+        assert!(matches!(
+            validate("ics20-2"),
+            Err(TokenTransferError::UnsupportedVersion { version }) if version.as_str() == "ics20-2"
+        ));
+    }
+
     /// If the relayer passed in an unsupported version, then fail
     #[test]
     fn test_on_chan_open_init_incorrect_version() {
@@ -578,4 +971,582 @@ pub(crate) mod test {
 
         assert!(res.is_err());
     }
+
+    fn custom_version() -> Version {
+        Version::new("transfer-v2-custom".to_string())
+    }
+
+    /// An empty version negotiates the most preferred custom version
+    #[test]
+    fn test_on_chan_open_init_custom_version() {
+        let (ctx, order, connection_hops, port_id, channel_id, counterparty) = get_defaults();
+        let mut ctx =
+            ctx.with_supported_versions(vec![custom_version(), Version::new(VERSION.to_string())]);
+
+        let in_version = Version::new("".to_string());
+        let (_, out_version) = on_chan_open_init_execute(
+            &mut ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &in_version,
+        )
+        .unwrap();
+
+        assert_eq!(out_version, custom_version());
+
+        let in_version = Version::new(VERSION.to_string());
+        let res = on_chan_open_init_validate(
+            &ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &in_version,
+        );
+
+        assert!(res.is_ok());
+    }
+
+    /// A counterparty proposing a fee-wrapped ICS-20 version gets it back, so
+    /// that the channel stays fee-enabled
+    #[test]
+    fn test_on_chan_open_try_fee_wrapped_version() {
+        let (mut ctx, order, connection_hops, port_id, channel_id, counterparty) = get_defaults();
+        let counterparty_version = Version::new_app_version(VERSION, Some("ics29-1"));
+
+        let res = on_chan_open_try_validate(
+            &ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &counterparty_version,
+        );
+        assert!(res.is_ok(), "validation failed: {res:?}");
+
+        let (_, out_version) = on_chan_open_try_execute(
+            &mut ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &counterparty_version,
+        )
+        .unwrap();
+        assert_eq!(out_version, counterparty_version);
+
+        let counterparty_version = Version::new_app_version("ics20-2", Some("ics29-1"));
+        let res = on_chan_open_try_validate(
+            &ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &counterparty_version,
+        );
+        assert!(matches!(
+            res,
+            Err(TokenTransferError::UnsupportedVersion { .. })
+        ));
+    }
+
+    /// A counterparty proposing a supported custom version gets it back,
+    /// while the default version is rejected once it is no longer supported
+    #[test]
+    fn test_on_chan_open_try_custom_version() {
+        let (ctx, order, connection_hops, port_id, channel_id, counterparty) = get_defaults();
+        let mut ctx = ctx.with_supported_versions(vec![custom_version()]);
+
+        let counterparty_version = custom_version();
+        let res = on_chan_open_try_validate(
+            &ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &counterparty_version,
+        );
+
+        assert!(res.is_ok());
+
+        let (_, out_version) = on_chan_open_try_execute(
+            &mut ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &counterparty_version,
+        )
+        .unwrap();
+
+        assert_eq!(out_version, custom_version());
+
+        let counterparty_version = Version::new(VERSION.to_string());
+        let res = on_chan_open_try_validate(
+            &ctx,
+            order,
+            &connection_hops,
+            &port_id,
+            &channel_id,
+            &counterparty,
+            &counterparty_version,
+        );
+
+        assert!(matches!(
+            res,
+            Err(TokenTransferError::UnsupportedVersion { .. })
+        ));
+    }
+
+    fn packet_sending(amount: u64) -> Packet {
+        let mut data = PacketData::new_dummy();
+        data.token.amount = amount.into();
+
+        let mut packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+        packet.data = serde_json::to_vec(&data).unwrap();
+        packet
+    }
+
+    fn ctx_with_escrowed(amount: u64) -> DummyTransferModule {
+        DummyTransferModule::new().with_escrow_balance(
+            PortId::default(),
+            ChannelId::default(),
+            "uatom".parse().unwrap(),
+            amount.into(),
+        )
+    }
+
+    /// Packets whose data fails validation are acknowledged with an error
+    /// before any tokens are moved
+    #[test]
+    fn test_on_recv_rejects_invalid_packet_data() {
+        let mut ctx = ctx_with_escrowed(15);
+        let account = crate::test_utils::get_dummy_bech32_account();
+        let mut packet = packet_sending(10);
+        packet.data = format!(
+            r#"{{"denom":"transfer/channel-0/uatom","amount":"-10","sender":"{account}","receiver":"{account}"}}"#
+        )
+        .into_bytes();
+
+        let (extras, ack) = on_recv_packet_execute(&mut ctx, &packet);
+
+        let expected_ack: Acknowledgement =
+            AcknowledgementStatus::error("-10".parse::<Amount>().unwrap_err().into()).into();
+        assert_eq!(ack, expected_ack);
+        assert!(extras.events.is_empty());
+
+        let balance = ctx
+            .get_escrow_balance(
+                &PortId::default(),
+                &ChannelId::default(),
+                &"uatom".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(balance, 15u64.into());
+    }
+
+    fn error_ack() -> Acknowledgement {
+        AcknowledgementStatus::error(StatusValue::new("transfer failed").unwrap()).into()
+    }
+
+    /// An error acknowledgement refunds the escrowed tokens and deducts them
+    /// from the channel's escrow balance
+    #[test]
+    fn test_on_ack_refund_within_escrow_balance() {
+        let mut ctx = ctx_with_escrowed(15);
+        let packet = packet_sending(10);
+        let relayer = get_dummy_account_id();
+
+        let res = on_acknowledgement_packet_validate(&ctx, &packet, &error_ack(), &relayer);
+        assert!(res.is_ok(), "validation failed: {res:?}");
+
+        let (_, res) = on_acknowledgement_packet_execute(&mut ctx, &packet, &error_ack(), &relayer);
+        assert!(res.is_ok(), "execution failed: {res:?}");
+
+        let balance = ctx
+            .get_escrow_balance(
+                &PortId::default(),
+                &ChannelId::default(),
+                &"uatom".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(balance, 5u64.into());
+    }
+
+    /// An error acknowledgement cannot refund more than was escrowed on the channel
+    #[test]
+    fn test_on_ack_refund_exceeding_escrow_balance() {
+        let mut ctx = ctx_with_escrowed(5);
+        let packet = packet_sending(10);
+        let relayer = get_dummy_account_id();
+
+        let res = on_acknowledgement_packet_validate(&ctx, &packet, &error_ack(), &relayer);
+        assert!(
+            matches!(
+                res,
+                Err(TokenTransferError::InsufficientEscrowBalance { escrowed, requested, .. })
+                    if escrowed == 5u64.into() && requested == 10u64.into()
+            ),
+            "unexpected result: {res:?}"
+        );
+
+        let (_, res) = on_acknowledgement_packet_execute(&mut ctx, &packet, &error_ack(), &relayer);
+        assert!(matches!(
+            res,
+            Err(TokenTransferError::InsufficientEscrowBalance { .. })
+        ));
+    }
+
+    /// A timed-out packet of native tokens releases them from escrow back to
+    /// the sender, deducting them from the channel's escrow balance
+    #[test]
+    fn test_on_timeout_unescrows_native_tokens() {
+        let mut ctx = ctx_with_escrowed(15);
+        let packet = packet_sending(10);
+        let relayer = get_dummy_account_id();
+
+        let res = on_timeout_packet_validate(&ctx, &packet, &relayer);
+        assert!(res.is_ok(), "validation failed: {res:?}");
+
+        let (extras, res) = on_timeout_packet_execute(&mut ctx, &packet, &relayer);
+        assert!(res.is_ok(), "execution failed: {res:?}");
+        assert_eq!(extras.events.len(), 1);
+
+        let denom = "uatom".parse().unwrap();
+        let balance = ctx
+            .get_escrow_balance(&PortId::default(), &ChannelId::default(), &denom)
+            .unwrap();
+        assert_eq!(balance, 5u64.into());
+        assert_eq!(
+            ctx.minted_balance(&PacketData::new_dummy().sender, &denom),
+            0u64.into()
+        );
+    }
+
+    /// A timed-out packet cannot release more than was escrowed on the channel
+    #[test]
+    fn test_on_timeout_refund_exceeding_escrow_balance() {
+        let mut ctx = ctx_with_escrowed(5);
+        let packet = packet_sending(10);
+        let relayer = get_dummy_account_id();
+
+        let res = on_timeout_packet_validate(&ctx, &packet, &relayer);
+        assert!(
+            matches!(
+                res,
+                Err(TokenTransferError::InsufficientEscrowBalance { escrowed, requested, .. })
+                    if escrowed == 5u64.into() && requested == 10u64.into()
+            ),
+            "unexpected result: {res:?}"
+        );
+
+        let (extras, res) = on_timeout_packet_execute(&mut ctx, &packet, &relayer);
+        assert!(matches!(
+            res,
+            Err(TokenTransferError::InsufficientEscrowBalance { .. })
+        ));
+        assert!(extras.events.is_empty());
+
+        let balance = ctx
+            .get_escrow_balance(
+                &PortId::default(),
+                &ChannelId::default(),
+                &"uatom".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(balance, 5u64.into());
+    }
+
+    /// A timed-out packet of vouchers mints back to the sender the vouchers
+    /// burned when sending, leaving the escrow balances untouched
+    #[test]
+    fn test_on_timeout_mints_back_vouchers() {
+        let mut ctx = DummyTransferModule::new();
+        let mut packet = packet_sending(10);
+        let mut data: PacketData = serde_json::from_slice(&packet.data).unwrap();
+        data.token.denom.add_trace_prefix(TracePrefix::new(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+        ));
+        packet.data = serde_json::to_vec(&data).unwrap();
+        let relayer = get_dummy_account_id();
+
+        let res = on_timeout_packet_validate(&ctx, &packet, &relayer);
+        assert!(res.is_ok(), "validation failed: {res:?}");
+
+        let (_, res) = on_timeout_packet_execute(&mut ctx, &packet, &relayer);
+        assert!(res.is_ok(), "execution failed: {res:?}");
+
+        assert_eq!(
+            ctx.minted_balance(&data.sender, &data.token.denom),
+            10u64.into()
+        );
+        let balance = ctx
+            .get_escrow_balance(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                &data.token.denom,
+            )
+            .unwrap();
+        assert_eq!(balance, 0u64.into());
+    }
+
+    /// Simulating the timeout of a packet of native tokens previews their
+    /// release from escrow, leaving the escrow balance untouched
+    #[test]
+    fn test_simulate_timeout_refund_unescrow() {
+        let ctx = ctx_with_escrowed(15);
+        let packet = packet_sending(10);
+
+        let effect = simulate_timeout_refund(&ctx, &packet).unwrap();
+
+        assert_eq!(effect.kind, RefundKind::Unescrow);
+        assert_eq!(effect.coin.denom, "uatom".parse().unwrap());
+        assert_eq!(effect.coin.amount, 10u64.into());
+        assert_eq!(effect.receiver, PacketData::new_dummy().sender);
+
+        let balance = ctx
+            .get_escrow_balance(
+                &PortId::default(),
+                &ChannelId::default(),
+                &"uatom".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(balance, 15u64.into());
+
+        assert!(matches!(
+            simulate_timeout_refund(&ctx_with_escrowed(5), &packet),
+            Err(TokenTransferError::InsufficientEscrowBalance { .. })
+        ));
+    }
+
+    /// Simulating the timeout of a packet of vouchers previews minting them
+    /// back to the sender
+    #[test]
+    fn test_simulate_timeout_refund_mint() {
+        let ctx = DummyTransferModule::new();
+        let mut packet = packet_sending(10);
+        let mut data: PacketData = serde_json::from_slice(&packet.data).unwrap();
+        data.token.denom.add_trace_prefix(TracePrefix::new(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+        ));
+        packet.data = serde_json::to_vec(&data).unwrap();
+
+        let effect = simulate_timeout_refund(&ctx, &packet).unwrap();
+
+        assert_eq!(
+            effect,
+            RefundEffect {
+                receiver: data.sender,
+                coin: data.token,
+                kind: RefundKind::Mint,
+            }
+        );
+    }
+
+    /// Packets sent on a channel negotiated with the protobuf version are
+    /// decoded as protobuf when refunding them
+    #[test]
+    fn test_on_timeout_decodes_by_channel_version() {
+        let mut packet = packet_sending(10);
+        let mut data = PacketData::new_dummy();
+        data.token.amount = 10u64.into();
+        packet.data = RawPacketData::from(data).encode_to_vec();
+        let relayer = get_dummy_account_id();
+
+        let mut ctx = ctx_with_escrowed(15).with_channel_version(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+            Version::new(PROTOBUF_VERSION.to_string()),
+        );
+        let res = on_timeout_packet_validate(&ctx, &packet, &relayer);
+        assert!(res.is_ok(), "validation failed: {res:?}");
+
+        let (_, res) = on_timeout_packet_execute(&mut ctx, &packet, &relayer);
+        assert!(res.is_ok(), "execution failed: {res:?}");
+        let balance = ctx
+            .get_escrow_balance(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                &"uatom".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(balance, 5u64.into());
+
+        let res = on_timeout_packet_validate(&ctx_with_escrowed(15), &packet, &relayer);
+        assert!(res.is_err(), "protobuf data decoded as JSON: {res:?}");
+    }
+
+    fn packet_receiving(denom: &str) -> Packet {
+        let mut data = PacketData::new_dummy();
+        data.token.denom = denom.parse().unwrap();
+
+        let mut packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+        packet.data = serde_json::to_vec(&data).unwrap();
+        packet
+    }
+
+    fn denom_metadata_events(extras: &ModuleExtras) -> usize {
+        extras
+            .events
+            .iter()
+            .filter(|event| event.kind == "denomination_metadata")
+            .count()
+    }
+
+    /// Receiving vouchers stores their metadata on the first receipt of each
+    /// new denomination only
+    #[test]
+    fn test_on_recv_stores_denom_metadata_once() {
+        let mut ctx = DummyTransferModule::new();
+        let packet = packet_receiving("uatom");
+        let prefix = TracePrefix::new(packet.port_id_on_b.clone(), packet.chan_id_on_b.clone());
+        let mut voucher: PrefixedDenom = "uatom".parse().unwrap();
+        voucher.add_trace_prefix(prefix);
+
+        assert_eq!(ctx.get_denom_metadata(&voucher).unwrap(), None);
+
+        let (extras, ack) = on_recv_packet_execute(&mut ctx, &packet);
+        assert_eq!(
+            ack,
+            AcknowledgementStatus::success(ack_success_b64()).into()
+        );
+        assert_eq!(denom_metadata_events(&extras), 1);
+        assert_eq!(
+            ctx.get_denom_metadata(&voucher).unwrap(),
+            Some(DenomMetadata::for_voucher(&voucher))
+        );
+
+        let (extras, _) = on_recv_packet_execute(&mut ctx, &packet);
+        assert_eq!(denom_metadata_events(&extras), 0);
+
+        let (extras, _) = on_recv_packet_execute(&mut ctx, &packet_receiving("uosmo"));
+        assert_eq!(denom_metadata_events(&extras), 1);
+    }
+
+    /// Packet data received on a channel negotiated with the protobuf version
+    /// is decoded as protobuf; the same bytes are rejected on an ICS-20 channel
+    #[test]
+    fn test_on_recv_decodes_by_channel_version() {
+        let mut packet = packet_receiving("uatom");
+        packet.data = RawPacketData::from(PacketData::new_dummy()).encode_to_vec();
+
+        let mut ctx = DummyTransferModule::new().with_channel_version(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+            Version::new(PROTOBUF_VERSION.to_string()),
+        );
+        let (_, ack) = on_recv_packet_execute(&mut ctx, &packet);
+        assert_eq!(
+            ack,
+            AcknowledgementStatus::success(ack_success_b64()).into()
+        );
+
+        let mut ctx = DummyTransferModule::new();
+        let (_, ack) = on_recv_packet_execute(&mut ctx, &packet);
+        assert_ne!(
+            ack,
+            AcknowledgementStatus::success(ack_success_b64()).into()
+        );
+    }
+
+    fn packet_with_memo(memo: &str) -> (Packet, PacketData) {
+        let mut data = PacketData::new_dummy();
+        data.memo = memo.to_string().into();
+
+        let mut packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+        packet.data = serde_json::to_vec(&data).unwrap();
+        (packet, data)
+    }
+
+    /// Received memos are passed through unchanged into the receive event
+    #[test]
+    fn test_on_recv_preserves_memo() {
+        let memo = r#"{"wasm":{"contract":"cosmos1contract","msg":{}}}"#;
+        let (packet, data) = packet_with_memo(memo);
+        let mut ctx = DummyTransferModule::new();
+
+        let (extras, ack) = on_recv_packet_execute(&mut ctx, &packet);
+        assert_eq!(
+            ack,
+            AcknowledgementStatus::success(ack_success_b64()).into()
+        );
+
+        let recv_event = RecvEvent {
+            sender: data.sender,
+            receiver: data.receiver,
+            denom: data.token.denom,
+            amount: data.token.amount,
+            memo: memo.to_string().into(),
+            success: true,
+        };
+        assert!(extras.events.contains(&recv_event.into()));
+    }
+
+    /// Memos larger than the host's limit are acknowledged with an error
+    #[test]
+    fn test_on_recv_rejects_oversized_memo() {
+        let (packet, _) = packet_with_memo(&"a".repeat(DEFAULT_MAX_MEMO_BYTES + 1));
+        let mut ctx = DummyTransferModule::new();
+
+        let (extras, ack) = on_recv_packet_execute(&mut ctx, &packet);
+
+        let expected_ack: Acknowledgement = AcknowledgementStatus::error(
+            TokenTransferError::MemoTooLarge {
+                size: DEFAULT_MAX_MEMO_BYTES + 1,
+                max: DEFAULT_MAX_MEMO_BYTES,
+            }
+            .into(),
+        )
+        .into();
+        assert_eq!(ack, expected_ack);
+        assert!(extras.events.is_empty());
+    }
+
+    /// Funds escrowed before balances were tracked can be refunded once the
+    /// escrow balances are seeded from the escrow account holdings
+    #[test]
+    fn test_on_ack_refund_after_seeding_escrow_balances() {
+        let mut ctx = DummyTransferModule::new();
+        let packet = packet_sending(10);
+        let relayer = get_dummy_account_id();
+
+        let res = on_acknowledgement_packet_validate(&ctx, &packet, &error_ack(), &relayer);
+        assert!(matches!(
+            res,
+            Err(TokenTransferError::InsufficientEscrowBalance { .. })
+        ));
+
+        let holdings = [PrefixedCoin {
+            denom: "uatom".parse().unwrap(),
+            amount: 15u64.into(),
+        }];
+        ctx.seed_escrow_balances(&PortId::default(), &ChannelId::default(), &holdings)
+            .unwrap();
+
+        let res = on_acknowledgement_packet_validate(&ctx, &packet, &error_ack(), &relayer);
+        assert!(res.is_ok(), "validation failed: {res:?}");
+
+        let (_, res) = on_acknowledgement_packet_execute(&mut ctx, &packet, &error_ack(), &relayer);
+        assert!(res.is_ok(), "execution failed: {res:?}");
+
+        let balance = ctx
+            .get_escrow_balance(
+                &PortId::default(),
+                &ChannelId::default(),
+                &"uatom".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(balance, 5u64.into());
+    }
 }
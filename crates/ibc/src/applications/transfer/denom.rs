@@ -13,6 +13,9 @@ use crate::prelude::*;
 #[cfg(feature = "serde")]
 use crate::serializers::serde_string;
 
+/// The prefix reserved for hashed IBC voucher denominations, i.e. `ibc/{hash}`.
+pub const IBC_DENOM_PREFIX: &str = "ibc/";
+
 /// The "base" of a denomination.
 ///
 /// For example, given the token `my_port-1/my_channel-1/my_port-2/my_channel-2/base_denom`,
@@ -35,6 +38,14 @@ impl BaseDenom {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Returns the hash part if the denomination has the `ibc/{hash}` form reserved for
+    /// hashed IBC vouchers, where `{hash}` is a hex-encoded SHA-256 digest.
+    pub fn ibc_denom_hash(&self) -> Option<&str> {
+        self.0
+            .strip_prefix(IBC_DENOM_PREFIX)
+            .filter(|hash| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+    }
 }
 
 impl FromStr for BaseDenom {
@@ -124,6 +135,27 @@ impl TracePath {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Replaces `old_channel` with `new_channel` in the leading prefix of the
+    /// path, provided that prefix is on `port_id`.
+    ///
+    /// Only the leading prefix refers to a channel end on the local chain; the
+    /// remaining prefixes name channels on other chains and are left untouched.
+    /// Returns true iff the path was modified.
+    pub fn remap_channel(
+        &mut self,
+        port_id: &PortId,
+        old_channel: &ChannelId,
+        new_channel: &ChannelId,
+    ) -> bool {
+        match self.0.last_mut() {
+            Some(prefix) if &prefix.port_id == port_id && &prefix.channel_id == old_channel => {
+                prefix.channel_id = new_channel.clone();
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl<'a> TryFrom<Vec<&'a str>> for TracePath {
@@ -218,6 +250,80 @@ impl PrefixedDenom {
     pub fn add_trace_prefix(&mut self, prefix: TracePrefix) {
         self.trace_path.add_prefix(prefix)
     }
+
+    /// Replaces `old_channel` with `new_channel` in the leading prefix of the trace path,
+    /// provided that prefix is on `port_id`. Returns true iff the denomination was modified.
+    pub fn remap_trace_channel(
+        &mut self,
+        port_id: &PortId,
+        old_channel: &ChannelId,
+        new_channel: &ChannelId,
+    ) -> bool {
+        self.trace_path
+            .remap_channel(port_id, old_channel, new_channel)
+    }
+
+    /// Returns the canonical form of the denomination, where every leading
+    /// `{port-id}/channel-{n}` pair of the base denomination is moved into the
+    /// trace path, as the SDK does when splitting a full denomination path.
+    ///
+    /// Denominations parsed with [`FromStr`] are already canonical. This is
+    /// meant for denominations built from a base denomination that holds a
+    /// full path, so that they compare, display and hash like the SDK's.
+    pub fn canonical(&self) -> PrefixedDenom {
+        let mut parts: Vec<&str> = self.base_denom.as_str().split('/').collect();
+
+        // The prefixes found in the base denomination are closer to the base
+        // than those already in the trace path, i.e. come first in its
+        // reversed order.
+        let mut prefixes = vec![];
+        while parts.len() > 2 && is_sdk_channel_id(parts[1]) {
+            match PortId::from_str(parts[0]) {
+                Ok(port_id) => {
+                    let channel_id = ChannelId::from_str(parts[1])
+                        .expect("`channel-{n}` is a valid channel identifier");
+                    prefixes.push(TracePrefix::new(port_id, channel_id));
+                    parts.drain(..2);
+                }
+                Err(_) => break,
+            }
+        }
+        prefixes.reverse();
+        prefixes.extend(self.trace_path.0.iter().cloned());
+
+        PrefixedDenom {
+            trace_path: TracePath(prefixes),
+            base_denom: BaseDenom(parts.join("/")),
+        }
+    }
+}
+
+/// Returns true iff `s` is a channel identifier of the `channel-{n}` form
+/// that the SDK recognizes in denomination paths.
+fn is_sdk_channel_id(s: &str) -> bool {
+    s.strip_prefix(ChannelId::prefix())
+        .and_then(|s| s.strip_prefix('-'))
+        .map(|counter| !counter.is_empty() && counter.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(false)
+}
+
+/// Rewrites every denomination trace whose leading prefix references `old_channel` on `port_id` so
+/// that it references `new_channel` instead, returning the number of traces that were modified.
+///
+/// This is meant for channel-migration tooling that renumbers local channels. It only rewrites the
+/// traces; see `TokenTransferExecutionContext::remap_channel_in_denom_traces` to also migrate the
+/// voucher and escrow balances held by the host.
+pub fn remap_channel_in_traces<'a>(
+    traces: impl IntoIterator<Item = &'a mut PrefixedDenom>,
+    port_id: &PortId,
+    old_channel: &ChannelId,
+    new_channel: &ChannelId,
+) -> usize {
+    traces
+        .into_iter()
+        .map(|denom| denom.remap_trace_channel(port_id, old_channel, new_channel))
+        .filter(|remapped| *remapped)
+        .count()
 }
 
 /// Returns true if the denomination originally came from the sender chain and
@@ -377,6 +483,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ibc_denom_hash() -> Result<(), TokenTransferError> {
+        let hash = "27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+        assert_eq!(
+            BaseDenom::from_str(&format!("ibc/{hash}"))?.ibc_denom_hash(),
+            Some(hash)
+        );
+        assert_eq!(BaseDenom::from_str("uatom")?.ibc_denom_hash(), None);
+        assert_eq!(BaseDenom::from_str("ibc/uatom")?.ibc_denom_hash(), None);
+        assert_eq!(BaseDenom::from_str("ibc/")?.ibc_denom_hash(), None);
+
+        Ok(())
+    }
+
     #[test]
     fn test_denom_trace() -> Result<(), TokenTransferError> {
         assert_eq!(
@@ -451,4 +571,98 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_remap_channel_in_traces() -> Result<(), TokenTransferError> {
+        let old_channel = ChannelId::new(0);
+        let new_channel = ChannelId::new(7);
+        let port_id = PortId::transfer();
+
+        let mut traces = vec![
+            PrefixedDenom::from_str("transfer/channel-0/uatom")?,
+            PrefixedDenom::from_str("transfer/channel-0/transfer/channel-1/uatom")?,
+            // only the leading hop refers to a local channel
+            PrefixedDenom::from_str("transfer/channel-1/transfer/channel-0/uatom")?,
+            // the same channel id on another port is a different channel end
+            PrefixedDenom::from_str("other/channel-0/uatom")?,
+            PrefixedDenom::from_str("uatom")?,
+        ];
+
+        let remapped =
+            remap_channel_in_traces(traces.iter_mut(), &port_id, &old_channel, &new_channel);
+        assert_eq!(remapped, 2);
+
+        assert_eq!(
+            traces,
+            vec![
+                PrefixedDenom::from_str("transfer/channel-7/uatom")?,
+                PrefixedDenom::from_str("transfer/channel-7/transfer/channel-1/uatom")?,
+                PrefixedDenom::from_str("transfer/channel-1/transfer/channel-0/uatom")?,
+                PrefixedDenom::from_str("other/channel-0/uatom")?,
+                PrefixedDenom::from_str("uatom")?,
+            ]
+        );
+
+        // vouchers now resolve as coming back through the new channel
+        assert!(is_receiver_chain_source(
+            port_id.clone(),
+            new_channel.clone(),
+            &traces[0]
+        ));
+        assert!(!is_receiver_chain_source(port_id, old_channel, &traces[0]));
+
+        Ok(())
+    }
+
+    /// Parsing and displaying roundtrips the SDK's full denomination paths
+    #[test]
+    fn test_denom_sdk_roundtrip() -> Result<(), TokenTransferError> {
+        for (full_path, path, base_denom) in [
+            ("uatom", "", "uatom"),
+            ("transfer/channel-0/uatom", "transfer/channel-0", "uatom"),
+            (
+                "transfer/channel-1/transfer/channel-0/uatom",
+                "transfer/channel-1/transfer/channel-0",
+                "uatom",
+            ),
+        ] {
+            let denom = PrefixedDenom::from_str(full_path)?;
+            assert_eq!(denom.to_string(), full_path);
+            assert_eq!(denom.canonical(), denom);
+
+            let raw = RawDenomTrace::from(denom.clone());
+            assert_eq!(raw.path, path);
+            assert_eq!(raw.base_denom, base_denom);
+            assert_eq!(PrefixedDenom::try_from(raw)?, denom);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_denom_canonical() -> Result<(), TokenTransferError> {
+        let denom = PrefixedDenom {
+            trace_path: "transfer/channel-1".parse()?,
+            base_denom: BaseDenom("transfer/channel-0/uatom".to_string()),
+        };
+        let canonical = denom.canonical();
+        assert_eq!(
+            canonical,
+            PrefixedDenom::from_str("transfer/channel-1/transfer/channel-0/uatom")?
+        );
+        assert_eq!(canonical.to_string(), denom.to_string());
+
+        // Path segments that the SDK does not recognize as a trace stay in
+        // the base denomination
+        for base_denom in [
+            "gamm/pool/1",
+            "factory/osmo1a8rwfsnjkhcelzwj8v5q3jn2mc9luhjtc5lvaf/uatom",
+            "transfer/channel-0",
+        ] {
+            let denom = PrefixedDenom::from(BaseDenom(base_denom.to_string()));
+            assert_eq!(denom.canonical(), denom);
+        }
+
+        Ok(())
+    }
 }
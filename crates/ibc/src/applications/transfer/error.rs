@@ -6,8 +6,10 @@ use displaydoc::Display;
 use ibc_proto::protobuf::Error as TendermintProtoError;
 use uint::FromDecStrErr;
 
+use crate::applications::transfer::{Amount, Imbalance, PrefixedDenom};
 use crate::core::ics04_channel::acknowledgement::StatusValue;
 use crate::core::ics04_channel::channel::Order;
+use crate::core::ics04_channel::Version;
 use crate::core::ics24_host::identifier::{ChannelId, IdentifierError, PortId};
 use crate::core::ContextError;
 use crate::prelude::*;
@@ -25,6 +27,10 @@ pub enum TokenTransferError {
     },
     /// base denomination is empty
     EmptyBaseDenom,
+    /// denomination `{denom}` uses the reserved `ibc/` prefix but does not resolve to a known denom trace
+    InvalidDenomForTransfer { denom: String },
+    /// memo of `{size}` bytes exceeds the maximum of `{max}` bytes
+    MemoTooLarge { size: usize, max: usize },
     /// invalid prot id n trace at position: `{pos}`, validation error: `{validation_error}`
     InvalidTracePortId {
         pos: usize,
@@ -41,11 +47,42 @@ pub enum TokenTransferError {
     InvalidAmount(FromDecStrErr),
     /// invalid token
     InvalidToken,
+    /// token amount must be greater than zero
+    ZeroAmount,
+    /// packet sender must not be empty
+    EmptySender,
+    /// packet receiver must not be empty
+    EmptyReceiver,
     /// expected `{expect_order}` channel, got `{got_order}`
     ChannelNotUnordered {
         expect_order: Order,
         got_order: Order,
     },
+    /// transfer version `{version}` is not supported
+    UnsupportedVersion { version: Version },
+    /// insufficient escrow balance on port_id `{port_id}` and channel_id `{channel_id}`: `{escrowed}` `{denom}` escrowed, `{requested}` requested
+    InsufficientEscrowBalance {
+        port_id: PortId,
+        channel_id: ChannelId,
+        denom: PrefixedDenom,
+        escrowed: Amount,
+        requested: Amount,
+    },
+    /// escrow balance of `{denom}` on port_id `{port_id}` and channel_id `{channel_id}` overflows
+    EscrowBalanceOverflow {
+        port_id: PortId,
+        channel_id: ChannelId,
+        denom: PrefixedDenom,
+    },
+    /// escrowed tokens are not matched by counterparty vouchers: `{imbalances:?}`
+    EscrowImbalance { imbalances: Vec<Imbalance> },
+    /// insufficient funds to transfer `{amount}` `{denom}`
+    InsufficientFunds {
+        denom: PrefixedDenom,
+        amount: Amount,
+    },
+    /// the transfer module is not ready: `{reason}`
+    ModuleNotReady { reason: String },
     /// channel cannot be closed
     CantCloseChannel,
     /// failed to deserialize packet data
@@ -73,6 +110,23 @@ pub enum TokenTransferError {
     Utf8Decode(Utf8Error),
 }
 
+impl TokenTransferError {
+    /// Returns whether the same message may succeed if retried later, e.g.
+    /// by a relayer backing off.
+    ///
+    /// Only [`TokenTransferError::ModuleNotReady`] is retriable: the host is
+    /// temporarily unable to process transfers (e.g. its accounts are still
+    /// being initialised at genesis). Every other variant reports an invalid
+    /// message or a state that retrying the same message does not change,
+    /// such as [`TokenTransferError::InsufficientFunds`].
+    ///
+    /// Note that an error while receiving a packet is written as an error
+    /// acknowledgement whether it is retriable or not.
+    pub fn retriable(&self) -> bool {
+        matches!(self, Self::ModuleNotReady { .. })
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for TokenTransferError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -118,3 +172,23 @@ impl From<TokenTransferError> for StatusValue {
         StatusValue::new(err.to_string()).expect("error message must not be empty")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retriable_classification() {
+        let insufficient_funds = TokenTransferError::InsufficientFunds {
+            denom: "uatom".parse().unwrap(),
+            amount: 10u64.into(),
+        };
+        let module_not_ready = TokenTransferError::ModuleNotReady {
+            reason: "accounts are being initialised".to_string(),
+        };
+
+        assert!(!insufficient_funds.retriable());
+        assert!(!TokenTransferError::ZeroAmount.retriable());
+        assert!(module_not_ready.retriable());
+    }
+}
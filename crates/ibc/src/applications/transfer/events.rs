@@ -1,6 +1,6 @@
 //! Defines all token transfer event types
 
-use crate::applications::transfer::{Amount, PrefixedDenom, MODULE_ID_STR};
+use crate::applications::transfer::{Amount, DenomMetadata, PrefixedDenom, MODULE_ID_STR};
 use crate::core::events::ModuleEvent;
 use crate::core::ics04_channel::acknowledgement::AcknowledgementStatus;
 use crate::prelude::*;
@@ -11,6 +11,7 @@ use super::Memo;
 const EVENT_TYPE_PACKET: &str = "fungible_token_packet";
 const EVENT_TYPE_TIMEOUT: &str = "timeout";
 const EVENT_TYPE_DENOM_TRACE: &str = "denomination_trace";
+const EVENT_TYPE_DENOM_METADATA: &str = "denomination_metadata";
 const EVENT_TYPE_TRANSFER: &str = "ibc_transfer";
 
 /// Contains all events variants that can be emitted from the token transfer application
@@ -20,6 +21,7 @@ pub enum Event {
     AckStatus(AckStatusEvent),
     Timeout(TimeoutEvent),
     DenomTrace(DenomTraceEvent),
+    DenomMetadata(DenomMetadataEvent),
     Transfer(TransferEvent),
 }
 
@@ -167,6 +169,33 @@ impl From<DenomTraceEvent> for ModuleEvent {
     }
 }
 
+/// Event emitted in the [`onRecvPacket`][super::context::on_recv_packet_execute]
+/// module callback when the metadata of a newly received voucher is stored
+pub struct DenomMetadataEvent {
+    pub metadata: DenomMetadata,
+}
+
+impl From<DenomMetadataEvent> for ModuleEvent {
+    fn from(ev: DenomMetadataEvent) -> Self {
+        let DenomMetadataEvent {
+            metadata:
+                DenomMetadata {
+                    base,
+                    display,
+                    exponent,
+                },
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_DENOM_METADATA.to_string(),
+            attributes: vec![
+                ("base", base).into(),
+                ("display", display).into(),
+                ("exponent", exponent).into(),
+            ],
+        }
+    }
+}
+
 /// Event emitted in [`sendTransfer`][super::send_transfer] after a successful
 /// transfer
 pub struct TransferEvent {
@@ -208,6 +237,7 @@ impl From<Event> for ModuleEvent {
             Event::AckStatus(ev) => ev.into(),
             Event::Timeout(ev) => ev.into(),
             Event::DenomTrace(ev) => ev.into(),
+            Event::DenomMetadata(ev) => ev.into(),
             Event::Transfer(ev) => ev.into(),
         }
     }
@@ -7,6 +7,9 @@ use core::str::FromStr;
 
 use crate::prelude::*;
 
+/// The default maximum size in bytes of a token transfer memo.
+pub const DEFAULT_MAX_MEMO_BYTES: usize = 32 * 1024;
+
 /// Represents the token transfer memo
 #[cfg_attr(
     feature = "parity-scale-codec",
@@ -0,0 +1,57 @@
+//! Display metadata for the vouchers minted by the token transfer module.
+
+use super::denom::PrefixedDenom;
+use crate::prelude::*;
+
+/// Tells wallets how to display a denomination, in the manner of the Cosmos
+/// SDK bank module's denomination metadata.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenomMetadata {
+    /// The denomination that amounts are expressed in on chain.
+    pub base: PrefixedDenom,
+    /// The name to display amounts under.
+    pub display: String,
+    /// The power of ten that converts a `base` amount into a `display` amount.
+    pub exponent: u32,
+}
+
+impl DenomMetadata {
+    /// Returns the metadata of a voucher received for the first time.
+    ///
+    /// Transfer packets carry no display information, so the voucher is
+    /// displayed under its base denomination, without the trace path, and
+    /// with no exponent.
+    pub fn for_voucher(denom: &PrefixedDenom) -> Self {
+        Self {
+            base: denom.clone(),
+            display: denom.base_denom.as_str().to_string(),
+            exponent: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voucher_metadata_displays_base_denom() {
+        let denom: PrefixedDenom = "transfer/channel-0/uatom".parse().unwrap();
+
+        let metadata = DenomMetadata::for_voucher(&denom);
+
+        assert_eq!(metadata.base, denom);
+        assert_eq!(metadata.display, "uatom");
+        assert_eq!(metadata.exponent, 0);
+    }
+}
@@ -2,18 +2,22 @@
 
 pub mod amount;
 pub mod coin;
+pub mod conservation;
 pub mod context;
 pub mod denom;
 pub mod error;
 pub mod events;
 pub mod memo;
+pub mod metadata;
 pub mod msgs;
 pub mod packet;
 
 pub use amount::*;
 pub use coin::*;
+pub use conservation::*;
 pub use denom::*;
 pub use memo::*;
+pub use metadata::*;
 
 mod relay;
 
@@ -29,6 +33,10 @@ pub const PORT_ID_STR: &str = "transfer";
 /// ICS20 application current version.
 pub const VERSION: &str = "ics20-1";
 
+/// Version of ICS20 channels whose packet data is encoded with protobuf
+/// rather than JSON.
+pub const PROTOBUF_VERSION: &str = "ics20-1-proto";
+
 /// The successful string used for creating an acknowledgement status,
 /// equivalent to `base64::encode(0x01)`.
 pub const ACK_SUCCESS_B64: &str = "AQ==";
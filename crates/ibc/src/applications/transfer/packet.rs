@@ -1,6 +1,7 @@
 //! Contains the `PacketData` type that defines the structure of token transfers' packet bytes
 
 use alloc::string::ToString;
+use alloc::vec::Vec;
 use core::convert::TryFrom;
 use core::str::FromStr;
 
@@ -8,9 +9,12 @@ use core::str::FromStr;
 use crate::alloc::borrow::ToOwned;
 
 use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use prost::Message;
 
 use super::error::TokenTransferError;
-use super::{Amount, Memo, PrefixedCoin, PrefixedDenom};
+use super::{Amount, Memo, PrefixedCoin, PrefixedDenom, PROTOBUF_VERSION, VERSION};
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::Version;
 use crate::signer::Signer;
 
 /// Defines the structure of token transfers' packet bytes
@@ -60,11 +64,103 @@ impl From<PacketData> for RawPacketData {
     }
 }
 
+/// The encoding of the packet data sent over a token transfer channel, as
+/// negotiated through the channel version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacketDataEncoding {
+    /// JSON-encoded `FungibleTokenPacketData`, used by `ics20-1` channels.
+    Json,
+    /// Protobuf-encoded `FungibleTokenPacketData`.
+    Protobuf,
+}
+
+impl PacketDataEncoding {
+    /// Returns the packet data encoding of channels of the given version.
+    pub fn from_version(version: &Version) -> Result<Self, TokenTransferError> {
+        match version.app_version().as_str() {
+            VERSION => Ok(Self::Json),
+            PROTOBUF_VERSION => Ok(Self::Protobuf),
+            _ => Err(TokenTransferError::UnsupportedVersion {
+                version: version.clone(),
+            }),
+        }
+    }
+
+    /// Encodes `data` into packet bytes.
+    pub fn encode(&self, data: &PacketData) -> Vec<u8> {
+        match self {
+            Self::Json => {
+                serde_json::to_vec(data).expect("PacketData's infallible Serialize impl failed")
+            }
+            Self::Protobuf => RawPacketData::from(data.clone()).encode_to_vec(),
+        }
+    }
+
+    /// Decodes packet bytes into a `PacketData`.
+    pub fn decode(&self, data: &[u8]) -> Result<PacketData, TokenTransferError> {
+        let raw_pkt_data = match self {
+            Self::Json => {
+                let json = core::str::from_utf8(data).map_err(TokenTransferError::Utf8Decode)?;
+                serde_json::from_str::<RawPacketData>(json)
+                    .map_err(|_| TokenTransferError::PacketDataDeserialization)?
+            }
+            Self::Protobuf => RawPacketData::decode(data)
+                .map_err(|_| TokenTransferError::PacketDataDeserialization)?,
+        };
+        PacketData::try_from(raw_pkt_data)
+    }
+}
+
+/// A token transfer packet whose data has been decoded and validated, ready
+/// to be executed on the receiving chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidatedTransfer<AccountId> {
+    /// The decoded packet data
+    pub data: PacketData,
+    /// The receiver of the tokens, parsed as a host account
+    pub receiver: AccountId,
+}
+
+/// Decodes the ICS-20 packet data carried by `packet`, which is encoded with
+/// `encoding`, and validates the token, sender and receiver it contains.
+///
+/// Each failure is reported with the most specific `TokenTransferError`
+/// available, so that it can be surfaced in the error acknowledgement.
+pub fn decode_and_validate_transfer<AccountId>(
+    packet: &Packet,
+    encoding: PacketDataEncoding,
+) -> Result<ValidatedTransfer<AccountId>, TokenTransferError>
+where
+    AccountId: TryFrom<Signer>,
+{
+    let data = encoding.decode(&packet.data)?;
+
+    if data.token.amount.is_zero() {
+        return Err(TokenTransferError::ZeroAmount);
+    }
+    if data.sender.as_ref().trim().is_empty() {
+        return Err(TokenTransferError::EmptySender);
+    }
+    if data.receiver.as_ref().trim().is_empty() {
+        return Err(TokenTransferError::EmptyReceiver);
+    }
+
+    let receiver = data
+        .receiver
+        .clone()
+        .try_into()
+        .map_err(|_| TokenTransferError::ParseAccountFailure)?;
+
+    Ok(ValidatedTransfer { data, receiver })
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
     use crate::applications::transfer::BaseCoin;
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::prelude::*;
     use crate::test_utils::get_dummy_bech32_account;
     use primitive_types::U256;
 
@@ -117,4 +213,121 @@ mod tests {
         PacketData::new_dummy().deser_json_assert_eq(dummy_json_packet_data());
         PacketData::new_dummy().deser_json_assert_eq(dummy_json_packet_data_without_memo());
     }
+
+    fn transfer_packet(data: &[u8]) -> Packet {
+        let mut packet = Packet::try_from(get_dummy_raw_packet(1, 1)).unwrap();
+        packet.data = data.to_vec();
+        packet
+    }
+
+    #[test]
+    fn test_decode_and_validate_transfer() {
+        let transfer = decode_and_validate_transfer::<Signer>(
+            &transfer_packet(dummy_json_packet_data().as_bytes()),
+            PacketDataEncoding::Json,
+        )
+        .unwrap();
+
+        assert_eq!(transfer.data, PacketData::new_dummy());
+        assert_eq!(transfer.receiver, PacketData::new_dummy().receiver);
+    }
+
+    #[test]
+    fn test_packet_data_encoding_from_version() {
+        assert_eq!(
+            PacketDataEncoding::from_version(&Version::new(VERSION.to_string())).unwrap(),
+            PacketDataEncoding::Json
+        );
+        assert_eq!(
+            PacketDataEncoding::from_version(&Version::new(PROTOBUF_VERSION.to_string())).unwrap(),
+            PacketDataEncoding::Protobuf
+        );
+        assert!(matches!(
+            PacketDataEncoding::from_version(&Version::new("ics20-2".to_string())),
+            Err(TokenTransferError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_and_validate_transfer_by_channel_version() {
+        let json_packet = transfer_packet(dummy_json_packet_data().as_bytes());
+        let proto_packet =
+            transfer_packet(&RawPacketData::from(PacketData::new_dummy()).encode_to_vec());
+        let decode = |packet: &Packet, version: &str| {
+            let encoding = PacketDataEncoding::from_version(&Version::new(version.to_string()))?;
+            decode_and_validate_transfer::<Signer>(packet, encoding)
+        };
+
+        assert_eq!(
+            decode(&json_packet, VERSION).unwrap().data,
+            PacketData::new_dummy()
+        );
+        assert_eq!(
+            decode(&proto_packet, PROTOBUF_VERSION).unwrap().data,
+            PacketData::new_dummy()
+        );
+        assert!(matches!(
+            decode(&json_packet, PROTOBUF_VERSION),
+            Err(TokenTransferError::PacketDataDeserialization)
+        ));
+        assert!(decode(&proto_packet, VERSION).is_err());
+    }
+
+    #[test]
+    fn test_decode_and_validate_invalid_transfer() {
+        let account = get_dummy_bech32_account();
+        let json = |denom: &str, amount: &str, sender: &str, receiver: &str| {
+            format!(
+                r#"{{"denom":"{denom}","amount":"{amount}","sender":"{sender}","receiver":"{receiver}"}}"#
+            )
+        };
+
+        let decode = |data: &[u8]| {
+            decode_and_validate_transfer::<Signer>(&transfer_packet(data), PacketDataEncoding::Json)
+        };
+
+        assert!(matches!(
+            decode(&[0xff, 0xfe]),
+            Err(TokenTransferError::Utf8Decode(_))
+        ));
+        assert!(matches!(
+            decode(b"not json"),
+            Err(TokenTransferError::PacketDataDeserialization)
+        ));
+        assert!(matches!(
+            decode(json("", "10", &account, &account).as_bytes()),
+            Err(TokenTransferError::EmptyBaseDenom)
+        ));
+        assert!(matches!(
+            decode(json("uatom", "-1", &account, &account).as_bytes()),
+            Err(TokenTransferError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            decode(json("uatom", "0", &account, &account).as_bytes()),
+            Err(TokenTransferError::ZeroAmount)
+        ));
+        assert!(matches!(
+            decode(json("uatom", "10", "", &account).as_bytes()),
+            Err(TokenTransferError::EmptySender)
+        ));
+        assert!(matches!(
+            decode(json("uatom", "10", &account, " ").as_bytes()),
+            Err(TokenTransferError::EmptyReceiver)
+        ));
+        assert!(matches!(
+            decode(json("uatom", &"9".repeat(100), &account, &account).as_bytes()),
+            Err(TokenTransferError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            decode(
+                format!(r#"{{"denom":"uatom","sender":"{account}","receiver":"{account}"}}"#)
+                    .as_bytes()
+            ),
+            Err(TokenTransferError::PacketDataDeserialization)
+        ));
+        assert!(matches!(
+            decode(format!(r#"{{"denom":"uatom","amount":"10","sender":"{account}"}}"#).as_bytes()),
+            Err(TokenTransferError::PacketDataDeserialization)
+        ));
+    }
 }
@@ -3,7 +3,9 @@
 use crate::applications::transfer::error::TokenTransferError;
 use crate::applications::transfer::is_sender_chain_source;
 use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::{Amount, PrefixedCoin};
 use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::prelude::*;
 
 use super::context::{TokenTransferExecutionContext, TokenTransferValidationContext};
@@ -28,10 +30,13 @@ pub fn refund_packet_token_execute(
         &data.token.denom,
     ) {
         // unescrow tokens back to sender
-        let escrow_address =
-            ctx_a.get_escrow_account(&packet.port_id_on_a, &packet.chan_id_on_a)?;
-
-        ctx_a.send_coins_execute(&escrow_address, &sender, &data.token)
+        unescrow_coins_execute(
+            ctx_a,
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            &sender,
+            &data.token,
+        )
     }
     // mint vouchers back to sender
     else {
@@ -55,11 +60,110 @@ pub fn refund_packet_token_validate(
         packet.chan_id_on_a.clone(),
         &data.token.denom,
     ) {
-        let escrow_address =
-            ctx_a.get_escrow_account(&packet.port_id_on_a, &packet.chan_id_on_a)?;
-
-        ctx_a.send_coins_validate(&escrow_address, &sender, &data.token)
+        unescrow_coins_validate(
+            ctx_a,
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            &sender,
+            &data.token,
+        )
     } else {
         ctx_a.mint_coins_validate(&sender, &data.token)
     }
 }
+
+/// Validates moving `coin` from `from_account` into the escrow account of the
+/// given channel.
+fn escrow_coins_validate<Ctx: TokenTransferValidationContext>(
+    ctx: &Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    from_account: &Ctx::AccountId,
+    coin: &PrefixedCoin,
+) -> Result<(), TokenTransferError> {
+    escrow_balance_after_escrow(ctx, port_id, channel_id, coin)?;
+
+    let escrow_address = ctx.get_escrow_account(port_id, channel_id)?;
+    ctx.send_coins_validate(from_account, &escrow_address, coin)
+}
+
+/// Moves `coin` from `from_account` into the escrow account of the given
+/// channel and records it in the channel's escrow balance.
+fn escrow_coins_execute<Ctx: TokenTransferExecutionContext>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    from_account: &Ctx::AccountId,
+    coin: &PrefixedCoin,
+) -> Result<(), TokenTransferError> {
+    let balance = escrow_balance_after_escrow(ctx, port_id, channel_id, coin)?;
+
+    let escrow_address = ctx.get_escrow_account(port_id, channel_id)?;
+    ctx.send_coins_execute(from_account, &escrow_address, coin)?;
+    ctx.store_escrow_balance(port_id, channel_id, &coin.denom, balance)
+}
+
+/// Validates moving `coin` out of the escrow account of the given channel to
+/// `to_account`. Fails if more than the escrowed amount would be released.
+fn unescrow_coins_validate<Ctx: TokenTransferValidationContext>(
+    ctx: &Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    to_account: &Ctx::AccountId,
+    coin: &PrefixedCoin,
+) -> Result<(), TokenTransferError> {
+    escrow_balance_after_unescrow(ctx, port_id, channel_id, coin)?;
+
+    let escrow_address = ctx.get_escrow_account(port_id, channel_id)?;
+    ctx.send_coins_validate(&escrow_address, to_account, coin)
+}
+
+/// Moves `coin` out of the escrow account of the given channel to
+/// `to_account` and deducts it from the channel's escrow balance.
+fn unescrow_coins_execute<Ctx: TokenTransferExecutionContext>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    to_account: &Ctx::AccountId,
+    coin: &PrefixedCoin,
+) -> Result<(), TokenTransferError> {
+    let balance = escrow_balance_after_unescrow(ctx, port_id, channel_id, coin)?;
+
+    let escrow_address = ctx.get_escrow_account(port_id, channel_id)?;
+    ctx.send_coins_execute(&escrow_address, to_account, coin)?;
+    ctx.store_escrow_balance(port_id, channel_id, &coin.denom, balance)
+}
+
+fn escrow_balance_after_escrow(
+    ctx: &impl TokenTransferValidationContext,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    coin: &PrefixedCoin,
+) -> Result<Amount, TokenTransferError> {
+    ctx.get_escrow_balance(port_id, channel_id, &coin.denom)?
+        .checked_add(coin.amount)
+        .ok_or_else(|| TokenTransferError::EscrowBalanceOverflow {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            denom: coin.denom.clone(),
+        })
+}
+
+fn escrow_balance_after_unescrow(
+    ctx: &impl TokenTransferValidationContext,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    coin: &PrefixedCoin,
+) -> Result<Amount, TokenTransferError> {
+    let escrowed = ctx.get_escrow_balance(port_id, channel_id, &coin.denom)?;
+
+    escrowed
+        .checked_sub(coin.amount)
+        .ok_or_else(|| TokenTransferError::InsufficientEscrowBalance {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            denom: coin.denom.clone(),
+            escrowed,
+            requested: coin.amount,
+        })
+}
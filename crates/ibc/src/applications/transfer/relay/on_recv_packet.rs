@@ -1,8 +1,9 @@
 use crate::applications::transfer::context::TokenTransferExecutionContext;
 use crate::applications::transfer::error::TokenTransferError;
-use crate::applications::transfer::events::DenomTraceEvent;
-use crate::applications::transfer::packet::PacketData;
-use crate::applications::transfer::{is_receiver_chain_source, TracePrefix};
+use crate::applications::transfer::events::{DenomMetadataEvent, DenomTraceEvent};
+use crate::applications::transfer::packet::ValidatedTransfer;
+use crate::applications::transfer::relay::{unescrow_coins_execute, unescrow_coins_validate};
+use crate::applications::transfer::{is_receiver_chain_source, DenomMetadata, TracePrefix};
 use crate::core::ics04_channel::packet::Packet;
 use crate::core::router::ModuleExtras;
 use crate::prelude::*;
@@ -16,18 +17,16 @@ use crate::prelude::*;
 pub fn process_recv_packet_execute<Ctx: TokenTransferExecutionContext>(
     ctx_b: &mut Ctx,
     packet: &Packet,
-    data: PacketData,
+    transfer: ValidatedTransfer<Ctx::AccountId>,
 ) -> Result<ModuleExtras, (ModuleExtras, TokenTransferError)> {
     ctx_b
         .can_receive_coins()
         .map_err(|err| (ModuleExtras::empty(), err))?;
 
-    let receiver_account = data.receiver.clone().try_into().map_err(|_| {
-        (
-            ModuleExtras::empty(),
-            TokenTransferError::ParseAccountFailure,
-        )
-    })?;
+    let ValidatedTransfer {
+        data,
+        receiver: receiver_account,
+    } = transfer;
 
     let extras = if is_receiver_chain_source(
         packet.port_id_on_a.clone(),
@@ -42,10 +41,6 @@ pub fn process_recv_packet_execute<Ctx: TokenTransferExecutionContext>(
             c
         };
 
-        let escrow_address = ctx_b
-            .get_escrow_account(&packet.port_id_on_b, &packet.chan_id_on_b)
-            .map_err(|token_err| (ModuleExtras::empty(), token_err))?;
-
         // Note: it is correct to do the validation here because `recv_packet()`
         // works slightly differently. We do not have a
         // `on_recv_packet_validate()` callback because regardless of whether or
@@ -56,13 +51,23 @@ pub fn process_recv_packet_execute<Ctx: TokenTransferExecutionContext>(
         // a `TokenTransferAcknowledgement::Error` acknowledgement, which
         // gets relayed back to the sender so that the escrowed tokens
         // can be refunded.
-        ctx_b
-            .send_coins_validate(&escrow_address, &receiver_account, &coin)
-            .map_err(|token_err| (ModuleExtras::empty(), token_err))?;
+        unescrow_coins_validate(
+            ctx_b,
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+            &receiver_account,
+            &coin,
+        )
+        .map_err(|token_err| (ModuleExtras::empty(), token_err))?;
 
-        ctx_b
-            .send_coins_execute(&escrow_address, &receiver_account, &coin)
-            .map_err(|token_err| (ModuleExtras::empty(), token_err))?;
+        unescrow_coins_execute(
+            ctx_b,
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+            &receiver_account,
+            &coin,
+        )
+        .map_err(|token_err| (ModuleExtras::empty(), token_err))?;
 
         ModuleExtras::empty()
     } else {
@@ -74,7 +79,7 @@ pub fn process_recv_packet_execute<Ctx: TokenTransferExecutionContext>(
             c
         };
 
-        let extras = {
+        let mut extras = {
             let denom_trace_event = DenomTraceEvent {
                 trace_hash: ctx_b.denom_hash_string(&coin.denom),
                 denom: coin.denom.clone(),
@@ -103,6 +108,18 @@ pub fn process_recv_packet_execute<Ctx: TokenTransferExecutionContext>(
             .mint_coins_execute(&receiver_account, &coin)
             .map_err(|token_err| (extras.clone(), token_err))?;
 
+        // The first voucher received for a denomination records its metadata.
+        let stored_metadata = ctx_b
+            .get_denom_metadata(&coin.denom)
+            .map_err(|token_err| (extras.clone(), token_err))?;
+        if stored_metadata.is_none() {
+            let metadata = DenomMetadata::for_voucher(&coin.denom);
+            ctx_b
+                .store_denom_metadata(metadata.clone())
+                .map_err(|token_err| (extras.clone(), token_err))?;
+            extras.events.push(DenomMetadataEvent { metadata }.into());
+        }
+
         extras
     };
 
@@ -1,10 +1,11 @@
 use crate::applications::transfer::context::{
-    TokenTransferExecutionContext, TokenTransferValidationContext,
+    validate_memo, TokenTransferExecutionContext, TokenTransferValidationContext,
 };
 use crate::applications::transfer::error::TokenTransferError;
 use crate::applications::transfer::events::TransferEvent;
 use crate::applications::transfer::msgs::transfer::MsgTransfer;
-use crate::applications::transfer::{is_sender_chain_source, MODULE_ID_STR};
+use crate::applications::transfer::relay::{escrow_coins_execute, escrow_coins_validate};
+use crate::applications::transfer::{is_sender_chain_source, PrefixedDenom, MODULE_ID_STR};
 use crate::core::events::{MessageEvent, ModuleEvent};
 use crate::core::ics04_channel::context::{
     SendPacketExecutionContext, SendPacketValidationContext,
@@ -32,7 +33,7 @@ where
 pub fn send_transfer_validate<SendPacketCtx, TokenCtx>(
     send_packet_ctx_a: &SendPacketCtx,
     token_ctx_a: &TokenCtx,
-    msg: MsgTransfer,
+    mut msg: MsgTransfer,
 ) -> Result<(), TokenTransferError>
 where
     SendPacketCtx: SendPacketValidationContext,
@@ -40,6 +41,8 @@ where
 {
     token_ctx_a.can_send_coins()?;
 
+    validate_memo(token_ctx_a, &msg.packet_data.memo)?;
+
     let chan_end_path_on_a = ChannelEndPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
     let chan_end_on_a = send_packet_ctx_a.channel_end(&chan_end_path_on_a)?;
 
@@ -56,6 +59,7 @@ where
     let seq_send_path_on_a = SeqSendPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
     let sequence = send_packet_ctx_a.get_next_sequence_send(&seq_send_path_on_a)?;
 
+    msg.packet_data.token.denom = resolve_denom_trace(token_ctx_a, &msg.packet_data.token.denom)?;
     let token = &msg.packet_data.token;
 
     let sender: TokenCtx::AccountId = msg
@@ -70,16 +74,21 @@ where
         msg.chan_id_on_a.clone(),
         &token.denom,
     ) {
-        let escrow_address =
-            token_ctx_a.get_escrow_account(&msg.port_id_on_a, &msg.chan_id_on_a)?;
-        token_ctx_a.send_coins_validate(&sender, &escrow_address, token)?;
+        escrow_coins_validate(
+            token_ctx_a,
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &sender,
+            token,
+        )?;
     } else {
         token_ctx_a.burn_coins_validate(&sender, token)?;
     }
 
     let packet = {
-        let data = serde_json::to_vec(&msg.packet_data)
-            .expect("PacketData's infallible Serialize impl failed");
+        let data = token_ctx_a
+            .packet_data_encoding(&msg.port_id_on_a, &msg.chan_id_on_a)?
+            .encode(&msg.packet_data);
 
         Packet {
             seq_on_a: sequence,
@@ -98,11 +107,36 @@ where
     Ok(())
 }
 
+/// Returns the denomination trace of `denom` if it is a hashed `ibc/{hash}`
+/// voucher, so that it is burned rather than escrowed and the counterparty
+/// receives its full path, and `denom` itself otherwise.
+///
+/// A native token must not masquerade as a hashed IBC voucher, otherwise the
+/// counterparty would trace it back to a denomination it never sent, so
+/// hashes without a registered trace are rejected.
+fn resolve_denom_trace(
+    token_ctx_a: &impl TokenTransferValidationContext,
+    denom: &PrefixedDenom,
+) -> Result<PrefixedDenom, TokenTransferError> {
+    if !denom.trace_path.is_empty() {
+        return Ok(denom.clone());
+    }
+
+    match denom.base_denom.ibc_denom_hash() {
+        Some(denom_hash) => token_ctx_a.denom_trace(denom_hash).ok_or_else(|| {
+            TokenTransferError::InvalidDenomForTransfer {
+                denom: denom.to_string(),
+            }
+        }),
+        None => Ok(denom.clone()),
+    }
+}
+
 /// Executes the token transfer. A prior call to [`send_transfer_validate`] MUST have succeeded.
 pub fn send_transfer_execute<SendPacketCtx, TokenCtx>(
     send_packet_ctx_a: &mut SendPacketCtx,
     token_ctx_a: &mut TokenCtx,
-    msg: MsgTransfer,
+    mut msg: MsgTransfer,
 ) -> Result<(), TokenTransferError>
 where
     SendPacketCtx: SendPacketExecutionContext,
@@ -125,6 +159,7 @@ where
     let seq_send_path_on_a = SeqSendPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
     let sequence = send_packet_ctx_a.get_next_sequence_send(&seq_send_path_on_a)?;
 
+    msg.packet_data.token.denom = resolve_denom_trace(token_ctx_a, &msg.packet_data.token.denom)?;
     let token = &msg.packet_data.token;
 
     let sender = msg
@@ -139,18 +174,21 @@ where
         msg.chan_id_on_a.clone(),
         &token.denom,
     ) {
-        let escrow_address =
-            token_ctx_a.get_escrow_account(&msg.port_id_on_a, &msg.chan_id_on_a)?;
-        token_ctx_a.send_coins_execute(&sender, &escrow_address, token)?;
+        escrow_coins_execute(
+            token_ctx_a,
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &sender,
+            token,
+        )?;
     } else {
         token_ctx_a.burn_coins_execute(&sender, token)?;
     }
 
     let packet = {
-        let data = {
-            serde_json::to_vec(&msg.packet_data)
-                .expect("PacketData's infallible Serialize impl failed")
-        };
+        let data = token_ctx_a
+            .packet_data_encoding(&msg.port_id_on_a, &msg.chan_id_on_a)?
+            .encode(&msg.packet_data);
 
         Packet {
             seq_on_a: sequence,
@@ -186,3 +224,197 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::applications::transfer::packet::PacketDataEncoding;
+    use crate::applications::transfer::{BaseCoin, PrefixedCoin, PROTOBUF_VERSION};
+    use crate::core::ics02_client::height::Height;
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::timestamp::ZERO_DURATION;
+    use crate::mock::context::MockContext;
+    use crate::test_utils::DummyTransferModule;
+
+    #[fixture]
+    fn context() -> MockContext {
+        let chan_end_on_a = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::new("ics20-1".to_string()),
+        )
+        .unwrap();
+
+        let conn_end_on_a = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        MockContext::default()
+            .with_client(&ClientId::default(), Height::new(0, 5).unwrap())
+            .with_connection(ConnectionId::default(), conn_end_on_a)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end_on_a)
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into())
+    }
+
+    fn msg_with_denom(denom: &str) -> MsgTransfer {
+        let mut msg = MsgTransfer::new_dummy(Height::new(0, 10).unwrap().into(), None);
+        msg.packet_data.token = PrefixedCoin::from(BaseCoin {
+            denom: denom.parse().unwrap(),
+            amount: msg.packet_data.token.amount,
+        });
+        msg
+    }
+
+    #[rstest]
+    fn send_transfer_validate_native_denom(context: MockContext) {
+        let res = send_transfer_validate(
+            &context,
+            &DummyTransferModule::new(),
+            msg_with_denom("uatom"),
+        );
+
+        assert!(res.is_ok(), "validation failed: {res:?}");
+    }
+
+    #[rstest]
+    fn send_transfer_validate_fake_ibc_denom(context: MockContext) {
+        let msg =
+            msg_with_denom("ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2");
+
+        let res = send_transfer_validate(&context, &DummyTransferModule::new(), msg);
+
+        assert!(
+            matches!(res, Err(TokenTransferError::InvalidDenomForTransfer { .. })),
+            "unexpected result: {res:?}"
+        );
+    }
+
+    #[rstest]
+    fn send_transfer_burns_hashed_voucher(mut context: MockContext) {
+        let trace: PrefixedDenom = format!("{}/channel-0/uatom", PortId::default())
+            .parse()
+            .unwrap();
+        let mut token_ctx = DummyTransferModule::new().with_denom_trace(trace.clone());
+        let denom_hash = token_ctx.denom_hash_string(&trace).unwrap();
+
+        let msg = msg_with_denom(&format!("ibc/{denom_hash}"));
+        let sender = msg.packet_data.sender.clone();
+        let voucher = PrefixedCoin {
+            denom: trace.clone(),
+            amount: msg.packet_data.token.amount,
+        };
+        token_ctx.mint_coins_execute(&sender, &voucher).unwrap();
+
+        let res = send_transfer(&mut context, &mut token_ctx, msg);
+        assert!(res.is_ok(), "transfer failed: {res:?}");
+
+        // The voucher is burned under its trace, and nothing is escrowed.
+        assert_eq!(token_ctx.minted_balance(&sender, &trace), 0u64.into());
+        assert_eq!(
+            token_ctx
+                .get_escrow_balance(&PortId::default(), &ChannelId::default(), &trace)
+                .unwrap(),
+            0u64.into()
+        );
+    }
+
+    #[rstest]
+    fn send_transfer_encodes_by_channel_version(mut context: MockContext) {
+        let mut token_ctx = DummyTransferModule::new().with_channel_version(
+            PortId::default(),
+            ChannelId::default(),
+            Version::new(PROTOBUF_VERSION.to_string()),
+        );
+        let msg = msg_with_denom("uatom");
+        let packet_data = msg.packet_data.clone();
+
+        let res = send_transfer(&mut context, &mut token_ctx, msg);
+        assert!(res.is_ok(), "transfer failed: {res:?}");
+
+        let sent = context
+            .events
+            .iter()
+            .find_map(|event| event.as_send_packet())
+            .expect("no send packet event");
+        assert_eq!(
+            PacketDataEncoding::Protobuf
+                .decode(sent.packet_data())
+                .unwrap(),
+            packet_data
+        );
+    }
+
+    #[rstest]
+    fn send_transfer_fail_insufficient_funds_to_escrow(context: MockContext) {
+        let msg = msg_with_denom("uatom");
+        let funds = PrefixedCoin {
+            denom: msg.packet_data.token.denom.clone(),
+            amount: 1u64.into(),
+        };
+        let token_ctx =
+            DummyTransferModule::new().with_funds(msg.packet_data.sender.clone(), funds);
+
+        let res = send_transfer_validate(&context, &token_ctx, msg);
+
+        assert!(
+            matches!(&res, Err(e @ TokenTransferError::InsufficientFunds { .. }) if !e.retriable()),
+            "unexpected result: {res:?}"
+        );
+    }
+
+    #[rstest]
+    fn send_transfer_fail_insufficient_funds_to_burn(context: MockContext) {
+        let trace: PrefixedDenom = format!("{}/channel-0/uatom", PortId::default())
+            .parse()
+            .unwrap();
+        let token_ctx = DummyTransferModule::new().with_denom_trace(trace.clone());
+        let denom_hash = token_ctx.denom_hash_string(&trace).unwrap();
+
+        // No voucher was minted to the sender.
+        let res = send_transfer_validate(
+            &context,
+            &token_ctx,
+            msg_with_denom(&format!("ibc/{denom_hash}")),
+        );
+
+        assert!(
+            matches!(&res, Err(e @ TokenTransferError::InsufficientFunds { .. }) if !e.retriable()),
+            "unexpected result: {res:?}"
+        );
+    }
+
+    #[rstest]
+    fn send_transfer_validate_oversized_memo(context: MockContext) {
+        let mut msg = msg_with_denom("uatom");
+        msg.packet_data.memo = "a"
+            .repeat(crate::applications::transfer::DEFAULT_MAX_MEMO_BYTES + 1)
+            .into();
+
+        let res = send_transfer_validate(&context, &DummyTransferModule::new(), msg);
+
+        assert!(
+            matches!(res, Err(TokenTransferError::MemoTooLarge { .. })),
+            "unexpected result: {res:?}"
+        );
+    }
+}
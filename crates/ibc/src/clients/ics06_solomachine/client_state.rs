@@ -0,0 +1,651 @@
+//! Implements the core [`ClientState`](crate::core::ics02_client::client_state::ClientState) trait
+//! for the solo machine light client.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics06_solomachine::client_type as solomachine_client_type;
+use crate::clients::ics06_solomachine::consensus_state::ConsensusState as SoloMachineConsensusState;
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::header::Header;
+use crate::clients::ics06_solomachine::proto::{
+    ClientState as RawSoloMachineClientState,
+    TimestampedSignatureData as RawTimestampedSignatureData,
+};
+use crate::clients::ics06_solomachine::sign_bytes::SignBytes;
+use crate::core::ics02_client::client_state::{
+    ClientStateCommon, ClientStateExecution, ClientStateValidation, Status, UpdateKind,
+};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::ClientExecutionContext;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics23_commitment::merkle::apply_prefix;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath, Path};
+use crate::core::timestamp::Timestamp;
+use crate::Height;
+
+pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ClientState";
+
+/// Defines the solo machine light client's client state.
+///
+/// The client tracks the solo machine's current `sequence`, which is also
+/// its latest height (at revision 0), and its current consensus state.
+/// Proofs are signatures by the current key at the current sequence. Executing
+/// a message whose proofs the client verified advances the sequence, so that
+/// a proof can only be used once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientState {
+    pub sequence: u64,
+    pub is_frozen: bool,
+    pub consensus_state: SoloMachineConsensusState,
+}
+
+impl ClientState {
+    pub fn new(sequence: u64, consensus_state: SoloMachineConsensusState) -> Result<Self, Error> {
+        if sequence == 0 {
+            return Err(Error::ZeroSequence);
+        }
+
+        Ok(Self {
+            sequence,
+            is_frozen: false,
+            consensus_state,
+        })
+    }
+
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence).expect("Never fails because the sequence is non-zero")
+    }
+
+    /// Verifies that `proof` signs `data` under `path` at the current
+    /// sequence.
+    fn verify_signed_data(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        path: Path,
+        data: Vec<u8>,
+    ) -> Result<(), Error> {
+        let proof = RawTimestampedSignatureData::decode(proof.as_bytes()).map_err(Error::Decode)?;
+        let timestamp =
+            Timestamp::from_nanoseconds(proof.timestamp).map_err(Error::InvalidTimestamp)?;
+        self.check_timestamp(timestamp)?;
+
+        let merkle_path = apply_prefix(prefix, vec![path.to_string()]);
+        let sign_bytes = SignBytes {
+            sequence: self.sequence,
+            timestamp,
+            diversifier: self.consensus_state.diversifier.clone(),
+            path: merkle_path.encode_to_vec(),
+            data,
+        };
+
+        sign_bytes.verify_signature(self.consensus_state.public_key, &proof.signature_data)
+    }
+
+    /// Moves the client to the next sequence with `consensus_state`, and
+    /// returns the new height.
+    fn advance_sequence<E>(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        consensus_state: SoloMachineConsensusState,
+    ) -> Result<Height, ClientError>
+    where
+        E: ClientExecutionContext,
+        <E as ClientExecutionContext>::AnyClientState: From<ClientState>,
+        <E as ClientExecutionContext>::AnyConsensusState: From<SoloMachineConsensusState>,
+    {
+        let sequence = self
+            .sequence
+            .checked_add(1)
+            .ok_or(Error::SequenceOverflow)?;
+        let new_client_state = ClientState {
+            sequence,
+            consensus_state: consensus_state.clone(),
+            ..self.clone()
+        };
+        let new_height = new_client_state.latest_height();
+
+        ctx.store_consensus_state(
+            ClientConsensusStatePath::new(client_id, &new_height),
+            consensus_state.into(),
+        )?;
+        ctx.store_client_state(ClientStatePath::new(client_id), new_client_state.into())?;
+
+        Ok(new_height)
+    }
+
+    /// Signatures may not predate the latest update.
+    fn check_timestamp(&self, timestamp: Timestamp) -> Result<(), Error> {
+        if timestamp < self.consensus_state.timestamp {
+            return Err(Error::TimestampTooOld {
+                consensus_timestamp: self.consensus_state.timestamp,
+                timestamp,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl ClientStateCommon for ClientState {
+    fn verify_consensus_state(&self, consensus_state: Any) -> Result<(), ClientError> {
+        let _solomachine_consensus_state = SoloMachineConsensusState::try_from(consensus_state)?;
+
+        Ok(())
+    }
+
+    fn client_type(&self) -> ClientType {
+        solomachine_client_type()
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    /// Proofs are only valid at the current sequence.
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        let latest_height = self.latest_height();
+        if latest_height != proof_height {
+            return Err(ClientError::InvalidProofHeight {
+                latest_height,
+                proof_height,
+            });
+        }
+        Ok(())
+    }
+
+    /// A header is signed at the current sequence, and moves the client to
+    /// the next sequence.
+    fn header_height(&self, header: Any) -> Result<Height, ClientError> {
+        let _header = Header::try_from(header)?;
+        let sequence = self
+            .sequence
+            .checked_add(1)
+            .ok_or(Error::SequenceOverflow)?;
+        Height::new(0, sequence)
+    }
+
+    fn verify_upgrade_client(
+        &self,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+        _proof_upgrade_client: CommitmentProofBytes,
+        _proof_upgrade_consensus_state: CommitmentProofBytes,
+        _root: &CommitmentRoot,
+    ) -> Result<(), ClientError> {
+        Err(Error::UpgradeNotSupported.into())
+    }
+
+    /// `proof` is a `TimestampedSignatureData`, signing the
+    /// [`SignBytes`] of `value` under `path`. The `root` is ignored.
+    fn verify_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        self.verify_signed_data(prefix, proof, path, value)
+            .map_err(Into::into)
+    }
+
+    /// Same as [`ClientStateCommon::verify_membership`] above, with empty
+    /// data.
+    fn verify_non_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        self.verify_signed_data(prefix, proof, path, Vec::new())
+            .map_err(Into::into)
+    }
+}
+
+impl<ClientValidationContext> ClientStateValidation<ClientValidationContext> for ClientState {
+    /// The header must be signed by the current key at the current sequence.
+    fn verify_client_message(
+        &self,
+        _ctx: &ClientValidationContext,
+        _client_id: &ClientId,
+        client_message: Any,
+        update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        match update_kind {
+            UpdateKind::UpdateClient => {
+                let header = Header::try_from(client_message)?;
+                self.check_timestamp(header.timestamp)?;
+
+                header
+                    .sign_bytes(self.sequence, &self.consensus_state.diversifier)
+                    .verify_signature(self.consensus_state.public_key, &header.signature)
+                    .map_err(Into::into)
+            }
+            UpdateKind::SubmitMisbehaviour => Err(Error::MisbehaviourNotSupported.into()),
+        }
+    }
+
+    fn check_for_misbehaviour(
+        &self,
+        _ctx: &ClientValidationContext,
+        _client_id: &ClientId,
+        _client_message: Any,
+        _update_kind: &UpdateKind,
+    ) -> Result<bool, ClientError> {
+        Ok(false)
+    }
+
+    fn status(
+        &self,
+        _ctx: &ClientValidationContext,
+        _client_id: &ClientId,
+    ) -> Result<Status, ClientError> {
+        if self.is_frozen {
+            return Ok(Status::Frozen);
+        }
+
+        Ok(Status::Active)
+    }
+}
+
+impl<E> ClientStateExecution<E> for ClientState
+where
+    E: ClientExecutionContext,
+    <E as ClientExecutionContext>::AnyClientState: From<ClientState>,
+    <E as ClientExecutionContext>::AnyConsensusState: From<SoloMachineConsensusState>,
+{
+    fn initialise(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        consensus_state: Any,
+    ) -> Result<(), ClientError> {
+        let solomachine_consensus_state = SoloMachineConsensusState::try_from(consensus_state)?;
+
+        ctx.store_client_state(ClientStatePath::new(client_id), self.clone().into())?;
+        ctx.store_consensus_state(
+            ClientConsensusStatePath::new(client_id, &self.latest_height()),
+            solomachine_consensus_state.into(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Advances the client by one sequence, rotating the key and the
+    /// diversifier to the ones the header sets, if any.
+    fn update_state(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        header: Any,
+    ) -> Result<Vec<Height>, ClientError> {
+        let header = Header::try_from(header)?;
+
+        let new_consensus_state = SoloMachineConsensusState::new(
+            header
+                .new_public_key
+                .unwrap_or(self.consensus_state.public_key),
+            header
+                .new_diversifier
+                .unwrap_or_else(|| self.consensus_state.diversifier.clone()),
+            header.timestamp,
+        );
+        let new_height = self.advance_sequence(ctx, client_id, new_consensus_state)?;
+
+        Ok(vec![new_height])
+    }
+
+    fn update_state_on_misbehaviour(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _client_message: Any,
+        _update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        Err(Error::MisbehaviourNotSupported.into())
+    }
+
+    fn update_state_on_upgrade(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+    ) -> Result<Height, ClientError> {
+        Err(Error::UpgradeNotSupported.into())
+    }
+
+    /// Proofs are signatures at the current sequence, so moving to the next
+    /// sequence prevents them from being verified again.
+    fn update_state_on_verified_proofs(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+    ) -> Result<(), ClientError> {
+        self.advance_sequence(ctx, client_id, self.consensus_state.clone())?;
+
+        Ok(())
+    }
+}
+
+impl Protobuf<RawSoloMachineClientState> for ClientState {}
+
+impl TryFrom<RawSoloMachineClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawSoloMachineClientState) -> Result<Self, Self::Error> {
+        let consensus_state = raw
+            .consensus_state
+            .ok_or(Error::MissingConsensusState)?
+            .try_into()?;
+
+        Ok(Self {
+            is_frozen: raw.is_frozen,
+            ..Self::new(raw.sequence, consensus_state)?
+        })
+    }
+}
+
+impl From<ClientState> for RawSoloMachineClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            sequence: value.sequence,
+            is_frozen: value.is_frozen,
+            consensus_state: Some(value.consensus_state.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_client_state<B: Buf>(buf: B) -> Result<ClientState, Error> {
+            RawSoloMachineClientState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => {
+                decode_client_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSoloMachineClientState>::encode_vec(&client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::*;
+
+    use ed25519_consensus::SigningKey;
+    use tendermint::PublicKey;
+
+    pub(crate) fn public_key(key: &SigningKey) -> PublicKey {
+        PublicKey::from_raw_ed25519(key.verification_key().as_bytes()).unwrap()
+    }
+
+    /// Signs `value` under `path`, prefixed by `prefix`, at the current
+    /// sequence of `client_state`.
+    pub(crate) fn prove(
+        key: &SigningKey,
+        client_state: &ClientState,
+        prefix: &CommitmentPrefix,
+        path: Path,
+        value: Vec<u8>,
+    ) -> CommitmentProofBytes {
+        let timestamp = client_state.consensus_state.timestamp;
+        let sign_bytes = SignBytes {
+            sequence: client_state.sequence,
+            timestamp,
+            diversifier: client_state.consensus_state.diversifier.clone(),
+            path: apply_prefix(prefix, vec![path.to_string()]).encode_to_vec(),
+            data: value,
+        };
+        let proof = RawTimestampedSignatureData {
+            signature_data: key.sign(&sign_bytes.encode_vec()).to_bytes().to_vec(),
+            timestamp: timestamp.nanoseconds(),
+        };
+
+        proof.encode_to_vec().try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ed25519_consensus::SigningKey;
+
+    use super::test_util::public_key;
+
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::core::ics24_host::path::ChannelEndPath;
+    use crate::core::ContextError;
+
+    const DIVERSIFIER: &str = "wallet";
+
+    /// Stores the client and consensus states of a single client.
+    #[derive(Default)]
+    struct Store {
+        client_state: Option<ClientState>,
+        consensus_states: Vec<(Height, SoloMachineConsensusState)>,
+    }
+
+    impl ClientExecutionContext for Store {
+        type ClientValidationContext = Store;
+        type AnyClientState = ClientState;
+        type AnyConsensusState = SoloMachineConsensusState;
+
+        fn store_client_state(
+            &mut self,
+            _client_state_path: ClientStatePath,
+            client_state: ClientState,
+        ) -> Result<(), ContextError> {
+            self.client_state = Some(client_state);
+            Ok(())
+        }
+
+        fn store_consensus_state(
+            &mut self,
+            consensus_state_path: ClientConsensusStatePath,
+            consensus_state: SoloMachineConsensusState,
+        ) -> Result<(), ContextError> {
+            let height = Height::new(consensus_state_path.epoch, consensus_state_path.height)
+                .expect("Never fails because the path height is non-zero");
+            self.consensus_states.push((height, consensus_state));
+            Ok(())
+        }
+    }
+
+    fn timestamp(nanoseconds: u64) -> Timestamp {
+        Timestamp::from_nanoseconds(nanoseconds).unwrap()
+    }
+
+    fn client_state(key: &SigningKey) -> ClientState {
+        let consensus_state =
+            SoloMachineConsensusState::new(public_key(key), DIVERSIFIER.to_string(), timestamp(10));
+
+        ClientState::new(1, consensus_state).unwrap()
+    }
+
+    fn prefix() -> CommitmentPrefix {
+        CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap()
+    }
+
+    /// Signs `value` under `path` at the current sequence of `client_state`.
+    fn prove(
+        key: &SigningKey,
+        client_state: &ClientState,
+        path: Path,
+        value: Vec<u8>,
+    ) -> CommitmentProofBytes {
+        test_util::prove(key, client_state, &prefix(), path, value)
+    }
+
+    /// Returns the channel end that a chain opening a channel with the solo
+    /// machine expects the solo machine to have committed.
+    fn counterparty_channel_end(state: State) -> (Path, ChannelEnd) {
+        let chan_end = ChannelEnd::new(
+            state,
+            Order::Unordered,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::new("ics20-1".to_string()),
+        )
+        .unwrap();
+
+        (
+            ChannelEndPath::new(&PortId::transfer(), &ChannelId::default()).into(),
+            chan_end,
+        )
+    }
+
+    #[test]
+    fn verify_signed_channel_end() {
+        let key = SigningKey::from([1; 32]);
+        let client_state = client_state(&key);
+        let root = CommitmentRoot::from(vec![]);
+
+        let (path, chan_end) = counterparty_channel_end(State::Open);
+        let proof = prove(&key, &client_state, path.clone(), chan_end.encode_vec());
+
+        assert!(client_state
+            .validate_proof_height(Height::new(0, 1).unwrap())
+            .is_ok());
+        let res =
+            client_state.verify_membership(&prefix(), &proof, &root, path, chan_end.encode_vec());
+        assert!(res.is_ok(), "signed channel end should verify: {res:?}");
+
+        let (path, chan_end) = counterparty_channel_end(State::TryOpen);
+        let res =
+            client_state.verify_membership(&prefix(), &proof, &root, path, chan_end.encode_vec());
+        assert!(res.is_err(), "a channel end that was not signed verified");
+
+        let other_key = SigningKey::from([2; 32]);
+        let (path, chan_end) = counterparty_channel_end(State::Open);
+        let proof = prove(
+            &other_key,
+            &client_state,
+            path.clone(),
+            chan_end.encode_vec(),
+        );
+        let res =
+            client_state.verify_membership(&prefix(), &proof, &root, path, chan_end.encode_vec());
+        assert!(res.is_err(), "a signature by another key verified");
+    }
+
+    #[test]
+    fn update_state_rotates_key() {
+        let key = SigningKey::from([1; 32]);
+        let new_key = SigningKey::from([2; 32]);
+        let client_state = client_state(&key);
+        let client_id = ClientId::default();
+        let mut store = Store::default();
+
+        let mut header = Header {
+            timestamp: timestamp(30),
+            signature: Vec::new(),
+            new_public_key: Some(public_key(&new_key)),
+            new_diversifier: None,
+        };
+        header.signature = new_key
+            .sign(&header.sign_bytes(1, DIVERSIFIER).encode_vec())
+            .to_bytes()
+            .to_vec();
+        let res = client_state.verify_client_message(
+            &store,
+            &client_id,
+            header.clone().into(),
+            &UpdateKind::UpdateClient,
+        );
+        assert!(res.is_err(), "header signed by the next key verified");
+
+        header.signature = key
+            .sign(&header.sign_bytes(1, DIVERSIFIER).encode_vec())
+            .to_bytes()
+            .to_vec();
+        client_state
+            .verify_client_message(
+                &store,
+                &client_id,
+                header.clone().into(),
+                &UpdateKind::UpdateClient,
+            )
+            .unwrap();
+
+        let heights = client_state
+            .update_state(&mut store, &client_id, header.into())
+            .unwrap();
+        assert_eq!(heights, vec![Height::new(0, 2).unwrap()]);
+
+        let new_client_state = store.client_state.clone().unwrap();
+        assert_eq!(new_client_state.sequence, 2);
+        assert_eq!(
+            new_client_state.consensus_state.public_key,
+            public_key(&new_key)
+        );
+        assert_eq!(new_client_state.consensus_state.diversifier, DIVERSIFIER);
+        assert_eq!(
+            store.consensus_states,
+            vec![(heights[0], new_client_state.consensus_state.clone())]
+        );
+
+        let root = CommitmentRoot::from(vec![]);
+        let (path, chan_end) = counterparty_channel_end(State::Open);
+        let stale_proof = prove(&key, &client_state, path.clone(), chan_end.encode_vec());
+        let res = new_client_state.verify_membership(
+            &prefix(),
+            &stale_proof,
+            &root,
+            path.clone(),
+            chan_end.encode_vec(),
+        );
+        assert!(res.is_err(), "a proof by the rotated key verified");
+
+        let proof = prove(
+            &new_key,
+            &new_client_state,
+            path.clone(),
+            chan_end.encode_vec(),
+        );
+        let res = new_client_state.verify_membership(
+            &prefix(),
+            &proof,
+            &root,
+            path,
+            chan_end.encode_vec(),
+        );
+        assert!(res.is_ok(), "proof by the new key should verify: {res:?}");
+    }
+}
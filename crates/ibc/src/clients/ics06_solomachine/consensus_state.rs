@@ -0,0 +1,144 @@
+//! Defines the solo machine light client's `ConsensusState` type
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use tendermint::PublicKey;
+use tendermint_proto::crypto::PublicKey as RawPublicKey;
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::proto::ConsensusState as RawSoloMachineConsensusState;
+use crate::core::ics02_client::consensus_state::ConsensusState as ConsensusStateTrait;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::core::timestamp::Timestamp;
+
+pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v3.ConsensusState";
+
+pub const ED25519_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+/// Defines the solo machine light client's consensus state.
+///
+/// It holds the key the solo machine currently signs with, the diversifier
+/// that lets the same key be used by several clients, and the timestamp of
+/// the latest update. The solo machine has no commitment root, so `root` is
+/// empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+    pub public_key: PublicKey,
+    pub diversifier: String,
+    pub timestamp: Timestamp,
+    pub root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: Timestamp) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            timestamp,
+            root: CommitmentRoot::from(vec![]),
+        }
+    }
+}
+
+/// Decodes a Cosmos SDK `ed25519.PubKey`, the only key type supported.
+///
+/// Its encoding is the one of the `ed25519` variant of Tendermint's
+/// `PublicKey`, which is used to decode it.
+pub(crate) fn decode_public_key(raw: Any) -> Result<PublicKey, Error> {
+    if raw.type_url != ED25519_PUBLIC_KEY_TYPE_URL {
+        return Err(Error::UnsupportedPublicKey {
+            type_url: raw.type_url,
+        });
+    }
+
+    RawPublicKey::decode(raw.value.as_slice())
+        .map_err(Error::Decode)?
+        .try_into()
+        .map_err(Error::InvalidPublicKey)
+}
+
+/// See [`decode_public_key`].
+pub(crate) fn encode_public_key(public_key: PublicKey) -> Any {
+    Any {
+        type_url: ED25519_PUBLIC_KEY_TYPE_URL.to_string(),
+        value: RawPublicKey::from(public_key).encode_to_vec(),
+    }
+}
+
+impl Protobuf<RawSoloMachineConsensusState> for ConsensusState {}
+
+impl TryFrom<RawSoloMachineConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawSoloMachineConsensusState) -> Result<Self, Self::Error> {
+        let public_key = decode_public_key(raw.public_key.ok_or(Error::MissingPublicKey)?)?;
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::InvalidTimestamp)?;
+
+        Ok(Self::new(public_key, raw.diversifier, timestamp))
+    }
+}
+
+impl From<ConsensusState> for RawSoloMachineConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        Self {
+            public_key: Some(encode_public_key(value.public_key)),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_consensus_state<B: Buf>(buf: B) -> Result<ConsensusState, Error> {
+            RawSoloMachineConsensusState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => {
+                decode_consensus_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSoloMachineConsensusState>::encode_vec(&consensus_state),
+        }
+    }
+}
+
+impl ConsensusStateTrait for ConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    fn encode_vec(&self) -> Vec<u8> {
+        <Self as Protobuf<Any>>::encode_vec(self)
+    }
+}
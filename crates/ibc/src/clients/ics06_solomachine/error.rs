@@ -0,0 +1,66 @@
+//! Defines the solo machine light client's error type
+
+use crate::prelude::*;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::core::timestamp::{ParseTimestampError, Timestamp};
+
+use displaydoc::Display;
+use tendermint::crypto::signature::Error as SignatureError;
+use tendermint::Error as TendermintError;
+
+/// The main error type
+#[derive(Debug, Display)]
+pub enum Error {
+    /// the sequence of a solo machine client must be non-zero
+    ZeroSequence,
+    /// missing consensus state
+    MissingConsensusState,
+    /// missing public key
+    MissingPublicKey,
+    /// unsupported public key type: `{type_url}`
+    UnsupportedPublicKey { type_url: String },
+    /// invalid public key: `{0}`
+    InvalidPublicKey(TendermintError),
+    /// invalid signature: `{0}`
+    InvalidSignature(TendermintError),
+    /// signature verification failed: `{0}`
+    SignatureVerification(SignatureError),
+    /// invalid timestamp: `{0}`
+    InvalidTimestamp(ParseTimestampError),
+    /// decode error: `{0}`
+    Decode(prost::DecodeError),
+    /// timestamp `{timestamp}` is older than the consensus state timestamp `{consensus_timestamp}`
+    TimestampTooOld {
+        consensus_timestamp: Timestamp,
+        timestamp: Timestamp,
+    },
+    /// the sequence of the solo machine client overflowed
+    SequenceOverflow,
+    /// the solo machine client does not support misbehaviour
+    MisbehaviourNotSupported,
+    /// the solo machine client cannot be upgraded
+    UpgradeNotSupported,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            Self::InvalidPublicKey(e) => Some(e),
+            Self::InvalidSignature(e) => Some(e),
+            Self::SignatureVerification(e) => Some(e),
+            Self::InvalidTimestamp(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ClientError {
+    fn from(e: Error) -> Self {
+        Self::ClientSpecific {
+            description: e.to_string(),
+        }
+    }
+}
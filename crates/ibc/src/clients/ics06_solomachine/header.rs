@@ -0,0 +1,116 @@
+//! Defines the solo machine light client's `Header` type
+
+use crate::prelude::*;
+
+use bytes::Buf;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use tendermint::PublicKey;
+
+use crate::clients::ics06_solomachine::consensus_state::{decode_public_key, encode_public_key};
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::proto::{Header as RawHeader, HeaderData as RawHeaderData};
+use crate::clients::ics06_solomachine::sign_bytes::{SignBytes, HEADER_SIGN_PATH};
+use crate::core::ics02_client::error::ClientError;
+use crate::core::timestamp::Timestamp;
+
+pub const SOLOMACHINE_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Header";
+
+/// Solo machine header, signed by the current key at the current sequence of
+/// the client.
+///
+/// Applying it advances the client by one sequence and, if `new_public_key`
+/// or `new_diversifier` is set, rotates the key or the diversifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub timestamp: Timestamp,
+    pub signature: Vec<u8>,
+    pub new_public_key: Option<PublicKey>,
+    pub new_diversifier: Option<String>,
+}
+
+impl Header {
+    /// Returns the bytes the solo machine signs for this header, given the
+    /// current sequence of the client and the diversifier of its consensus
+    /// state.
+    pub fn sign_bytes(&self, sequence: u64, diversifier: &str) -> SignBytes {
+        let header_data = RawHeaderData {
+            new_pub_key: self.new_public_key.map(encode_public_key),
+            new_diversifier: self.new_diversifier.clone().unwrap_or_default(),
+        };
+
+        SignBytes {
+            sequence,
+            timestamp: self.timestamp,
+            diversifier: diversifier.to_string(),
+            path: HEADER_SIGN_PATH.to_vec(),
+            data: header_data.encode_to_vec(),
+        }
+    }
+}
+
+impl Protobuf<RawHeader> for Header {}
+
+impl TryFrom<RawHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::InvalidTimestamp)?;
+        let new_public_key = raw.new_public_key.map(decode_public_key).transpose()?;
+        let new_diversifier = if raw.new_diversifier.is_empty() {
+            None
+        } else {
+            Some(raw.new_diversifier)
+        };
+
+        Ok(Self {
+            timestamp,
+            signature: raw.signature,
+            new_public_key,
+            new_diversifier,
+        })
+    }
+}
+
+impl From<Header> for RawHeader {
+    fn from(value: Header) -> Self {
+        RawHeader {
+            timestamp: value.timestamp.nanoseconds(),
+            signature: value.signature,
+            new_public_key: value.new_public_key.map(encode_public_key),
+            new_diversifier: value.new_diversifier.unwrap_or_default(),
+        }
+    }
+}
+
+impl Protobuf<Any> for Header {}
+
+impl TryFrom<Any> for Header {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use core::ops::Deref;
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_HEADER_TYPE_URL => decode_header(raw.value.deref()).map_err(Into::into),
+            _ => Err(ClientError::UnknownHeaderType {
+                header_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<Header> for Any {
+    fn from(header: Header) -> Self {
+        Any {
+            type_url: SOLOMACHINE_HEADER_TYPE_URL.to_string(),
+            value: Protobuf::<RawHeader>::encode_vec(&header),
+        }
+    }
+}
+
+fn decode_header<B: Buf>(buf: B) -> Result<Header, Error> {
+    RawHeader::decode(buf).map_err(Error::Decode)?.try_into()
+}
@@ -0,0 +1,39 @@
+//! ICS 06: Solo Machine Client implements a client for a standalone machine,
+//! such as a phone or a wallet, holding a single key pair.
+//!
+//! Instead of verifying Merkle proofs against a commitment root, the client
+//! verifies signatures by the solo machine's public key over the value
+//! committed under a given path. Every signature is bound to the client's
+//! current sequence, which each update, and each message whose proofs the
+//! client verified, advances.
+//!
+//! The client and consensus states, headers and signatures are encoded as
+//! the `ibc.lightclients.solomachine.v3` protobuf messages.
+
+use crate::core::ics02_client::client_type::ClientType;
+use core::str::FromStr;
+
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
+pub mod proto;
+pub mod sign_bytes;
+
+pub(crate) const SOLOMACHINE_CLIENT_TYPE: &str = "06-solomachine";
+
+/// Returns the solo machine `ClientType`
+pub fn client_type() -> ClientType {
+    ClientType::from_str(SOLOMACHINE_CLIENT_TYPE).expect("Never fails because it's valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ensures that the validation in `ClientType::from_str` doesn't fail for the solo machine client type
+    #[test]
+    pub fn test_solomachine_client_type() {
+        let _ = ClientType::from_str(SOLOMACHINE_CLIENT_TYPE).unwrap();
+    }
+}
@@ -0,0 +1,94 @@
+//! Protobuf definitions of the `ibc.lightclients.solomachine.v3` messages.
+//!
+//! The protobuf definitions we depend on stop at `solomachine.v2`, whose
+//! signatures are bound to a fixed set of data types rather than to a path.
+//! The messages are mirrored here until they provide `v3`.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+
+/// ClientState defines a solo machine client that tracks the current
+/// consensus state and if the client is frozen.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ClientState {
+    /// latest sequence of the client state
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    /// frozen sequence of the solo machine
+    #[prost(bool, tag = "2")]
+    pub is_frozen: bool,
+    #[prost(message, optional, tag = "3")]
+    pub consensus_state: Option<ConsensusState>,
+}
+
+/// ConsensusState defines a solo machine consensus state. The sequence of a
+/// consensus state is contained in the "height" key used in storing the
+/// consensus state.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ConsensusState {
+    /// public key of the solo machine
+    #[prost(message, optional, tag = "1")]
+    pub public_key: Option<Any>,
+    /// diversifier allows the same public key to be re-used across different
+    /// solo machine clients (potentially on different chains) without being
+    /// considered misbehaviour.
+    #[prost(string, tag = "2")]
+    pub diversifier: String,
+    #[prost(uint64, tag = "3")]
+    pub timestamp: u64,
+}
+
+/// Header defines a solo machine consensus header
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Header {
+    #[prost(uint64, tag = "1")]
+    pub timestamp: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub new_public_key: Option<Any>,
+    #[prost(string, tag = "4")]
+    pub new_diversifier: String,
+}
+
+/// TimestampedSignatureData contains the signature data and the timestamp of
+/// the signature.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TimestampedSignatureData {
+    #[prost(bytes = "vec", tag = "1")]
+    pub signature_data: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+}
+
+/// SignBytes defines the signed bytes used for signature verification.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignBytes {
+    /// the sequence number
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    /// the proof timestamp
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+    /// the public key diversifier
+    #[prost(string, tag = "3")]
+    pub diversifier: String,
+    /// the standardised path bytes
+    #[prost(bytes = "vec", tag = "4")]
+    pub path: Vec<u8>,
+    /// the marshaled data bytes
+    #[prost(bytes = "vec", tag = "5")]
+    pub data: Vec<u8>,
+}
+
+/// HeaderData returns the SignBytes data for update verification.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HeaderData {
+    /// header public key
+    #[prost(message, optional, tag = "1")]
+    pub new_pub_key: Option<Any>,
+    /// header diversifier
+    #[prost(string, tag = "2")]
+    pub new_diversifier: String,
+}
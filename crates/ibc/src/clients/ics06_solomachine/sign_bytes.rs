@@ -0,0 +1,54 @@
+//! Defines the bytes a solo machine signs to prove a value or a header
+
+use crate::prelude::*;
+
+use prost::Message;
+use tendermint::crypto::default::signature::Verifier;
+use tendermint::crypto::signature::Verifier as _;
+use tendermint::{PublicKey, Signature};
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::proto::SignBytes as RawSignBytes;
+use crate::core::timestamp::Timestamp;
+
+/// The path under which a solo machine signs its headers.
+pub const HEADER_SIGN_PATH: &[u8] = b"solomachine:header";
+
+/// The data signed by a solo machine for a given sequence.
+///
+/// Binding the `path` into the signature lets any value be proven, rather
+/// than one of a fixed set of data types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignBytes {
+    pub sequence: u64,
+    pub timestamp: Timestamp,
+    pub diversifier: String,
+    pub path: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+impl SignBytes {
+    pub fn encode_vec(&self) -> Vec<u8> {
+        RawSignBytes::from(self.clone()).encode_to_vec()
+    }
+
+    /// Verifies that `signature` is a signature of these bytes by `public_key`.
+    pub fn verify_signature(&self, public_key: PublicKey, signature: &[u8]) -> Result<(), Error> {
+        let signature = Signature::try_from(signature).map_err(Error::InvalidSignature)?;
+
+        Verifier::verify(public_key, &self.encode_vec(), &signature)
+            .map_err(Error::SignatureVerification)
+    }
+}
+
+impl From<SignBytes> for RawSignBytes {
+    fn from(value: SignBytes) -> Self {
+        RawSignBytes {
+            sequence: value.sequence,
+            timestamp: value.timestamp.nanoseconds(),
+            diversifier: value.diversifier,
+            path: value.path,
+            data: value.data,
+        }
+    }
+}
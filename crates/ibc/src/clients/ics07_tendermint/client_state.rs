@@ -60,6 +60,29 @@ pub struct AllowUpdate {
     pub after_misbehaviour: bool,
 }
 
+/// Determines how the client reacts to an update whose header conflicts with
+/// a consensus state already stored at the same height.
+///
+/// Note: this is a host-local setting which is not part of the protobuf
+/// encoding of the client state. Decoding a client state always yields the
+/// default policy.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Treat the conflict as misbehaviour and freeze the client.
+    FreezeClient,
+    /// Reject the update with an error, without freezing the client.
+    Reject,
+    /// Keep the existing consensus state and ignore the conflicting header.
+    Ignore,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        ConflictPolicy::FreezeClient
+    }
+}
+
 /// Contains the core implementation of the Tendermint light client
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq)]
@@ -74,6 +97,8 @@ pub struct ClientState {
     pub upgrade_path: Vec<String>,
     allow_update: AllowUpdate,
     frozen_height: Option<Height>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    conflict_policy: ConflictPolicy,
     #[cfg_attr(feature = "serde", serde(skip))]
     verifier: ProdVerifier,
 }
@@ -102,6 +127,7 @@ impl ClientState {
             upgrade_path,
             allow_update,
             frozen_height: None,
+            conflict_policy: ConflictPolicy::default(),
             verifier: ProdVerifier::default(),
         }
     }
@@ -147,11 +173,18 @@ impl ClientState {
         }
     }
 
+    pub fn with_conflict_policy(self, conflict_policy: ConflictPolicy) -> Self {
+        Self {
+            conflict_policy,
+            ..self
+        }
+    }
+
     pub fn validate(&self) -> Result<(), Error> {
         self.chain_id.validate_length(3, MaxChainIdLen)?;
 
-        // `TrustThreshold` is guaranteed to be in the range `[0, 1)`, but a `TrustThreshold::ZERO`
-        // value is invalid in this context
+        // `TrustThreshold` is guaranteed to be in the range `(0, 1]` or to be the
+        // `TrustThreshold::ZERO` of upgrading clients, which is invalid in this context
         if self.trust_level == TrustThreshold::ZERO {
             return Err(Error::InvalidTrustThreshold {
                 reason: "ClientState trust-level cannot be zero".to_string(),
@@ -252,7 +285,16 @@ impl ClientState {
         self.frozen_height.is_some()
     }
 
-    // Resets custom fields to zero values (used in `update_client`)
+    pub fn conflict_policy(&self) -> ConflictPolicy {
+        self.conflict_policy
+    }
+
+    /// Resets the client-chosen fields to their zero values, keeping only the
+    /// chain-chosen ones (chain id, unbonding period, latest height, proof
+    /// specs and upgrade path).
+    ///
+    /// This is the form in which an upgraded client state is committed by the
+    /// upgrading chain, and against which `MsgUpgradeClient` is verified.
     pub fn zero_custom_fields(&mut self) {
         self.trusting_period = ZERO_DURATION;
         self.trust_level = TrustThreshold::ZERO;
@@ -260,6 +302,7 @@ impl ClientState {
         self.allow_update.after_misbehaviour = false;
         self.frozen_height = None;
         self.max_clock_drift = ZERO_DURATION;
+        self.conflict_policy = ConflictPolicy::default();
     }
 }
 
@@ -293,6 +336,10 @@ impl ClientStateCommon for ClientState {
         Ok(())
     }
 
+    fn header_height(&self, header: Any) -> Result<Height, ClientError> {
+        Ok(TmHeader::try_from(header)?.height())
+    }
+
     /// Perform client-specific verifications and check all data in the new
     /// client state to be the same across all valid Tendermint clients for the
     /// new chain.
@@ -541,7 +588,7 @@ where
             let new_client_state = self.clone().with_header(header)?;
 
             ctx.store_consensus_state(
-                ClientConsensusStatePath::new(client_id, &new_client_state.latest_height),
+                ClientConsensusStatePath::new(client_id, &header_height),
                 new_consensus_state.into(),
             )?;
             ctx.store_client_state(ClientStatePath::new(client_id), new_client_state.into())?;
@@ -592,7 +639,8 @@ where
             upgraded_tm_client_state.proof_specs,
             upgraded_tm_client_state.upgrade_path,
             self.allow_update,
-        )?;
+        )?
+        .with_conflict_policy(self.conflict_policy);
 
         // The new consensus state is merely used as a trusted kernel against
         // which headers on the new chain can be verified. The root is just a
@@ -1084,6 +1132,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn zero_custom_fields_keeps_chain_chosen_fields() {
+        let chain_id = ChainId::new("ibc", 1).unwrap();
+        let latest_height = Height::new(1, 10).unwrap();
+        let unbonding_period = Duration::new(128000, 0);
+        let upgrade_path = vec!["upgrade".to_string(), "upgradedIBCState".to_string()];
+
+        let mut client_state = ClientState::new(
+            chain_id.clone(),
+            TrustThreshold::ONE_THIRD,
+            Duration::new(64000, 0),
+            unbonding_period,
+            Duration::new(3, 0),
+            latest_height,
+            ProofSpecs::default(),
+            upgrade_path.clone(),
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+        )
+        .unwrap()
+        .with_conflict_policy(ConflictPolicy::Reject)
+        .with_frozen_height(Height::min(1));
+
+        client_state.zero_custom_fields();
+
+        let upgrade_form = ClientState::new_without_validation(
+            chain_id,
+            TrustThreshold::ZERO,
+            ZERO_DURATION,
+            unbonding_period,
+            ZERO_DURATION,
+            latest_height,
+            ProofSpecs::default(),
+            upgrade_path,
+            AllowUpdate {
+                after_expiry: false,
+                after_misbehaviour: false,
+            },
+        );
+        assert_eq!(client_state, upgrade_form);
+
+        let raw = RawTmClientState::from(client_state);
+        assert_eq!(
+            raw.frozen_height,
+            Some(RawHeight {
+                revision_number: 0,
+                revision_height: 0,
+            })
+        );
+        assert_eq!(raw.latest_height, Some(latest_height.into()));
+    }
+
+    /// The light client verification options carry the trust threshold of
+    /// the client state, whichever it is
+    #[test]
+    fn tm_client_state_configurable_trust_threshold() {
+        for trust_level in [
+            TrustThreshold::new(1, 2).unwrap(),
+            TrustThreshold::TWO_THIRDS,
+        ] {
+            let client_state = ClientState::new(
+                ChainId::new("ibc", 1).unwrap(),
+                trust_level,
+                Duration::new(64000, 0),
+                Duration::new(128000, 0),
+                Duration::new(3, 0),
+                Height::new(1, 10).unwrap(),
+                ProofSpecs::default(),
+                vec![],
+                AllowUpdate {
+                    after_expiry: false,
+                    after_misbehaviour: false,
+                },
+            )
+            .unwrap();
+
+            let trust_threshold = client_state
+                .as_light_client_options()
+                .unwrap()
+                .trust_threshold;
+            assert_eq!(trust_threshold.numerator(), trust_level.numerator());
+            assert_eq!(trust_threshold.denominator(), trust_level.denominator());
+        }
+    }
+
     #[test]
     fn tm_client_state_malformed_with_frozen_height() {
         let tm_client_state_from_raw = ClientState::new_dummy_from_raw(RawHeight {
@@ -11,7 +11,7 @@ use crate::core::ics02_client::error::ClientError;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::path::ClientConsensusStatePath;
 
-use super::{check_header_trusted_next_validator_set, ClientState};
+use super::{check_header_trusted_next_validator_set, ClientState, ConflictPolicy};
 
 impl ClientState {
     pub fn verify_header<ClientValidationContext>(
@@ -88,9 +88,54 @@ impl ClientState {
                 .into_result()?;
         }
 
+        // Clients rejecting conflicting headers must do so before the update
+        // is executed, rather than when checking for misbehaviour.
+        if self.conflict_policy == ConflictPolicy::Reject
+            && self.has_conflicting_consensus_state(ctx, client_id, &header)?
+        {
+            return Err(Error::ConflictingConsensusState {
+                height: header.height(),
+            }
+            .into());
+        }
+
+        // A header that installs a new consensus state must keep the
+        // timestamps of the stored consensus states increasing with height.
+        let path_at_header_height = ClientConsensusStatePath::new(client_id, &header.height());
+        if ctx.consensus_state(&path_at_header_height).is_err() {
+            self.verify_timestamp_monotonicity(ctx, client_id, &header)?;
+        }
+
         Ok(())
     }
 
+    /// Returns whether a consensus state other than the one of `header` is
+    /// already stored at the header's height.
+    fn has_conflicting_consensus_state<ClientValidationContext>(
+        &self,
+        ctx: &ClientValidationContext,
+        client_id: &ClientId,
+        header: &TmHeader,
+    ) -> Result<bool, ClientError>
+    where
+        ClientValidationContext: TmValidationContext,
+    {
+        let path_at_header_height = ClientConsensusStatePath::new(client_id, &header.height());
+
+        match ctx.consensus_state(&path_at_header_height).ok() {
+            Some(existing_consensus_state) => {
+                let existing_consensus_state: TmConsensusState = existing_consensus_state
+                    .try_into()
+                    .map_err(|err| ClientError::Other {
+                        description: err.to_string(),
+                    })?;
+
+                Ok(existing_consensus_state != TmConsensusState::from(header.clone()))
+            }
+            None => Ok(false),
+        }
+    }
+
     pub fn check_for_misbehaviour_update_client<ClientValidationContext>(
         &self,
         ctx: &ClientValidationContext,
@@ -100,8 +145,6 @@ impl ClientState {
     where
         ClientValidationContext: TmValidationContext,
     {
-        let header_consensus_state = TmConsensusState::from(header.clone());
-
         let maybe_existing_consensus_state = {
             let path_at_header_height = ClientConsensusStatePath::new(client_id, &header.height());
 
@@ -109,16 +152,19 @@ impl ClientState {
         };
 
         match maybe_existing_consensus_state {
-            Some(existing_consensus_state) => {
-                let existing_consensus_state: TmConsensusState = existing_consensus_state
-                    .try_into()
-                    .map_err(|err| ClientError::Other {
-                        description: err.to_string(),
-                    })?;
+            Some(_) => {
+                if !self.has_conflicting_consensus_state(ctx, client_id, &header)? {
+                    return Ok(false);
+                }
 
-                // There is evidence of misbehaviour if the stored consensus state
-                // is different from the new one we received.
-                Ok(existing_consensus_state != header_consensus_state)
+                // The stored consensus state is different from the new one we
+                // received. How this is handled depends on the client's policy.
+                match self.conflict_policy {
+                    ConflictPolicy::FreezeClient => Ok(true),
+                    // Conflicting headers are rejected by `verify_header`, and
+                    // `update_state` keeps the existing consensus state.
+                    ConflictPolicy::Reject | ConflictPolicy::Ignore => Ok(false),
+                }
             }
             None => {
                 // If no header was previously installed, we ensure the monotonicity of timestamps.
@@ -165,4 +211,55 @@ impl ClientState {
             }
         }
     }
+
+    /// Checks that storing the consensus state of `header` keeps the
+    /// timestamps of the client's consensus states strictly increasing with
+    /// height, i.e. that the header's timestamp falls strictly between the
+    /// timestamps of the neighbouring stored consensus states.
+    fn verify_timestamp_monotonicity<ClientValidationContext>(
+        &self,
+        ctx: &ClientValidationContext,
+        client_id: &ClientId,
+        header: &TmHeader,
+    ) -> Result<(), ClientError>
+    where
+        ClientValidationContext: TmValidationContext,
+    {
+        let header_height = header.height();
+        let header_time = header.signed_header.header().time;
+
+        if let Some(prev_cs) = ctx.prev_consensus_state(client_id, &header_height)? {
+            let prev_cs: TmConsensusState =
+                prev_cs.try_into().map_err(|err| ClientError::Other {
+                    description: err.to_string(),
+                })?;
+
+            if header_time <= prev_cs.timestamp {
+                return Err(ClientError::HeaderVerificationFailure {
+                    reason: format!(
+                        "header timestamp {header_time} is not after previous timestamp {}",
+                        prev_cs.timestamp
+                    ),
+                });
+            }
+        }
+
+        if let Some(next_cs) = ctx.next_consensus_state(client_id, &header_height)? {
+            let next_cs: TmConsensusState =
+                next_cs.try_into().map_err(|err| ClientError::Other {
+                    description: err.to_string(),
+                })?;
+
+            if header_time >= next_cs.timestamp {
+                return Err(ClientError::HeaderVerificationFailure {
+                    reason: format!(
+                        "header timestamp {header_time} is not before next timestamp {}",
+                        next_cs.timestamp
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -95,6 +95,8 @@ pub enum Error {
     MisbehaviourHeadersBlockHashesEqual,
     /// headers are not at same height and are monotonically increasing
     MisbehaviourHeadersNotAtSameHeight,
+    /// header conflicts with the consensus state stored at height `{height}`
+    ConflictingConsensusState { height: Height },
 }
 
 #[cfg(feature = "std")]
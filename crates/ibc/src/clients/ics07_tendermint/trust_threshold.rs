@@ -1,6 +1,6 @@
 //! IBC Domain type definition for [`TrustThreshold`]
 //! represented as a fraction with valid values in the
-//! range `[0, 1)`.
+//! range `(0, 1]`.
 
 use core::{
     convert::TryFrom,
@@ -62,14 +62,11 @@ impl TrustThreshold {
     /// Instantiate a TrustThreshold with the given denominator and
     /// numerator.
     ///
-    /// The constructor succeeds if long as the resulting fraction
-    /// is in the range`[0, 1)`.
+    /// The constructor succeeds as long as the resulting fraction
+    /// is in the range `(0, 1]`.
     pub fn new(numerator: u64, denominator: u64) -> Result<Self, ClientError> {
-        // The two parameters cannot yield a fraction that is bigger or equal to 1
-        if (numerator > denominator)
-            || (denominator == 0 && numerator != 0)
-            || (numerator == denominator && numerator != 0)
-        {
+        // The two parameters cannot yield a fraction that is zero or bigger than 1
+        if numerator == 0 || numerator > denominator {
             return Err(ClientError::InvalidTrustThreshold {
                 numerator,
                 denominator,
@@ -134,6 +131,10 @@ impl TryFrom<Fraction> for TrustThreshold {
     type Error = ClientError;
 
     fn try_from(value: Fraction) -> Result<Self, Self::Error> {
+        // The zeroed trust threshold of an upgrading client
+        if value.numerator == 0 && value.denominator == 0 {
+            return Ok(Self::ZERO);
+        }
         Self::new(value.numerator, value.denominator)
     }
 }
@@ -149,3 +150,42 @@ impl Display for TrustThreshold {
         write!(f, "{}/{}", self.numerator, self.denominator)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trust_threshold_range() {
+        for (numerator, denominator) in [(1, 3), (1, 2), (2, 3), (1, 1)] {
+            let trust_threshold = TrustThreshold::new(numerator, denominator).unwrap();
+            assert_eq!(trust_threshold.numerator(), numerator);
+            assert_eq!(trust_threshold.denominator(), denominator);
+        }
+
+        for (numerator, denominator) in [(0, 1), (0, 0), (1, 0), (3, 2)] {
+            assert!(matches!(
+                TrustThreshold::new(numerator, denominator),
+                Err(ClientError::InvalidTrustThreshold { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn trust_threshold_from_raw() {
+        let raw = |numerator, denominator| Fraction {
+            numerator,
+            denominator,
+        };
+
+        assert_eq!(
+            TrustThreshold::try_from(raw(1, 2)).unwrap(),
+            TrustThreshold::new(1, 2).unwrap()
+        );
+        assert_eq!(
+            TrustThreshold::try_from(raw(0, 0)).unwrap(),
+            TrustThreshold::ZERO
+        );
+        assert!(TrustThreshold::try_from(raw(0, 1)).is_err());
+    }
+}
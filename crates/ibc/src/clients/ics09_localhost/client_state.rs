@@ -0,0 +1,349 @@
+//! Implements the core [`ClientState`](crate::core::ics02_client::client_state::ClientState) trait
+//! for the localhost light client.
+
+use crate::prelude::*;
+
+use core::str::FromStr;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::localhost::v1::ClientState as RawLocalhostClientState;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics09_localhost::client_type as localhost_client_type;
+use crate::clients::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
+use crate::clients::ics09_localhost::error::Error;
+use crate::clients::ics09_localhost::{
+    ExecutionContext as LocalhostExecutionContext, ValidationContext as LocalhostValidationContext,
+};
+use crate::core::ics02_client::client_state::{
+    ClientStateCommon, ClientStateExecution, ClientStateValidation, Status, UpdateKind,
+};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::ClientExecutionContext;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
+use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath, Path};
+use crate::Height;
+
+pub const LOCALHOST_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.localhost.v1.ClientState";
+
+/// Defines the localhost light client's client state.
+///
+/// The client tracks the host chain itself: `latest_height` is bumped to the
+/// host's latest height on every update, and membership is verified by
+/// reading the host's own commitment store rather than checking a proof.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientState {
+    pub chain_id: ChainId,
+    pub latest_height: Height,
+}
+
+impl ClientState {
+    pub fn new(chain_id: ChainId, latest_height: Height) -> Self {
+        Self {
+            chain_id,
+            latest_height,
+        }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    pub fn with_latest_height(self, latest_height: Height) -> Self {
+        Self {
+            latest_height,
+            ..self
+        }
+    }
+}
+
+impl ClientStateCommon for ClientState {
+    fn verify_consensus_state(&self, consensus_state: Any) -> Result<(), ClientError> {
+        let _localhost_consensus_state = LocalhostConsensusState::try_from(consensus_state)?;
+
+        Ok(())
+    }
+
+    fn client_type(&self) -> ClientType {
+        localhost_client_type()
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        if self.latest_height < proof_height {
+            return Err(ClientError::InvalidProofHeight {
+                latest_height: self.latest_height,
+                proof_height,
+            });
+        }
+        Ok(())
+    }
+
+    /// Localhost headers carry no height, as every update moves the client
+    /// to the current host height.
+    fn header_height(&self, _header: Any) -> Result<Height, ClientError> {
+        Err(Error::HeaderHeightNotSupported.into())
+    }
+
+    fn verify_upgrade_client(
+        &self,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+        _proof_upgrade_client: CommitmentProofBytes,
+        _proof_upgrade_consensus_state: CommitmentProofBytes,
+        _root: &CommitmentRoot,
+    ) -> Result<(), ClientError> {
+        Err(Error::UpgradeNotSupported.into())
+    }
+
+    /// The localhost client reads the committed value from the host's store,
+    /// which is only reachable through
+    /// [`ClientStateValidation::verify_membership_with_context`].
+    fn verify_membership(
+        &self,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: Path,
+        _value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        Err(Error::MissingHostContext.into())
+    }
+
+    /// See [`ClientStateCommon::verify_membership`] above.
+    fn verify_non_membership(
+        &self,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: Path,
+    ) -> Result<(), ClientError> {
+        Err(Error::MissingHostContext.into())
+    }
+}
+
+impl<ClientValidationContext> ClientStateValidation<ClientValidationContext> for ClientState
+where
+    ClientValidationContext: LocalhostValidationContext,
+{
+    /// Any header is accepted: updating the localhost client only tracks the
+    /// host's own height, which needs no verification.
+    fn verify_client_message(
+        &self,
+        _ctx: &ClientValidationContext,
+        _client_id: &ClientId,
+        _client_message: Any,
+        update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        match update_kind {
+            UpdateKind::UpdateClient => Ok(()),
+            UpdateKind::SubmitMisbehaviour => Err(Error::MisbehaviourNotSupported.into()),
+        }
+    }
+
+    fn check_for_misbehaviour(
+        &self,
+        _ctx: &ClientValidationContext,
+        _client_id: &ClientId,
+        _client_message: Any,
+        _update_kind: &UpdateKind,
+    ) -> Result<bool, ClientError> {
+        Ok(false)
+    }
+
+    /// The localhost client can neither expire nor be frozen.
+    fn status(
+        &self,
+        _ctx: &ClientValidationContext,
+        _client_id: &ClientId,
+    ) -> Result<Status, ClientError> {
+        Ok(Status::Active)
+    }
+
+    fn verify_membership_with_context(
+        &self,
+        ctx: &ClientValidationContext,
+        prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        verify_host_prefix(ctx, prefix)?;
+
+        match ctx.host_commitment(&path)? {
+            Some(committed_value) if committed_value == value => Ok(()),
+            Some(_) => Err(Error::CommitmentMismatch { path }.into()),
+            None => Err(Error::MissingCommitment { path }.into()),
+        }
+    }
+
+    fn verify_non_membership_with_context(
+        &self,
+        ctx: &ClientValidationContext,
+        prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        verify_host_prefix(ctx, prefix)?;
+
+        match ctx.host_commitment(&path)? {
+            Some(_) => Err(Error::UnexpectedCommitment { path }.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<E> ClientStateExecution<E> for ClientState
+where
+    E: LocalhostExecutionContext,
+    <E as ClientExecutionContext>::AnyClientState: From<ClientState>,
+    <E as ClientExecutionContext>::AnyConsensusState: From<LocalhostConsensusState>,
+{
+    fn initialise(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        consensus_state: Any,
+    ) -> Result<(), ClientError> {
+        let localhost_consensus_state = LocalhostConsensusState::try_from(consensus_state)?;
+
+        ctx.store_client_state(ClientStatePath::new(client_id), self.clone().into())?;
+        ctx.store_consensus_state(
+            ClientConsensusStatePath::new(client_id, &self.latest_height),
+            localhost_consensus_state.into(),
+        )?;
+
+        Ok(())
+    }
+
+    /// The header is ignored: the client is bumped to the host's latest
+    /// height, recording the host's current timestamp as its consensus state.
+    fn update_state(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        _header: Any,
+    ) -> Result<Vec<Height>, ClientError> {
+        let host_height = ctx.host_height()?;
+        let host_timestamp = ctx.host_timestamp()?;
+
+        let new_client_state = self.clone().with_latest_height(host_height);
+        let new_consensus_state = LocalhostConsensusState::new(host_timestamp);
+
+        ctx.store_consensus_state(
+            ClientConsensusStatePath::new(client_id, &host_height),
+            new_consensus_state.into(),
+        )?;
+        ctx.store_client_state(ClientStatePath::new(client_id), new_client_state.into())?;
+
+        Ok(vec![host_height])
+    }
+
+    fn update_state_on_misbehaviour(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _client_message: Any,
+        _update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        Err(Error::MisbehaviourNotSupported.into())
+    }
+
+    fn update_state_on_upgrade(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+    ) -> Result<Height, ClientError> {
+        Err(Error::UpgradeNotSupported.into())
+    }
+}
+
+fn verify_host_prefix<Ctx>(ctx: &Ctx, prefix: &CommitmentPrefix) -> Result<(), Error>
+where
+    Ctx: LocalhostValidationContext,
+{
+    let host_prefix = ctx.host_commitment_prefix();
+    if prefix != &host_prefix {
+        return Err(Error::CommitmentPrefixMismatch {
+            expected: host_prefix,
+            actual: prefix.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+impl Protobuf<RawLocalhostClientState> for ClientState {}
+
+impl TryFrom<RawLocalhostClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawLocalhostClientState) -> Result<Self, Self::Error> {
+        let chain_id = ChainId::from_str(raw.chain_id.as_str())?;
+
+        let latest_height = raw
+            .height
+            .ok_or(Error::MissingLatestHeight)?
+            .try_into()
+            .map_err(|_| Error::MissingLatestHeight)?;
+
+        Ok(Self::new(chain_id, latest_height))
+    }
+}
+
+impl From<ClientState> for RawLocalhostClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            chain_id: value.chain_id.to_string(),
+            height: Some(value.latest_height.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_client_state<B: Buf>(buf: B) -> Result<ClientState, Error> {
+            RawLocalhostClientState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            LOCALHOST_CLIENT_STATE_TYPE_URL => {
+                decode_client_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: LOCALHOST_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawLocalhostClientState>::encode_vec(&client_state),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Defines the localhost light client's `ConsensusState` type
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use tendermint_proto::google::protobuf::Timestamp as RawTimestamp;
+
+use crate::core::ics02_client::consensus_state::ConsensusState as ConsensusStateTrait;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::core::timestamp::Timestamp;
+
+pub const LOCALHOST_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.localhost.v1.ConsensusState";
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Defines the localhost light client's consensus state.
+///
+/// The localhost client does not verify proofs against a commitment root, so
+/// its consensus state only records the host's timestamp at a given height,
+/// and has an empty root. There is no protobuf definition for it; it is
+/// encoded as a `google.protobuf.Timestamp`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+    pub timestamp: Timestamp,
+    pub root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(timestamp: Timestamp) -> Self {
+        Self {
+            timestamp,
+            root: CommitmentRoot::from(vec![]),
+        }
+    }
+}
+
+impl Protobuf<RawTimestamp> for ConsensusState {}
+
+impl TryFrom<RawTimestamp> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: RawTimestamp) -> Result<Self, Self::Error> {
+        let seconds = u64::try_from(raw.seconds).map_err(|_| ClientError::Other {
+            description: "negative localhost consensus state timestamp".to_string(),
+        })?;
+        let nanos = u64::try_from(raw.nanos).map_err(|_| ClientError::Other {
+            description: "negative localhost consensus state timestamp".to_string(),
+        })?;
+        let nanoseconds = seconds
+            .checked_mul(NANOS_PER_SEC)
+            .and_then(|ns| ns.checked_add(nanos))
+            .ok_or(ClientError::Other {
+                description: "localhost consensus state timestamp out of range".to_string(),
+            })?;
+
+        Ok(Self::new(
+            Timestamp::from_nanoseconds(nanoseconds)
+                .map_err(ClientError::InvalidPacketTimestamp)?,
+        ))
+    }
+}
+
+impl From<ConsensusState> for RawTimestamp {
+    fn from(value: ConsensusState) -> Self {
+        let nanoseconds = value.timestamp.nanoseconds();
+
+        RawTimestamp {
+            seconds: (nanoseconds / NANOS_PER_SEC) as i64,
+            nanos: (nanoseconds % NANOS_PER_SEC) as i32,
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+        use prost::Message;
+
+        fn decode_consensus_state<B: Buf>(buf: B) -> Result<ConsensusState, ClientError> {
+            RawTimestamp::decode(buf)
+                .map_err(ClientError::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            LOCALHOST_CONSENSUS_STATE_TYPE_URL => decode_consensus_state(raw.value.deref()),
+            _ => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: LOCALHOST_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawTimestamp>::encode_vec(&consensus_state),
+        }
+    }
+}
+
+impl ConsensusStateTrait for ConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    fn encode_vec(&self) -> Vec<u8> {
+        <Self as Protobuf<Any>>::encode_vec(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consensus_state_roundtrip() {
+        let consensus_state =
+            ConsensusState::new(Timestamp::from_nanoseconds(1_690_000_000_123_456_789).unwrap());
+
+        let any: Any = consensus_state.clone().into();
+
+        assert_eq!(ConsensusState::try_from(any).unwrap(), consensus_state);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::core::ics02_client::ClientExecutionContext;
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::path::Path;
+use crate::core::timestamp::Timestamp;
+use crate::core::ContextError;
+use crate::prelude::*;
+use crate::Height;
+
+/// Client's context required during validation
+pub trait ValidationContext {
+    /// Returns the prefix under which the host commits its IBC state.
+    fn host_commitment_prefix(&self) -> CommitmentPrefix;
+
+    /// Returns the value committed by the host under `path`, rooted at
+    /// [`ValidationContext::host_commitment_prefix`].
+    ///
+    /// Returns `None` if nothing is committed under that path.
+    fn host_commitment(&self, path: &Path) -> Result<Option<Vec<u8>>, ContextError>;
+}
+
+/// Client's context required during execution
+pub trait ExecutionContext: ClientExecutionContext {
+    /// Returns the current height of the local chain.
+    fn host_height(&self) -> Result<Height, ContextError>;
+
+    /// Returns the current timestamp of the local chain.
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError>;
+}
@@ -0,0 +1,65 @@
+//! Defines the localhost light client's error type
+
+use crate::prelude::*;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::identifier::IdentifierError;
+use crate::core::ics24_host::path::Path;
+
+use displaydoc::Display;
+
+/// The main error type
+#[derive(Debug, Display)]
+pub enum Error {
+    /// invalid identifier: `{0}`
+    InvalidIdentifier(IdentifierError),
+    /// missing latest height
+    MissingLatestHeight,
+    /// decode error: `{0}`
+    Decode(prost::DecodeError),
+    /// commitment prefix `{actual:?}` does not match the host commitment prefix `{expected:?}`
+    CommitmentPrefixMismatch {
+        expected: CommitmentPrefix,
+        actual: CommitmentPrefix,
+    },
+    /// no value is committed by the host at path `{path}`
+    MissingCommitment { path: Path },
+    /// the value committed by the host at path `{path}` does not match the expected value
+    CommitmentMismatch { path: Path },
+    /// a value is committed by the host at path `{path}`
+    UnexpectedCommitment { path: Path },
+    /// membership verification requires access to the host's store
+    MissingHostContext,
+    /// the localhost client does not support misbehaviour
+    MisbehaviourNotSupported,
+    /// the localhost client cannot be upgraded
+    UpgradeNotSupported,
+    /// localhost headers carry no height
+    HeaderHeightNotSupported,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            Self::InvalidIdentifier(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ClientError {
+    fn from(e: Error) -> Self {
+        Self::ClientSpecific {
+            description: e.to_string(),
+        }
+    }
+}
+
+impl From<IdentifierError> for Error {
+    fn from(e: IdentifierError) -> Self {
+        Self::InvalidIdentifier(e)
+    }
+}
@@ -0,0 +1,33 @@
+//! ICS 09: Localhost Client implements a loopback client, allowing two modules
+//! running on the same chain to communicate over IBC.
+//!
+//! Instead of verifying cryptographic proofs, the client reads the value
+//! committed under a given path directly from the host's own store.
+
+use crate::core::ics02_client::client_type::ClientType;
+use core::str::FromStr;
+
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+
+mod context;
+pub use context::*;
+
+pub(crate) const LOCALHOST_CLIENT_TYPE: &str = "09-localhost";
+
+/// Returns the localhost `ClientType`
+pub fn client_type() -> ClientType {
+    ClientType::from_str(LOCALHOST_CLIENT_TYPE).expect("Never fails because it's valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ensures that the validation in `ClientType::from_str` doesn't fail for the localhost client type
+    #[test]
+    pub fn test_localhost_client_type() {
+        let _ = ClientType::from_str(LOCALHOST_CLIENT_TYPE).unwrap();
+    }
+}
@@ -2,7 +2,9 @@
 
 use core::any::Any;
 
+pub mod ics06_solomachine;
 pub mod ics07_tendermint;
+pub mod ics09_localhost;
 
 /// Allows type to be converted to `&dyn Any`
 pub trait AsAny: Any {
@@ -0,0 +1,121 @@
+//! Async counterparts of [`ValidationContext`](crate::core::ValidationContext)
+//! and [`ExecutionContext`](crate::core::ExecutionContext), for hosts whose
+//! storage can only be accessed asynchronously.
+use crate::prelude::*;
+
+use async_trait::async_trait;
+
+use crate::core::events::IbcEvent;
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::proof::DEFAULT_MAX_PROOF_SIZE;
+use crate::core::ics02_client::ClientExecutionContext;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::core::ics24_host::path::{ChannelEndPath, ClientConsensusStatePath};
+use crate::core::{structured_log_string, ContextError};
+use crate::signer::Signer;
+
+/// Async version of [`ValidationContext`](crate::core::ValidationContext).
+///
+/// Only the store lookups are async. Light client verification keeps using
+/// the synchronous client validation context.
+#[async_trait]
+pub trait AsyncValidationContext: Send + Sync {
+    type ClientValidationContext;
+    type E: ClientExecutionContext;
+    type AnyConsensusState: ConsensusState;
+    type AnyClientState: ClientState<Self::ClientValidationContext, Self::E>;
+
+    /// Retrieve the context that implements all clients' `ValidationContext`.
+    fn get_client_validation_context(&self) -> &Self::ClientValidationContext;
+
+    /// Returns the ClientState for the given identifier `client_id`.
+    async fn client_state(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Self::AnyClientState, ContextError>;
+
+    /// Retrieve the consensus state for the given client ID at the specified
+    /// height.
+    async fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Self::AnyConsensusState, ContextError>;
+
+    /// Returns the ConnectionEnd for the given identifier `conn_id`.
+    async fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError>;
+
+    /// Returns the ChannelEnd for the given `port_id` and `chan_id`.
+    async fn channel_end(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<ChannelEnd, ContextError>;
+
+    /// Returns the maximum size in bytes accepted for a single proof.
+    fn max_proof_size(&self) -> usize {
+        DEFAULT_MAX_PROOF_SIZE
+    }
+
+    /// Validates the `signer` field of IBC messages, which represents the address
+    /// of the user/relayer that signed the given message.
+    fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError>;
+
+    /// Returns the bech32 prefix that the signers of IBC messages must use.
+    /// Defaults to `None`, which leaves signer validation entirely to the host.
+    fn signer_bech32_prefix(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the account on whose behalf `signer` signs the messages being
+    /// processed, if any. Defaults to `None`.
+    fn message_granter(&self, _signer: &Signer) -> Option<Signer> {
+        None
+    }
+
+    /// Validates the `signer` field of IBC messages signed on behalf of
+    /// `granter`. Defaults to validating `signer` alone.
+    fn validate_signer_with_grant(
+        &self,
+        signer: &Signer,
+        _granter: &Signer,
+    ) -> Result<(), ContextError> {
+        self.validate_message_signer(signer)
+    }
+}
+
+/// Async version of [`ExecutionContext`](crate::core::ExecutionContext).
+#[async_trait]
+pub trait AsyncExecutionContext: AsyncValidationContext {
+    /// Retrieve the context that implements all clients' `ExecutionContext`.
+    fn get_client_execution_context(&mut self) -> &mut Self::E;
+
+    /// Stores the given channel_end at a path associated with the port_id and
+    /// channel id.
+    async fn store_channel(
+        &mut self,
+        channel_end_path: &ChannelEndPath,
+        channel_end: ChannelEnd,
+    ) -> Result<(), ContextError>;
+
+    /// Emit the given IBC event
+    fn emit_ibc_event(&mut self, event: IbcEvent);
+
+    /// Emit the given IBC events, in order. Defaults to emitting them one by
+    /// one with `emit_ibc_event`.
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>) {
+        for event in events {
+            self.emit_ibc_event(event)
+        }
+    }
+
+    /// Log the given message.
+    fn log_message(&mut self, message: String);
+
+    /// Log the given structured message. Defaults to logging its string form,
+    /// as given by [`structured_log_string`], with `log_message`.
+    fn log_structured(&mut self, key: &str, fields: &[(&str, String)]) {
+        self.log_message(structured_log_string(key, fields))
+    }
+}
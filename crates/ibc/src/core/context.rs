@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-use crate::signer::Signer;
+use crate::signer::{Signer, SignerError};
 use alloc::string::String;
 use core::time::Duration;
 use derive_more::From;
@@ -8,19 +8,25 @@ use displaydoc::Display;
 use ibc_proto::google::protobuf::Any;
 
 use crate::core::events::IbcEvent;
+use crate::core::ics02_client::client_state::ClientStateCommon;
+use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::proof::DEFAULT_MAX_PROOF_SIZE;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::version::{
     get_compatible_versions, pick_version, Version as ConnectionVersion,
 };
 use crate::core::ics04_channel::channel::ChannelEnd;
-use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::commitment::{
+    AcknowledgementCommitment, Hasher, PacketCommitment, Sha256Hasher,
+};
 use crate::core::ics04_channel::context::calculate_block_delay;
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::error::PacketError;
-use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::packet::{Receipt, Sequence, DEFAULT_MAX_PACKET_DATA_SIZE};
 use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::identifier::ChannelId;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::identifier::ConnectionId;
 use crate::core::ics24_host::path::{
@@ -34,6 +40,7 @@ use super::ics02_client::client_state::ClientState;
 use super::ics02_client::consensus_state::ConsensusState;
 use super::ics02_client::ClientExecutionContext;
 use super::ics24_host::identifier::PortId;
+use super::router::{Capability, ModuleId};
 
 /// Top-level error
 #[derive(Debug, Display, From)]
@@ -46,6 +53,11 @@ pub enum ContextError {
     ChannelError(ChannelError),
     /// ICS04 Packet error: {0}
     PacketError(PacketError),
+    /// signer error: {0}
+    SignerError(SignerError),
+    /// host height `{current}` is lower than the previously observed height `{last}`
+    #[from(ignore)]
+    NonMonotonicHostHeight { current: Height, last: Height },
 }
 
 #[cfg(feature = "std")]
@@ -56,6 +68,8 @@ impl std::error::Error for ContextError {
             Self::ConnectionError(e) => Some(e),
             Self::ChannelError(e) => Some(e),
             Self::PacketError(e) => Some(e),
+            Self::SignerError(e) => Some(e),
+            Self::NonMonotonicHostHeight { .. } => None,
         }
     }
 }
@@ -74,6 +88,11 @@ pub enum RouterError {
     UnknownPort { port_id: PortId },
     /// module not found
     ModuleNotFound,
+    /// port `{port_id}` is already bound to module `{module_id}`
+    PortAlreadyBound {
+        port_id: PortId,
+        module_id: ModuleId,
+    },
 }
 
 impl From<ContextError> for RouterError {
@@ -124,6 +143,10 @@ pub trait ValidationContext {
         client_cons_state_path: &ClientConsensusStatePath,
     ) -> Result<Self::AnyConsensusState, ContextError>;
 
+    /// Returns the heights of all the consensus states stored for the given
+    /// client, in ascending order.
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ContextError>;
+
     /// Returns the time when the client state for the given [`ClientId`] was updated with a header for the given [`Height`]
     fn client_update_time(
         &self,
@@ -150,6 +173,15 @@ pub trait ValidationContext {
         height: &Height,
     ) -> Result<Self::AnyConsensusState, ContextError>;
 
+    /// Checks that the host's own view of its height and time is consistent.
+    ///
+    /// Called by the packet handlers before they compare packet timeouts with
+    /// the host time. The default implementation accepts any host state; hosts
+    /// opt in by calling [`validate_host_timestamp_consistency`] from it.
+    fn validate_self_consistency(&self) -> Result<(), ContextError> {
+        Ok(())
+    }
+
     /// Returns a natural number, counting how many clients have been created
     /// thus far. The value of this counter should increase only via method
     /// `ExecutionContext::increase_client_counter`.
@@ -210,13 +242,28 @@ pub trait ValidationContext {
     /// Returns the sequence number for the next packet to be acknowledged for the given store path
     fn get_next_sequence_ack(&self, seq_ack_path: &SeqAckPath) -> Result<Sequence, ContextError>;
 
+    /// Returns the sequence numbers for the next packets to be sent, received
+    /// and acknowledged on the given channel, in that order
+    fn get_next_sequences(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(Sequence, Sequence, Sequence), ContextError> {
+        Ok((
+            self.get_next_sequence_send(&SeqSendPath::new(port_id, channel_id))?,
+            self.get_next_sequence_recv(&SeqRecvPath::new(port_id, channel_id))?,
+            self.get_next_sequence_ack(&SeqAckPath::new(port_id, channel_id))?,
+        ))
+    }
+
     /// Returns the packet commitment for the given store path
     fn get_packet_commitment(
         &self,
         commitment_path: &CommitmentPath,
     ) -> Result<PacketCommitment, ContextError>;
 
-    /// Returns the packet receipt for the given store path
+    /// Returns the packet receipt for the given store path, or
+    /// `PacketError::PacketReceiptNotFound` if the packet was not received.
     fn get_packet_receipt(&self, receipt_path: &ReceiptPath) -> Result<Receipt, ContextError>;
 
     /// Returns the packet acknowledgement for the given store path
@@ -225,14 +272,62 @@ pub trait ValidationContext {
         ack_path: &AckPath,
     ) -> Result<AcknowledgementCommitment, ContextError>;
 
+    /// Returns all the packet commitments stored for the given channel, ordered by sequence.
+    ///
+    /// The order must not depend on the order in which the commitments were stored, so that
+    /// every node returns the same list for the same state.
+    fn packet_commitments(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<Vec<(Sequence, PacketCommitment)>, ContextError>;
+
+    /// Returns the packet acknowledgements stored for the given channel at the given sequences,
+    /// ordered by sequence. Sequences without an acknowledgement are skipped, and an empty list
+    /// of sequences returns all the acknowledgements stored for the channel.
+    ///
+    /// As for `packet_commitments`, the order must not depend on the order in which the
+    /// acknowledgements were stored.
+    fn packet_acknowledgements(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: &[Sequence],
+    ) -> Result<Vec<(Sequence, AcknowledgementCommitment)>, ContextError>;
+
     /// Returns a counter on the number of channel ids have been created thus far.
     /// The value of this counter should increase only via method
     /// `ExecutionContext::increase_channel_counter`.
     fn channel_counter(&self) -> Result<u64, ContextError>;
 
+    /// Returns the identifier that the next channel opened on `port_id` will
+    /// be given, without allocating it.
+    ///
+    /// Defaults to `channel-{n}`, where `n` is the
+    /// [`channel_counter`](Self::channel_counter). Hosts with another
+    /// identifier scheme must override it together with
+    /// [`ExecutionContext::generate_channel_identifier`].
+    fn next_channel_identifier(&self, _port_id: &PortId) -> Result<ChannelId, ContextError> {
+        Ok(ChannelId::new(self.channel_counter()?))
+    }
+
     /// Returns the maximum expected time per block
     fn max_expected_time_per_block(&self) -> Duration;
 
+    /// Returns the maximum size in bytes of the data of packets received on the given channel.
+    fn max_packet_data_size(&self, _channel_end_path: &ChannelEndPath) -> usize {
+        DEFAULT_MAX_PACKET_DATA_SIZE
+    }
+
+    /// Returns the maximum size in bytes of the proofs submitted for verification.
+    fn max_proof_size(&self) -> usize {
+        DEFAULT_MAX_PROOF_SIZE
+    }
+
+    /// Returns the hash function used to compute packet and acknowledgement
+    /// commitments. Defaults to [`Sha256Hasher`].
+    fn commitment_hasher(&self) -> &dyn Hasher {
+        &Sha256Hasher
+    }
+
     /// Calculates the block delay period using the connection's delay period and the maximum
     /// expected time per block.
     fn block_delay(&self, delay_period_time: &Duration) -> u64 {
@@ -242,6 +337,69 @@ pub trait ValidationContext {
     /// Validates the `signer` field of IBC messages, which represents the address
     /// of the user/relayer that signed the given message.
     fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError>;
+
+    /// Returns the bech32 prefix that the signers of IBC messages must use.
+    ///
+    /// When set, handlers reject signers that are not bech32 addresses with
+    /// this prefix before calling
+    /// [`validate_message_signer`](Self::validate_message_signer). Defaults to
+    /// `None`, which leaves signer validation entirely to the host.
+    fn signer_bech32_prefix(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the account on whose behalf `signer` signs the messages being
+    /// processed, if any. Hosts supporting signer grants resolve it from the
+    /// transaction wrapping the messages. Defaults to `None`.
+    fn message_granter(&self, _signer: &Signer) -> Option<Signer> {
+        None
+    }
+
+    /// Validates the `signer` field of IBC messages signed on behalf of
+    /// `granter`, which must have granted `signer` the right to do so.
+    ///
+    /// Hosts without signer grants validate `signer` alone, as
+    /// [`validate_message_signer`](Self::validate_message_signer) does.
+    fn validate_signer_with_grant(
+        &self,
+        signer: &Signer,
+        _granter: &Signer,
+    ) -> Result<(), ContextError> {
+        self.validate_message_signer(signer)
+    }
+
+    /// Returns the client types that may be created on the host. An empty list,
+    /// the default, allows every client type the host can decode.
+    fn allowed_client_types(&self) -> Vec<ClientType> {
+        Vec::new()
+    }
+
+    /// Checks that the module of `capability` holds the capability for its
+    /// port, i.e. that it may drive the channel handshakes on that port.
+    ///
+    /// The default implementation does not track capabilities and accepts
+    /// every module.
+    fn authenticate_capability(&self, _capability: &Capability) -> Result<(), ContextError> {
+        Ok(())
+    }
+
+    /// Returns the host timestamp at which a packet was last received on the
+    /// given channel, if the host records it.
+    ///
+    /// The default implementation records nothing and returns `None`.
+    fn last_recv_time(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<Option<Timestamp>, ContextError> {
+        Ok(None)
+    }
+
+    /// Returns the sink to which the entrypoints report the outcome of every
+    /// message they handle. Defaults to [`NoopTelemetry`].
+    fn telemetry(&self) -> &dyn Telemetry {
+        &NoopTelemetry
+    }
 }
 
 /// Context to be implemented by the host that provides all "write-only" methods.
@@ -252,9 +410,9 @@ pub trait ExecutionContext: ValidationContext {
     fn get_client_execution_context(&mut self) -> &mut Self::E;
 
     /// Called upon client creation.
-    /// Increases the counter which keeps track of how many clients have been created.
-    /// Should never fail.
-    fn increase_client_counter(&mut self);
+    /// Increases the counter which keeps track of how many clients have been created,
+    /// and returns its value before the increase.
+    fn increase_client_counter(&mut self) -> Result<u64, ContextError>;
 
     /// Called upon successful client update.
     /// Implementations are expected to use this to record the specified time as the time at which
@@ -276,6 +434,59 @@ pub trait ExecutionContext: ValidationContext {
         host_height: Height,
     ) -> Result<(), ContextError>;
 
+    /// Deletes the consensus state stored at the given path, if any.
+    fn delete_consensus_state(
+        &mut self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<(), ContextError>;
+
+    /// Deletes the time recorded by [`store_update_time`](Self::store_update_time)
+    /// for the given client and height, if any.
+    fn delete_update_time(
+        &mut self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<(), ContextError>;
+
+    /// Deletes the height recorded by [`store_update_height`](Self::store_update_height)
+    /// for the given client and height, if any.
+    fn delete_update_height(
+        &mut self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<(), ContextError>;
+
+    /// Deletes the consensus states of `client_id` stored at heights lower
+    /// than `before_height`, along with their processed time and height, and
+    /// returns how many were deleted. The consensus state at the latest
+    /// height of the client is always kept.
+    ///
+    /// Hosts may call this on their own schedule to reclaim storage. The
+    /// default implementation deletes each height returned by
+    /// [`consensus_state_heights`](ValidationContext::consensus_state_heights)
+    /// that is below the cutoff.
+    fn prune_consensus_states(
+        &mut self,
+        client_id: &ClientId,
+        before_height: &Height,
+    ) -> Result<usize, ContextError> {
+        let latest_height = self.client_state(client_id)?.latest_height();
+
+        let heights: Vec<Height> = self
+            .consensus_state_heights(client_id)?
+            .into_iter()
+            .filter(|height| height < before_height && height != &latest_height)
+            .collect();
+
+        for height in heights.iter() {
+            self.delete_consensus_state(&ClientConsensusStatePath::new(client_id, height))?;
+            self.delete_update_time(client_id, height)?;
+            self.delete_update_height(client_id, height)?;
+        }
+
+        Ok(heights.len())
+    }
+
     /// Stores the given connection_end at path
     fn store_connection(
         &mut self,
@@ -291,9 +502,9 @@ pub trait ExecutionContext: ValidationContext {
     ) -> Result<(), ContextError>;
 
     /// Called upon connection identifier creation (Init or Try process).
-    /// Increases the counter which keeps track of how many connections have been created.
-    /// Should never fail.
-    fn increase_connection_counter(&mut self);
+    /// Increases the counter which keeps track of how many connections have been created,
+    /// and returns its value before the increase.
+    fn increase_connection_counter(&mut self) -> Result<u64, ContextError>;
 
     /// Stores the given packet commitment at the given store path
     fn store_packet_commitment(
@@ -358,9 +569,124 @@ pub trait ExecutionContext: ValidationContext {
     /// Should never fail.
     fn increase_channel_counter(&mut self);
 
+    /// Allocates the identifier of a channel being opened on `port_id` (Init
+    /// or Try message processing), which must be the one returned by
+    /// [`ValidationContext::next_channel_identifier`] just before.
+    ///
+    /// Defaults to returning that identifier and increasing the channel
+    /// counter.
+    fn generate_channel_identifier(&mut self, port_id: &PortId) -> Result<ChannelId, ContextError> {
+        let chan_id = self.next_channel_identifier(port_id)?;
+        self.increase_channel_counter();
+        Ok(chan_id)
+    }
+
     /// Emit the given IBC event
     fn emit_ibc_event(&mut self, event: IbcEvent);
 
+    /// Emit the given IBC events, in order. Defaults to emitting them one by
+    /// one with `emit_ibc_event`; hosts that forward events to an external
+    /// sink may override it to do so in a single batch.
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>) {
+        for event in events {
+            self.emit_ibc_event(event)
+        }
+    }
+
     /// Log the given message.
     fn log_message(&mut self, message: String);
+
+    /// Log the given structured message, made of a `key` naming the logged
+    /// operation and the `fields` describing it. Defaults to logging its
+    /// string form, as given by [`structured_log_string`], with `log_message`.
+    fn log_structured(&mut self, key: &str, fields: &[(&str, String)]) {
+        self.log_message(structured_log_string(key, fields))
+    }
+
+    /// Claims the capability for `port_id` on behalf of `module_id`, binding
+    /// the port to the module. Fails if the capability is already held by
+    /// another module.
+    ///
+    /// The default implementation does not track capabilities, and hands out
+    /// the capability without recording it.
+    fn claim_capability(
+        &mut self,
+        port_id: PortId,
+        module_id: ModuleId,
+    ) -> Result<Capability, ContextError> {
+        Ok(Capability::new(port_id, module_id))
+    }
+
+    /// Records the host timestamp at which a packet was received on the
+    /// given channel, to be returned by
+    /// [`ValidationContext::last_recv_time`].
+    ///
+    /// The default implementation records nothing.
+    fn store_last_recv_time(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        Ok(())
+    }
+}
+
+/// Receives the outcome of the IBC handlers, e.g. to maintain
+/// Prometheus-style counters of successful and failed executions.
+pub trait Telemetry {
+    /// Called once a handler has run, with the name of the handler (such as
+    /// `chan_open_confirm`) and whether it succeeded.
+    fn on_handler_result(&self, handler: &str, ok: bool);
+}
+
+/// A [`Telemetry`] that discards everything reported to it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopTelemetry;
+
+impl Telemetry for NoopTelemetry {
+    fn on_handler_result(&self, _handler: &str, _ok: bool) {}
+}
+
+/// Checks that `host_timestamp` is consistent with the host consensus state at
+/// `host_height`, i.e. that it is not earlier than the timestamp of that
+/// consensus state.
+pub fn validate_host_timestamp_consistency<Ctx>(ctx: &Ctx) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let host_height = ctx.host_height()?;
+    let host_timestamp = ctx.host_timestamp()?;
+    let consensus_timestamp = ctx.host_consensus_state(&host_height)?.timestamp();
+
+    if host_timestamp < consensus_timestamp {
+        return Err(ClientError::InconsistentHostTimestamp {
+            height: host_height,
+            host_timestamp,
+            consensus_timestamp,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Validates the signer of an IBC message, checking its bech32 prefix first
+/// when the host requires one.
+pub(crate) fn validate_signer<Ctx>(ctx: &Ctx, signer: &Signer) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    if let Some(prefix) = ctx.signer_bech32_prefix() {
+        signer.validate_bech32(&prefix)?;
+    }
+
+    ctx.validate_message_signer(signer)
+}
+
+/// Formats a structured log message as `key field1=value1 field2=value2`.
+pub fn structured_log_string(key: &str, fields: &[(&str, String)]) -> String {
+    fields.iter().fold(key.to_string(), |log, (name, value)| {
+        format!("{log} {name}={value}")
+    })
 }
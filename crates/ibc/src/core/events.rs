@@ -13,6 +13,7 @@ use crate::core::ics03_connection::events as ConnectionEvents;
 use crate::core::ics04_channel::error as channel_error;
 use crate::core::ics04_channel::events as ChannelEvents;
 use crate::core::timestamp::ParseTimestampError;
+use crate::Height;
 
 use super::ics24_host::identifier::IdentifierError;
 
@@ -161,6 +162,182 @@ impl IbcEvent {
             IbcEvent::Message(_) => MESSAGE_EVENT,
         }
     }
+
+    /// Returns the inner event if this is an `IbcEvent::CreateClient`.
+    pub fn as_create_client(&self) -> Option<&ClientEvents::CreateClient> {
+        match self {
+            IbcEvent::CreateClient(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::UpdateClient`.
+    pub fn as_update_client(&self) -> Option<&ClientEvents::UpdateClient> {
+        match self {
+            IbcEvent::UpdateClient(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::UpgradeClient`.
+    pub fn as_upgrade_client(&self) -> Option<&ClientEvents::UpgradeClient> {
+        match self {
+            IbcEvent::UpgradeClient(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::ClientMisbehaviour`.
+    pub fn as_client_misbehaviour(&self) -> Option<&ClientEvents::ClientMisbehaviour> {
+        match self {
+            IbcEvent::ClientMisbehaviour(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenInitConnection`.
+    pub fn as_open_init_connection(&self) -> Option<&ConnectionEvents::OpenInit> {
+        match self {
+            IbcEvent::OpenInitConnection(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenTryConnection`.
+    pub fn as_open_try_connection(&self) -> Option<&ConnectionEvents::OpenTry> {
+        match self {
+            IbcEvent::OpenTryConnection(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenAckConnection`.
+    pub fn as_open_ack_connection(&self) -> Option<&ConnectionEvents::OpenAck> {
+        match self {
+            IbcEvent::OpenAckConnection(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenConfirmConnection`.
+    pub fn as_open_confirm_connection(&self) -> Option<&ConnectionEvents::OpenConfirm> {
+        match self {
+            IbcEvent::OpenConfirmConnection(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenInitChannel`.
+    pub fn as_open_init_channel(&self) -> Option<&ChannelEvents::OpenInit> {
+        match self {
+            IbcEvent::OpenInitChannel(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenTryChannel`.
+    pub fn as_open_try_channel(&self) -> Option<&ChannelEvents::OpenTry> {
+        match self {
+            IbcEvent::OpenTryChannel(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenAckChannel`.
+    pub fn as_open_ack_channel(&self) -> Option<&ChannelEvents::OpenAck> {
+        match self {
+            IbcEvent::OpenAckChannel(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::OpenConfirmChannel`.
+    pub fn as_open_confirm_channel(&self) -> Option<&ChannelEvents::OpenConfirm> {
+        match self {
+            IbcEvent::OpenConfirmChannel(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::CloseInitChannel`.
+    pub fn as_close_init_channel(&self) -> Option<&ChannelEvents::CloseInit> {
+        match self {
+            IbcEvent::CloseInitChannel(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::CloseConfirmChannel`.
+    pub fn as_close_confirm_channel(&self) -> Option<&ChannelEvents::CloseConfirm> {
+        match self {
+            IbcEvent::CloseConfirmChannel(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::SendPacket`.
+    pub fn as_send_packet(&self) -> Option<&ChannelEvents::SendPacket> {
+        match self {
+            IbcEvent::SendPacket(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::ReceivePacket`.
+    pub fn as_receive_packet(&self) -> Option<&ChannelEvents::ReceivePacket> {
+        match self {
+            IbcEvent::ReceivePacket(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::WriteAcknowledgement`.
+    pub fn as_write_acknowledgement(&self) -> Option<&ChannelEvents::WriteAcknowledgement> {
+        match self {
+            IbcEvent::WriteAcknowledgement(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::AcknowledgePacket`.
+    pub fn as_acknowledge_packet(&self) -> Option<&ChannelEvents::AcknowledgePacket> {
+        match self {
+            IbcEvent::AcknowledgePacket(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::TimeoutPacket`.
+    pub fn as_timeout_packet(&self) -> Option<&ChannelEvents::TimeoutPacket> {
+        match self {
+            IbcEvent::TimeoutPacket(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::ChannelClosed`.
+    pub fn as_channel_closed(&self) -> Option<&ChannelEvents::ChannelClosed> {
+        match self {
+            IbcEvent::ChannelClosed(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::Module`.
+    pub fn as_module(&self) -> Option<&ModuleEvent> {
+        match self {
+            IbcEvent::Module(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner event if this is an `IbcEvent::Message`.
+    pub fn as_message(&self) -> Option<&MessageEvent> {
+        match self {
+            IbcEvent::Message(event) => Some(event),
+            _ => None,
+        }
+    }
 }
 
 /// The event type emitted by IBC applications
@@ -282,6 +459,57 @@ impl From<MessageEvent> for IbcEvent {
     }
 }
 
+/// The ABCI event attribute under which [`SequencedEvent`]s expose their
+/// sequence number.
+pub const EVENT_SEQUENCE_ATTRIBUTE_KEY: &str = "event_sequence";
+
+/// Assigns monotonic sequence numbers to the events emitted within a block.
+///
+/// The sequence restarts from zero whenever the host height changes, so that
+/// indexers can order events of the same block, even identical ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventSequencer {
+    height: Option<Height>,
+    next_sequence: u64,
+}
+
+impl EventSequencer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the sequence number of the next event emitted at `host_height`.
+    pub fn next_sequence(&mut self, host_height: Height) -> u64 {
+        if self.height != Some(host_height) {
+            self.height = Some(host_height);
+            self.next_sequence = 0;
+        }
+
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        sequence
+    }
+}
+
+/// An [`IbcEvent`] along with its sequence number within its block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SequencedEvent {
+    pub sequence: u64,
+    pub event: IbcEvent,
+}
+
+impl TryFrom<SequencedEvent> for abci::Event {
+    type Error = Error;
+
+    fn try_from(event: SequencedEvent) -> Result<Self, Self::Error> {
+        let mut abci_event = abci::Event::try_from(event.event)?;
+        abci_event
+            .attributes
+            .push((EVENT_SEQUENCE_ATTRIBUTE_KEY, event.sequence.to_string()).into());
+        Ok(abci_event)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -310,4 +538,50 @@ pub mod tests {
         ));
         let _ = abci::Event::try_from(ibc_event);
     }
+
+    #[test]
+    fn test_typed_event_accessors() {
+        let packet = Packet::try_from(get_dummy_raw_packet(1, 1)).unwrap();
+        let ibc_event = IbcEvent::SendPacket(SendPacket::new(
+            packet.clone(),
+            Order::Unordered,
+            ConnectionId::default(),
+        ));
+
+        let send_packet = ibc_event.as_send_packet().unwrap();
+        assert_eq!(send_packet.seq_on_a(), &packet.seq_on_a);
+        assert!(ibc_event.as_receive_packet().is_none());
+        assert!(ibc_event.as_message().is_none());
+
+        let ibc_event = IbcEvent::Message(MessageEvent::Channel);
+        assert_eq!(ibc_event.as_message(), Some(&MessageEvent::Channel));
+        assert!(ibc_event.as_send_packet().is_none());
+    }
+
+    #[test]
+    fn test_event_sequencer_resets_on_new_height() {
+        let mut sequencer = EventSequencer::new();
+        let height = Height::new(0, 5).unwrap();
+
+        assert_eq!(sequencer.next_sequence(height), 0);
+        assert_eq!(sequencer.next_sequence(height), 1);
+        assert_eq!(sequencer.next_sequence(height), 2);
+
+        assert_eq!(sequencer.next_sequence(height.increment()), 0);
+        assert_eq!(sequencer.next_sequence(height.increment()), 1);
+    }
+
+    #[test]
+    fn test_sequenced_event_attribute() {
+        let event = SequencedEvent {
+            sequence: 7,
+            event: IbcEvent::Message(MessageEvent::Channel),
+        };
+
+        let abci_event = abci::Event::try_from(event).unwrap();
+        let attribute = abci_event.attributes.last().unwrap();
+
+        assert_eq!(attribute.key, EVENT_SEQUENCE_ATTRIBUTE_KEY);
+        assert_eq!(attribute.value, "7");
+    }
 }
@@ -0,0 +1,594 @@
+//! Forwarding of the host context methods, for context wrappers.
+//!
+//! Wrappers such as the tracing context only intercept a few of the
+//! [`ValidationContext`](crate::core::ValidationContext) and
+//! [`ExecutionContext`](crate::core::ExecutionContext) methods. They forward
+//! the others to the wrapped context with [`forward_context!`], so that the
+//! signature of each forwarded method is only spelled out once.
+
+/// Implements the listed context methods by forwarding them to the field
+/// `$inner` of the wrapper.
+///
+/// The methods are listed by name within the trait impl of the wrapper,
+/// which implements the associated types and the methods that are not listed
+/// itself:
+///
+/// ```ignore
+/// impl<Ctx: ValidationContext> ValidationContext for Wrapper<Ctx> {
+///     type ClientValidationContext = Ctx::ClientValidationContext;
+///     // ...
+///
+///     forward_context!(inner; get_client_validation_context, client_state);
+///
+///     fn host_height(&self) -> Result<Height, ContextError> {
+///         // ...
+///     }
+/// }
+/// ```
+macro_rules! forward_context {
+    ($inner:ident; $($method:ident),+ $(,)?) => {
+        $( $crate::core::forward::forward_context!(@ $inner $method); )+
+    };
+    (@ $inner:ident get_client_validation_context) => {
+        fn get_client_validation_context(&self) -> &Self::ClientValidationContext {
+            self.$inner.get_client_validation_context()
+        }
+    };
+    (@ $inner:ident client_state) => {
+        fn client_state(
+            &self,
+            client_id: &$crate::core::ics24_host::identifier::ClientId,
+        ) -> Result<Self::AnyClientState, $crate::core::ContextError> {
+            self.$inner.client_state(client_id)
+        }
+    };
+    (@ $inner:ident decode_client_state) => {
+        fn decode_client_state(
+            &self,
+            client_state: ::ibc_proto::google::protobuf::Any,
+        ) -> Result<Self::AnyClientState, $crate::core::ContextError> {
+            self.$inner.decode_client_state(client_state)
+        }
+    };
+    (@ $inner:ident consensus_state) => {
+        fn consensus_state(
+            &self,
+            client_cons_state_path: &$crate::core::ics24_host::path::ClientConsensusStatePath,
+        ) -> Result<Self::AnyConsensusState, $crate::core::ContextError> {
+            self.$inner.consensus_state(client_cons_state_path)
+        }
+    };
+    (@ $inner:ident consensus_state_heights) => {
+        fn consensus_state_heights(
+            &self,
+            client_id: &$crate::core::ics24_host::identifier::ClientId,
+        ) -> Result<$crate::prelude::Vec<$crate::Height>, $crate::core::ContextError> {
+            self.$inner.consensus_state_heights(client_id)
+        }
+    };
+    (@ $inner:ident client_update_time) => {
+        fn client_update_time(
+            &self,
+            client_id: &$crate::core::ics24_host::identifier::ClientId,
+            height: &$crate::Height,
+        ) -> Result<$crate::core::timestamp::Timestamp, $crate::core::ContextError> {
+            self.$inner.client_update_time(client_id, height)
+        }
+    };
+    (@ $inner:ident client_update_height) => {
+        fn client_update_height(
+            &self,
+            client_id: &$crate::core::ics24_host::identifier::ClientId,
+            height: &$crate::Height,
+        ) -> Result<$crate::Height, $crate::core::ContextError> {
+            self.$inner.client_update_height(client_id, height)
+        }
+    };
+    (@ $inner:ident host_height) => {
+        fn host_height(&self) -> Result<$crate::Height, $crate::core::ContextError> {
+            self.$inner.host_height()
+        }
+    };
+    (@ $inner:ident host_timestamp) => {
+        fn host_timestamp(
+            &self,
+        ) -> Result<$crate::core::timestamp::Timestamp, $crate::core::ContextError> {
+            self.$inner.host_timestamp()
+        }
+    };
+    (@ $inner:ident host_consensus_state) => {
+        fn host_consensus_state(
+            &self,
+            height: &$crate::Height,
+        ) -> Result<Self::AnyConsensusState, $crate::core::ContextError> {
+            self.$inner.host_consensus_state(height)
+        }
+    };
+    (@ $inner:ident validate_self_consistency) => {
+        fn validate_self_consistency(&self) -> Result<(), $crate::core::ContextError> {
+            self.$inner.validate_self_consistency()
+        }
+    };
+    (@ $inner:ident client_counter) => {
+        fn client_counter(&self) -> Result<u64, $crate::core::ContextError> {
+            self.$inner.client_counter()
+        }
+    };
+    (@ $inner:ident connection_end) => {
+        fn connection_end(
+            &self,
+            conn_id: &$crate::core::ics24_host::identifier::ConnectionId,
+        ) -> Result<
+            $crate::core::ics03_connection::connection::ConnectionEnd,
+            $crate::core::ContextError,
+        > {
+            self.$inner.connection_end(conn_id)
+        }
+    };
+    (@ $inner:ident validate_self_client) => {
+        fn validate_self_client(
+            &self,
+            client_state_of_host_on_counterparty: ::ibc_proto::google::protobuf::Any,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.validate_self_client(client_state_of_host_on_counterparty)
+        }
+    };
+    (@ $inner:ident commitment_prefix) => {
+        fn commitment_prefix(
+            &self,
+        ) -> $crate::core::ics23_commitment::commitment::CommitmentPrefix {
+            self.$inner.commitment_prefix()
+        }
+    };
+    (@ $inner:ident connection_counter) => {
+        fn connection_counter(&self) -> Result<u64, $crate::core::ContextError> {
+            self.$inner.connection_counter()
+        }
+    };
+    (@ $inner:ident get_compatible_versions) => {
+        fn get_compatible_versions(
+            &self,
+        ) -> $crate::prelude::Vec<$crate::core::ics03_connection::version::Version> {
+            self.$inner.get_compatible_versions()
+        }
+    };
+    (@ $inner:ident pick_version) => {
+        fn pick_version(
+            &self,
+            counterparty_candidate_versions: &[$crate::core::ics03_connection::version::Version],
+        ) -> Result<$crate::core::ics03_connection::version::Version, $crate::core::ContextError> {
+            self.$inner.pick_version(counterparty_candidate_versions)
+        }
+    };
+    (@ $inner:ident channel_end) => {
+        fn channel_end(
+            &self,
+            channel_end_path: &$crate::core::ics24_host::path::ChannelEndPath,
+        ) -> Result<$crate::core::ics04_channel::channel::ChannelEnd, $crate::core::ContextError> {
+            self.$inner.channel_end(channel_end_path)
+        }
+    };
+    (@ $inner:ident get_next_sequence_send) => {
+        fn get_next_sequence_send(
+            &self,
+            seq_send_path: &$crate::core::ics24_host::path::SeqSendPath,
+        ) -> Result<$crate::core::ics04_channel::packet::Sequence, $crate::core::ContextError> {
+            self.$inner.get_next_sequence_send(seq_send_path)
+        }
+    };
+    (@ $inner:ident get_next_sequence_recv) => {
+        fn get_next_sequence_recv(
+            &self,
+            seq_recv_path: &$crate::core::ics24_host::path::SeqRecvPath,
+        ) -> Result<$crate::core::ics04_channel::packet::Sequence, $crate::core::ContextError> {
+            self.$inner.get_next_sequence_recv(seq_recv_path)
+        }
+    };
+    (@ $inner:ident get_next_sequence_ack) => {
+        fn get_next_sequence_ack(
+            &self,
+            seq_ack_path: &$crate::core::ics24_host::path::SeqAckPath,
+        ) -> Result<$crate::core::ics04_channel::packet::Sequence, $crate::core::ContextError> {
+            self.$inner.get_next_sequence_ack(seq_ack_path)
+        }
+    };
+    (@ $inner:ident get_next_sequences) => {
+        fn get_next_sequences(
+            &self,
+            port_id: &$crate::core::ics24_host::identifier::PortId,
+            channel_id: &$crate::core::ics24_host::identifier::ChannelId,
+        ) -> Result<
+            (
+                $crate::core::ics04_channel::packet::Sequence,
+                $crate::core::ics04_channel::packet::Sequence,
+                $crate::core::ics04_channel::packet::Sequence,
+            ),
+            $crate::core::ContextError,
+        > {
+            self.$inner.get_next_sequences(port_id, channel_id)
+        }
+    };
+    (@ $inner:ident get_packet_commitment) => {
+        fn get_packet_commitment(
+            &self,
+            commitment_path: &$crate::core::ics24_host::path::CommitmentPath,
+        ) -> Result<
+            $crate::core::ics04_channel::commitment::PacketCommitment,
+            $crate::core::ContextError,
+        > {
+            self.$inner.get_packet_commitment(commitment_path)
+        }
+    };
+    (@ $inner:ident get_packet_receipt) => {
+        fn get_packet_receipt(
+            &self,
+            receipt_path: &$crate::core::ics24_host::path::ReceiptPath,
+        ) -> Result<$crate::core::ics04_channel::packet::Receipt, $crate::core::ContextError> {
+            self.$inner.get_packet_receipt(receipt_path)
+        }
+    };
+    (@ $inner:ident get_packet_acknowledgement) => {
+        fn get_packet_acknowledgement(
+            &self,
+            ack_path: &$crate::core::ics24_host::path::AckPath,
+        ) -> Result<
+            $crate::core::ics04_channel::commitment::AcknowledgementCommitment,
+            $crate::core::ContextError,
+        > {
+            self.$inner.get_packet_acknowledgement(ack_path)
+        }
+    };
+    (@ $inner:ident packet_commitments) => {
+        fn packet_commitments(
+            &self,
+            channel_end_path: &$crate::core::ics24_host::path::ChannelEndPath,
+        ) -> Result<
+            $crate::prelude::Vec<(
+                $crate::core::ics04_channel::packet::Sequence,
+                $crate::core::ics04_channel::commitment::PacketCommitment,
+            )>,
+            $crate::core::ContextError,
+        > {
+            self.$inner.packet_commitments(channel_end_path)
+        }
+    };
+    (@ $inner:ident packet_acknowledgements) => {
+        fn packet_acknowledgements(
+            &self,
+            channel_end_path: &$crate::core::ics24_host::path::ChannelEndPath,
+            sequences: &[$crate::core::ics04_channel::packet::Sequence],
+        ) -> Result<
+            $crate::prelude::Vec<(
+                $crate::core::ics04_channel::packet::Sequence,
+                $crate::core::ics04_channel::commitment::AcknowledgementCommitment,
+            )>,
+            $crate::core::ContextError,
+        > {
+            self.$inner.packet_acknowledgements(channel_end_path, sequences)
+        }
+    };
+    (@ $inner:ident channel_counter) => {
+        fn channel_counter(&self) -> Result<u64, $crate::core::ContextError> {
+            self.$inner.channel_counter()
+        }
+    };
+    (@ $inner:ident next_channel_identifier) => {
+        fn next_channel_identifier(
+            &self,
+            port_id: &$crate::core::ics24_host::identifier::PortId,
+        ) -> Result<$crate::core::ics24_host::identifier::ChannelId, $crate::core::ContextError> {
+            self.$inner.next_channel_identifier(port_id)
+        }
+    };
+    (@ $inner:ident max_expected_time_per_block) => {
+        fn max_expected_time_per_block(&self) -> ::core::time::Duration {
+            self.$inner.max_expected_time_per_block()
+        }
+    };
+    (@ $inner:ident max_packet_data_size) => {
+        fn max_packet_data_size(
+            &self,
+            channel_end_path: &$crate::core::ics24_host::path::ChannelEndPath,
+        ) -> usize {
+            self.$inner.max_packet_data_size(channel_end_path)
+        }
+    };
+    (@ $inner:ident max_proof_size) => {
+        fn max_proof_size(&self) -> usize {
+            self.$inner.max_proof_size()
+        }
+    };
+    (@ $inner:ident commitment_hasher) => {
+        fn commitment_hasher(&self) -> &dyn $crate::core::ics04_channel::commitment::Hasher {
+            self.$inner.commitment_hasher()
+        }
+    };
+    (@ $inner:ident block_delay) => {
+        fn block_delay(&self, delay_period_time: &::core::time::Duration) -> u64 {
+            self.$inner.block_delay(delay_period_time)
+        }
+    };
+    (@ $inner:ident validate_message_signer) => {
+        fn validate_message_signer(
+            &self,
+            signer: &$crate::signer::Signer,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.validate_message_signer(signer)
+        }
+    };
+    (@ $inner:ident signer_bech32_prefix) => {
+        fn signer_bech32_prefix(&self) -> Option<$crate::prelude::String> {
+            self.$inner.signer_bech32_prefix()
+        }
+    };
+    (@ $inner:ident message_granter) => {
+        fn message_granter(
+            &self,
+            signer: &$crate::signer::Signer,
+        ) -> Option<$crate::signer::Signer> {
+            self.$inner.message_granter(signer)
+        }
+    };
+    (@ $inner:ident validate_signer_with_grant) => {
+        fn validate_signer_with_grant(
+            &self,
+            signer: &$crate::signer::Signer,
+            granter: &$crate::signer::Signer,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.validate_signer_with_grant(signer, granter)
+        }
+    };
+    (@ $inner:ident allowed_client_types) => {
+        fn allowed_client_types(
+            &self,
+        ) -> $crate::prelude::Vec<$crate::core::ics02_client::client_type::ClientType> {
+            self.$inner.allowed_client_types()
+        }
+    };
+    (@ $inner:ident authenticate_capability) => {
+        fn authenticate_capability(
+            &self,
+            capability: &$crate::core::router::Capability,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.authenticate_capability(capability)
+        }
+    };
+    (@ $inner:ident last_recv_time) => {
+        fn last_recv_time(
+            &self,
+            port_id: &$crate::core::ics24_host::identifier::PortId,
+            channel_id: &$crate::core::ics24_host::identifier::ChannelId,
+        ) -> Result<Option<$crate::core::timestamp::Timestamp>, $crate::core::ContextError> {
+            self.$inner.last_recv_time(port_id, channel_id)
+        }
+    };
+    (@ $inner:ident telemetry) => {
+        fn telemetry(&self) -> &dyn $crate::core::Telemetry {
+            self.$inner.telemetry()
+        }
+    };
+    (@ $inner:ident get_client_execution_context) => {
+        fn get_client_execution_context(&mut self) -> &mut Self::E {
+            self.$inner.get_client_execution_context()
+        }
+    };
+    (@ $inner:ident increase_client_counter) => {
+        fn increase_client_counter(&mut self) -> Result<u64, $crate::core::ContextError> {
+            self.$inner.increase_client_counter()
+        }
+    };
+    (@ $inner:ident store_update_time) => {
+        fn store_update_time(
+            &mut self,
+            client_id: $crate::core::ics24_host::identifier::ClientId,
+            height: $crate::Height,
+            timestamp: $crate::core::timestamp::Timestamp,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_update_time(client_id, height, timestamp)
+        }
+    };
+    (@ $inner:ident store_update_height) => {
+        fn store_update_height(
+            &mut self,
+            client_id: $crate::core::ics24_host::identifier::ClientId,
+            height: $crate::Height,
+            host_height: $crate::Height,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_update_height(client_id, height, host_height)
+        }
+    };
+    (@ $inner:ident delete_consensus_state) => {
+        fn delete_consensus_state(
+            &mut self,
+            client_cons_state_path: &$crate::core::ics24_host::path::ClientConsensusStatePath,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.delete_consensus_state(client_cons_state_path)
+        }
+    };
+    (@ $inner:ident delete_update_time) => {
+        fn delete_update_time(
+            &mut self,
+            client_id: &$crate::core::ics24_host::identifier::ClientId,
+            height: &$crate::Height,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.delete_update_time(client_id, height)
+        }
+    };
+    (@ $inner:ident delete_update_height) => {
+        fn delete_update_height(
+            &mut self,
+            client_id: &$crate::core::ics24_host::identifier::ClientId,
+            height: &$crate::Height,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.delete_update_height(client_id, height)
+        }
+    };
+    (@ $inner:ident prune_consensus_states) => {
+        fn prune_consensus_states(
+            &mut self,
+            client_id: &$crate::core::ics24_host::identifier::ClientId,
+            before_height: &$crate::Height,
+        ) -> Result<usize, $crate::core::ContextError> {
+            self.$inner.prune_consensus_states(client_id, before_height)
+        }
+    };
+    (@ $inner:ident store_connection) => {
+        fn store_connection(
+            &mut self,
+            connection_path: &$crate::core::ics24_host::path::ConnectionPath,
+            connection_end: $crate::core::ics03_connection::connection::ConnectionEnd,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_connection(connection_path, connection_end)
+        }
+    };
+    (@ $inner:ident store_connection_to_client) => {
+        fn store_connection_to_client(
+            &mut self,
+            client_connection_path: &$crate::core::ics24_host::path::ClientConnectionPath,
+            conn_id: $crate::core::ics24_host::identifier::ConnectionId,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_connection_to_client(client_connection_path, conn_id)
+        }
+    };
+    (@ $inner:ident increase_connection_counter) => {
+        fn increase_connection_counter(&mut self) -> Result<u64, $crate::core::ContextError> {
+            self.$inner.increase_connection_counter()
+        }
+    };
+    (@ $inner:ident store_packet_commitment) => {
+        fn store_packet_commitment(
+            &mut self,
+            commitment_path: &$crate::core::ics24_host::path::CommitmentPath,
+            commitment: $crate::core::ics04_channel::commitment::PacketCommitment,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_packet_commitment(commitment_path, commitment)
+        }
+    };
+    (@ $inner:ident delete_packet_commitment) => {
+        fn delete_packet_commitment(
+            &mut self,
+            commitment_path: &$crate::core::ics24_host::path::CommitmentPath,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.delete_packet_commitment(commitment_path)
+        }
+    };
+    (@ $inner:ident store_packet_receipt) => {
+        fn store_packet_receipt(
+            &mut self,
+            receipt_path: &$crate::core::ics24_host::path::ReceiptPath,
+            receipt: $crate::core::ics04_channel::packet::Receipt,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_packet_receipt(receipt_path, receipt)
+        }
+    };
+    (@ $inner:ident store_packet_acknowledgement) => {
+        fn store_packet_acknowledgement(
+            &mut self,
+            ack_path: &$crate::core::ics24_host::path::AckPath,
+            ack_commitment: $crate::core::ics04_channel::commitment::AcknowledgementCommitment,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_packet_acknowledgement(ack_path, ack_commitment)
+        }
+    };
+    (@ $inner:ident delete_packet_acknowledgement) => {
+        fn delete_packet_acknowledgement(
+            &mut self,
+            ack_path: &$crate::core::ics24_host::path::AckPath,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.delete_packet_acknowledgement(ack_path)
+        }
+    };
+    (@ $inner:ident store_channel) => {
+        fn store_channel(
+            &mut self,
+            channel_end_path: &$crate::core::ics24_host::path::ChannelEndPath,
+            channel_end: $crate::core::ics04_channel::channel::ChannelEnd,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_channel(channel_end_path, channel_end)
+        }
+    };
+    (@ $inner:ident store_next_sequence_send) => {
+        fn store_next_sequence_send(
+            &mut self,
+            seq_send_path: &$crate::core::ics24_host::path::SeqSendPath,
+            seq: $crate::core::ics04_channel::packet::Sequence,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_next_sequence_send(seq_send_path, seq)
+        }
+    };
+    (@ $inner:ident store_next_sequence_recv) => {
+        fn store_next_sequence_recv(
+            &mut self,
+            seq_recv_path: &$crate::core::ics24_host::path::SeqRecvPath,
+            seq: $crate::core::ics04_channel::packet::Sequence,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_next_sequence_recv(seq_recv_path, seq)
+        }
+    };
+    (@ $inner:ident store_next_sequence_ack) => {
+        fn store_next_sequence_ack(
+            &mut self,
+            seq_ack_path: &$crate::core::ics24_host::path::SeqAckPath,
+            seq: $crate::core::ics04_channel::packet::Sequence,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_next_sequence_ack(seq_ack_path, seq)
+        }
+    };
+    (@ $inner:ident increase_channel_counter) => {
+        fn increase_channel_counter(&mut self) {
+            self.$inner.increase_channel_counter()
+        }
+    };
+    (@ $inner:ident generate_channel_identifier) => {
+        fn generate_channel_identifier(
+            &mut self,
+            port_id: &$crate::core::ics24_host::identifier::PortId,
+        ) -> Result<$crate::core::ics24_host::identifier::ChannelId, $crate::core::ContextError> {
+            self.$inner.generate_channel_identifier(port_id)
+        }
+    };
+    (@ $inner:ident emit_ibc_event) => {
+        fn emit_ibc_event(&mut self, event: $crate::core::events::IbcEvent) {
+            self.$inner.emit_ibc_event(event)
+        }
+    };
+    (@ $inner:ident emit_ibc_events) => {
+        fn emit_ibc_events(
+            &mut self,
+            events: $crate::prelude::Vec<$crate::core::events::IbcEvent>,
+        ) {
+            self.$inner.emit_ibc_events(events)
+        }
+    };
+    (@ $inner:ident log_message) => {
+        fn log_message(&mut self, message: $crate::prelude::String) {
+            self.$inner.log_message(message)
+        }
+    };
+    (@ $inner:ident log_structured) => {
+        fn log_structured(&mut self, key: &str, fields: &[(&str, $crate::prelude::String)]) {
+            self.$inner.log_structured(key, fields)
+        }
+    };
+    (@ $inner:ident claim_capability) => {
+        fn claim_capability(
+            &mut self,
+            port_id: $crate::core::ics24_host::identifier::PortId,
+            module_id: $crate::core::router::ModuleId,
+        ) -> Result<$crate::core::router::Capability, $crate::core::ContextError> {
+            self.$inner.claim_capability(port_id, module_id)
+        }
+    };
+    (@ $inner:ident store_last_recv_time) => {
+        fn store_last_recv_time(
+            &mut self,
+            port_id: &$crate::core::ics24_host::identifier::PortId,
+            channel_id: &$crate::core::ics24_host::identifier::ChannelId,
+            timestamp: $crate::core::timestamp::Timestamp,
+        ) -> Result<(), $crate::core::ContextError> {
+            self.$inner.store_last_recv_time(port_id, channel_id, timestamp)
+        }
+    };
+}
+
+pub(crate) use forward_context;
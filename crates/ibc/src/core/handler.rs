@@ -1,5 +1,7 @@
 use super::context::RouterError;
-use super::ics02_client::handler::{create_client, update_client, upgrade_client};
+use super::ics02_client::handler::{
+    create_client, update_client, update_client_batch, upgrade_client,
+};
 use super::ics02_client::msgs::{ClientMsg, MsgUpdateOrMisbehaviour};
 use super::ics03_connection::handler::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try,
@@ -30,8 +32,9 @@ use super::ics04_channel::msgs::{
     channel_msg_to_port_id, packet_msg_to_port_id, ChannelMsg, PacketMsg,
 };
 use super::msgs::MsgEnvelope;
-use super::router::Router;
-use super::{ExecutionContext, ValidationContext};
+use super::router::{Capability, Router};
+use super::{ContextError, ExecutionContext, ValidationContext};
+use crate::Height;
 
 /// Entrypoint which performs both validation and message execution
 pub fn dispatch(
@@ -43,6 +46,55 @@ pub fn dispatch(
     execute(ctx, router, msg)
 }
 
+/// Guards against a host whose reported height goes backwards between the
+/// messages of a session, e.g. the messages of one block.
+///
+/// A guard starts out without any observed height, and then remembers the
+/// highest host height seen by [`HostHeightGuard::dispatch`].
+#[derive(Clone, Debug, Default)]
+pub struct HostHeightGuard {
+    last_host_height: Option<Height>,
+}
+
+impl HostHeightGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the last host height observed by this guard, if any.
+    pub fn last_host_height(&self) -> Option<Height> {
+        self.last_host_height
+    }
+
+    /// Checks that the current host height is not lower than the last observed
+    /// one, and records it.
+    pub fn observe(&mut self, ctx: &impl ValidationContext) -> Result<Height, ContextError> {
+        let current = ctx.host_height()?;
+
+        if let Some(last) = self.last_host_height {
+            if current < last {
+                return Err(ContextError::NonMonotonicHostHeight { current, last });
+            }
+        }
+
+        self.last_host_height = Some(current);
+
+        Ok(current)
+    }
+
+    /// Same as [`dispatch`], but first rejects the message if the host height
+    /// went backwards since the previous message dispatched through this guard.
+    pub fn dispatch(
+        &mut self,
+        ctx: &mut impl ExecutionContext,
+        router: &mut impl Router,
+        msg: MsgEnvelope,
+    ) -> Result<(), RouterError> {
+        self.observe(ctx)?;
+        dispatch(ctx, router, msg)
+    }
+}
+
 /// Entrypoint which only performs message validation
 ///
 /// If a transaction contains `n` messages `m_1` ... `m_n`, then
@@ -51,11 +103,30 @@ pub fn dispatch(
 /// That is, the state transition of message `i` must be applied before
 /// message `i+1` is validated. This is equivalent to calling
 /// `dispatch()` on each successively.
+///
+/// A validation failure is reported to the host's telemetry, while the
+/// outcome of a message that passes validation is reported by `execute()`,
+/// so that every message is reported once.
 pub fn validate<Ctx>(
     ctx: &Ctx,
     router: &mut impl Router,
     msg: MsgEnvelope,
 ) -> Result<(), RouterError>
+where
+    Ctx: ValidationContext,
+{
+    let handler = handler_name(&msg);
+    match validate_msg(ctx, router, msg) {
+        Ok(()) => Ok(()),
+        res => report_handler_result(ctx, handler, res),
+    }
+}
+
+fn validate_msg<Ctx>(
+    ctx: &Ctx,
+    router: &mut impl Router,
+    msg: MsgEnvelope,
+) -> Result<(), RouterError>
 where
     Ctx: ValidationContext,
 {
@@ -65,6 +136,7 @@ where
             ClientMsg::UpdateClient(msg) => {
                 update_client::validate(ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg))
             }
+            ClientMsg::UpdateClientBatch(msg) => update_client_batch::validate(ctx, msg),
             ClientMsg::Misbehaviour(msg) => {
                 update_client::validate(ctx, MsgUpdateOrMisbehaviour::Misbehaviour(msg))
             }
@@ -85,6 +157,9 @@ where
                 .ok_or(RouterError::UnknownPort {
                     port_id: port_id.clone(),
                 })?;
+            // Only the module holding the port's capability may drive the
+            // channel handshakes on it.
+            ctx.authenticate_capability(&Capability::new(port_id.clone(), module_id.clone()))?;
             let module = router
                 .get_route(&module_id)
                 .ok_or(RouterError::ModuleNotFound)?;
@@ -111,7 +186,7 @@ where
                 .ok_or(RouterError::ModuleNotFound)?;
 
             match msg {
-                PacketMsg::Recv(msg) => recv_packet_validate(ctx, msg),
+                PacketMsg::Recv(msg) => recv_packet_validate(ctx, module, msg),
                 PacketMsg::Ack(msg) => acknowledgement_packet_validate(ctx, module, msg),
                 PacketMsg::Timeout(msg) => {
                     timeout_packet_validate(ctx, module, TimeoutMsgType::Timeout(msg))
@@ -131,6 +206,19 @@ pub fn execute<Ctx>(
     router: &mut impl Router,
     msg: MsgEnvelope,
 ) -> Result<(), RouterError>
+where
+    Ctx: ExecutionContext,
+{
+    let handler = handler_name(&msg);
+    let res = execute_msg(ctx, router, msg);
+    report_handler_result(ctx, handler, res)
+}
+
+fn execute_msg<Ctx>(
+    ctx: &mut Ctx,
+    router: &mut impl Router,
+    msg: MsgEnvelope,
+) -> Result<(), RouterError>
 where
     Ctx: ExecutionContext,
 {
@@ -140,6 +228,7 @@ where
             ClientMsg::UpdateClient(msg) => {
                 update_client::execute(ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg))
             }
+            ClientMsg::UpdateClientBatch(msg) => update_client_batch::execute(ctx, msg),
             ClientMsg::Misbehaviour(msg) => {
                 update_client::execute(ctx, MsgUpdateOrMisbehaviour::Misbehaviour(msg))
             }
@@ -160,6 +249,9 @@ where
                 .ok_or(RouterError::UnknownPort {
                     port_id: port_id.clone(),
                 })?;
+            // Only the module holding the port's capability may drive the
+            // channel handshakes on it.
+            ctx.authenticate_capability(&Capability::new(port_id.clone(), module_id.clone()))?;
             let module = router
                 .get_route_mut(&module_id)
                 .ok_or(RouterError::ModuleNotFound)?;
@@ -200,6 +292,53 @@ where
     }
 }
 
+/// Returns the name under which the outcome of `msg` is reported to the
+/// host's telemetry.
+fn handler_name(msg: &MsgEnvelope) -> &'static str {
+    match msg {
+        MsgEnvelope::Client(msg) => match msg {
+            ClientMsg::CreateClient(_) => "create_client",
+            ClientMsg::UpdateClient(_) => "update_client",
+            ClientMsg::UpdateClientBatch(_) => "update_client_batch",
+            ClientMsg::Misbehaviour(_) => "misbehaviour",
+            ClientMsg::UpgradeClient(_) => "upgrade_client",
+        },
+        MsgEnvelope::Connection(msg) => match msg {
+            ConnectionMsg::OpenInit(_) => "conn_open_init",
+            ConnectionMsg::OpenTry(_) => "conn_open_try",
+            ConnectionMsg::OpenAck(_) => "conn_open_ack",
+            ConnectionMsg::OpenConfirm(_) => "conn_open_confirm",
+        },
+        MsgEnvelope::Channel(msg) => match msg {
+            ChannelMsg::OpenInit(_) => "chan_open_init",
+            ChannelMsg::OpenTry(_) => "chan_open_try",
+            ChannelMsg::OpenAck(_) => "chan_open_ack",
+            ChannelMsg::OpenConfirm(_) => "chan_open_confirm",
+            ChannelMsg::CloseInit(_) => "chan_close_init",
+            ChannelMsg::CloseConfirm(_) => "chan_close_confirm",
+        },
+        MsgEnvelope::Packet(msg) => match msg {
+            PacketMsg::Recv(_) => "recv_packet",
+            PacketMsg::Ack(_) => "acknowledgement_packet",
+            PacketMsg::Timeout(_) => "timeout_packet",
+            PacketMsg::TimeoutOnClose(_) => "timeout_on_close_packet",
+        },
+    }
+}
+
+/// Reports the outcome of `handler` to the host's telemetry.
+fn report_handler_result<Ctx>(
+    ctx: &Ctx,
+    handler: &str,
+    res: Result<(), RouterError>,
+) -> Result<(), RouterError>
+where
+    Ctx: ValidationContext,
+{
+    ctx.telemetry().on_handler_result(handler, res.is_ok());
+    res
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +394,7 @@ mod tests {
     use crate::core::msgs::MsgEnvelope;
     use crate::core::router::ModuleId;
     use crate::core::timestamp::Timestamp;
+    use crate::core::Telemetry;
     use crate::mock::client_state::MockClientState;
     use crate::mock::consensus_state::MockConsensusState;
     use crate::mock::context::MockContext;
@@ -263,6 +403,8 @@ mod tests {
     use crate::prelude::*;
     use crate::test_utils::{get_dummy_account_id, DummyTransferModule};
     use crate::Height;
+    use alloc::sync::Arc;
+    use parking_lot::Mutex;
 
     #[test]
     /// These tests exercise two main paths: (1) the ability of the ICS26 routing module to dispatch
@@ -398,7 +540,9 @@ mod tests {
             "ICS26 routing dispatch test 'client creation' failed for message {create_client_msg:?} with result: {res:?}",
         );
 
-        router.scope_port_to_module(msg_chan_init.port_id_on_a.clone(), transfer_module_id);
+        router
+            .bind_port(msg_chan_init.port_id_on_a.clone(), transfer_module_id)
+            .unwrap();
 
         // Figure out the ID of the client that was just created.
         assert!(matches!(
@@ -621,11 +765,13 @@ mod tests {
         for test in tests {
             let res = match test.msg.clone() {
                 TestMsg::Ics26(msg) => dispatch(&mut ctx, &mut router, msg).map(|_| ()),
-                TestMsg::Ics20(msg) => send_transfer(&mut ctx, &mut DummyTransferModule, msg)
-                    .map_err(|e: TokenTransferError| ChannelError::AppModule {
-                        description: e.to_string(),
-                    })
-                    .map_err(|e| RouterError::ContextError(e.into())),
+                TestMsg::Ics20(msg) => {
+                    send_transfer(&mut ctx, &mut DummyTransferModule::new(), msg)
+                        .map_err(|e: TokenTransferError| ChannelError::AppModule {
+                            description: e.to_string(),
+                        })
+                        .map_err(|e| RouterError::ContextError(e.into()))
+                }
             };
 
             assert_eq!(
@@ -676,7 +822,7 @@ mod tests {
             .unwrap();
 
         // Note: messages will be using the default port
-        router.scope_port_to_module(PortId::default(), module_id);
+        router.bind_port(PortId::default(), module_id).unwrap();
 
         (ctx, router)
     }
@@ -703,6 +849,65 @@ mod tests {
         assert!(matches!(ctx.events[1], IbcEvent::OpenInitChannel(_)));
     }
 
+    #[test]
+    fn test_chan_open_init_capability_holder() {
+        let (mut ctx, mut router) = get_channel_events_ctx_router();
+        let module_id = ModuleId::new(MODULE_ID_STR.to_string());
+
+        ctx.claim_capability(PortId::default(), module_id.clone())
+            .unwrap();
+        // Claiming the capability again for the same module is a no-op.
+        ctx.claim_capability(PortId::default(), module_id).unwrap();
+
+        let msg_chan_open_init =
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init(None)).unwrap();
+
+        let res = dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Channel(ChannelMsg::OpenInit(msg_chan_open_init)),
+        );
+        assert!(res.is_ok(), "result: {res:?}");
+    }
+
+    #[test]
+    fn test_chan_open_init_rejects_module_without_capability() {
+        let (mut ctx, mut router) = get_channel_events_ctx_router();
+        let other_module_id = ModuleId::new("othermodule".to_string());
+
+        // The port is bound to another module than the one the router
+        // dispatches its messages to.
+        ctx.claim_capability(PortId::default(), other_module_id)
+            .unwrap();
+
+        let res = ctx.claim_capability(PortId::default(), ModuleId::new(MODULE_ID_STR.to_string()));
+        assert!(matches!(
+            res,
+            Err(ContextError::ChannelError(
+                ChannelError::CapabilityAlreadyClaimed { .. }
+            ))
+        ));
+
+        let msg_chan_open_init =
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init(None)).unwrap();
+
+        let res = dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Channel(ChannelMsg::OpenInit(msg_chan_open_init)),
+        );
+        assert!(
+            matches!(
+                res,
+                Err(RouterError::ContextError(ContextError::ChannelError(
+                    ChannelError::CapabilityNotOwned { .. }
+                )))
+            ),
+            "result: {res:?}"
+        );
+        assert!(ctx.events.is_empty());
+    }
+
     #[test]
     fn test_chan_open_try_event() {
         let (mut ctx, mut router) = get_channel_events_ctx_router();
@@ -793,6 +998,74 @@ mod tests {
         assert!(matches!(ctx.events[1], IbcEvent::OpenConfirmChannel(_)));
     }
 
+    /// Counts the handler results reported to it
+    #[derive(Default)]
+    struct CountingTelemetry {
+        results: Mutex<Vec<(String, bool)>>,
+    }
+
+    impl Telemetry for CountingTelemetry {
+        fn on_handler_result(&self, handler: &str, ok: bool) {
+            self.results.lock().push((handler.to_string(), ok));
+        }
+    }
+
+    #[test]
+    fn test_chan_open_confirm_telemetry() {
+        let telemetry = Arc::new(CountingTelemetry::default());
+        let (ctx, mut router) = get_channel_events_ctx_router();
+        let mut ctx = ctx.with_telemetry(telemetry.clone()).with_channel(
+            PortId::default(),
+            ChannelId::default(),
+            ChannelEnd::new(
+                ChannelState::TryOpen,
+                ChannelOrder::Unordered,
+                ChannelCounterparty::new(PortId::default(), Some(ChannelId::default())),
+                vec![ConnectionId::new(0)],
+                ChannelVersion::default(),
+            )
+            .unwrap(),
+        );
+
+        let msg_chan_open_confirm =
+            MsgChannelOpenConfirm::try_from(get_dummy_raw_msg_chan_open_confirm(1)).unwrap();
+
+        dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Channel(ChannelMsg::OpenConfirm(msg_chan_open_confirm)),
+        )
+        .unwrap();
+
+        assert_eq!(
+            *telemetry.results.lock(),
+            vec![("chan_open_confirm".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn test_chan_open_confirm_failure_telemetry() {
+        let telemetry = Arc::new(CountingTelemetry::default());
+        let (ctx, mut router) = get_channel_events_ctx_router();
+        // no channel end is stored, so the message fails validation
+        let mut ctx = ctx.with_telemetry(telemetry.clone());
+
+        let msg_chan_open_confirm =
+            MsgChannelOpenConfirm::try_from(get_dummy_raw_msg_chan_open_confirm(1)).unwrap();
+
+        let res = dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Channel(ChannelMsg::OpenConfirm(msg_chan_open_confirm)),
+        );
+        assert!(res.is_err());
+
+        assert_eq!(
+            *telemetry.results.lock(),
+            vec![("chan_open_confirm".to_string(), false)]
+        );
+    }
+
     #[test]
     fn test_chan_close_init_event() {
         let (ctx, mut router) = get_channel_events_ctx_router();
@@ -860,4 +1133,47 @@ mod tests {
         ));
         assert!(matches!(ctx.events[1], IbcEvent::CloseConfirmChannel(_)));
     }
+
+    #[test]
+    fn host_height_guard_rejects_decreasing_height() {
+        let mut router = MockRouter::default();
+        let client_height = Height::new(0, 5).unwrap();
+        let create_client_msg = || {
+            MsgEnvelope::Client(ClientMsg::CreateClient(MsgCreateClient::new(
+                MockClientState::new(MockHeader::new(client_height)).into(),
+                MockConsensusState::new(MockHeader::new(client_height)).into(),
+                get_dummy_account_id(),
+            )))
+        };
+
+        let mut ctx_high = MockContext::default().with_height(Height::new(0, 10).unwrap());
+        let mut ctx_low = MockContext::default();
+
+        let mut guard = HostHeightGuard::new();
+        assert_eq!(guard.last_host_height(), None);
+
+        guard
+            .dispatch(&mut ctx_high, &mut router, create_client_msg())
+            .unwrap();
+        assert_eq!(guard.last_host_height(), Some(Height::new(0, 10).unwrap()));
+
+        // the same height is accepted
+        guard
+            .dispatch(&mut ctx_high, &mut router, create_client_msg())
+            .unwrap();
+
+        let res = guard.dispatch(&mut ctx_low, &mut router, create_client_msg());
+        assert!(
+            matches!(
+                res,
+                Err(RouterError::ContextError(ContextError::NonMonotonicHostHeight { current, last }))
+                    if current == Height::new(0, 5).unwrap() && last == Height::new(0, 10).unwrap()
+            ),
+            "unexpected result: {res:?}"
+        );
+
+        // the rejected message was not executed, and the last height is kept
+        assert_eq!(ctx_low.client_counter().unwrap(), 0);
+        assert_eq!(guard.last_host_height(), Some(Height::new(0, 10).unwrap()));
+    }
 }
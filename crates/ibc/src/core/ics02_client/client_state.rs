@@ -82,6 +82,10 @@ pub trait ClientStateCommon {
     /// Validate that the client is at a sufficient height
     fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError>;
 
+    /// Returns the height of the consensus state that updating the client
+    /// with `header` would store. The header is decoded but not verified.
+    fn header_height(&self, header: Any) -> Result<Height, ClientError>;
+
     /// Verify the upgraded client and consensus states and validate proofs
     /// against the given root.
     ///
@@ -172,6 +176,47 @@ pub trait ClientStateValidation<ClientValidationContext> {
         ctx: &ClientValidationContext,
         client_id: &ClientId,
     ) -> Result<Status, ClientError>;
+
+    /// Verifies the existence of a value at a given `Path`, with access to
+    /// the client's `ValidationContext`. This is what the core handlers call.
+    ///
+    /// The default implementation delegates to
+    /// [`ClientStateCommon::verify_membership`], which is all that clients
+    /// verifying a proof against a commitment root need. Clients that read
+    /// the host's store instead (e.g. localhost) override it.
+    fn verify_membership_with_context(
+        &self,
+        _ctx: &ClientValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError>
+    where
+        Self: ClientStateCommon,
+    {
+        self.verify_membership(prefix, proof, root, path, value)
+    }
+
+    /// Verifies the absence of a value at a given `Path`, with access to the
+    /// client's `ValidationContext`. This is what the core handlers call.
+    ///
+    /// The default implementation delegates to
+    /// [`ClientStateCommon::verify_non_membership`].
+    fn verify_non_membership_with_context(
+        &self,
+        _ctx: &ClientValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError>
+    where
+        Self: ClientStateCommon,
+    {
+        self.verify_non_membership(prefix, proof, root, path)
+    }
 }
 
 /// `ClientState` methods which require access to the client's
@@ -231,6 +276,21 @@ where
         upgraded_client_state: Any,
         upgraded_consensus_state: Any,
     ) -> Result<Height, ClientError>;
+
+    /// Updates the client state once the proofs of a message were verified
+    /// against it. The core handlers call it when executing every message
+    /// whose proofs they verified.
+    ///
+    /// The default implementation does nothing, which suits clients verifying
+    /// proofs against a commitment root. Clients whose proofs may only be
+    /// used once (e.g. solo machine) override it.
+    fn update_state_on_verified_proofs(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+    ) -> Result<(), ClientError> {
+        Ok(())
+    }
 }
 
 /// Derive macro that implements [`ClientState`] for enums containing variants
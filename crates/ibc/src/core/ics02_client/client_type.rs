@@ -6,9 +6,8 @@ use core::{
     str::FromStr,
 };
 
-use crate::core::ics24_host::{
-    identifier::validate::validate_client_type, identifier::IdentifierError,
-};
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics24_host::identifier::validate::validate_client_type;
 
 #[cfg_attr(
     feature = "parity-scale-codec",
@@ -28,11 +27,15 @@ use crate::core::ics24_host::{
 pub struct ClientType(String);
 
 impl ClientType {
-    /// Constructs a new `ClientType` from the given `String` if it ends with a valid client identifier.
-    pub fn new(s: &str) -> Result<Self, IdentifierError> {
-        let s_trim = s.trim();
-        validate_client_type(s_trim)?;
-        Ok(Self(s_trim.to_string()))
+    /// Constructs a new `ClientType` from the given `String` if it is a valid
+    /// prefix of client identifiers, i.e. it only uses the identifier
+    /// charset and fits in a client identifier along with any counter.
+    pub fn new(s: &str) -> Result<Self, ClientError> {
+        validate_client_type(s).map_err(|e| ClientError::InvalidClientType {
+            client_type: s.to_string(),
+            validation_error: e,
+        })?;
+        Ok(Self(s.to_string()))
     }
 
     /// Yields this identifier as a borrowed `&str`
@@ -42,7 +45,7 @@ impl ClientType {
 }
 
 impl FromStr for ClientType {
-    type Err = IdentifierError;
+    type Err = ClientError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::new(s)
@@ -54,3 +57,44 @@ impl Display for ClientType {
         write!(f, "ClientType({})", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ics24_host::identifier::IdentifierError;
+
+    #[test]
+    fn client_type_rejects_slash() {
+        assert!(matches!(
+            ClientType::new("07-tendermint/evil"),
+            Err(ClientError::InvalidClientType {
+                validation_error: IdentifierError::ContainSeparator { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn client_type_rejects_whitespace() {
+        for client_type in ["07 tendermint", " 07-tendermint", "07-tendermint\n"] {
+            assert!(matches!(
+                ClientType::new(client_type),
+                Err(ClientError::InvalidClientType {
+                    validation_error: IdentifierError::InvalidCharacter { .. },
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn client_type_accepts_known_types() {
+        assert_eq!(
+            ClientType::new(crate::mock::client_state::MOCK_CLIENT_TYPE)
+                .unwrap()
+                .as_str(),
+            "9999-mock"
+        );
+        assert!(ClientType::new("07-tendermint").is_ok());
+    }
+}
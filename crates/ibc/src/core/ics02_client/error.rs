@@ -25,12 +25,21 @@ pub enum ClientError {
         counter: u64,
         validation_error: IdentifierError,
     },
+    /// invalid client type `{client_type}`, validation error: `{validation_error}`
+    InvalidClientType {
+        client_type: String,
+        validation_error: IdentifierError,
+    },
+    /// client counter did not increase: went from `{previous}` to `{current}`
+    ClientCounterRegression { previous: u64, current: u64 },
     /// client is frozen with description: `{description}`
     ClientFrozen { description: String },
     /// client is not active. Status=`{status}`
     ClientNotActive { status: Status },
     /// client state not found: `{client_id}`
     ClientStateNotFound { client_id: ClientId },
+    /// client type `{client_type}` is not allowed on this host
+    ClientTypeNotAllowed { client_type: ClientType },
     /// client state already exists: `{client_id}`
     ClientStateAlreadyExists { client_id: ClientId },
     /// consensus state not found at: `{client_id}` at height `{height}`
@@ -77,6 +86,8 @@ pub enum ClientError {
     InvalidHeight,
     /// height cannot end up zero or negative
     InvalidHeightResult,
+    /// proof of `{size}` bytes exceeds the maximum of `{max}` bytes
+    ProofTooLarge { size: usize, max: usize },
     /// the proof height is insufficient: latest_height=`{latest_height}` proof_height=`{proof_height}`
     InvalidProofHeight {
         latest_height: Height,
@@ -88,6 +99,14 @@ pub enum ClientError {
     InvalidPacketTimestamp(crate::core::timestamp::ParseTimestampError),
     /// mismatch between client and arguments types
     ClientArgsTypeMismatch { client_type: ClientType },
+    /// client update batch is empty
+    EmptyUpdateBatch,
+    /// header `{index}` of the update batch has height `{height}`, which is not above the previous height `{previous_height}`
+    BatchHeaderOutOfOrder {
+        index: usize,
+        height: Height,
+        previous_height: Height,
+    },
     /// received header height (`{header_height}`) is lower than (or equal to) client latest height (`{latest_height}`)
     LowHeaderHeight {
         header_height: Height,
@@ -97,6 +116,12 @@ pub enum ClientError {
     InvalidConsensusStateTimestamp { time1: Timestamp, time2: Timestamp },
     /// the local consensus state could not be retrieved for height `{height}`
     MissingLocalConsensusState { height: Height },
+    /// host timestamp `{host_timestamp}` is earlier than the timestamp `{consensus_timestamp}` of the host consensus state at height `{height}`
+    InconsistentHostTimestamp {
+        height: Height,
+        host_timestamp: Timestamp,
+        consensus_timestamp: Timestamp,
+    },
     /// invalid signer error: `{reason}`
     InvalidSigner { reason: String },
     /// ics23 verification failure error: `{0}`
@@ -136,6 +161,10 @@ impl std::error::Error for ClientError {
                 validation_error: e,
                 ..
             } => Some(e),
+            Self::InvalidClientType {
+                validation_error: e,
+                ..
+            } => Some(e),
             Self::InvalidMsgUpdateClientId(e) => Some(e),
             Self::InvalidClientIdentifier(e) => Some(e),
             Self::InvalidRawHeader(e) => Some(e),
@@ -1,5 +1,26 @@
 //! This module implements the processing logic for ICS2 (client abstractions and functions) msgs.
 
+use crate::core::ics02_client::client_state::ClientStateExecution;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::core::{ContextError, ExecutionContext};
+
 pub mod create_client;
 pub mod update_client;
+pub mod update_client_batch;
 pub mod upgrade_client;
+
+/// Lets the client `client_id` update its state after it verified the proofs
+/// of the message being executed, see
+/// [`ClientStateExecution::update_state_on_verified_proofs`].
+pub(crate) fn update_client_on_verified_proofs<Ctx>(
+    ctx: &mut Ctx,
+    client_id: &ClientId,
+) -> Result<(), ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    let client_state = ctx.client_state(client_id)?;
+    client_state.update_state_on_verified_proofs(ctx.get_client_execution_context(), client_id)?;
+
+    Ok(())
+}
@@ -11,6 +11,7 @@ use crate::core::ics02_client::error::ClientError;
 use crate::core::ics02_client::events::CreateClient;
 use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
 use crate::core::ics24_host::identifier::ClientId;
+use crate::core::validate_signer;
 use crate::core::ExecutionContext;
 use crate::core::ValidationContext;
 
@@ -24,7 +25,7 @@ where
         signer,
     } = msg;
 
-    ctx.validate_message_signer(&signer)?;
+    validate_signer(ctx, &signer)?;
 
     // Construct this client's identifier
     let id_counter = ctx.client_counter()?;
@@ -35,6 +36,11 @@ where
 
     let client_type = client_state.client_type();
 
+    let allowed_client_types = ctx.allowed_client_types();
+    if !allowed_client_types.is_empty() && !allowed_client_types.contains(&client_type) {
+        return Err(ClientError::ClientTypeNotAllowed { client_type }.into());
+    }
+
     let client_id = ClientId::new(client_type, id_counter).map_err(|e| {
         ClientError::ClientIdentifierConstructor {
             client_type: client_state.client_type(),
@@ -75,6 +81,16 @@ where
         })
     })?;
 
+    let previous_counter = ctx.increase_client_counter()?;
+    let current_counter = ctx.client_counter()?;
+    if current_counter <= previous_counter {
+        return Err(ClientError::ClientCounterRegression {
+            previous: previous_counter,
+            current: current_counter,
+        }
+        .into());
+    }
+
     client_state.initialise(
         ctx.get_client_execution_context(),
         &client_id,
@@ -85,7 +101,6 @@ where
 
     ctx.store_update_time(client_id.clone(), latest_height, ctx.host_timestamp()?)?;
     ctx.store_update_height(client_id.clone(), latest_height, ctx.host_height()?)?;
-    ctx.increase_client_counter();
 
     let event = IbcEvent::CreateClient(CreateClient::new(
         client_id.clone(),
@@ -154,6 +169,63 @@ mod tests {
         assert_eq!(ctx.client_state(&client_id).unwrap(), expected_client_state);
     }
 
+    #[test]
+    fn test_create_client_counter_increases() {
+        let mut ctx = MockContext::default();
+        let height = Height::new(0, 42).unwrap();
+        let msg = MsgCreateClient::new(
+            MockClientState::new(MockHeader::new(height)).into(),
+            MockConsensusState::new(MockHeader::new(height)).into(),
+            get_dummy_account_id(),
+        );
+
+        execute(&mut ctx, msg.clone()).unwrap();
+        execute(&mut ctx, msg.clone()).unwrap();
+
+        let client_ids: Vec<ClientId> = ctx
+            .events
+            .iter()
+            .filter_map(|event| event.as_create_client())
+            .map(|create_client| create_client.client_id().clone())
+            .collect();
+        assert_eq!(
+            client_ids,
+            vec![
+                ClientId::new(mock_client_type(), 0).unwrap(),
+                ClientId::new(mock_client_type(), 1).unwrap(),
+            ]
+        );
+        assert_eq!(ctx.client_counter().unwrap(), 2);
+
+        // A counter reset to a value it already used derives the identifier
+        // of an existing client.
+        ctx.ibc_store.lock().client_ids_counter = 1;
+        let res = validate(&ctx, msg.clone());
+        assert!(matches!(
+            res,
+            Err(ContextError::ClientError(
+                ClientError::ClientStateAlreadyExists { ref client_id }
+            )) if client_id == &ClientId::new(mock_client_type(), 1).unwrap()
+        ));
+
+        // The mock counter saturates instead of overflowing, so a full counter
+        // stops increasing, and the client is not created.
+        ctx.ibc_store.lock().client_ids_counter = u64::MAX;
+        let res = execute(&mut ctx, msg);
+        assert!(matches!(
+            res,
+            Err(ContextError::ClientError(
+                ClientError::ClientCounterRegression {
+                    previous: u64::MAX,
+                    current: u64::MAX,
+                }
+            ))
+        ));
+        assert!(ctx
+            .client_state(&ClientId::new(mock_client_type(), u64::MAX).unwrap())
+            .is_err());
+    }
+
     #[test]
     fn test_tm_create_client_ok() {
         let signer = get_dummy_account_id();
@@ -187,4 +259,37 @@ mod tests {
         assert_eq!(expected_client_state.client_type(), client_type);
         assert_eq!(ctx.client_state(&client_id).unwrap(), expected_client_state);
     }
+
+    #[test]
+    fn test_create_client_allowed_client_types() {
+        let signer = get_dummy_account_id();
+        let ctx = MockContext::default().with_allowed_client_types(vec![tm_client_type()]);
+
+        let tm_header = get_dummy_tendermint_header();
+        let msg = MsgCreateClient::new(
+            TmClientState::new_dummy_from_header(tm_header.clone()).into(),
+            TmConsensusState::from(tm_header).into(),
+            signer.clone(),
+        );
+
+        let res = validate(&ctx, msg);
+        assert!(res.is_ok(), "tendermint client is allowed: {res:?}");
+
+        let height = Height::new(0, 42).unwrap();
+        let msg = MsgCreateClient::new(
+            MockClientState::new(MockHeader::new(height)).into(),
+            MockConsensusState::new(MockHeader::new(height)).into(),
+            signer,
+        );
+
+        let res = validate(&ctx, msg);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(ClientError::ClientTypeNotAllowed { ref client_type }))
+                    if *client_type == mock_client_type()
+            ),
+            "mock client is not allowed: {res:?}"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 //! Protocol logic specific to processing ICS2 messages of type `MsgUpdateAnyClient`.
 
 use crate::prelude::*;
+use ibc_proto::google::protobuf::Any;
 use prost::Message;
 
 use crate::core::context::ContextError;
@@ -12,13 +13,15 @@ use crate::core::ics02_client::client_state::UpdateKind;
 use crate::core::ics02_client::error::ClientError;
 use crate::core::ics02_client::events::{ClientMisbehaviour, UpdateClient};
 use crate::core::ics02_client::msgs::MsgUpdateOrMisbehaviour;
-use crate::core::{ExecutionContext, ValidationContext};
+use crate::core::ics24_host::identifier::ClientId;
+use crate::core::{validate_signer, ExecutionContext, ValidationContext};
+use crate::Height;
 
 pub(crate) fn validate<Ctx>(ctx: &Ctx, msg: MsgUpdateOrMisbehaviour) -> Result<(), ContextError>
 where
     Ctx: ValidationContext,
 {
-    ctx.validate_message_signer(msg.signer())?;
+    validate_signer(ctx, msg.signer())?;
 
     let client_id = msg.client_id().clone();
     let update_kind = match msg {
@@ -29,20 +32,38 @@ where
     // Read client state from the host chain store. The client should already exist.
     let client_state = ctx.client_state(&client_id)?;
 
+    verify_client_message(
+        ctx,
+        &client_id,
+        &client_state,
+        msg.client_message(),
+        &update_kind,
+    )
+}
+
+/// Verifies a client message against the current state of an active client.
+pub(crate) fn verify_client_message<Ctx>(
+    ctx: &Ctx,
+    client_id: &ClientId,
+    client_state: &Ctx::AnyClientState,
+    client_message: Any,
+    update_kind: &UpdateKind,
+) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
     {
-        let status = client_state.status(ctx.get_client_validation_context(), &client_id)?;
+        let status = client_state.status(ctx.get_client_validation_context(), client_id)?;
         if !status.is_active() {
             return Err(ClientError::ClientNotActive { status }.into());
         }
     }
 
-    let client_message = msg.client_message();
-
     client_state.verify_client_message(
         ctx.get_client_validation_context(),
-        &client_id,
+        client_id,
         client_message,
-        &update_kind,
+        update_kind,
     )?;
 
     Ok(())
@@ -69,19 +90,7 @@ where
     )?;
 
     if found_misbehaviour {
-        client_state.update_state_on_misbehaviour(
-            ctx.get_client_execution_context(),
-            &client_id,
-            client_message,
-            &update_kind,
-        )?;
-
-        let event = IbcEvent::ClientMisbehaviour(ClientMisbehaviour::new(
-            client_id,
-            client_state.client_type(),
-        ));
-        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client));
-        ctx.emit_ibc_event(event);
+        apply_misbehaviour(ctx, &client_id, &client_state, client_message, &update_kind)?;
     } else {
         if !matches!(update_kind, UpdateKind::UpdateClient) {
             return Err(ClientError::MisbehaviourHandlingFailure {
@@ -90,45 +99,89 @@ where
             .into());
         }
 
-        let header = client_message;
+        apply_header(ctx, &client_id, &client_state, client_message)?;
+    }
 
-        let consensus_heights = client_state.update_state(
-            ctx.get_client_execution_context(),
-            &client_id,
-            header.clone(),
-        )?;
+    Ok(())
+}
 
-        // Store host height and time for all updated headers
-        {
-            let host_timestamp = ctx.host_timestamp()?;
-            let host_height = ctx.host_height()?;
+/// Freezes the client on misbehaviour found in a verified client message, and
+/// emits the `ClientMisbehaviour` event.
+pub(crate) fn apply_misbehaviour<Ctx>(
+    ctx: &mut Ctx,
+    client_id: &ClientId,
+    client_state: &Ctx::AnyClientState,
+    client_message: Any,
+    update_kind: &UpdateKind,
+) -> Result<(), ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    client_state.update_state_on_misbehaviour(
+        ctx.get_client_execution_context(),
+        client_id,
+        client_message,
+        update_kind,
+    )?;
 
-            for consensus_height in consensus_heights.iter() {
-                ctx.store_update_time(client_id.clone(), *consensus_height, host_timestamp)?;
-                ctx.store_update_height(client_id.clone(), *consensus_height, host_height)?;
-            }
-        }
+    let event = IbcEvent::ClientMisbehaviour(ClientMisbehaviour::new(
+        client_id.clone(),
+        client_state.client_type(),
+    ));
+    ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client));
+    ctx.emit_ibc_event(event);
 
-        {
-            let event = {
-                let consensus_height = consensus_heights.get(0).ok_or(ClientError::Other {
-                    description: "client update state returned no updated height".to_string(),
-                })?;
-
-                IbcEvent::UpdateClient(UpdateClient::new(
-                    client_id,
-                    client_state.client_type(),
-                    *consensus_height,
-                    consensus_heights,
-                    header.encode_to_vec(),
-                ))
-            };
-            ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client));
-            ctx.emit_ibc_event(event);
+    Ok(())
+}
+
+/// Updates the client with a header that was already verified, records the
+/// host height and time of every updated consensus state, and emits the
+/// `UpdateClient` event. Returns the updated consensus heights.
+pub(crate) fn apply_header<Ctx>(
+    ctx: &mut Ctx,
+    client_id: &ClientId,
+    client_state: &Ctx::AnyClientState,
+    header: Any,
+) -> Result<Vec<Height>, ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    let consensus_heights = client_state.update_state(
+        ctx.get_client_execution_context(),
+        client_id,
+        header.clone(),
+    )?;
+
+    // Store host height and time for all updated headers
+    {
+        let host_timestamp = ctx.host_timestamp()?;
+        let host_height = ctx.host_height()?;
+
+        for consensus_height in consensus_heights.iter() {
+            ctx.store_update_time(client_id.clone(), *consensus_height, host_timestamp)?;
+            ctx.store_update_height(client_id.clone(), *consensus_height, host_height)?;
         }
     }
 
-    Ok(())
+    {
+        let event = {
+            let consensus_height = consensus_heights.get(0).ok_or(ClientError::Other {
+                description: "client update state returned no updated height".to_string(),
+            })?;
+
+            IbcEvent::UpdateClient(UpdateClient::new(
+                client_id.clone(),
+                client_state.client_type(),
+                *consensus_height,
+                consensus_heights.clone(),
+                header.encode_to_vec(),
+            ))
+        };
+        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client));
+        ctx.emit_ibc_event(event);
+    }
+
+    Ok(consensus_heights)
 }
 
 #[cfg(test)]
@@ -140,10 +193,14 @@ mod tests {
     use ibc_proto::google::protobuf::Any;
     use test_log::test;
 
-    use crate::clients::ics07_tendermint::client_state::ClientState as TmClientState;
+    use crate::clients::ics07_tendermint::client_state::{
+        ClientState as TmClientState, ConflictPolicy,
+    };
     use crate::clients::ics07_tendermint::client_type as tm_client_type;
+    use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
     use crate::clients::ics07_tendermint::header::Header as TmHeader;
     use crate::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+    use crate::clients::ics09_localhost::client_type as localhost_client_type;
     use crate::core::events::IbcEvent;
     use crate::core::ics02_client::client_type::ClientType;
     use crate::core::ics02_client::handler::update_client::{execute, validate};
@@ -151,11 +208,12 @@ mod tests {
     use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
     use crate::core::ics23_commitment::specs::ProofSpecs;
     use crate::core::ics24_host::identifier::{ChainId, ClientId};
+    use crate::core::ics24_host::path::ClientConsensusStatePath;
     use crate::core::timestamp::Timestamp;
     use crate::downcast;
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::client_state::MockClientState;
-    use crate::mock::context::{AnyConsensusState, MockContext};
+    use crate::mock::context::{AnyClientState, AnyConsensusState, MockContext};
     use crate::mock::header::MockHeader;
     use crate::mock::host::{HostBlock, HostType};
     use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
@@ -191,6 +249,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_update_localhost_client_to_host_height() {
+        let client_id = ClientId::new(localhost_client_type(), 0).unwrap();
+        let signer = get_dummy_account_id();
+
+        let mut ctx = MockContext::default().with_client_parametrized(
+            &client_id,
+            Height::new(0, 1).unwrap(),
+            Some(localhost_client_type()),
+            None,
+        );
+        ctx.advance_host_chain_height();
+        let host_height = ctx.host_height().unwrap();
+
+        // The header is ignored by the localhost client
+        let msg = MsgUpdateClient {
+            client_id,
+            header: MockHeader::new(Height::new(0, 1).unwrap()).into(),
+            signer,
+        };
+
+        let res = validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(res.is_ok(), "validation happy path");
+
+        let res = execute(&mut ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(res.is_ok(), "execution happy path");
+
+        let client_state = ctx.client_state(&msg.client_id).unwrap();
+        assert_eq!(client_state.latest_height(), host_height);
+        assert!(ctx
+            .consensus_state(&ClientConsensusStatePath::new(&msg.client_id, &host_height))
+            .is_ok());
+    }
+
     #[test]
     fn test_update_nonexisting_client() {
         let client_id = ClientId::from_str("mockclient1").unwrap();
@@ -258,6 +350,49 @@ mod tests {
         assert_eq!(client_state.latest_height(), latest_header_height);
     }
 
+    /// A header can be verified on its own, without the client being updated
+    #[test]
+    fn test_verify_tendermint_header_without_update() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let client_height = Height::new(1, 20).unwrap();
+        let update_height = Height::new(1, 21).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB", 1).unwrap();
+
+        let ctx = MockContext::new(
+            ChainId::new("mockgaiaA", 1).unwrap(),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_with_chain_id(
+            chain_id_b.clone(),
+            &client_id,
+            client_height,
+            Some(tm_client_type()),
+            Some(client_height),
+        );
+
+        let ctx_b = MockContext::new(chain_id_b, HostType::SyntheticTendermint, 5, update_height);
+        let mut block = ctx_b.host_block(&update_height).unwrap().clone();
+        block.set_trusted_height(client_height);
+
+        let client_state = ctx.client_state(&client_id).unwrap();
+        let res = client_state.verify_client_message(
+            ctx.get_client_validation_context(),
+            &client_id,
+            block.into(),
+            &UpdateKind::UpdateClient,
+        );
+        assert!(res.is_ok(), "result: {res:?}");
+
+        assert_eq!(ctx.client_state(&client_id).unwrap(), client_state);
+        assert!(ctx
+            .consensus_state(&ClientConsensusStatePath::new(&client_id, &update_height))
+            .is_err());
+        assert!(ctx.client_update_time(&client_id, &update_height).is_err());
+        assert!(ctx.events.is_empty());
+    }
+
     #[test]
     fn test_update_synthetic_tendermint_client_non_adjacent_ok() {
         let client_id = ClientId::new(tm_client_type(), 0).unwrap();
@@ -433,6 +568,125 @@ mod tests {
         assert_eq!(client_state, ctx_a.latest_client_states(&msg.client_id));
     }
 
+    /// Builds a context holding a Tendermint client with the given conflict
+    /// policy, along with an update message whose header conflicts with the
+    /// consensus state already stored at the header's height.
+    fn conflicting_update_fixture(policy: ConflictPolicy) -> (MockContext, MsgUpdateClient) {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let client_height = Height::new(1, 20).unwrap();
+        let update_height = Height::new(1, 21).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB", 1).unwrap();
+
+        let ctx = MockContext::new(
+            ChainId::new("mockgaiaA", 1).unwrap(),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_with_chain_id(
+            chain_id_b.clone(),
+            &client_id,
+            client_height,
+            Some(tm_client_type()), // The target host chain (B) is synthetic TM.
+            Some(client_height),
+        );
+
+        let ctx_b = MockContext::new(chain_id_b, HostType::SyntheticTendermint, 5, update_height);
+
+        let mut block = ctx_b.host_block(&update_height).unwrap().clone();
+        block.set_trusted_height(client_height);
+
+        {
+            let header_consensus_state: TmConsensusState =
+                downcast!(AnyConsensusState::from(block.clone()) => AnyConsensusState::Tendermint)
+                    .unwrap();
+            let conflicting_consensus_state = TmConsensusState::new(
+                b"conflicting_root".to_vec().into(),
+                header_consensus_state.timestamp,
+                header_consensus_state.next_validators_hash,
+            );
+
+            let mut ibc_store = ctx.ibc_store.lock();
+            let client_record = ibc_store.clients.get_mut(&client_id).unwrap();
+
+            let client_state =
+                downcast!(client_record.client_state.clone().unwrap() => AnyClientState::Tendermint)
+                    .unwrap();
+            client_record.client_state = Some(client_state.with_conflict_policy(policy).into());
+            client_record
+                .consensus_states
+                .insert(update_height, conflicting_consensus_state.into());
+        }
+
+        let msg = MsgUpdateClient {
+            client_id,
+            header: block.into(),
+            signer: get_dummy_account_id(),
+        };
+
+        (ctx, msg)
+    }
+
+    #[test]
+    fn test_update_conflicting_header_freeze_client() {
+        let (mut ctx, msg) = conflicting_update_fixture(ConflictPolicy::FreezeClient);
+
+        let res = validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(res.is_ok(), "result: {res:?}");
+
+        let res = execute(&mut ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(res.is_ok(), "result: {res:?}");
+
+        ensure_misbehaviour(&ctx, &msg.client_id, &tm_client_type());
+    }
+
+    #[test]
+    fn test_update_conflicting_header_reject() {
+        let (ctx, msg) = conflicting_update_fixture(ConflictPolicy::Reject);
+        let update_height = Height::new(1, 21).unwrap();
+        let stored_consensus_state = ctx.latest_consensus_states(&msg.client_id, &update_height);
+
+        let res = validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(
+            matches!(&res, Err(e) if e.to_string().contains("conflicts with the consensus state")),
+            "conflicting header must be rejected by validation: {res:?}"
+        );
+
+        let client_state = ctx.client_state(&msg.client_id).unwrap();
+        assert!(client_state
+            .status(&ctx, &msg.client_id)
+            .unwrap()
+            .is_active());
+        assert_eq!(
+            ctx.latest_consensus_states(&msg.client_id, &update_height),
+            stored_consensus_state
+        );
+        assert!(ctx.events.is_empty());
+    }
+
+    #[test]
+    fn test_update_conflicting_header_ignore() {
+        let (mut ctx, msg) = conflicting_update_fixture(ConflictPolicy::Ignore);
+        let update_height = Height::new(1, 21).unwrap();
+        let stored_consensus_state = ctx.latest_consensus_states(&msg.client_id, &update_height);
+
+        let res = validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(res.is_ok(), "result: {res:?}");
+
+        let res = execute(&mut ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(res.is_ok(), "result: {res:?}");
+
+        let client_state = ctx.client_state(&msg.client_id).unwrap();
+        assert!(client_state
+            .status(&ctx, &msg.client_id)
+            .unwrap()
+            .is_active());
+        assert_eq!(
+            ctx.latest_consensus_states(&msg.client_id, &update_height),
+            stored_consensus_state
+        );
+    }
+
     #[test]
     fn test_update_synthetic_tendermint_client_lower_height() {
         let client_id = ClientId::new(tm_client_type(), 0).unwrap();
@@ -700,4 +954,140 @@ mod tests {
         assert!(res.is_ok());
         ensure_misbehaviour(&ctx_a, &client_id, &tm_client_type());
     }
+
+    /// Builds a context holding a Tendermint client with consensus states at
+    /// heights 18, 20 and 22, with timestamps 20 seconds apart, where the
+    /// returned timestamp is the one at height 20. Updates trust height 18.
+    fn timestamp_monotonicity_fixture() -> (MockContext, ClientId, Timestamp) {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB", 1).unwrap();
+        let trusted_height = Height::new(1, 18).unwrap();
+        let prev_height = Height::new(1, 20).unwrap();
+        let next_height = Height::new(1, 22).unwrap();
+        // both stored consensus states, and the headers in between, must be
+        // in the past of the host
+        let prev_timestamp = (Timestamp::now() - Duration::from_secs(60)).unwrap();
+        let trusted_timestamp = (prev_timestamp - Duration::from_secs(20)).unwrap();
+        let next_timestamp = (prev_timestamp + Duration::from_secs(20)).unwrap();
+
+        let ctx = MockContext::new(
+            ChainId::new("mockgaiaA", 1).unwrap(),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_with_chain_id(
+            chain_id_b.clone(),
+            &client_id,
+            prev_height,
+            Some(tm_client_type()),
+            Some(prev_height),
+        );
+
+        let trusted_block = HostBlock::generate_tm_block(
+            chain_id_b.clone(),
+            trusted_height.revision_height(),
+            trusted_timestamp,
+        );
+        let prev_block = HostBlock::generate_tm_block(
+            chain_id_b.clone(),
+            prev_height.revision_height(),
+            prev_timestamp,
+        );
+        let next_block =
+            HostBlock::generate_tm_block(chain_id_b, next_height.revision_height(), next_timestamp);
+        let client_state = TmClientState::new_dummy_from_header(next_block.header().clone());
+
+        {
+            let mut ibc_store = ctx.ibc_store.lock();
+            let client_record = ibc_store.clients.get_mut(&client_id).unwrap();
+
+            client_record.client_state = Some(client_state.into());
+            client_record
+                .consensus_states
+                .insert(trusted_height, trusted_block.into());
+            client_record
+                .consensus_states
+                .insert(prev_height, prev_block.into());
+            client_record
+                .consensus_states
+                .insert(next_height, next_block.into());
+        }
+
+        (ctx, client_id, prev_timestamp)
+    }
+
+    fn tm_update_at(client_id: &ClientId, height: u64, timestamp: Timestamp) -> MsgUpdateClient {
+        let chain_id_b = ChainId::new("mockgaiaB", 1).unwrap();
+        let mut tm_block = HostBlock::generate_tm_block(chain_id_b, height, timestamp);
+        tm_block.trusted_height = Height::new(1, 18).unwrap();
+
+        MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: TmHeader::from(tm_block).into(),
+            signer: get_dummy_account_id(),
+        }
+    }
+
+    #[test]
+    fn test_update_client_monotonic_timestamp_ok() {
+        let (mut ctx, client_id, prev_timestamp) = timestamp_monotonicity_fixture();
+        let msg = tm_update_at(
+            &client_id,
+            21,
+            (prev_timestamp + Duration::from_secs(10)).unwrap(),
+        );
+
+        let res = validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone()));
+        assert!(res.is_ok(), "result: {res:?}");
+        let res = execute(&mut ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg));
+        assert!(res.is_ok(), "result: {res:?}");
+
+        let update_height = Height::new(1, 21).unwrap();
+        assert!(ctx
+            .consensus_state(&ClientConsensusStatePath::new(&client_id, &update_height))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_update_client_rejects_timestamp_before_prev_consensus_state() {
+        let (ctx, client_id, prev_timestamp) = timestamp_monotonicity_fixture();
+        let msg = tm_update_at(
+            &client_id,
+            21,
+            (prev_timestamp - Duration::from_secs(10)).unwrap(),
+        );
+
+        let res = validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg));
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(
+                    ClientError::HeaderVerificationFailure { .. }
+                ))
+            ),
+            "result: {res:?}"
+        );
+    }
+
+    #[test]
+    fn test_update_client_rejects_timestamp_after_next_consensus_state() {
+        let (ctx, client_id, prev_timestamp) = timestamp_monotonicity_fixture();
+        let msg = tm_update_at(
+            &client_id,
+            21,
+            (prev_timestamp + Duration::from_secs(30)).unwrap(),
+        );
+
+        let res = validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg));
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(
+                    ClientError::HeaderVerificationFailure { .. }
+                ))
+            ),
+            "result: {res:?}"
+        );
+    }
 }
@@ -0,0 +1,365 @@
+//! Protocol logic specific to processing ICS2 messages of type `MsgUpdateClientBatch`.
+
+use crate::prelude::*;
+
+use crate::core::context::ContextError;
+use crate::core::ics02_client::client_state::UpdateKind;
+use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::handler::update_client::{
+    self, apply_header, apply_misbehaviour, verify_client_message,
+};
+use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use crate::core::ics02_client::msgs::update_client_batch::MsgUpdateClientBatch;
+use crate::core::ics02_client::msgs::MsgUpdateOrMisbehaviour;
+use crate::core::{ExecutionContext, ValidationContext};
+use crate::Height;
+
+pub(crate) fn validate<Ctx>(ctx: &Ctx, msg: MsgUpdateClientBatch) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let MsgUpdateClientBatch {
+        client_id,
+        mut headers,
+        signer,
+    } = msg;
+
+    if headers.is_empty() {
+        return Err(ClientError::EmptyUpdateBatch.into());
+    }
+
+    let client_state = ctx.client_state(&client_id)?;
+
+    let mut previous_height: Option<Height> = None;
+    for (index, header) in headers.iter().enumerate() {
+        let height = client_state.header_height(header.clone())?;
+
+        if let Some(previous_height) = previous_height {
+            verify_ascending_height(index, height, previous_height)?;
+        }
+        previous_height = Some(height);
+    }
+
+    // Only the first header can be verified against the stored client state:
+    // the following ones may rely on the consensus states left by the headers
+    // preceding them, and are verified as they are applied in `execute`.
+    update_client::validate(
+        ctx,
+        MsgUpdateOrMisbehaviour::UpdateClient(MsgUpdateClient {
+            client_id,
+            header: headers.swap_remove(0),
+            signer,
+        }),
+    )
+}
+
+pub(crate) fn execute<Ctx>(ctx: &mut Ctx, msg: MsgUpdateClientBatch) -> Result<(), ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    let MsgUpdateClientBatch {
+        client_id,
+        headers,
+        signer: _,
+    } = msg;
+
+    let update_kind = UpdateKind::UpdateClient;
+
+    for (index, header) in headers.into_iter().enumerate() {
+        let client_state = ctx.client_state(&client_id)?;
+
+        // The first header was verified in `validate`. The following ones are
+        // verified against the state left by the headers applied before them,
+        // and the batch stops at the first one failing verification, keeping
+        // the updates already applied.
+        if index > 0 {
+            if let Err(e) =
+                verify_client_message(ctx, &client_id, &client_state, header.clone(), &update_kind)
+            {
+                ctx.log_message(format!(
+                    "update client batch stopped at header {index}: {e}"
+                ));
+                break;
+            }
+        }
+
+        let found_misbehaviour = client_state.check_for_misbehaviour(
+            ctx.get_client_validation_context(),
+            &client_id,
+            header.clone(),
+            &update_kind,
+        )?;
+
+        if found_misbehaviour {
+            // The client is frozen, so the remaining headers cannot be applied
+            apply_misbehaviour(ctx, &client_id, &client_state, header, &update_kind)?;
+            break;
+        }
+
+        apply_header(ctx, &client_id, &client_state, header)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that the header at position `index` of a batch is above the header
+/// preceding it. Headers may skip heights, as clients verifying headers
+/// non-adjacently allow.
+fn verify_ascending_height(
+    index: usize,
+    height: Height,
+    previous_height: Height,
+) -> Result<(), ClientError> {
+    if height <= previous_height {
+        return Err(ClientError::BatchHeaderOutOfOrder {
+            index,
+            height,
+            previous_height,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::client_type as tm_client_type;
+    use crate::clients::ics07_tendermint::header::Header as TmHeader;
+    use crate::core::dispatch;
+    use crate::core::events::IbcEvent;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics24_host::identifier::{ChainId, ClientId};
+    use crate::core::ics24_host::path::ClientConsensusStatePath;
+    use crate::core::timestamp::Timestamp;
+    use crate::core::MsgEnvelope;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::host::{HostBlock, HostType};
+    use crate::mock::router::MockRouter;
+    use crate::test_utils::get_dummy_account_id;
+
+    fn msg_with_heights(client_id: &ClientId, heights: &[u64]) -> MsgUpdateClientBatch {
+        let timestamp = Timestamp::now();
+        let headers = heights
+            .iter()
+            .map(|height| {
+                MockHeader::new(Height::new(0, *height).unwrap())
+                    .with_timestamp(timestamp)
+                    .into()
+            })
+            .collect();
+
+        MsgUpdateClientBatch::new(client_id.clone(), headers, get_dummy_account_id())
+    }
+
+    fn heights(heights: &[u64]) -> Vec<Height> {
+        heights
+            .iter()
+            .map(|height| Height::new(0, *height).unwrap())
+            .collect()
+    }
+
+    fn update_client_heights(ctx: &MockContext) -> Vec<Height> {
+        ctx.events
+            .iter()
+            .filter_map(|event| match event {
+                IbcEvent::UpdateClient(update) => Some(*update.consensus_height()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_update_client_batch_ok() {
+        let client_id = ClientId::default();
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42).unwrap());
+        let msg = msg_with_heights(&client_id, &[43, 44, 45]);
+
+        let res = validate(&ctx, msg.clone());
+        assert!(res.is_ok(), "validation happy path: {res:?}");
+
+        let res = execute(&mut ctx, msg);
+        assert!(res.is_ok(), "execution happy path: {res:?}");
+
+        for height in [43, 44, 45] {
+            let height = Height::new(0, height).unwrap();
+            assert!(ctx
+                .consensus_state(&ClientConsensusStatePath::new(&client_id, &height))
+                .is_ok());
+            assert!(ctx.client_update_time(&client_id, &height).is_ok());
+        }
+        assert_eq!(
+            ctx.client_state(&client_id).unwrap().latest_height(),
+            Height::new(0, 45).unwrap()
+        );
+        assert_eq!(update_client_heights(&ctx), heights(&[43, 44, 45]));
+    }
+
+    /// Headers may skip heights
+    #[test]
+    fn test_update_client_batch_skipping_heights() {
+        let client_id = ClientId::default();
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42).unwrap());
+        let msg = msg_with_heights(&client_id, &[43, 45, 50]);
+
+        let res = validate(&ctx, msg.clone());
+        assert!(res.is_ok(), "validation failed: {res:?}");
+
+        let res = execute(&mut ctx, msg);
+        assert!(res.is_ok(), "execution failed: {res:?}");
+        assert_eq!(update_client_heights(&ctx), heights(&[43, 45, 50]));
+    }
+
+    fn tm_context(client_id: &ClientId, client_height: Height) -> MockContext {
+        MockContext::new(
+            ChainId::new("mockgaiaA", 1).unwrap(),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_with_chain_id(
+            ChainId::new("mockgaiaB", 1).unwrap(),
+            client_id,
+            client_height,
+            Some(tm_client_type()),
+            Some(client_height),
+        )
+    }
+
+    /// Returns a batch of headers of the synthetic Tendermint chain B at the
+    /// given heights, each trusting the height paired with it. The headers are
+    /// timestamped after the consensus states of clients created before.
+    fn tm_msg(client_id: &ClientId, heights: &[(u64, u64)]) -> MsgUpdateClientBatch {
+        let headers = heights
+            .iter()
+            .map(|(height, trusted_height)| {
+                let mut block = HostBlock::generate_tm_block(
+                    ChainId::new("mockgaiaB", 1).unwrap(),
+                    *height,
+                    Timestamp::now(),
+                );
+                block.trusted_height = Height::new(1, *trusted_height).unwrap();
+                TmHeader::from(block).into()
+            })
+            .collect();
+
+        MsgUpdateClientBatch::new(client_id.clone(), headers, get_dummy_account_id())
+    }
+
+    fn tm_heights(heights: &[u64]) -> Vec<Height> {
+        heights
+            .iter()
+            .map(|height| Height::new(1, *height).unwrap())
+            .collect()
+    }
+
+    /// Each header is verified against the consensus state stored by the
+    /// header preceding it
+    #[test]
+    fn test_update_client_batch_tendermint_chained() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let mut ctx = tm_context(&client_id, Height::new(1, 20).unwrap());
+        let mut router = MockRouter::default();
+        let msg = tm_msg(&client_id, &[(21, 20), (22, 21), (25, 22)]);
+
+        let res = dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Client(ClientMsg::UpdateClientBatch(msg)),
+        );
+        assert!(res.is_ok(), "dispatch failed: {res:?}");
+
+        assert_eq!(update_client_heights(&ctx), tm_heights(&[21, 22, 25]));
+        assert_eq!(
+            ctx.client_state(&client_id).unwrap().latest_height(),
+            Height::new(1, 25).unwrap()
+        );
+    }
+
+    /// A header failing verification stops the batch, keeping the headers
+    /// applied before it
+    #[test]
+    fn test_update_client_batch_bad_middle_header() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let mut ctx = tm_context(&client_id, Height::new(1, 20).unwrap());
+        let mut router = MockRouter::default();
+        // The second header trusts a height the client has no consensus state at
+        let msg = tm_msg(&client_id, &[(21, 20), (22, 15), (23, 21)]);
+
+        let res = dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Client(ClientMsg::UpdateClientBatch(msg)),
+        );
+        assert!(res.is_ok(), "dispatch failed: {res:?}");
+
+        assert_eq!(update_client_heights(&ctx), tm_heights(&[21]));
+        assert_eq!(
+            ctx.client_state(&client_id).unwrap().latest_height(),
+            Height::new(1, 21).unwrap()
+        );
+        for height in [22, 23] {
+            assert!(ctx
+                .consensus_state(&ClientConsensusStatePath::new(
+                    &client_id,
+                    &Height::new(1, height).unwrap()
+                ))
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_update_client_batch_empty() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42).unwrap());
+
+        let res = validate(&ctx, msg_with_heights(&client_id, &[]));
+        assert!(matches!(
+            res,
+            Err(ContextError::ClientError(ClientError::EmptyUpdateBatch))
+        ));
+    }
+
+    #[test]
+    fn test_update_client_batch_out_of_order() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42).unwrap());
+        let msg = msg_with_heights(&client_id, &[44, 43]);
+
+        // Both headers are above the client height, but not in order
+        let res = validate(&ctx, msg);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(ClientError::BatchHeaderOutOfOrder { index: 1, height, previous_height }))
+                    if height == Height::new(0, 43).unwrap()
+                        && previous_height == Height::new(0, 44).unwrap()
+            ),
+            "unexpected result: {res:?}"
+        );
+    }
+
+    #[test]
+    fn test_verify_ascending_height() {
+        let height = |revision_number, revision_height| {
+            Height::new(revision_number, revision_height).unwrap()
+        };
+
+        assert!(verify_ascending_height(1, height(0, 11), height(0, 10)).is_ok());
+        assert!(verify_ascending_height(1, height(0, 12), height(0, 10)).is_ok());
+        assert!(verify_ascending_height(1, height(1, 3), height(0, 10)).is_ok());
+        assert!(matches!(
+            verify_ascending_height(1, height(0, 10), height(0, 10)),
+            Err(ClientError::BatchHeaderOutOfOrder { index: 1, .. })
+        ));
+        assert!(matches!(
+            verify_ascending_height(2, height(0, 9), height(0, 10)),
+            Err(ClientError::BatchHeaderOutOfOrder { index: 2, .. })
+        ));
+    }
+}
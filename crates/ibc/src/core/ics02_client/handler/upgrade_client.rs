@@ -10,8 +10,9 @@ use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
 use crate::core::ics02_client::events::UpgradeClient;
 use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
+use crate::core::ics02_client::proof::verify_proof_size;
 use crate::core::ics24_host::path::ClientConsensusStatePath;
-use crate::core::{ExecutionContext, ValidationContext};
+use crate::core::{validate_signer, ExecutionContext, ValidationContext};
 
 pub(crate) fn validate<Ctx>(ctx: &Ctx, msg: MsgUpgradeClient) -> Result<(), ContextError>
 where
@@ -21,7 +22,7 @@ where
         client_id, signer, ..
     } = msg;
 
-    ctx.validate_message_signer(&signer)?;
+    validate_signer(ctx, &signer)?;
 
     // Read the current latest client state from the host chain store.
     let old_client_state = ctx.client_state(&client_id)?;
@@ -44,6 +45,9 @@ where
             height: old_client_state.latest_height(),
         })?;
 
+    verify_proof_size(ctx, &msg.proof_upgrade_client)?;
+    verify_proof_size(ctx, &msg.proof_upgrade_consensus_state)?;
+
     // Validate the upgraded client state and consensus state and verify proofs against the root
     old_client_state.verify_upgrade_client(
         msg.upgraded_client_state.clone(),
@@ -91,13 +95,23 @@ mod tests {
     use crate::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
 
     use crate::core::ics02_client::error::UpgradeClientError;
+    use crate::core::ics02_client::handler::update_client;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::MsgUpdateOrMisbehaviour;
+    use crate::core::ics02_client::proof::verify_membership;
     use crate::core::ics03_connection::handler::test_util::{Expect, Fixture};
+    use crate::core::ics23_commitment::commitment::test_util::get_dummy_commitment_proof_bytes;
+    use crate::core::ics23_commitment::error::CommitmentError;
     use crate::core::ics24_host::identifier::ClientId;
+    use crate::core::ics24_host::path::{ClientStatePath, Path};
+    use crate::core::timestamp::Timestamp;
     use crate::downcast;
     use crate::Height;
 
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::context::{AnyClientState, AnyConsensusState, MockContext};
+    use crate::mock::header::MockHeader;
+    use crate::test_utils::get_dummy_account_id;
 
     enum Ctx {
         Default,
@@ -240,4 +254,87 @@ mod tests {
         });
         upgrade_client_validate(&fxt, Expect::Failure(Some(expected_err)));
     }
+
+    /// Upgrades the client of the fixture, then updates it at its new
+    /// revision, returning the context, the client and the updated height.
+    fn upgrade_then_update_client() -> (MockContext, ClientId, Height) {
+        let mut fxt = msg_upgrade_client_fixture(Ctx::WithClient, Msg::Default);
+        upgrade_client_validate(&fxt, Expect::Success);
+        upgrade_client_execute(&mut fxt, Expect::Success);
+
+        let Fixture { mut ctx, msg } = fxt;
+        let client_id = msg.client_id;
+
+        // The upgraded client keeps tracking the counterparty at its new revision.
+        let new_height = Height::new(1, 30).unwrap();
+        let update_msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: MockHeader::new(new_height)
+                .with_timestamp(Timestamp::now())
+                .into(),
+            signer: get_dummy_account_id(),
+        };
+        update_client::validate(
+            &ctx,
+            MsgUpdateOrMisbehaviour::UpdateClient(update_msg.clone()),
+        )
+        .unwrap();
+        update_client::execute(&mut ctx, MsgUpdateOrMisbehaviour::UpdateClient(update_msg))
+            .unwrap();
+
+        let client_state = ctx.client_state(&client_id).unwrap();
+        assert_eq!(client_state.latest_height(), new_height);
+
+        (ctx, client_id, new_height)
+    }
+
+    /// Verifies a proof of the client state against the consensus state stored
+    /// at `height`.
+    fn verify_client_state_proof(
+        ctx: &MockContext,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<(), ClientError> {
+        let client_state = ctx.client_state(client_id).unwrap();
+        let consensus_state = ctx
+            .consensus_state(&ClientConsensusStatePath::new(client_id, height))
+            .unwrap();
+
+        verify_membership(
+            ctx,
+            &client_state,
+            &ctx.commitment_prefix(),
+            &get_dummy_commitment_proof_bytes(),
+            consensus_state.root(),
+            Path::ClientState(ClientStatePath::new(client_id)),
+            vec![1],
+        )
+    }
+
+    #[test]
+    fn upgrade_client_then_verify_at_new_revision() {
+        let (ctx, client_id, new_height) = upgrade_then_update_client();
+
+        // Proofs at the new revision verify against the stored consensus state.
+        let res = verify_client_state_proof(&ctx, &client_id, &new_height);
+        assert!(res.is_ok(), "verification failed: {res:?}");
+    }
+
+    #[test]
+    fn upgrade_client_then_reject_proof_at_new_revision() {
+        let (ctx, client_id, new_height) = upgrade_then_update_client();
+
+        // Proofs at the new revision are still checked by the upgraded client.
+        let ctx = ctx.with_verification_result(false);
+        let res = verify_client_state_proof(&ctx, &client_id, &new_height);
+        assert!(
+            matches!(
+                res,
+                Err(ClientError::Ics23Verification(
+                    CommitmentError::VerificationFailure
+                ))
+            ),
+            "unexpected result: {res:?}"
+        );
+    }
 }
@@ -73,6 +73,15 @@ impl Height {
         }
     }
 
+    /// Returns the height `n` blocks after this one, in the same revision,
+    /// or `None` if the revision height would overflow.
+    pub fn increment_by(&self, n: u64) -> Option<Height> {
+        Some(Height {
+            revision_number: self.revision_number,
+            revision_height: self.revision_height.checked_add(n)?,
+        })
+    }
+
     pub fn increment(&self) -> Height {
         self.add(1)
     }
@@ -91,6 +100,13 @@ impl Height {
     pub fn decrement(&self) -> Result<Height, ClientError> {
         self.sub(1)
     }
+
+    /// Returns the height `n` blocks before this one, in the same revision,
+    /// or `None` if there is no such height, i.e. if `n` is not lower than
+    /// the revision height.
+    pub fn checked_sub(&self, n: u64) -> Option<Height> {
+        self.sub(n).ok()
+    }
 }
 
 impl PartialOrd for Height {
@@ -99,6 +115,9 @@ impl PartialOrd for Height {
     }
 }
 
+/// Heights are ordered by revision number first, then by revision height:
+/// any height of a revision is lower than every height of a later revision,
+/// whatever their revision heights.
 impl Ord for Height {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.revision_number < other.revision_number {
@@ -263,3 +282,39 @@ fn test_invalid_height() {
         })
     );
 }
+
+#[test]
+fn test_cross_revision_ordering() {
+    let height =
+        |revision_number, revision_height| Height::new(revision_number, revision_height).unwrap();
+
+    assert!(height(0, 100) < height(1, 1));
+    assert!(height(1, 1) > height(0, u64::MAX));
+    assert!(height(1, 5) < height(1, 6));
+    assert_eq!(height(2, 7).cmp(&height(2, 7)), Ordering::Equal);
+    assert_eq!(height(0, 100).max(height(1, 1)), height(1, 1));
+}
+
+#[test]
+fn test_increment_by() {
+    let height = Height::new(1, 10).unwrap();
+
+    assert_eq!(height.increment_by(5), Some(Height::new(1, 15).unwrap()));
+    assert_eq!(height.increment_by(1), Some(height.increment()));
+    assert_eq!(height.increment_by(0), Some(height));
+    assert_eq!(
+        height.increment_by(u64::MAX - 10),
+        Height::new(1, u64::MAX).ok()
+    );
+    assert_eq!(height.increment_by(u64::MAX - 9), None);
+}
+
+#[test]
+fn test_checked_sub() {
+    let height = Height::new(1, 10).unwrap();
+
+    assert_eq!(height.checked_sub(3), Some(Height::new(1, 7).unwrap()));
+    assert_eq!(height.checked_sub(9), Some(Height::min(1)));
+    assert_eq!(height.checked_sub(10), None);
+    assert_eq!(height.checked_sub(u64::MAX), None);
+}
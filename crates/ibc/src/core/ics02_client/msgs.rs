@@ -5,6 +5,7 @@ use ibc_proto::google::protobuf::Any;
 use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
 use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
 use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use crate::core::ics02_client::msgs::update_client_batch::MsgUpdateClientBatch;
 use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::signer::Signer;
@@ -12,6 +13,7 @@ use crate::signer::Signer;
 pub mod create_client;
 pub mod misbehaviour;
 pub mod update_client;
+pub mod update_client_batch;
 pub mod upgrade_client;
 
 /// Encodes all the different client messages
@@ -20,6 +22,7 @@ pub mod upgrade_client;
 pub enum ClientMsg {
     CreateClient(MsgCreateClient),
     UpdateClient(MsgUpdateClient),
+    UpdateClientBatch(MsgUpdateClientBatch),
     Misbehaviour(MsgSubmitMisbehaviour),
     UpgradeClient(MsgUpgradeClient),
 }
@@ -0,0 +1,125 @@
+//! Definition of domain type message `MsgUpdateClientBatch`.
+
+use crate::core::Msg;
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::signer::Signer;
+
+pub(crate) const TYPE_URL: &str = "/ibc.core.client.v1.MsgUpdateClientBatch";
+
+/// Raw protobuf encoding of [`MsgUpdateClientBatch`].
+///
+/// `ibc-proto` has no definition for this message, so it is defined here,
+/// mirroring `MsgUpdateClient` with a repeated `headers` field.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgUpdateClientBatch {
+    /// client unique identifier
+    #[prost(string, tag = "1")]
+    pub client_id: String,
+    /// headers to update the client with, in ascending height order
+    #[prost(message, repeated, tag = "2")]
+    pub headers: Vec<Any>,
+    /// signer address
+    #[prost(string, tag = "3")]
+    pub signer: String,
+}
+
+/// Represents the message that updates an on-chain (IBC) client with several
+/// headers at once. The headers must be in strictly ascending height order,
+/// but may skip heights.
+///
+/// Each header is verified against the state left by the headers applied
+/// before it. The batch stops at the first header failing verification, and
+/// the headers applied before it are kept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgUpdateClientBatch {
+    pub client_id: ClientId,
+    pub headers: Vec<Any>,
+    pub signer: Signer,
+}
+
+impl MsgUpdateClientBatch {
+    pub fn new(client_id: ClientId, headers: Vec<Any>, signer: Signer) -> Self {
+        MsgUpdateClientBatch {
+            client_id,
+            headers,
+            signer,
+        }
+    }
+}
+
+impl Msg for MsgUpdateClientBatch {
+    type Raw = RawMsgUpdateClientBatch;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgUpdateClientBatch> for MsgUpdateClientBatch {}
+
+impl TryFrom<RawMsgUpdateClientBatch> for MsgUpdateClientBatch {
+    type Error = ClientError;
+
+    fn try_from(raw: RawMsgUpdateClientBatch) -> Result<Self, Self::Error> {
+        Ok(MsgUpdateClientBatch {
+            client_id: raw
+                .client_id
+                .parse()
+                .map_err(ClientError::InvalidMsgUpdateClientId)?,
+            headers: raw.headers,
+            signer: raw.signer.into(),
+        })
+    }
+}
+
+impl From<MsgUpdateClientBatch> for RawMsgUpdateClientBatch {
+    fn from(ics_msg: MsgUpdateClientBatch) -> Self {
+        RawMsgUpdateClientBatch {
+            client_id: ics_msg.client_id.to_string(),
+            headers: ics_msg.headers,
+            signer: ics_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::header::test_util::get_dummy_ics07_header;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::MsgEnvelope;
+    use crate::test_utils::get_dummy_account_id;
+
+    #[test]
+    fn msg_update_client_batch_serialization() {
+        let client_id: ClientId = "tendermint".parse().unwrap();
+        let header: Any = get_dummy_ics07_header().into();
+
+        let msg = MsgUpdateClientBatch::new(
+            client_id,
+            vec![header.clone(), header],
+            get_dummy_account_id(),
+        );
+        let raw = RawMsgUpdateClientBatch::from(msg.clone());
+        let msg_back = MsgUpdateClientBatch::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgUpdateClientBatch::from(msg_back.clone());
+        assert_eq!(msg, msg_back);
+        assert_eq!(raw, raw_back);
+
+        match MsgEnvelope::try_from(msg.to_any()) {
+            Ok(MsgEnvelope::Client(ClientMsg::UpdateClientBatch(decoded))) => {
+                assert_eq!(decoded, msg)
+            }
+            other => panic!("unexpected decoding result: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Bounds on the proofs submitted to light clients for verification.
+
+use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::path::Path;
+use crate::core::ValidationContext;
+use crate::prelude::*;
+
+/// Default maximum size in bytes of a proof submitted for verification.
+pub const DEFAULT_MAX_PROOF_SIZE: usize = 128 * 1024;
+
+/// Checks that `proof` is no larger than the host's
+/// [`ValidationContext::max_proof_size`], so that oversized proofs are
+/// rejected before any verification work is done on them.
+pub fn verify_proof_size<Ctx>(ctx: &Ctx, proof: &CommitmentProofBytes) -> Result<(), ClientError>
+where
+    Ctx: ValidationContext,
+{
+    verify_proof_size_within(proof, ctx.max_proof_size())
+}
+
+/// Checks that `proof` is no larger than `max` bytes.
+pub fn verify_proof_size_within(
+    proof: &CommitmentProofBytes,
+    max: usize,
+) -> Result<(), ClientError> {
+    let size = proof.as_bytes().len();
+    if size > max {
+        return Err(ClientError::ProofTooLarge { size, max });
+    }
+
+    Ok(())
+}
+
+/// Verifies the existence of `value` at `path` on the counterparty tracked by
+/// `client_state`, rejecting proofs larger than the host's
+/// [`ValidationContext::max_proof_size`] before handing them to
+/// [`ClientStateValidation::verify_membership_with_context`].
+///
+/// The core handlers verify every membership proof through this function.
+pub fn verify_membership<Ctx>(
+    ctx: &Ctx,
+    client_state: &Ctx::AnyClientState,
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+    value: Vec<u8>,
+) -> Result<(), ClientError>
+where
+    Ctx: ValidationContext,
+{
+    verify_membership_within(
+        ctx.get_client_validation_context(),
+        client_state,
+        ctx.max_proof_size(),
+        prefix,
+        proof,
+        root,
+        path,
+        value,
+    )
+}
+
+/// Like [`verify_membership`], for handlers holding the client's validation
+/// context and the maximum proof size rather than the host's context.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_membership_within<V, CS>(
+    client_validation_ctx: &V,
+    client_state: &CS,
+    max_proof_size: usize,
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+    value: Vec<u8>,
+) -> Result<(), ClientError>
+where
+    CS: ClientStateCommon + ClientStateValidation<V>,
+{
+    verify_proof_size_within(proof, max_proof_size)?;
+
+    client_state.verify_membership_with_context(
+        client_validation_ctx,
+        prefix,
+        proof,
+        root,
+        path,
+        value,
+    )
+}
+
+/// Verifies the absence of a value at `path` on the counterparty tracked by
+/// `client_state`, rejecting proofs larger than the host's
+/// [`ValidationContext::max_proof_size`] before handing them to
+/// [`ClientStateValidation::verify_non_membership_with_context`].
+///
+/// The core handlers verify every non-membership proof through this function.
+pub fn verify_non_membership<Ctx>(
+    ctx: &Ctx,
+    client_state: &Ctx::AnyClientState,
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+) -> Result<(), ClientError>
+where
+    Ctx: ValidationContext,
+{
+    verify_proof_size(ctx, proof)?;
+
+    client_state.verify_non_membership_with_context(
+        ctx.get_client_validation_context(),
+        prefix,
+        proof,
+        root,
+        path,
+    )
+}
@@ -468,6 +468,15 @@ impl Counterparty {
         }
         Ok(())
     }
+
+    /// Called upon initiating or trying a connection handshake on the host
+    /// chain to verify that the counterparty commitment prefix is not empty,
+    /// since no proof could ever be verified against it.
+    pub(crate) fn verify_prefix_not_empty(&self) -> Result<(), ConnectionError> {
+        self.prefix
+            .verify_not_empty()
+            .map_err(ConnectionError::InvalidCounterpartyPrefix)
+    }
 }
 
 #[cfg_attr(
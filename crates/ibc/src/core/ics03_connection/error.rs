@@ -2,6 +2,7 @@
 
 use crate::core::ics02_client::error as client_error;
 use crate::core::ics03_connection::version::Version;
+use crate::core::ics23_commitment::error::CommitmentError;
 use crate::core::ics24_host::identifier::{ClientId, ConnectionId, IdentifierError};
 use crate::core::timestamp::{Timestamp, TimestampOverflowError};
 use crate::Height;
@@ -16,6 +17,8 @@ pub enum ConnectionError {
     Client(client_error::ClientError),
     /// invalid connection state: expected `{expected}`, actual `{actual}`
     InvalidState { expected: String, actual: String },
+    /// connection `{connection_id}` is not open
+    ConnectionNotOpen { connection_id: ConnectionId },
     /// invalid connection end error: `{0}`
     InvalidConnectionEnd(ProtoError),
     /// consensus height claimed by the client on the other party is too advanced: `{target_height}` (host chain current height: `{current_height}`)
@@ -25,6 +28,10 @@ pub enum ConnectionError {
     },
     /// identifier error: `{0}`
     InvalidIdentifier(IdentifierError),
+    /// connection end already exists: `{connection_id}`
+    ConnectionAlreadyExists { connection_id: ConnectionId },
+    /// connection counter did not increase: went from `{previous}` to `{current}`
+    ConnectionCounterRegression { previous: u64, current: u64 },
     /// ConnectionEnd domain object could not be constructed out of empty proto object
     EmptyProtoConnectionEnd,
     /// empty supported versions
@@ -57,6 +64,8 @@ pub enum ConnectionError {
     InvalidCounterparty,
     /// missing counterparty
     MissingCounterparty,
+    /// invalid counterparty commitment prefix: `{0}`
+    InvalidCounterpartyPrefix(CommitmentError),
     /// missing client state
     MissingClientState,
     /// the consensus proof verification failed (height: `{height}`), client error: `{client_error}`
@@ -102,6 +111,7 @@ impl std::error::Error for ConnectionError {
                 client_error: e, ..
             } => Some(e),
             Self::TimestampOverflow(e) => Some(e),
+            Self::InvalidCounterpartyPrefix(e) => Some(e),
             _ => None,
         }
     }
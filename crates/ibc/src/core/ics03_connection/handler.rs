@@ -1,11 +1,85 @@
 //! This module implements the processing logic for ICS3 (connection open
 //! handshake) messages.
 
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::proof::verify_membership;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::error::ConnectionError;
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::core::ics24_host::path::{ConnectionPath, Path};
+use crate::core::{ContextError, ExecutionContext, ValidationContext};
+
 pub mod conn_open_ack;
 pub mod conn_open_confirm;
 pub mod conn_open_init;
 pub mod conn_open_try;
 
+/// Verifies that the counterparty stores `expected_conn_end` under `conn_id`.
+///
+/// The `proof` is checked against the root of `consensus_state`, the
+/// counterparty's consensus state tracked by `client_state`, under the
+/// counterparty's commitment `prefix`.
+pub fn verify_conn_end_membership<Ctx>(
+    ctx: &Ctx,
+    client_state: &Ctx::AnyClientState,
+    consensus_state: &Ctx::AnyConsensusState,
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    conn_id: &ConnectionId,
+    expected_conn_end: &ConnectionEnd,
+) -> Result<(), ConnectionError>
+where
+    Ctx: ValidationContext,
+{
+    verify_membership(
+        ctx,
+        client_state,
+        prefix,
+        proof,
+        consensus_state.root(),
+        Path::Connection(ConnectionPath::new(conn_id)),
+        expected_conn_end.encode_vec(),
+    )
+    .map_err(ConnectionError::VerifyConnectionState)
+}
+
+/// Returns the identifier of the next connection to be created, failing if a
+/// connection end is already stored under it, e.g. because the connection
+/// counter of the host was reset to a value it already used.
+pub(crate) fn next_connection_id<Ctx>(ctx: &Ctx) -> Result<ConnectionId, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let connection_id = ConnectionId::new(ctx.connection_counter()?);
+
+    if ctx.connection_end(&connection_id).is_ok() {
+        return Err(ConnectionError::ConnectionAlreadyExists { connection_id }.into());
+    }
+
+    Ok(connection_id)
+}
+
+/// Increases the connection counter, failing if it does not move forward.
+pub(crate) fn increase_connection_counter<Ctx>(ctx: &mut Ctx) -> Result<(), ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    let previous_counter = ctx.increase_connection_counter()?;
+    let current_counter = ctx.connection_counter()?;
+    if current_counter <= previous_counter {
+        return Err(ConnectionError::ConnectionCounterRegression {
+            previous: previous_counter,
+            current: current_counter,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 pub mod test_util {
     use core::fmt::Debug;
@@ -42,3 +116,86 @@ pub mod test_util {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::clients::ics09_localhost::client_type as localhost_client_type;
+    use crate::core::ics03_connection::connection::{Counterparty, State};
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics23_commitment::commitment::test_util::get_dummy_commitment_proof_bytes;
+    use crate::core::ics24_host::identifier::ClientId;
+    use crate::core::ics24_host::path::ClientConsensusStatePath;
+    use crate::core::timestamp::ZERO_DURATION;
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    /// Verifies `expected_conn_end` against the connection stored under
+    /// `conn_id` on the host, through a localhost client.
+    fn verify_localhost_conn_end(
+        stored_conn_end: ConnectionEnd,
+        expected_conn_end: &ConnectionEnd,
+    ) -> Result<(), ConnectionError> {
+        let client_id = ClientId::new(localhost_client_type(), 0).unwrap();
+        let conn_id = ConnectionId::new(1);
+        let proof_height = Height::new(0, 5).unwrap();
+
+        let ctx = MockContext::default()
+            .with_client_parametrized(
+                &client_id,
+                proof_height,
+                Some(localhost_client_type()),
+                None,
+            )
+            .with_connection(conn_id.clone(), stored_conn_end);
+
+        let client_state = ctx.client_state(&client_id).unwrap();
+        let consensus_state = ctx
+            .consensus_state(&ClientConsensusStatePath::new(&client_id, &proof_height))
+            .unwrap();
+
+        verify_conn_end_membership(
+            &ctx,
+            &client_state,
+            &consensus_state,
+            &ctx.commitment_prefix(),
+            &get_dummy_commitment_proof_bytes(),
+            &conn_id,
+            expected_conn_end,
+        )
+    }
+
+    fn conn_end(state: State) -> ConnectionEnd {
+        let client_id = ClientId::new(localhost_client_type(), 0).unwrap();
+        ConnectionEnd::new(
+            state,
+            client_id.clone(),
+            Counterparty::new(
+                client_id,
+                Some(ConnectionId::new(0)),
+                MockContext::default().commitment_prefix(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_conn_end_membership_matching_conn_end() {
+        let res = verify_localhost_conn_end(conn_end(State::TryOpen), &conn_end(State::TryOpen));
+
+        assert!(res.is_ok(), "the stored connection end is proven: {res:?}");
+    }
+
+    #[test]
+    fn verify_conn_end_membership_mismatching_conn_end() {
+        let res = verify_localhost_conn_end(conn_end(State::Init), &conn_end(State::TryOpen));
+
+        assert!(
+            matches!(res, Err(ConnectionError::VerifyConnectionState(_))),
+            "a connection end other than the stored one is not proven: {res:?}"
+        );
+    }
+}
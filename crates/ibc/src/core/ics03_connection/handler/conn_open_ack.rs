@@ -1,20 +1,22 @@
 //! Protocol logic specific to processing ICS3 messages of type `MsgConnectionOpenAck`.
 
-use ibc_proto::protobuf::Protobuf;
 use prost::Message;
 
 use crate::core::context::ContextError;
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::events::OpenAck;
+use crate::core::ics03_connection::handler::verify_conn_end_membership;
 use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath, ConnectionPath};
-use crate::core::{ExecutionContext, ValidationContext};
+use crate::core::{validate_signer, ExecutionContext, ValidationContext};
 use crate::prelude::*;
 
 use crate::core::events::{IbcEvent, MessageEvent};
@@ -35,7 +37,7 @@ fn validate_impl<Ctx>(
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_signer(ctx_a, &msg.signer)?;
 
     let host_height = ctx_a.host_height().map_err(|_| ConnectionError::Other {
         description: "failed to get host height".to_string(),
@@ -88,29 +90,30 @@ where
                 vars.conn_end_on_a.delay_period(),
             )?;
 
-            client_state_of_b_on_a
-                .verify_membership(
-                    prefix_on_b,
-                    &msg.proof_conn_end_on_b,
-                    consensus_state_of_b_on_a.root(),
-                    Path::Connection(ConnectionPath::new(&msg.conn_id_on_b)),
-                    expected_conn_end_on_b.encode_vec(),
-                )
-                .map_err(ConnectionError::VerifyConnectionState)?;
+            verify_conn_end_membership(
+                ctx_a,
+                &client_state_of_b_on_a,
+                &consensus_state_of_b_on_a,
+                prefix_on_b,
+                &msg.proof_conn_end_on_b,
+                &msg.conn_id_on_b,
+                &expected_conn_end_on_b,
+            )?;
         }
 
-        client_state_of_b_on_a
-            .verify_membership(
-                prefix_on_b,
-                &msg.proof_client_state_of_a_on_b,
-                consensus_state_of_b_on_a.root(),
-                Path::ClientState(ClientStatePath::new(vars.client_id_on_b())),
-                msg.client_state_of_a_on_b.encode_to_vec(),
-            )
-            .map_err(|e| ConnectionError::ClientStateVerificationFailure {
-                client_id: vars.client_id_on_b().clone(),
-                client_error: e,
-            })?;
+        verify_membership(
+            ctx_a,
+            &client_state_of_b_on_a,
+            prefix_on_b,
+            &msg.proof_client_state_of_a_on_b,
+            consensus_state_of_b_on_a.root(),
+            Path::ClientState(ClientStatePath::new(vars.client_id_on_b())),
+            msg.client_state_of_a_on_b.encode_to_vec(),
+        )
+        .map_err(|e| ConnectionError::ClientStateVerificationFailure {
+            client_id: vars.client_id_on_b().clone(),
+            client_error: e,
+        })?;
 
         let expected_consensus_state_of_a_on_b =
             ctx_a.host_consensus_state(&msg.consensus_height_of_a_on_b)?;
@@ -118,18 +121,19 @@ where
         let client_cons_state_path_on_b =
             ClientConsensusStatePath::new(vars.client_id_on_b(), &msg.consensus_height_of_a_on_b);
 
-        client_state_of_b_on_a
-            .verify_membership(
-                prefix_on_b,
-                &msg.proof_consensus_state_of_a_on_b,
-                consensus_state_of_b_on_a.root(),
-                Path::ClientConsensusState(client_cons_state_path_on_b),
-                expected_consensus_state_of_a_on_b.encode_vec(),
-            )
-            .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
-                height: msg.proofs_height_on_b,
-                client_error: e,
-            })?;
+        verify_membership(
+            ctx_a,
+            &client_state_of_b_on_a,
+            prefix_on_b,
+            &msg.proof_consensus_state_of_a_on_b,
+            consensus_state_of_b_on_a.root(),
+            Path::ClientConsensusState(client_cons_state_path_on_b),
+            expected_consensus_state_of_a_on_b.encode_vec(),
+        )
+        .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
+            height: msg.proofs_height_on_b,
+            client_error: e,
+        })?;
     }
 
     Ok(())
@@ -162,6 +166,8 @@ where
 
     ctx_a.log_message("success: conn_open_ack verification passed".to_string());
 
+    update_client_on_verified_proofs(ctx_a, vars.client_id_on_a())?;
+
     {
         let new_conn_end_on_a = {
             let mut counterparty = vars.conn_end_on_a.counterparty().clone();
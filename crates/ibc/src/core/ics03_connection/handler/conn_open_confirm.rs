@@ -6,6 +6,8 @@ use crate::core::context::ContextError;
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::events::OpenConfirm;
@@ -13,7 +15,7 @@ use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenCon
 use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{ClientConsensusStatePath, ConnectionPath};
-use crate::core::{ExecutionContext, ValidationContext};
+use crate::core::{validate_signer, ExecutionContext, ValidationContext};
 use crate::prelude::*;
 
 use crate::core::events::{IbcEvent, MessageEvent};
@@ -34,7 +36,7 @@ fn validate_impl<Ctx>(
 where
     Ctx: ValidationContext,
 {
-    ctx_b.validate_message_signer(&msg.signer)?;
+    validate_signer(ctx_b, &msg.signer)?;
 
     let conn_end_on_b = vars.conn_end_on_b();
 
@@ -76,15 +78,16 @@ where
             conn_end_on_b.delay_period(),
         )?;
 
-        client_state_of_a_on_b
-            .verify_membership(
-                prefix_on_a,
-                &msg.proof_conn_end_on_a,
-                consensus_state_of_a_on_b.root(),
-                Path::Connection(ConnectionPath::new(conn_id_on_a)),
-                expected_conn_end_on_a.encode_vec(),
-            )
-            .map_err(ConnectionError::VerifyConnectionState)?;
+        verify_membership(
+            ctx_b,
+            &client_state_of_a_on_b,
+            prefix_on_a,
+            &msg.proof_conn_end_on_a,
+            consensus_state_of_a_on_b.root(),
+            Path::Connection(ConnectionPath::new(conn_id_on_a)),
+            expected_conn_end_on_a.encode_vec(),
+        )
+        .map_err(ConnectionError::VerifyConnectionState)?;
     }
 
     Ok(())
@@ -123,6 +126,8 @@ where
     ctx_b.emit_ibc_event(event);
     ctx_b.log_message("success: conn_open_confirm verification passed".to_string());
 
+    update_client_on_verified_proofs(ctx_b, client_id_on_b)?;
+
     {
         let new_conn_end_on_b = {
             let mut new_conn_end_on_b = vars.conn_end_on_b;
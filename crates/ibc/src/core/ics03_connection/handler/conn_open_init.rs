@@ -7,16 +7,18 @@ use crate::core::context::ContextError;
 use crate::core::events::{IbcEvent, MessageEvent};
 use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
 use crate::core::ics03_connection::events::OpenInit;
+use crate::core::ics03_connection::handler::{increase_connection_counter, next_connection_id};
 use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
-use crate::core::ics24_host::identifier::ConnectionId;
 use crate::core::ics24_host::path::{ClientConnectionPath, ConnectionPath};
-use crate::core::{ExecutionContext, ValidationContext};
+use crate::core::{validate_signer, ExecutionContext, ValidationContext};
 
 pub(crate) fn validate<Ctx>(ctx_a: &Ctx, msg: MsgConnectionOpenInit) -> Result<(), ContextError>
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_signer(ctx_a, &msg.signer)?;
+
+    msg.counterparty.verify_prefix_not_empty()?;
 
     // An IBC client running on the local (host) chain should exist.
     let client_state_of_b_on_a = ctx_a.client_state(&msg.client_id_on_a)?;
@@ -33,6 +35,8 @@ where
         version.verify_is_supported(&ctx_a.get_compatible_versions())?;
     }
 
+    next_connection_id(ctx_a)?;
+
     Ok(())
 }
 
@@ -60,7 +64,8 @@ where
     )?;
 
     // Construct the identifier for the new connection.
-    let conn_id_on_a = ConnectionId::new(ctx_a.connection_counter()?);
+    let conn_id_on_a = next_connection_id(ctx_a)?;
+    increase_connection_counter(ctx_a)?;
 
     ctx_a.log_message(format!(
         "success: conn_open_init: generated new connection identifier: {conn_id_on_a}"
@@ -78,7 +83,6 @@ where
         ctx_a.emit_ibc_event(event);
     }
 
-    ctx_a.increase_connection_counter();
     ctx_a.store_connection_to_client(
         &ClientConnectionPath::new(&msg.client_id_on_a),
         conn_id_on_a.clone(),
@@ -94,9 +98,13 @@ mod tests {
 
     use crate::core::events::IbcEvent;
     use crate::core::ics03_connection::connection::State;
+    use crate::core::ics03_connection::error::ConnectionError;
     use crate::core::ics03_connection::handler::test_util::{Expect, Fixture};
     use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
     use crate::core::ics03_connection::version::Version;
+    use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::core::ics23_commitment::error::CommitmentError;
+    use crate::core::ics24_host::identifier::ConnectionId;
     use crate::mock::context::MockContext;
     use crate::Height;
     use test_log::test;
@@ -111,6 +119,7 @@ mod tests {
         NoVersion,
         BadVersion,
         WithCounterpartyConnId,
+        EmptyCounterpartyPrefix,
     }
 
     fn conn_open_init_fixture(
@@ -123,6 +132,15 @@ mod tests {
             Msg::NoVersion => msg_default.with_version(None),
             Msg::BadVersion => msg_default.with_version(Some("random identifier 424242")),
             Msg::WithCounterpartyConnId => msg_default.with_counterparty_conn_id(2),
+            Msg::EmptyCounterpartyPrefix => {
+                let mut msg = msg_default;
+                msg.counterparty = Counterparty::new(
+                    msg.counterparty.client_id().clone(),
+                    None,
+                    CommitmentPrefix::default(),
+                );
+                msg
+            }
         };
 
         let ctx_default = MockContext::default();
@@ -220,4 +238,39 @@ mod tests {
         let expected_version = vec![fxt.msg.version.clone().unwrap()];
         conn_open_init_execute(&mut fxt, Expect::Success, expected_version);
     }
+
+    #[test]
+    fn conn_open_init_empty_counterparty_prefix() {
+        let fxt = conn_open_init_fixture(Ctx::WithClient, Msg::EmptyCounterpartyPrefix);
+        let res = validate(&fxt.ctx, fxt.msg.clone());
+
+        assert!(matches!(
+            res,
+            Err(ContextError::ConnectionError(
+                ConnectionError::InvalidCounterpartyPrefix(CommitmentError::EmptyCommitmentPrefix)
+            ))
+        ));
+    }
+
+    #[test]
+    fn conn_open_init_reused_connection_id() {
+        let mut fxt = conn_open_init_fixture(Ctx::WithClient, Msg::Default);
+        let res = execute(&mut fxt.ctx, fxt.msg.clone());
+        assert!(res.is_ok(), "execution failed: {res:?}");
+
+        // A counter reset to a value it already used derives the identifier
+        // of the connection just created.
+        fxt.ctx.ibc_store.lock().connection_ids_counter = 0;
+
+        let res = validate(&fxt.ctx, fxt.msg.clone());
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ConnectionError(
+                    ConnectionError::ConnectionAlreadyExists { ref connection_id }
+                )) if connection_id == &ConnectionId::new(0)
+            ),
+            "unexpected result: {res:?}"
+        );
+    }
 }
@@ -1,15 +1,19 @@
 //! Protocol logic specific to processing ICS3 messages of type `MsgConnectionOpenTry`.
 
-use ibc_proto::protobuf::Protobuf;
 use prost::Message;
 
 use crate::core::context::ContextError;
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::events::OpenTry;
+use crate::core::ics03_connection::handler::{
+    increase_connection_counter, next_connection_id, verify_conn_end_membership,
+};
 use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::identifier::ConnectionId;
@@ -17,7 +21,7 @@ use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{
     ClientConnectionPath, ClientConsensusStatePath, ClientStatePath, ConnectionPath,
 };
-use crate::core::{ExecutionContext, ValidationContext};
+use crate::core::{validate_signer, ExecutionContext, ValidationContext};
 use crate::prelude::*;
 
 use crate::core::events::{IbcEvent, MessageEvent};
@@ -38,7 +42,9 @@ fn validate_impl<Ctx>(
 where
     Ctx: ValidationContext,
 {
-    ctx_b.validate_message_signer(&msg.signer)?;
+    validate_signer(ctx_b, &msg.signer)?;
+
+    msg.counterparty.verify_prefix_not_empty()?;
 
     ctx_b.validate_self_client(msg.client_state_of_b_on_a.clone())?;
 
@@ -85,29 +91,30 @@ where
                 msg.delay_period,
             )?;
 
-            client_state_of_a_on_b
-                .verify_membership(
-                    prefix_on_a,
-                    &msg.proof_conn_end_on_a,
-                    consensus_state_of_a_on_b.root(),
-                    Path::Connection(ConnectionPath::new(&vars.conn_id_on_a)),
-                    expected_conn_end_on_a.encode_vec(),
-                )
-                .map_err(ConnectionError::VerifyConnectionState)?;
+            verify_conn_end_membership(
+                ctx_b,
+                &client_state_of_a_on_b,
+                &consensus_state_of_a_on_b,
+                prefix_on_a,
+                &msg.proof_conn_end_on_a,
+                &vars.conn_id_on_a,
+                &expected_conn_end_on_a,
+            )?;
         }
 
-        client_state_of_a_on_b
-            .verify_membership(
-                prefix_on_a,
-                &msg.proof_client_state_of_b_on_a,
-                consensus_state_of_a_on_b.root(),
-                Path::ClientState(ClientStatePath::new(client_id_on_a)),
-                msg.client_state_of_b_on_a.encode_to_vec(),
-            )
-            .map_err(|e| ConnectionError::ClientStateVerificationFailure {
-                client_id: msg.client_id_on_b.clone(),
-                client_error: e,
-            })?;
+        verify_membership(
+            ctx_b,
+            &client_state_of_a_on_b,
+            prefix_on_a,
+            &msg.proof_client_state_of_b_on_a,
+            consensus_state_of_a_on_b.root(),
+            Path::ClientState(ClientStatePath::new(client_id_on_a)),
+            msg.client_state_of_b_on_a.encode_to_vec(),
+        )
+        .map_err(|e| ConnectionError::ClientStateVerificationFailure {
+            client_id: msg.client_id_on_b.clone(),
+            client_error: e,
+        })?;
 
         let expected_consensus_state_of_b_on_a =
             ctx_b.host_consensus_state(&msg.consensus_height_of_b_on_a)?;
@@ -115,18 +122,19 @@ where
         let client_cons_state_path_on_a =
             ClientConsensusStatePath::new(client_id_on_a, &msg.consensus_height_of_b_on_a);
 
-        client_state_of_a_on_b
-            .verify_membership(
-                prefix_on_a,
-                &msg.proof_consensus_state_of_b_on_a,
-                consensus_state_of_a_on_b.root(),
-                Path::ClientConsensusState(client_cons_state_path_on_a),
-                expected_consensus_state_of_b_on_a.encode_vec(),
-            )
-            .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
-                height: msg.proofs_height_on_a,
-                client_error: e,
-            })?;
+        verify_membership(
+            ctx_b,
+            &client_state_of_a_on_b,
+            prefix_on_a,
+            &msg.proof_consensus_state_of_b_on_a,
+            consensus_state_of_a_on_b.root(),
+            Path::ClientConsensusState(client_cons_state_path_on_a),
+            expected_consensus_state_of_b_on_a.encode_vec(),
+        )
+        .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
+            height: msg.proofs_height_on_a,
+            client_error: e,
+        })?;
     }
 
     Ok(())
@@ -148,6 +156,8 @@ fn execute_impl<Ctx>(
 where
     Ctx: ExecutionContext,
 {
+    increase_connection_counter(ctx_b)?;
+
     let conn_id_on_a = vars
         .conn_end_on_b
         .counterparty()
@@ -163,7 +173,8 @@ where
     ctx_b.emit_ibc_event(event);
     ctx_b.log_message("success: conn_open_try verification passed".to_string());
 
-    ctx_b.increase_connection_counter();
+    update_client_on_verified_proofs(ctx_b, &msg.client_id_on_b)?;
+
     ctx_b.store_connection_to_client(
         &ClientConnectionPath::new(&msg.client_id_on_b),
         vars.conn_id_on_b.clone(),
@@ -188,7 +199,7 @@ impl LocalVars {
         let version_on_b = ctx_b.pick_version(&msg.versions_on_a)?;
 
         Ok(Self {
-            conn_id_on_b: ConnectionId::new(ctx_b.connection_counter()?),
+            conn_id_on_b: next_connection_id(ctx_b)?,
             conn_end_on_b: ConnectionEnd::new(
                 State::TryOpen,
                 msg.client_id_on_b.clone(),
@@ -216,6 +227,9 @@ mod tests {
     use crate::core::ics03_connection::connection::State;
     use crate::core::ics03_connection::handler::test_util::{Expect, Fixture};
     use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+    use crate::core::ics03_connection::version::Version;
+    use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::core::ics23_commitment::error::CommitmentError;
     use crate::core::ics24_host::identifier::ChainId;
     use crate::mock::context::MockContext;
     use crate::mock::host::HostType;
@@ -231,6 +245,8 @@ mod tests {
         HeightAdvanced,
         HeightOld,
         ProofHeightMissing,
+        NoCommonFeatures,
+        EmptyCounterpartyPrefix,
     }
 
     fn conn_open_try_fixture(ctx_variant: Ctx, msg_variant: Msg) -> Fixture<MsgConnectionOpenTry> {
@@ -258,6 +274,29 @@ mod tests {
                 client_cons_state_height - 1,
                 host_chain_height.revision_height(),
             ),
+            Msg::NoCommonFeatures => {
+                let mut msg = MsgConnectionOpenTry::new_dummy(
+                    client_cons_state_height,
+                    host_chain_height.revision_height(),
+                );
+                msg.versions_on_a = vec![Version::with_features(
+                    "1".to_string(),
+                    vec!["ORDER_DAG".to_string()],
+                )];
+                msg
+            }
+            Msg::EmptyCounterpartyPrefix => {
+                let mut msg = MsgConnectionOpenTry::new_dummy(
+                    client_cons_state_height,
+                    host_chain_height.revision_height(),
+                );
+                msg.counterparty = Counterparty::new(
+                    msg.counterparty.client_id().clone(),
+                    msg.counterparty.connection_id().cloned(),
+                    CommitmentPrefix::default(),
+                );
+                msg
+            }
         };
 
         let ctx_new = MockContext::new(
@@ -344,9 +383,57 @@ mod tests {
         conn_open_try_validate(&fxt, Expect::Failure(None));
     }
 
+    #[test]
+    fn conn_open_try_no_common_features() {
+        let fxt = conn_open_try_fixture(Ctx::WithClient, Msg::NoCommonFeatures);
+        let res = validate(&fxt.ctx, fxt.msg.clone());
+
+        assert!(matches!(
+            res,
+            Err(ContextError::ConnectionError(
+                ConnectionError::NoCommonVersion
+            ))
+        ));
+    }
+
+    #[test]
+    fn conn_open_try_empty_counterparty_prefix() {
+        let fxt = conn_open_try_fixture(Ctx::WithClient, Msg::EmptyCounterpartyPrefix);
+        let res = validate(&fxt.ctx, fxt.msg.clone());
+
+        assert!(matches!(
+            res,
+            Err(ContextError::ConnectionError(
+                ConnectionError::InvalidCounterpartyPrefix(CommitmentError::EmptyCommitmentPrefix)
+            ))
+        ));
+    }
+
     #[test]
     fn conn_open_try_no_client() {
         let fxt = conn_open_try_fixture(Ctx::Default, Msg::Default);
         conn_open_try_validate(&fxt, Expect::Failure(None));
     }
+
+    #[test]
+    fn conn_open_try_reused_connection_id() {
+        let mut fxt = conn_open_try_fixture(Ctx::WithClient, Msg::Default);
+        let res = execute(&mut fxt.ctx, fxt.msg.clone());
+        assert!(res.is_ok(), "execution failed: {res:?}");
+
+        // A counter reset to a value it already used derives the identifier
+        // of the connection just created.
+        fxt.ctx.ibc_store.lock().connection_ids_counter = 0;
+
+        let res = validate(&fxt.ctx, fxt.msg.clone());
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ConnectionError(
+                    ConnectionError::ConnectionAlreadyExists { ref connection_id }
+                )) if connection_id == &ConnectionId::new(0)
+            ),
+            "unexpected result: {res:?}"
+        );
+    }
 }
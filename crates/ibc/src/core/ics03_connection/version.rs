@@ -34,6 +34,15 @@ pub struct Version {
 }
 
 impl Version {
+    /// Creates a version with the given identifier that supports only the
+    /// given features, e.g. to advertise support for ordered channels only.
+    pub fn with_features(identifier: String, features: Vec<String>) -> Self {
+        Self {
+            identifier,
+            features,
+        }
+    }
+
     /// Checks whether the version has a matching version identifier and its
     /// feature set is a subset of the supported features
     pub fn verify_is_supported(
@@ -197,6 +206,7 @@ mod tests {
 
     use crate::core::ics03_connection::error::ConnectionError;
     use crate::core::ics03_connection::version::{get_compatible_versions, pick_version, Version};
+    use crate::core::ics04_channel::channel::Order;
 
     fn get_dummy_features() -> Vec<String> {
         vec!["ORDER_RANDOM".to_string(), "ORDER_UNORDERED".to_string()]
@@ -387,6 +397,27 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn pick_disjoint_features() {
+        let supported = vec![Version::with_features(
+            "1".to_string(),
+            vec![Order::Ordered.as_str().to_owned()],
+        )];
+        let counterparty = vec![Version::with_features(
+            "1".to_string(),
+            vec![Order::Unordered.as_str().to_owned()],
+        )];
+
+        let version = pick_version(&supported, &counterparty);
+
+        assert!(matches!(version, Err(ConnectionError::NoCommonVersion)));
+
+        let counterparty = get_compatible_versions();
+        let version = pick_version(&supported, &counterparty).unwrap();
+
+        assert_eq!(version, supported[0]);
+    }
+
     #[test]
     fn serialize() {
         let def = Version::default();
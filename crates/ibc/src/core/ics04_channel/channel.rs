@@ -73,7 +73,7 @@ impl From<IdentifiedChannelEnd> for RawIdentifiedChannel {
     fn from(value: IdentifiedChannelEnd) -> Self {
         RawIdentifiedChannel {
             state: value.channel_end.state as i32,
-            ordering: value.channel_end.ordering as i32,
+            ordering: value.channel_end.ordering.to_i32(),
             counterparty: Some(value.channel_end.counterparty().clone().into()),
             connection_hops: value
                 .channel_end
@@ -154,7 +154,7 @@ impl From<ChannelEnd> for RawChannel {
     fn from(value: ChannelEnd) -> Self {
         RawChannel {
             state: value.state as i32,
-            ordering: value.ordering as i32,
+            ordering: value.ordering.to_i32(),
             counterparty: Some(value.counterparty().clone().into()),
             connection_hops: value
                 .connection_hops
@@ -207,6 +207,18 @@ impl ChannelEnd {
         self.state = s;
     }
 
+    /// Closes the channel end.
+    ///
+    /// Only an `Open` channel end can be closed gracefully: closing one that
+    /// is still being opened or that is already closed is rejected.
+    pub fn close(&mut self) -> Result<(), ChannelError> {
+        self.verify_state_matches(&State::Open)?;
+        self.state = State::Closed;
+        Ok(())
+    }
+
+    /// Updates the ChannelEnd to assume a new version `v`, e.g. the version
+    /// negotiated by the application module during the handshake.
     pub fn set_version(&mut self, v: Version) {
         self.version = v;
     }
@@ -240,6 +252,12 @@ impl ChannelEnd {
         &self.version
     }
 
+    /// Returns a mutable reference to the version, so that the handshake
+    /// handlers can store the version negotiated by the module.
+    pub fn version_mut(&mut self) -> &mut Version {
+        &mut self.version
+    }
+
     pub fn validate_basic(&self) -> Result<(), ChannelError> {
         if self.state == State::Uninitialized {
             return Err(ChannelError::InvalidState {
@@ -248,12 +266,7 @@ impl ChannelEnd {
             });
         }
 
-        if self.ordering == Order::None {
-            return Err(ChannelError::InvalidOrderType {
-                expected: "Channel ordering cannot be None".to_string(),
-                actual: self.ordering.to_string(),
-            });
-        }
+        self.ordering.verify_known()?;
 
         Ok(())
     }
@@ -285,6 +298,28 @@ impl ChannelEnd {
         self.ordering.eq(other)
     }
 
+    /// Checks that the ordering of a counterparty channel end matches the
+    /// ordering of this end, as both ends of a channel must agree on it.
+    pub fn verify_ordering_matches(&self, counterparty: &Order) -> Result<(), ChannelError> {
+        if !self.order_matches(counterparty) {
+            return Err(ChannelError::OrderingMismatch {
+                local: self.ordering,
+                counterparty: *counterparty,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that this end knows its counterparty channel id if a channel
+    /// end in state `state` must know it, as decided by
+    /// [`requires_counterparty_channel_id`].
+    pub fn verify_counterparty_channel_id(&self, state: &State) -> Result<(), ChannelError> {
+        if requires_counterparty_channel_id(state) && self.remote.channel_id.is_none() {
+            return Err(ChannelError::MissingCounterpartyChannelId { state: *state });
+        }
+        Ok(())
+    }
+
     pub fn connection_hops_matches(&self, other: &Vec<ConnectionId>) -> bool {
         self.connection_hops.eq(other)
     }
@@ -312,6 +347,50 @@ impl ChannelEnd {
     }
 }
 
+/// Returns whether a channel end in state `state` must know the identifier
+/// of its counterparty channel end.
+///
+/// The identifier is only learnt from the counterparty during the handshake,
+/// so a channel end in `Init` may not know it yet. From `TryOpen` onwards it
+/// must be set, and closing a channel also requires it since both ends are
+/// then notified about each other.
+pub fn requires_counterparty_channel_id(state: &State) -> bool {
+    match state {
+        State::Uninitialized | State::Init => false,
+        State::TryOpen | State::Open | State::Closed => true,
+    }
+}
+
+/// Builds the channel end that the counterparty chain is expected to have
+/// stored, against which the handshake handlers verify the channel proofs.
+///
+/// `local_ids` identifies the local channel end as the counterparty refers to
+/// it, i.e. the local port id and, once the counterparty knows it, the local
+/// channel id. `conn_id_on_counterparty` is the connection hop on the
+/// counterparty chain, and `ordering` and `version` are the ones the
+/// counterparty end is expected to have.
+///
+/// Fails if `local_ids` has no channel id while a channel end in `state`
+/// must know it (see [`requires_counterparty_channel_id`]).
+pub fn build_expected_counterparty_channel(
+    state: State,
+    ordering: Order,
+    local_ids: Counterparty,
+    conn_id_on_counterparty: ConnectionId,
+    version: Version,
+) -> Result<ChannelEnd, ChannelError> {
+    let expected_chan_end = ChannelEnd::new(
+        state,
+        ordering,
+        local_ids,
+        vec![conn_id_on_counterparty],
+        version,
+    )?;
+    expected_chan_end.verify_counterparty_channel_id(&state)?;
+
+    Ok(expected_chan_end)
+}
+
 /// Checks if the `connection_hops` has a length of `expected`.
 pub(crate) fn verify_connection_hops_length(
     connection_hops: &Vec<ConnectionId>,
@@ -471,12 +550,41 @@ impl Order {
             0 => Ok(Self::None),
             1 => Ok(Self::Unordered),
             2 => Ok(Self::Ordered),
-            _ => Err(ChannelError::InvalidOrderType {
-                expected: "Must be one of 0, 1, 2".to_string(),
-                actual: nr.to_string(),
+            _ => Err(ChannelError::UnknownOrderType {
+                order: nr.to_string(),
             }),
         }
     }
+
+    /// Yields the Order as its protobuf i32 value.
+    pub fn to_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    /// Checks that the ordering is either `Unordered` or `Ordered`, as
+    /// required of any channel being opened.
+    pub fn verify_known(&self) -> Result<(), ChannelError> {
+        if *self == Self::None {
+            return Err(ChannelError::UnknownOrderType {
+                order: self.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<i32> for Order {
+    type Error = ChannelError;
+
+    fn try_from(nr: i32) -> Result<Self, Self::Error> {
+        Self::from_i32(nr)
+    }
+}
+
+impl From<Order> for i32 {
+    fn from(order: Order) -> Self {
+        order.to_i32()
+    }
 }
 
 impl FromStr for Order {
@@ -611,9 +719,17 @@ mod tests {
     use test_log::test;
 
     use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+    use ibc_proto::protobuf::Protobuf;
+    use prost::Message;
 
     use crate::core::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
-    use crate::core::ics04_channel::channel::ChannelEnd;
+    use crate::core::ics04_channel::channel::{
+        build_expected_counterparty_channel, requires_counterparty_channel_id, ChannelEnd,
+        Counterparty, Order, State,
+    };
+    use crate::core::ics04_channel::error::ChannelError;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 
     #[test]
     fn channel_end_try_from_raw() {
@@ -704,6 +820,74 @@ mod tests {
         }
     }
 
+    /// Channel ends encoded by other proto versions decode as long as they
+    /// are semantically valid: unknown fields are skipped and a missing
+    /// version is empty
+    #[test]
+    fn channel_end_decode_other_proto_versions() {
+        let raw_channel_end = RawChannel {
+            version: "ics20-1".to_string(),
+            ..get_dummy_raw_channel_end(2, Some(0))
+        };
+
+        // An unknown varint field, with number 99 and value 1
+        let unknown_field = [0x98, 0x06, 0x01];
+        let mut bytes = raw_channel_end.encode_to_vec();
+        bytes.extend_from_slice(&unknown_field);
+        let channel_end = <ChannelEnd as Protobuf<RawChannel>>::decode_vec(&bytes).unwrap();
+        assert_eq!(channel_end, ChannelEnd::try_from(raw_channel_end).unwrap());
+
+        let raw_channel_end = get_dummy_raw_channel_end(2, Some(0));
+        let bytes = raw_channel_end.encode_to_vec();
+        let channel_end = <ChannelEnd as Protobuf<RawChannel>>::decode_vec(&bytes).unwrap();
+        assert_eq!(channel_end.version(), &Version::empty());
+
+        let mut bytes = RawChannel {
+            state: 0,
+            ..raw_channel_end
+        }
+        .encode_to_vec();
+        bytes.extend_from_slice(&unknown_field);
+        assert!(<ChannelEnd as Protobuf<RawChannel>>::decode_vec(&bytes).is_err());
+    }
+
+    #[test]
+    fn channel_end_verify_ordering_matches() {
+        use super::Order;
+        use crate::core::ics04_channel::error::ChannelError;
+
+        let channel_end = ChannelEnd::try_from(get_dummy_raw_channel_end(2, Some(0))).unwrap();
+        assert_eq!(channel_end.ordering, Order::Ordered);
+
+        assert!(channel_end.verify_ordering_matches(&Order::Ordered).is_ok());
+
+        let res = channel_end.verify_ordering_matches(&Order::Unordered);
+        assert!(matches!(
+            res,
+            Err(ChannelError::OrderingMismatch {
+                local: Order::Ordered,
+                counterparty: Order::Unordered,
+            })
+        ));
+    }
+
+    #[test]
+    fn channel_ordering_i32_roundtrip() {
+        for order in [Order::None, Order::Unordered, Order::Ordered] {
+            assert_eq!(Order::try_from(order.to_i32()).unwrap(), order);
+        }
+        for nr in [-1, 3] {
+            assert!(matches!(
+                Order::try_from(nr),
+                Err(ChannelError::UnknownOrderType { .. })
+            ));
+        }
+        assert!(matches!(
+            Order::None.verify_known(),
+            Err(ChannelError::UnknownOrderType { .. })
+        ));
+    }
+
     #[test]
     fn parse_channel_ordering_type() {
         use super::Order;
@@ -748,4 +932,132 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn counterparty_channel_id_requirement_per_state() {
+        // `Init` is where `ChanOpenInit` leaves the channel end and where
+        // `ChanOpenAck` finds it, before the counterparty id is learnt.
+        assert!(!requires_counterparty_channel_id(&State::Uninitialized));
+        assert!(!requires_counterparty_channel_id(&State::Init));
+        // `ChanOpenTry` and `ChanOpenConfirm`.
+        assert!(requires_counterparty_channel_id(&State::TryOpen));
+        assert!(requires_counterparty_channel_id(&State::Open));
+        // `ChanCloseInit` and `ChanCloseConfirm`.
+        assert!(requires_counterparty_channel_id(&State::Closed));
+    }
+
+    #[test]
+    fn verify_counterparty_channel_id() {
+        let channel_end = |channel_id| {
+            ChannelEnd::new(
+                State::Init,
+                Order::Unordered,
+                Counterparty::new(PortId::transfer(), channel_id),
+                vec![ConnectionId::default()],
+                Version::default(),
+            )
+            .unwrap()
+        };
+
+        let without_id = channel_end(None);
+        assert!(without_id
+            .verify_counterparty_channel_id(&State::Init)
+            .is_ok());
+        assert!(matches!(
+            without_id.verify_counterparty_channel_id(&State::Open),
+            Err(ChannelError::MissingCounterpartyChannelId { state: State::Open })
+        ));
+
+        let with_id = channel_end(Some(Default::default()));
+        assert!(with_id
+            .verify_counterparty_channel_id(&State::Closed)
+            .is_ok());
+    }
+
+    #[test]
+    fn build_expected_counterparty_channel_per_handshake_step() {
+        let port_id = PortId::transfer();
+        let chan_id = ChannelId::new(3);
+        let conn_id = ConnectionId::new(7);
+
+        // `ChanOpenTry`: the counterparty end is in `Init` and does not know
+        // the local channel id yet.
+        let expected = build_expected_counterparty_channel(
+            State::Init,
+            Order::Ordered,
+            Counterparty::new(port_id.clone(), None),
+            conn_id.clone(),
+            Version::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            expected,
+            ChannelEnd::new(
+                State::Init,
+                Order::Ordered,
+                Counterparty::new(port_id.clone(), None),
+                vec![conn_id.clone()],
+                Version::default(),
+            )
+            .unwrap()
+        );
+
+        // `ChanOpenAck`, `ChanOpenConfirm` and `ChanCloseConfirm`: the
+        // counterparty end knows the local channel id.
+        for state in [State::TryOpen, State::Open, State::Closed] {
+            let expected = build_expected_counterparty_channel(
+                state,
+                Order::Unordered,
+                Counterparty::new(port_id.clone(), Some(chan_id.clone())),
+                conn_id.clone(),
+                Version::default(),
+            )
+            .unwrap();
+            assert_eq!(expected.state(), &state);
+            assert_eq!(expected.ordering(), &Order::Unordered);
+            assert_eq!(expected.counterparty().port_id(), &port_id);
+            assert_eq!(expected.counterparty().channel_id(), Some(&chan_id));
+            assert_eq!(expected.connection_hops(), &vec![conn_id.clone()]);
+            assert_eq!(expected.version(), &Version::default());
+
+            let res = build_expected_counterparty_channel(
+                state,
+                Order::Unordered,
+                Counterparty::new(port_id.clone(), None),
+                conn_id.clone(),
+                Version::default(),
+            );
+            assert!(matches!(
+                res,
+                Err(ChannelError::MissingCounterpartyChannelId { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn close_only_open_channel() {
+        let channel_end = |state| {
+            ChannelEnd::new(
+                state,
+                Order::Unordered,
+                Counterparty::new(PortId::transfer(), Some(ChannelId::default())),
+                vec![ConnectionId::default()],
+                Version::default(),
+            )
+            .unwrap()
+        };
+
+        let mut open = channel_end(State::Open);
+        assert!(open.close().is_ok());
+        assert_eq!(open.state(), &State::Closed);
+
+        for state in [State::Init, State::TryOpen, State::Closed] {
+            let mut not_open = channel_end(state);
+            assert!(matches!(
+                not_open.close(),
+                Err(ChannelError::InvalidState { .. })
+            ));
+            assert_eq!(not_open.state(), &state);
+        }
+    }
 }
@@ -4,8 +4,6 @@ use crate::core::ics04_channel::timeout::TimeoutHeight;
 use crate::core::timestamp::Timestamp;
 use crate::prelude::*;
 
-use super::acknowledgement::Acknowledgement;
-
 /// Packet commitment
 #[cfg_attr(
     feature = "parity-scale-codec",
@@ -76,13 +74,55 @@ impl From<Vec<u8>> for AcknowledgementCommitment {
     }
 }
 
+/// The hash function used to compute packet and acknowledgement commitments.
+///
+/// Counterparties verify the commitments against SHA256 digests, so any
+/// implementation, e.g. one backed by a FIPS-validated module, must compute
+/// SHA256 for the Merkle proofs of the IBC provable store to be accepted.
+pub trait Hasher {
+    /// Returns the digest of `data`.
+    fn hash(&self, data: &[u8]) -> Vec<u8>;
+}
+
+/// The default [`Hasher`], computing SHA256 with the `sha2` crate.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sha256Hasher;
+
+impl Hasher for Sha256Hasher {
+    fn hash(&self, data: &[u8]) -> Vec<u8> {
+        use sha2::Digest;
+
+        sha2::Sha256::digest(data).to_vec()
+    }
+}
+
 /// Compute the commitment for a packet.
 ///
 /// Note that the absence of `timeout_height` is treated as
 /// `{revision_number: 0, revision_height: 0}` to be consistent with ibc-go,
 /// where this value is used to mean "no timeout height":
 /// <https://github.com/cosmos/ibc-go/blob/04791984b3d6c83f704c4f058e6ca0038d155d91/modules/core/04-channel/keeper/packet.go#L206>
-pub(crate) fn compute_packet_commitment(
+///
+/// The commitment is the SHA256 hash of the big-endian timeout timestamp,
+/// followed by the big-endian timeout revision number and height, followed
+/// by the SHA256 hash of the packet data, matching ibc-go's `CommitPacket`.
+pub fn compute_packet_commitment(
+    packet_data: &[u8],
+    timeout_height: &TimeoutHeight,
+    timeout_timestamp: &Timestamp,
+) -> PacketCommitment {
+    compute_packet_commitment_with(
+        &Sha256Hasher,
+        packet_data,
+        timeout_height,
+        timeout_timestamp,
+    )
+}
+
+/// Compute the commitment for a packet like [`compute_packet_commitment`],
+/// hashing with `hasher`.
+pub fn compute_packet_commitment_with(
+    hasher: &dyn Hasher,
     packet_data: &[u8],
     timeout_height: &TimeoutHeight,
     timeout_timestamp: &Timestamp,
@@ -95,23 +135,118 @@ pub(crate) fn compute_packet_commitment(
     let revision_height = timeout_height.commitment_revision_height().to_be_bytes();
     hash_input.append(&mut revision_height.to_vec());
 
-    let packet_data_hash = hash(packet_data);
+    let packet_data_hash = hasher.hash(packet_data);
     hash_input.append(&mut packet_data_hash.to_vec());
 
-    hash(&hash_input).into()
+    hasher.hash(&hash_input).into()
 }
 
 /// Compute the commitment for an acknowledgement.
-pub(crate) fn compute_ack_commitment(ack: &Acknowledgement) -> AcknowledgementCommitment {
-    hash(ack.as_ref()).into()
+///
+/// The commitment is the SHA256 hash of the acknowledgement bytes, matching
+/// ibc-go's `CommitAcknowledgement`.
+pub fn compute_ack_commitment(ack: &[u8]) -> AcknowledgementCommitment {
+    compute_ack_commitment_with(&Sha256Hasher, ack)
 }
 
-/// Helper function to hash a byte slice using SHA256.
-///
-/// Note that computing commitments with anything other than SHA256 will
-/// break the Merkle proofs of the IBC provable store.
-fn hash(data: impl AsRef<[u8]>) -> Vec<u8> {
-    use sha2::Digest;
+/// Compute the commitment for an acknowledgement like
+/// [`compute_ack_commitment`], hashing with `hasher`.
+pub fn compute_ack_commitment_with(hasher: &dyn Hasher, ack: &[u8]) -> AcknowledgementCommitment {
+    hasher.hash(ack).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::ics02_client::height::Height;
+    use crate::test_utils::ReversedSha256Hasher;
+
+    #[test]
+    fn test_compute_packet_commitment() {
+        // sha256(be(0x42) || be(42) || be(24) || sha256("packet data")),
+        // computed by hand following the layout of ibc-go's `CommitPacket`
+        // rather than taken from ibc-go's own test suite:
+        // <https://github.com/cosmos/ibc-go/blob/04791984b3d6c83f704c4f058e6ca0038d155d91/modules/core/04-channel/types/packet.go>
+        let expected: [u8; 32] = [
+            0xa9, 0x28, 0xb5, 0x1f, 0x62, 0xbd, 0x54, 0x00, 0x91, 0xec, 0x45, 0x1f, 0x4e, 0xf3,
+            0x45, 0x79, 0x4f, 0x05, 0x9e, 0x65, 0x91, 0x08, 0x16, 0x86, 0x61, 0x26, 0xdc, 0x36,
+            0x4f, 0x84, 0xcc, 0x15,
+        ];
+
+        let actual = compute_packet_commitment(
+            b"packet data",
+            &TimeoutHeight::At(Height::new(42, 24).unwrap()),
+            &Timestamp::from_nanoseconds(0x42).unwrap(),
+        );
+
+        assert_eq!(actual.as_ref(), &expected[..]);
+    }
+
+    #[test]
+    fn test_compute_packet_commitment_no_timeout_height() {
+        // Without a timeout height, both revision fields commit as zero.
+        let data = b"packet data";
+        let timestamp = Timestamp::from_nanoseconds(0x42).unwrap();
+
+        let mut hash_input = 0x42u64.to_be_bytes().to_vec();
+        hash_input.extend_from_slice(&[0; 16]);
+        hash_input.extend(Sha256Hasher.hash(data));
 
-    sha2::Sha256::digest(&data).to_vec()
+        let actual = compute_packet_commitment(data, &TimeoutHeight::Never, &timestamp);
+
+        assert_eq!(actual.into_vec(), Sha256Hasher.hash(&hash_input));
+    }
+
+    #[test]
+    fn test_compute_ack_commitment() {
+        // The commitment ibc-go stores for the ICS-20 success acknowledgement.
+        let expected: [u8; 32] = [
+            0x08, 0xf7, 0x55, 0x7e, 0xd5, 0x18, 0x26, 0xfe, 0x18, 0xd8, 0x45, 0x12, 0xbf, 0x24,
+            0xec, 0x75, 0x00, 0x1e, 0xdb, 0xaf, 0x21, 0x23, 0xa4, 0x77, 0xdf, 0x72, 0xa0, 0xa9,
+            0xf3, 0x64, 0x0a, 0x7c,
+        ];
+
+        let actual = compute_ack_commitment(br#"{"result":"AQ=="}"#);
+
+        assert_eq!(actual.as_ref(), &expected[..]);
+    }
+
+    #[test]
+    fn test_compute_commitments_with_hasher() {
+        let data = b"packet data";
+        let timeout_height = TimeoutHeight::At(Height::new(42, 24).unwrap());
+        let timestamp = Timestamp::from_nanoseconds(0x42).unwrap();
+
+        // The default hasher commits exactly as before.
+        assert_eq!(
+            compute_packet_commitment_with(&Sha256Hasher, data, &timeout_height, &timestamp),
+            compute_packet_commitment(data, &timeout_height, &timestamp)
+        );
+        assert_eq!(
+            compute_ack_commitment_with(&Sha256Hasher, data),
+            compute_ack_commitment(data)
+        );
+
+        let mut hash_input = 0x42u64.to_be_bytes().to_vec();
+        hash_input.extend(42u64.to_be_bytes());
+        hash_input.extend(24u64.to_be_bytes());
+        hash_input.extend(ReversedSha256Hasher.hash(data));
+
+        let actual = compute_packet_commitment_with(
+            &ReversedSha256Hasher,
+            data,
+            &timeout_height,
+            &timestamp,
+        );
+        assert_eq!(actual.as_ref(), ReversedSha256Hasher.hash(&hash_input));
+        assert_ne!(
+            actual,
+            compute_packet_commitment(data, &timeout_height, &timestamp)
+        );
+
+        let actual = compute_ack_commitment_with(&ReversedSha256Hasher, data);
+        assert_eq!(actual.as_ref(), ReversedSha256Hasher.hash(data));
+        assert_ne!(actual, compute_ack_commitment(data));
+    }
 }
@@ -14,7 +14,7 @@ use num_traits::float::FloatCore;
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
-use crate::core::ics04_channel::commitment::PacketCommitment;
+use crate::core::ics04_channel::commitment::{Hasher, PacketCommitment, Sha256Hasher};
 use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
 
 use super::packet::Sequence;
@@ -46,6 +46,12 @@ pub trait SendPacketValidationContext {
 
     fn get_next_sequence_send(&self, seq_send_path: &SeqSendPath)
         -> Result<Sequence, ContextError>;
+
+    /// Returns the hash function used to compute packet commitments.
+    /// Defaults to [`Sha256Hasher`].
+    fn commitment_hasher(&self) -> &dyn Hasher {
+        &Sha256Hasher
+    }
 }
 
 impl<T> SendPacketValidationContext for T
@@ -86,6 +92,10 @@ where
     ) -> Result<Sequence, ContextError> {
         self.get_next_sequence_send(seq_send_path)
     }
+
+    fn commitment_hasher(&self) -> &dyn Hasher {
+        ValidationContext::commitment_hasher(self)
+    }
 }
 
 /// Methods required in send packet execution, to be implemented by the host
@@ -107,6 +117,9 @@ pub trait SendPacketExecutionContext: SendPacketValidationContext {
 
     /// Logging facility
     fn log_message(&mut self, message: String);
+
+    /// Structured logging facility
+    fn log_structured(&mut self, key: &str, fields: &[(&str, String)]);
 }
 
 impl<T> SendPacketExecutionContext for T
@@ -136,6 +149,10 @@ where
     fn log_message(&mut self, message: String) {
         self.log_message(message)
     }
+
+    fn log_structured(&mut self, key: &str, fields: &[(&str, String)]) {
+        self.log_structured(key, fields)
+    }
 }
 
 pub(crate) fn calculate_block_delay(
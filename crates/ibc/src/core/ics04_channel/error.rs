@@ -1,17 +1,20 @@
 //! Defines the main channel, port and packet error types
 
-use super::channel::Counterparty;
+use super::channel::{Counterparty, Order};
 use super::packet::Sequence;
 use super::timeout::TimeoutHeight;
 use crate::core::ics02_client::error as client_error;
 use crate::core::ics03_connection::error as connection_error;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::Version;
+use crate::core::ics23_commitment::merkle::ProofKind;
 use crate::core::ics24_host::identifier::{
     ChannelId, ClientId, ConnectionId, IdentifierError, PortId,
 };
+use crate::core::router::ModuleId;
 use crate::core::timestamp::{ParseTimestampError, Timestamp};
 use crate::prelude::*;
+use crate::signer::Signer;
 use crate::Height;
 
 use displaydoc::Display;
@@ -26,16 +29,34 @@ pub enum ChannelError {
     InvalidState { expected: String, actual: String },
     /// invalid channel order type: expected `{expected}`, actual `{actual}`
     InvalidOrderType { expected: String, actual: String },
+    /// unknown channel order type `{order}`
+    UnknownOrderType { order: String },
+    /// channel ordering mismatch: local `{local}`, counterparty `{counterparty}`
+    OrderingMismatch { local: Order, counterparty: Order },
     /// invalid connection hops length: expected `{expected}`; actual `{actual}`
     InvalidConnectionHopsLength { expected: usize, actual: usize },
     /// invalid signer error: `{reason}`
     InvalidSigner { reason: String },
+    /// `{granter}` has not granted `{signer}` the right to sign on its behalf
+    MissingSignerGrant { signer: Signer, granter: Signer },
     /// invalid proof: missing height
     MissingHeight,
     /// packet data bytes must be valid UTF-8 (this restriction will be lifted in the future)
     NonUtf8PacketData,
     /// missing counterparty
     MissingCounterparty,
+    /// the capability for port `{port_id}` is already claimed by module `{module_id}`
+    CapabilityAlreadyClaimed {
+        port_id: PortId,
+        module_id: ModuleId,
+    },
+    /// module `{module_id}` does not hold the capability for port `{port_id}`
+    CapabilityNotOwned {
+        port_id: PortId,
+        module_id: ModuleId,
+    },
+    /// counterparty channel id must be set for a channel end in state `{state}`
+    MissingCounterpartyChannelId { state: State },
     /// version not supported: expected `{expected}`, actual `{actual}`
     VersionNotSupported { expected: Version, actual: Version },
     /// missing channel end
@@ -45,6 +66,13 @@ pub enum ChannelError {
         port_id: PortId,
         channel_id: ChannelId,
     },
+    /// the next send sequence of channel (`{port_id}`, `{channel_id}`) would overflow
+    SequenceOverflow {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
+    /// packet data of `{size}` bytes exceeds the maximum of `{max}` bytes
+    PacketDataTooLarge { size: usize, max: usize },
     /// Verification fails for the packet with the sequence number `{sequence}`, error: `{client_error}`
     PacketVerificationFailed {
         sequence: Sequence,
@@ -72,10 +100,17 @@ pub enum ChannelError {
     Other { description: String },
     /// Undefined counterparty connection for `{connection_id}`
     UndefinedConnectionCounterparty { connection_id: ConnectionId },
+    /// counterparty channel end is bound to connection `{actual}`, expected `{expected}`
+    MismatchedCounterpartyConnection {
+        expected: ConnectionId,
+        actual: ConnectionId,
+    },
     /// invalid proof: empty proof
     InvalidProof,
     /// identifier error: `{0}`
     InvalidIdentifier(IdentifierError),
+    /// the module bound to port `{port_id}` does not accept packets
+    ModuleNotAcceptingPackets { port_id: PortId },
 }
 
 #[derive(Debug, Display)]
@@ -91,7 +126,22 @@ pub enum PacketError {
     },
     /// Receiving chain block timestamp >= packet timeout timestamp
     LowPacketTimestamp,
-    /// Invalid packet sequence `{given_sequence}` ≠ next send sequence `{next_sequence}`
+    /// packet timeout height `{packet_height}` elapsed at host height `{host_height}`
+    PacketTimeoutHeightElapsed {
+        packet_height: TimeoutHeight,
+        host_height: Height,
+    },
+    /// packet timeout timestamp `{packet_ts}` elapsed at host timestamp `{host_ts}`
+    PacketTimeoutTimestampElapsed {
+        packet_ts: Timestamp,
+        host_ts: Timestamp,
+    },
+    /// packet sequence `{packet_sequence}` does not match the sequence `{proof_sequence}` of the proven commitment path
+    SequencePathMismatch {
+        packet_sequence: Sequence,
+        proof_sequence: Sequence,
+    },
+    /// Invalid packet sequence `{given_sequence}` ≠ next sequence `{next_sequence}`
     InvalidPacketSequence {
         given_sequence: Sequence,
         next_sequence: Sequence,
@@ -123,6 +173,12 @@ pub enum PacketError {
     InvalidAcknowledgement,
     /// Acknowledgment status cannot be empty
     EmptyAcknowledgementStatus,
+    /// timing out a packet on a `{ordering}` channel requires a {expected} proof, got a {actual} proof
+    MismatchedTimeoutProof {
+        ordering: Order,
+        expected: ProofKind,
+        actual: ProofKind,
+    },
     /// Acknowledgment for the packet `{sequence}` not found
     PacketAcknowledgementNotFound { sequence: Sequence },
     /// invalid proof: missing height
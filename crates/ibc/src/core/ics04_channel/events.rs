@@ -749,23 +749,23 @@ impl ReceivePacket {
         &self.timeout_timestamp_attr_on_b.timeout_timestamp
     }
 
-    pub fn seq_on_b(&self) -> &Sequence {
+    pub fn seq_on_a(&self) -> &Sequence {
         &self.seq_attr_on_a.sequence
     }
 
-    pub fn port_id_on_b(&self) -> &PortId {
+    pub fn port_id_on_a(&self) -> &PortId {
         &self.port_id_attr_on_a.src_port_id
     }
 
-    pub fn chan_id_on_b(&self) -> &ChannelId {
+    pub fn chan_id_on_a(&self) -> &ChannelId {
         &self.chan_id_attr_on_a.src_channel_id
     }
 
-    pub fn port_id_on_a(&self) -> &PortId {
+    pub fn port_id_on_b(&self) -> &PortId {
         &self.port_id_attr_on_b.dst_port_id
     }
 
-    pub fn chan_id_on_a(&self) -> &ChannelId {
+    pub fn chan_id_on_b(&self) -> &ChannelId {
         &self.chan_id_attr_on_b.dst_channel_id
     }
 
@@ -773,7 +773,7 @@ impl ReceivePacket {
         &self.channel_ordering_attr.order
     }
 
-    pub fn conn_id_on_a(&self) -> &ConnectionId {
+    pub fn conn_id_on_b(&self) -> &ConnectionId {
         &self.conn_id_attr_on_b.connection_id
     }
 
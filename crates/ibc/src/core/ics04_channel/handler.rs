@@ -11,3 +11,68 @@ pub(crate) mod recv_packet;
 pub(crate) mod send_packet;
 pub(crate) mod timeout;
 pub(crate) mod timeout_on_close;
+
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+
+use crate::core::ics02_client::proof::verify_proof_size;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics23_commitment::merkle::MerkleProof;
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// Validates the signer of a channel message, through the grant of the
+/// [`message_granter`](ValidationContext::message_granter) if the message is
+/// signed on its behalf.
+pub(crate) fn validate_channel_msg_signer<Ctx>(
+    ctx: &Ctx,
+    signer: &Signer,
+) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let granter = ctx.message_granter(signer);
+    validate_signer_prefix(ctx.signer_bech32_prefix(), signer, granter.as_ref())?;
+
+    match granter {
+        Some(granter) => ctx.validate_signer_with_grant(signer, &granter),
+        None => ctx.validate_message_signer(signer),
+    }
+}
+
+/// Checks that `signer` and its `granter`, if any, use the host's bech32
+/// `prefix`, if any.
+pub(crate) fn validate_signer_prefix(
+    prefix: Option<String>,
+    signer: &Signer,
+    granter: Option<&Signer>,
+) -> Result<(), ContextError> {
+    if let Some(prefix) = prefix {
+        signer.validate_bech32(&prefix)?;
+        if let Some(granter) = granter {
+            granter.validate_bech32(&prefix)?;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes `proof` as a Merkle proof, without verifying it, so that a handler
+/// can report an attested entry that does not match its message with a
+/// dedicated error rather than as a failed proof verification.
+///
+/// Returns `None` for proofs that are not Merkle proofs or that are larger
+/// than the host's [`max_proof_size`](ValidationContext::max_proof_size),
+/// which are left to the client to verify or reject.
+pub(crate) fn decode_unverified_proof<Ctx>(
+    ctx: &Ctx,
+    proof: &CommitmentProofBytes,
+) -> Option<MerkleProof>
+where
+    Ctx: ValidationContext,
+{
+    verify_proof_size(ctx, proof).ok()?;
+
+    RawMerkleProof::try_from(proof.clone())
+        .ok()
+        .map(MerkleProof::from)
+}
@@ -4,12 +4,17 @@ use crate::prelude::*;
 use crate::core::events::MessageEvent;
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics03_connection::delay::verify_conn_delay_passed;
 use crate::core::ics04_channel::channel::{Counterparty, Order, State as ChannelState};
-use crate::core::ics04_channel::commitment::{compute_ack_commitment, compute_packet_commitment};
+use crate::core::ics04_channel::commitment::{
+    compute_ack_commitment_with, compute_packet_commitment_with,
+};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::error::PacketError;
+use crate::core::ics04_channel::handler::validate_channel_msg_signer;
 use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{
@@ -78,6 +83,9 @@ where
 
     // apply state changes
     {
+        let conn_end_on_a = ctx_a.connection_end(conn_id_on_a)?;
+        update_client_on_verified_proofs(ctx_a, conn_end_on_a.client_id())?;
+
         let commitment_path_on_a = CommitmentPath {
             port_id: msg.packet.port_id_on_a.clone(),
             channel_id: msg.packet.chan_id_on_a.clone(),
@@ -96,7 +104,14 @@ where
 
     // emit events and logs
     {
-        ctx_a.log_message("success: packet acknowledgement".to_string());
+        ctx_a.log_structured(
+            "packet_acknowledgement",
+            &[
+                ("port_id", msg.packet.port_id_on_a.to_string()),
+                ("channel_id", msg.packet.chan_id_on_a.to_string()),
+                ("sequence", msg.packet.seq_on_a.to_string()),
+            ],
+        );
 
         // Note: Acknowledgement event was emitted at the beginning
 
@@ -116,7 +131,9 @@ fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgAcknowledgement) -> Result<(), ContextErr
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_a, &msg.signer)?;
+
+    ctx_a.validate_self_consistency()?;
 
     let packet = &msg.packet;
     let chan_end_path_on_a = ChannelEndPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
@@ -151,7 +168,8 @@ where
     };
 
     if commitment_on_a
-        != compute_packet_commitment(
+        != compute_packet_commitment_with(
+            ctx_a.commitment_hasher(),
             &packet.data,
             &packet.timeout_height_on_b,
             &packet.timeout_timestamp_on_b,
@@ -192,26 +210,28 @@ where
         let client_cons_state_path_on_a =
             ClientConsensusStatePath::new(client_id_on_a, &msg.proof_height_on_b);
         let consensus_state_of_b_on_a = ctx_a.consensus_state(&client_cons_state_path_on_a)?;
-        let ack_commitment = compute_ack_commitment(&msg.acknowledgement);
+        let ack_commitment =
+            compute_ack_commitment_with(ctx_a.commitment_hasher(), msg.acknowledgement.as_ref());
         let ack_path_on_b =
             AckPath::new(&packet.port_id_on_b, &packet.chan_id_on_b, packet.seq_on_a);
 
         verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, &conn_end_on_a)?;
 
         // Verify the proof for the packet against the chain store.
-        client_state_of_b_on_a
-            .verify_membership(
-                conn_end_on_a.counterparty().prefix(),
-                &msg.proof_acked_on_b,
-                consensus_state_of_b_on_a.root(),
-                Path::Ack(ack_path_on_b),
-                ack_commitment.into_vec(),
-            )
-            .map_err(|e| ChannelError::PacketVerificationFailed {
-                sequence: packet.seq_on_a,
-                client_error: e,
-            })
-            .map_err(PacketError::Channel)?;
+        verify_membership(
+            ctx_a,
+            &client_state_of_b_on_a,
+            conn_end_on_a.counterparty().prefix(),
+            &msg.proof_acked_on_b,
+            consensus_state_of_b_on_a.root(),
+            Path::Ack(ack_path_on_b),
+            ack_commitment.into_vec(),
+        )
+        .map_err(|e| ChannelError::PacketVerificationFailed {
+            sequence: packet.seq_on_a,
+            client_error: e,
+        })
+        .map_err(PacketError::Channel)?;
     }
 
     Ok(())
@@ -220,14 +240,19 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ics04_channel::commitment::{
+        compute_ack_commitment, compute_packet_commitment,
+    };
     use rstest::*;
     use test_log::test;
 
+    use crate::clients::ics09_localhost::client_type as localhost_client_type;
     use crate::core::ics02_client::height::Height;
     use crate::core::ics03_connection::connection::ConnectionEnd;
     use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::acknowledgement::Acknowledgement;
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
     use crate::core::ics04_channel::commitment::PacketCommitment;
     use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
@@ -404,6 +429,90 @@ mod tests {
         )
     }
 
+    /// Verifies the acknowledgement against the commitment stored on the
+    /// counterparty, which the localhost client reads from the host's store.
+    #[rstest]
+    fn ack_verify_committed_acknowledgement(fixture: Fixture) {
+        let Fixture {
+            msg,
+            packet_commitment,
+            chan_end_on_a_unordered,
+            client_height,
+            ..
+        } = fixture;
+        let client_id = ClientId::new(localhost_client_type(), 0).unwrap();
+        let ctx = MockContext::default().with_client_parametrized(
+            &client_id,
+            client_height,
+            Some(localhost_client_type()),
+            None,
+        );
+        let conn_end_on_a = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                ValidationContext::commitment_prefix(&ctx),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+        let mut ctx = ctx
+            .with_channel(
+                PortId::default(),
+                ChannelId::default(),
+                chan_end_on_a_unordered,
+            )
+            .with_connection(ConnectionId::default(), conn_end_on_a)
+            .with_packet_commitment(
+                msg.packet.port_id_on_a.clone(),
+                msg.packet.chan_id_on_a.clone(),
+                msg.packet.seq_on_a,
+                packet_commitment,
+            );
+        let ack_path_on_b = AckPath::new(
+            &msg.packet.port_id_on_b,
+            &msg.packet.chan_id_on_b,
+            msg.packet.seq_on_a,
+        );
+        ctx.store_packet_acknowledgement(
+            &ack_path_on_b,
+            compute_ack_commitment(msg.acknowledgement.as_ref()),
+        )
+        .unwrap();
+        ctx.store_update_time(
+            client_id.clone(),
+            client_height,
+            Timestamp::from_nanoseconds(1000).unwrap(),
+        )
+        .unwrap();
+        ctx.store_update_height(client_id, client_height, Height::new(0, 4).unwrap())
+            .unwrap();
+
+        let res = validate(&ctx, &msg);
+        assert!(
+            res.is_ok(),
+            "Validation should succeed for the committed acknowledgement: {res:?}"
+        );
+
+        let msg = MsgAcknowledgement {
+            acknowledgement: Acknowledgement::try_from(b"other ack".to_vec()).unwrap(),
+            ..msg
+        };
+        let res = validate(&ctx, &msg);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::Channel(
+                    ChannelError::PacketVerificationFailed { .. }
+                )))
+            ),
+            "Validation should fail for another acknowledgement: {res:?}"
+        );
+    }
+
     #[rstest]
     fn ack_unordered_chan_execute(fixture: Fixture) {
         let Fixture {
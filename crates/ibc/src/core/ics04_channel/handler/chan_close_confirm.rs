@@ -7,11 +7,16 @@ use ibc_proto::protobuf::Protobuf;
 use crate::core::events::{IbcEvent, MessageEvent};
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::State;
-use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State as ChannelState};
+use crate::core::ics04_channel::channel::{
+    build_expected_counterparty_channel, Counterparty, State as ChannelState,
+};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::CloseConfirm;
+use crate::core::ics04_channel::handler::validate_channel_msg_signer;
 use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{ChannelEndPath, ClientConsensusStatePath};
@@ -47,9 +52,12 @@ where
 
     // state changes
     {
+        let conn_end_on_b = ctx_b.connection_end(&chan_end_on_b.connection_hops()[0])?;
+        update_client_on_verified_proofs(ctx_b, conn_end_on_b.client_id())?;
+
         let chan_end_on_b = {
             let mut chan_end_on_b = chan_end_on_b.clone();
-            chan_end_on_b.set_state(State::Closed);
+            chan_end_on_b.close()?;
             chan_end_on_b
         };
         ctx_b.store_channel(&chan_end_path_on_b, chan_end_on_b)?;
@@ -57,19 +65,21 @@ where
 
     // emit events and logs
     {
-        ctx_b.log_message("success: channel close confirm".to_string());
+        ctx_b.log_structured(
+            "channel_close_confirm",
+            &[
+                ("port_id", msg.port_id_on_b.to_string()),
+                ("channel_id", msg.chan_id_on_b.to_string()),
+            ],
+        );
 
         let core_event = {
             let port_id_on_a = chan_end_on_b.counterparty().port_id.clone();
-            let chan_id_on_a = chan_end_on_b
-                .counterparty()
-                .channel_id
-                .clone()
-                .ok_or(ContextError::ChannelError(ChannelError::Other {
-                description:
-                    "internal error: ChannelEnd doesn't have a counterparty channel id in CloseInit"
-                        .to_string(),
-            }))?;
+            let chan_id_on_a = chan_end_on_b.counterparty().channel_id.clone().ok_or(
+                ChannelError::MissingCounterpartyChannelId {
+                    state: State::Closed,
+                },
+            )?;
             let conn_id_on_b = chan_end_on_b.connection_hops[0].clone();
 
             IbcEvent::CloseConfirmChannel(CloseConfirm::new(
@@ -99,14 +109,16 @@ fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgChannelCloseConfirm) -> Result<(), Contex
 where
     Ctx: ValidationContext,
 {
-    ctx_b.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_b, &msg.signer)?;
 
     // Retrieve the old channel end and validate it against the message.
     let chan_end_path_on_b = ChannelEndPath::new(&msg.port_id_on_b, &msg.chan_id_on_b);
     let chan_end_on_b = ctx_b.channel_end(&chan_end_path_on_b)?;
 
-    // Validate that the channel end is in a state where it can be closed.
-    chan_end_on_b.verify_not_closed()?;
+    // Validate that the channel end is in a state where it can be closed,
+    // see `ChannelEnd::close`.
+    chan_end_on_b.verify_state_matches(&ChannelState::Open)?;
+    chan_end_on_b.verify_counterparty_channel_id(&ChannelState::Closed)?;
 
     let conn_end_on_b = ctx_b.connection_end(&chan_end_on_b.connection_hops()[0])?;
 
@@ -131,36 +143,38 @@ where
         let consensus_state_of_a_on_b = ctx_b.consensus_state(&client_cons_state_path_on_b)?;
         let prefix_on_a = conn_end_on_b.counterparty().prefix();
         let port_id_on_a = &chan_end_on_b.counterparty().port_id;
-        let chan_id_on_a = chan_end_on_b
-            .counterparty()
-            .channel_id()
-            .ok_or(ChannelError::MissingCounterparty)?;
+        let chan_id_on_a = chan_end_on_b.counterparty().channel_id().ok_or(
+            ChannelError::MissingCounterpartyChannelId {
+                state: ChannelState::Closed,
+            },
+        )?;
         let conn_id_on_a = conn_end_on_b.counterparty().connection_id().ok_or(
             ChannelError::UndefinedConnectionCounterparty {
                 connection_id: chan_end_on_b.connection_hops()[0].clone(),
             },
         )?;
 
-        let expected_chan_end_on_a = ChannelEnd::new(
+        let expected_chan_end_on_a = build_expected_counterparty_channel(
             ChannelState::Closed,
             *chan_end_on_b.ordering(),
             Counterparty::new(msg.port_id_on_b.clone(), Some(msg.chan_id_on_b.clone())),
-            vec![conn_id_on_a.clone()],
+            conn_id_on_a.clone(),
             chan_end_on_b.version().clone(),
         )?;
         let chan_end_path_on_a = ChannelEndPath::new(port_id_on_a, chan_id_on_a);
 
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-        client_state_of_a_on_b
-            .verify_membership(
-                prefix_on_a,
-                &msg.proof_chan_end_on_a,
-                consensus_state_of_a_on_b.root(),
-                Path::ChannelEnd(chan_end_path_on_a),
-                expected_chan_end_on_a.encode_vec(),
-            )
-            .map_err(ChannelError::VerifyChannelFailed)?;
+        verify_membership(
+            ctx_b,
+            &client_state_of_a_on_b,
+            prefix_on_a,
+            &msg.proof_chan_end_on_a,
+            consensus_state_of_a_on_b.root(),
+            Path::ChannelEnd(chan_end_path_on_a),
+            expected_chan_end_on_a.encode_vec(),
+        )
+        .map_err(ChannelError::VerifyChannelFailed)?;
     }
 
     Ok(())
@@ -185,8 +199,13 @@ mod tests {
     use crate::core::router::Router;
     use crate::core::timestamp::ZERO_DURATION;
 
+    use crate::core::ics02_client::client_state::Status;
+    use crate::core::ics02_client::ClientExecutionContext;
+    use crate::core::ics24_host::path::ClientStatePath;
     use crate::mock::client_state::client_type as mock_client_type;
+    use crate::mock::client_state::MockClientState;
     use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
 
     use crate::applications::transfer::MODULE_ID_STR;
     use crate::mock::router::MockRouter;
@@ -241,6 +260,120 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_chan_close_confirm_validate_frozen_client() {
+        let client_id = ClientId::new(mock_client_type(), 24).unwrap();
+        let conn_id = ConnectionId::new(2);
+        let default_context = MockContext::default();
+        let client_consensus_state_height = default_context.host_height().unwrap();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty(Some(0))).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        let msg_chan_close_confirm = MsgChannelCloseConfirm::try_from(
+            get_dummy_raw_msg_chan_close_confirm(client_consensus_state_height.revision_height()),
+        )
+        .unwrap();
+
+        let chan_end = ChannelEnd::new(
+            ChannelState::Open,
+            Order::default(),
+            Counterparty::new(
+                msg_chan_close_confirm.port_id_on_b.clone(),
+                Some(msg_chan_close_confirm.chan_id_on_b.clone()),
+            ),
+            vec![conn_id.clone()],
+            Version::default(),
+        )
+        .unwrap();
+
+        let mut context = default_context
+            .with_client(&client_id, client_consensus_state_height)
+            .with_connection(conn_id, conn_end)
+            .with_channel(
+                msg_chan_close_confirm.port_id_on_b.clone(),
+                msg_chan_close_confirm.chan_id_on_b.clone(),
+                chan_end,
+            );
+
+        let frozen_client_state =
+            MockClientState::new(MockHeader::new(client_consensus_state_height))
+                .with_frozen_height(client_consensus_state_height);
+        context
+            .store_client_state(ClientStatePath::new(&client_id), frozen_client_state.into())
+            .unwrap();
+
+        let res = validate(&context, &msg_chan_close_confirm);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(ClientError::ClientNotActive {
+                    status: Status::Frozen
+                }))
+            ),
+            "Validation expected to fail against a frozen client: {res:?}"
+        );
+    }
+
+    #[test]
+    fn test_chan_close_confirm_validate_missing_counterparty_channel_id() {
+        let client_id = ClientId::new(mock_client_type(), 24).unwrap();
+        let conn_id = ConnectionId::new(2);
+        let default_context = MockContext::default();
+        let client_consensus_state_height = default_context.host_height().unwrap();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty(Some(0))).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        let msg_chan_close_confirm = MsgChannelCloseConfirm::try_from(
+            get_dummy_raw_msg_chan_close_confirm(client_consensus_state_height.revision_height()),
+        )
+        .unwrap();
+
+        let chan_end = ChannelEnd::new(
+            ChannelState::Open,
+            Order::default(),
+            Counterparty::new(msg_chan_close_confirm.port_id_on_b.clone(), None),
+            vec![conn_id.clone()],
+            Version::default(),
+        )
+        .unwrap();
+
+        let context = default_context
+            .with_client(&client_id, client_consensus_state_height)
+            .with_connection(conn_id, conn_end)
+            .with_channel(
+                msg_chan_close_confirm.port_id_on_b.clone(),
+                msg_chan_close_confirm.chan_id_on_b.clone(),
+                chan_end,
+            );
+
+        let res = validate(&context, &msg_chan_close_confirm);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(
+                    ChannelError::MissingCounterpartyChannelId {
+                        state: ChannelState::Closed
+                    }
+                ))
+            ),
+            "Validation expected to fail on the missing counterparty channel id: {res:?}"
+        );
+    }
+
     #[test]
     fn test_chan_close_confirm_execute() {
         let client_id = ClientId::new(mock_client_type(), 24).unwrap();
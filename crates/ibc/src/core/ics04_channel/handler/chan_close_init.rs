@@ -8,6 +8,7 @@ use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::CloseInit;
+use crate::core::ics04_channel::handler::validate_channel_msg_signer;
 use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
 use crate::core::ics24_host::path::ChannelEndPath;
 use crate::core::router::Module;
@@ -44,7 +45,7 @@ where
     {
         let chan_end_on_a = {
             let mut chan_end_on_a = chan_end_on_a.clone();
-            chan_end_on_a.set_state(State::Closed);
+            chan_end_on_a.close()?;
             chan_end_on_a
         };
 
@@ -53,19 +54,21 @@ where
 
     // emit events and logs
     {
-        ctx_a.log_message("success: channel close init".to_string());
+        ctx_a.log_structured(
+            "channel_close_init",
+            &[
+                ("port_id", msg.port_id_on_a.to_string()),
+                ("channel_id", msg.chan_id_on_a.to_string()),
+            ],
+        );
 
         let core_event = {
             let port_id_on_b = chan_end_on_a.counterparty().port_id.clone();
-            let chan_id_on_b = chan_end_on_a
-                .counterparty()
-                .channel_id
-                .clone()
-                .ok_or(ContextError::ChannelError(ChannelError::Other {
-                description:
-                    "internal error: ChannelEnd doesn't have a counterparty channel id in CloseInit"
-                        .to_string(),
-            }))?;
+            let chan_id_on_b = chan_end_on_a.counterparty().channel_id.clone().ok_or(
+                ChannelError::MissingCounterpartyChannelId {
+                    state: State::Closed,
+                },
+            )?;
             let conn_id_on_a = chan_end_on_a.connection_hops[0].clone();
 
             IbcEvent::CloseInitChannel(CloseInit::new(
@@ -95,13 +98,15 @@ fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelCloseInit) -> Result<(), ContextEr
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_a, &msg.signer)?;
 
     let chan_end_path_on_a = ChannelEndPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
     let chan_end_on_a = ctx_a.channel_end(&chan_end_path_on_a)?;
 
-    // Validate that the channel end is in a state where it can be closed.
-    chan_end_on_a.verify_not_closed()?;
+    // Validate that the channel end is in a state where it can be closed,
+    // see `ChannelEnd::close`.
+    chan_end_on_a.verify_state_matches(&State::Open)?;
+    chan_end_on_a.verify_counterparty_channel_id(&State::Closed)?;
 
     // An OPEN IBC connection running on the local (host) chain should exist.
     chan_end_on_a.verify_connection_hops_length()?;
@@ -146,6 +151,7 @@ mod tests {
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::context::MockContext;
     use crate::mock::router::MockRouter;
+    use crate::signer::Signer;
     use crate::test_utils::DummyTransferModule;
 
     #[test]
@@ -198,6 +204,181 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_chan_close_init_validate_signer_grant() {
+        let client_id = ClientId::new(mock_client_type(), 24).unwrap();
+        let conn_id = ConnectionId::new(2);
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty(Some(0))).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        let granter = Signer::from("granter".to_string());
+        let msg_chan_close_init =
+            MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap();
+
+        let chan_end = ChannelEnd::new(
+            ChannelState::Open,
+            Order::default(),
+            Counterparty::new(
+                msg_chan_close_init.port_id_on_a.clone(),
+                Some(msg_chan_close_init.chan_id_on_a.clone()),
+            ),
+            vec![conn_id.clone()],
+            Version::default(),
+        )
+        .unwrap();
+
+        let context = {
+            let default_context = MockContext::default();
+            let client_consensus_state_height = default_context.host_height().unwrap();
+
+            default_context
+                .with_client(&client_id, client_consensus_state_height)
+                .with_connection(conn_id, conn_end)
+                .with_channel(
+                    msg_chan_close_init.port_id_on_a.clone(),
+                    msg_chan_close_init.chan_id_on_a.clone(),
+                    chan_end,
+                )
+                .with_message_granter(msg_chan_close_init.signer.clone(), granter.clone())
+        };
+
+        let res = validate(&context, &msg_chan_close_init);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(
+                    ChannelError::MissingSignerGrant { .. }
+                ))
+            ),
+            "Validation expected to fail without a grant. Result: {res:?}"
+        );
+
+        let context = context.with_signer_grant(granter, msg_chan_close_init.signer.clone());
+        let res = validate(&context, &msg_chan_close_init);
+        assert!(
+            res.is_ok(),
+            "Validation expected to succeed with a grant. Error: {res:?}"
+        );
+    }
+
+    #[test]
+    fn test_chan_close_init_validate_not_open() {
+        let client_id = ClientId::new(mock_client_type(), 24).unwrap();
+        let conn_id = ConnectionId::new(2);
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty(Some(0))).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        let msg_chan_close_init =
+            MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap();
+
+        // The channel handshake is still in progress.
+        let chan_end = ChannelEnd::new(
+            ChannelState::TryOpen,
+            Order::default(),
+            Counterparty::new(
+                msg_chan_close_init.port_id_on_a.clone(),
+                Some(msg_chan_close_init.chan_id_on_a.clone()),
+            ),
+            vec![conn_id.clone()],
+            Version::default(),
+        )
+        .unwrap();
+
+        let context = {
+            let default_context = MockContext::default();
+            let client_consensus_state_height = default_context.host_height().unwrap();
+
+            default_context
+                .with_client(&client_id, client_consensus_state_height)
+                .with_connection(conn_id, conn_end)
+                .with_channel(
+                    msg_chan_close_init.port_id_on_a.clone(),
+                    msg_chan_close_init.chan_id_on_a.clone(),
+                    chan_end,
+                )
+        };
+
+        let res = validate(&context, &msg_chan_close_init);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(
+                    ChannelError::InvalidState { .. }
+                ))
+            ),
+            "Validation expected to fail on a channel that is not open: {res:?}"
+        );
+    }
+
+    #[test]
+    fn test_chan_close_init_validate_missing_counterparty_channel_id() {
+        let client_id = ClientId::new(mock_client_type(), 24).unwrap();
+        let conn_id = ConnectionId::new(2);
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty(Some(0))).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        let msg_chan_close_init =
+            MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap();
+
+        // Closing the channel requires the counterparty channel id.
+        let chan_end = ChannelEnd::new(
+            ChannelState::Open,
+            Order::default(),
+            Counterparty::new(msg_chan_close_init.port_id_on_a.clone(), None),
+            vec![conn_id.clone()],
+            Version::default(),
+        )
+        .unwrap();
+
+        let context = {
+            let default_context = MockContext::default();
+            let client_consensus_state_height = default_context.host_height().unwrap();
+
+            default_context
+                .with_client(&client_id, client_consensus_state_height)
+                .with_connection(conn_id, conn_end)
+                .with_channel(
+                    msg_chan_close_init.port_id_on_a.clone(),
+                    msg_chan_close_init.chan_id_on_a.clone(),
+                    chan_end,
+                )
+        };
+
+        let res = validate(&context, &msg_chan_close_init);
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(
+                    ChannelError::MissingCounterpartyChannelId {
+                        state: ChannelState::Closed
+                    }
+                ))
+            ),
+            "Validation expected to fail on the missing counterparty channel id: {res:?}"
+        );
+    }
+
     #[test]
     fn test_chan_close_init_execute() {
         let client_id = ClientId::new(mock_client_type(), 24).unwrap();
@@ -7,11 +7,17 @@ use crate::core::events::{IbcEvent, MessageEvent};
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::State;
-use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State as ChannelState};
+use crate::core::ics04_channel::channel::{
+    build_expected_counterparty_channel, Counterparty, State as ChannelState,
+};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::OpenAck;
+use crate::core::ics04_channel::handler::chan_open_try::verify_proven_channel_end;
+use crate::core::ics04_channel::handler::validate_channel_msg_signer;
 use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{ChannelEndPath, ClientConsensusStatePath};
@@ -48,10 +54,15 @@ where
 
     // state changes
     {
+        let conn_end_on_a = ctx_a.connection_end(&chan_end_on_a.connection_hops()[0])?;
+        update_client_on_verified_proofs(ctx_a, conn_end_on_a.client_id())?;
+
         let chan_end_on_a = {
             let mut chan_end_on_a = chan_end_on_a.clone();
 
             chan_end_on_a.set_state(State::Open);
+            // The proof verified in `validate` guarantees that `version_on_b`
+            // is the version chosen by the module on chain B.
             chan_end_on_a.set_version(msg.version_on_b.clone());
             chan_end_on_a.set_counterparty_channel_id(msg.chan_id_on_b.clone());
 
@@ -62,7 +73,13 @@ where
 
     // emit events and logs
     {
-        ctx_a.log_message("success: channel open ack".to_string());
+        ctx_a.log_structured(
+            "channel_open_ack",
+            &[
+                ("port_id", msg.port_id_on_a.to_string()),
+                ("channel_id", msg.chan_id_on_a.to_string()),
+            ],
+        );
 
         let core_event = {
             let port_id_on_b = chan_end_on_a.counterparty().port_id.clone();
@@ -95,7 +112,7 @@ fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelOpenAck) -> Result<(), ContextErro
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_a, &msg.signer)?;
 
     let chan_end_path_on_a = ChannelEndPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
     let chan_end_on_a = ctx_a.channel_end(&chan_end_path_on_a)?;
@@ -135,28 +152,31 @@ where
             },
         )?;
 
-        let expected_chan_end_on_b = ChannelEnd::new(
+        let expected_chan_end_on_b = build_expected_counterparty_channel(
             ChannelState::TryOpen,
             // Note: Both ends of a channel must have the same ordering, so it's
             // fine to use A's ordering here
             *chan_end_on_a.ordering(),
             Counterparty::new(msg.port_id_on_a.clone(), Some(msg.chan_id_on_a.clone())),
-            vec![conn_id_on_b.clone()],
+            conn_id_on_b.clone(),
             msg.version_on_b.clone(),
         )?;
         let chan_end_path_on_b = ChannelEndPath::new(port_id_on_b, &msg.chan_id_on_b);
 
+        verify_proven_channel_end(ctx_a, &msg.proof_chan_end_on_b, &expected_chan_end_on_b)?;
+
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-        client_state_of_b_on_a
-            .verify_membership(
-                prefix_on_b,
-                &msg.proof_chan_end_on_b,
-                consensus_state_of_b_on_a.root(),
-                Path::ChannelEnd(chan_end_path_on_b),
-                expected_chan_end_on_b.encode_vec(),
-            )
-            .map_err(ChannelError::VerifyChannelFailed)?;
+        verify_membership(
+            ctx_a,
+            &client_state_of_b_on_a,
+            prefix_on_b,
+            &msg.proof_chan_end_on_b,
+            consensus_state_of_b_on_a.root(),
+            Path::ChannelEnd(chan_end_path_on_b),
+            expected_chan_end_on_b.encode_vec(),
+        )
+        .map_err(ChannelError::VerifyChannelFailed)?;
     }
 
     Ok(())
@@ -169,6 +189,10 @@ mod tests {
     use rstest::*;
     use test_log::test;
 
+    use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+    use ibc_proto::ics23::commitment_proof::Proof;
+    use ibc_proto::ics23::{CommitmentProof, ExistenceProof};
+
     use crate::applications::transfer::MODULE_ID_STR;
     use crate::core::ics03_connection::connection::ConnectionEnd;
     use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
@@ -179,6 +203,8 @@ mod tests {
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
     use crate::core::ics04_channel::msgs::chan_open_ack::test_util::get_dummy_raw_msg_chan_open_ack;
     use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
     use crate::core::ics24_host::identifier::ClientId;
     use crate::core::ics24_host::identifier::ConnectionId;
     use crate::core::router::ModuleId;
@@ -400,4 +426,172 @@ mod tests {
         ));
         assert!(matches!(context.events[1], IbcEvent::OpenAckChannel(_)));
     }
+
+    #[rstest]
+    fn chan_open_ack_execute_stores_negotiated_version(fixture: Fixture) {
+        let Fixture {
+            context,
+            mut router,
+            module_id,
+            mut msg,
+            client_id_on_a,
+            conn_id_on_a,
+            conn_end_on_a,
+            mut chan_end_on_a,
+            proof_height,
+            ..
+        } = fixture;
+
+        // Chain A proposed the default version, and the module on chain B
+        // upgraded it during `chan_open_try`.
+        chan_end_on_a.set_version(Version::default());
+        msg.version_on_b = Version::new("ics20-2".to_string());
+
+        let mut context = context
+            .with_client(&client_id_on_a, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_a, conn_end_on_a)
+            .with_channel(
+                msg.port_id_on_a.clone(),
+                msg.chan_id_on_a.clone(),
+                chan_end_on_a,
+            );
+
+        let chan_end_path_on_a = ChannelEndPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
+
+        assert!(validate(&context, &msg).is_ok());
+
+        let module = router.get_route_mut(&module_id).unwrap();
+        let res = chan_open_ack_execute(&mut context, module, msg);
+
+        assert!(res.is_ok(), "Execution happy path");
+
+        let chan_end_on_a = context.channel_end(&chan_end_path_on_a).unwrap();
+        assert_eq!(
+            chan_end_on_a.version(),
+            &Version::new("ics20-2".to_string())
+        );
+    }
+
+    /// Returns a Merkle proof attesting to `chan_end_on_b`, which the mock
+    /// client accepts for any channel end.
+    fn proof_of_chan_end_on_b(chan_end_on_b: &ChannelEnd) -> CommitmentProofBytes {
+        let proof = ExistenceProof {
+            value: chan_end_on_b.encode_vec(),
+            ..Default::default()
+        };
+        RawMerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Proof::Exist(proof)),
+            }],
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    #[rstest]
+    fn chan_open_ack_fail_mismatched_counterparty_connection(fixture: Fixture) {
+        let Fixture {
+            context,
+            mut msg,
+            client_id_on_a,
+            conn_id_on_a,
+            conn_end_on_a,
+            chan_end_on_a,
+            proof_height,
+            ..
+        } = fixture;
+
+        let conn_id_on_b = conn_end_on_a
+            .counterparty()
+            .connection_id()
+            .unwrap()
+            .clone();
+        let other_conn_id = ConnectionId::new(42);
+        assert_ne!(conn_id_on_b, other_conn_id);
+
+        // The counterparty end on B is bound to another connection than the
+        // counterparty of A's connection.
+        let chan_end_on_b = ChannelEnd::new(
+            State::TryOpen,
+            *chan_end_on_a.ordering(),
+            Counterparty::new(msg.port_id_on_a.clone(), Some(msg.chan_id_on_a.clone())),
+            vec![other_conn_id.clone()],
+            msg.version_on_b.clone(),
+        )
+        .unwrap();
+        msg.proof_chan_end_on_b = proof_of_chan_end_on_b(&chan_end_on_b);
+
+        let context = context
+            .with_client(&client_id_on_a, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_a, conn_end_on_a)
+            .with_channel(
+                msg.port_id_on_a.clone(),
+                msg.chan_id_on_a.clone(),
+                chan_end_on_a,
+            );
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            matches!(
+                &res,
+                Err(ContextError::ChannelError(ChannelError::MismatchedCounterpartyConnection {
+                    expected,
+                    actual,
+                })) if expected == &conn_id_on_b && actual == &other_conn_id
+            ),
+            "Validation fails when the counterparty end is bound to another connection. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn chan_open_ack_fail_ordering_mismatch(fixture: Fixture) {
+        let Fixture {
+            context,
+            mut msg,
+            client_id_on_a,
+            conn_id_on_a,
+            conn_end_on_a,
+            chan_end_on_a,
+            proof_height,
+            ..
+        } = fixture;
+
+        // The counterparty end on B claims `Ordered`, while A's end is `Unordered`.
+        let chan_end_on_b = ChannelEnd::new(
+            State::TryOpen,
+            Order::Ordered,
+            Counterparty::new(msg.port_id_on_a.clone(), Some(msg.chan_id_on_a.clone())),
+            vec![conn_end_on_a
+                .counterparty()
+                .connection_id()
+                .unwrap()
+                .clone()],
+            msg.version_on_b.clone(),
+        )
+        .unwrap();
+        msg.proof_chan_end_on_b = proof_of_chan_end_on_b(&chan_end_on_b);
+
+        let context = context
+            .with_client(&client_id_on_a, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_a, conn_end_on_a)
+            .with_channel(
+                msg.port_id_on_a.clone(),
+                msg.chan_id_on_a.clone(),
+                chan_end_on_a,
+            );
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(ChannelError::OrderingMismatch {
+                    local: Order::Unordered,
+                    counterparty: Order::Ordered,
+                }))
+            ),
+            "Validation fails when the counterparty end has another ordering. got: {res:?}"
+        )
+    }
 }
@@ -7,15 +7,21 @@ use ibc_proto::protobuf::Protobuf;
 use crate::core::events::{IbcEvent, MessageEvent};
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
-use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership_within;
+use crate::core::ics03_connection::connection::{ConnectionEnd, State as ConnectionState};
 use crate::core::ics04_channel::channel::State;
-use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State as ChannelState};
+use crate::core::ics04_channel::channel::{
+    build_expected_counterparty_channel, ChannelEnd, Counterparty, State as ChannelState,
+};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::OpenConfirm;
+use crate::core::ics04_channel::handler::validate_channel_msg_signer;
 use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{ChannelEndPath, ClientConsensusStatePath};
-use crate::core::router::Module;
+use crate::core::router::{Module, ModuleExtras};
 use crate::core::{ContextError, ExecutionContext, ValidationContext};
 
 pub(crate) fn chan_open_confirm_validate<ValCtx>(
@@ -47,46 +53,20 @@ where
 
     // state changes
     {
-        let chan_end_on_b = {
-            let mut chan_end_on_b = chan_end_on_b.clone();
-            chan_end_on_b.set_state(State::Open);
+        let conn_end_on_b = ctx_b.connection_end(&chan_end_on_b.connection_hops()[0])?;
+        update_client_on_verified_proofs(ctx_b, conn_end_on_b.client_id())?;
 
-            chan_end_on_b
-        };
-        ctx_b.store_channel(&chan_end_path_on_b, chan_end_on_b)?;
+        ctx_b.store_channel(&chan_end_path_on_b, opened_chan_end(&chan_end_on_b))?;
     }
 
     // emit events and logs
     {
-        ctx_b.log_message("success: channel open confirm".to_string());
-
-        let conn_id_on_b = chan_end_on_b.connection_hops[0].clone();
-        let port_id_on_a = chan_end_on_b.counterparty().port_id.clone();
-        let chan_id_on_a = chan_end_on_b
-            .counterparty()
-            .channel_id
-            .clone()
-            .ok_or(ContextError::ChannelError(ChannelError::Other {
-            description:
-                "internal error: ChannelEnd doesn't have a counterparty channel id in OpenConfirm"
-                    .to_string(),
-        }))?;
-
-        let core_event = IbcEvent::OpenConfirmChannel(OpenConfirm::new(
-            msg.port_id_on_b.clone(),
-            msg.chan_id_on_b.clone(),
-            port_id_on_a,
-            chan_id_on_a,
-            conn_id_on_b,
-        ));
-        ctx_b.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel));
-        ctx_b.emit_ibc_event(core_event);
+        ctx_b.log_structured("channel_open_confirm", &log_fields(&msg));
 
-        for module_event in extras.events {
-            ctx_b.emit_ibc_event(IbcEvent::Module(module_event));
-        }
+        let (events, logs) = events_and_logs(&msg, &chan_end_on_b, extras)?;
+        ctx_b.emit_ibc_events(events);
 
-        for log_message in extras.log {
+        for log_message in logs {
             ctx_b.log_message(log_message);
         }
     }
@@ -98,71 +78,287 @@ fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgChannelOpenConfirm) -> Result<(), Context
 where
     Ctx: ValidationContext,
 {
-    ctx_b.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_b, &msg.signer)?;
 
     // Unwrap the old channel end and validate it against the message.
     let chan_end_path_on_b = ChannelEndPath::new(&msg.port_id_on_b, &msg.chan_id_on_b);
     let chan_end_on_b = ctx_b.channel_end(&chan_end_path_on_b)?;
-
-    // Validate that the channel end is in a state where it can be confirmed.
-    chan_end_on_b.verify_state_matches(&ChannelState::TryOpen)?;
-
-    // An OPEN IBC connection running on the local (host) chain should exist.
-    chan_end_on_b.verify_connection_hops_length()?;
+    verify_chan_end_on_b(&chan_end_on_b)?;
 
     let conn_end_on_b = ctx_b.connection_end(&chan_end_on_b.connection_hops()[0])?;
-
     conn_end_on_b.verify_state_matches(&ConnectionState::Open)?;
 
     // Verify proofs
     {
         let client_id_on_b = conn_end_on_b.client_id();
         let client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
-
-        {
-            let status = client_state_of_a_on_b
-                .status(ctx_b.get_client_validation_context(), client_id_on_b)?;
-            if !status.is_active() {
-                return Err(ClientError::ClientNotActive { status }.into());
-            }
-        }
-        client_state_of_a_on_b.validate_proof_height(msg.proof_height_on_a)?;
+        verify_client_of_a_on_b(
+            ctx_b.get_client_validation_context(),
+            client_id_on_b,
+            &client_state_of_a_on_b,
+            msg,
+        )?;
 
         let client_cons_state_path_on_b =
             ClientConsensusStatePath::new(client_id_on_b, &msg.proof_height_on_a);
         let consensus_state_of_a_on_b = ctx_b.consensus_state(&client_cons_state_path_on_b)?;
-        let prefix_on_a = conn_end_on_b.counterparty().prefix();
-        let port_id_on_a = &chan_end_on_b.counterparty().port_id;
-        let chan_id_on_a = chan_end_on_b
-            .counterparty()
-            .channel_id()
-            .ok_or(ChannelError::MissingCounterparty)?;
-        let conn_id_on_a = conn_end_on_b.counterparty().connection_id().ok_or(
-            ChannelError::UndefinedConnectionCounterparty {
-                connection_id: chan_end_on_b.connection_hops()[0].clone(),
-            },
+        verify_chan_end_on_a(
+            ctx_b.get_client_validation_context(),
+            &client_state_of_a_on_b,
+            &consensus_state_of_a_on_b,
+            ctx_b.max_proof_size(),
+            msg,
+            &chan_end_on_b,
+            &conn_end_on_b,
         )?;
+    }
 
-        let expected_chan_end_on_a = ChannelEnd::new(
-            ChannelState::Open,
-            *chan_end_on_b.ordering(),
-            Counterparty::new(msg.port_id_on_b.clone(), Some(msg.chan_id_on_b.clone())),
-            vec![conn_id_on_a.clone()],
-            chan_end_on_b.version.clone(),
+    Ok(())
+}
+
+/// Validates that the channel end on B is in a state where it can be
+/// confirmed, over a single connection hop.
+fn verify_chan_end_on_b(chan_end_on_b: &ChannelEnd) -> Result<(), ChannelError> {
+    chan_end_on_b.verify_state_matches(&ChannelState::TryOpen)?;
+    chan_end_on_b.verify_counterparty_channel_id(&ChannelState::Open)?;
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    chan_end_on_b.verify_connection_hops_length()
+}
+
+/// Validates that the client of A on B is active and can verify the proof of
+/// the message.
+fn verify_client_of_a_on_b<V, CS>(
+    client_validation_ctx: &V,
+    client_id_on_b: &ClientId,
+    client_state_of_a_on_b: &CS,
+    msg: &MsgChannelOpenConfirm,
+) -> Result<(), ContextError>
+where
+    CS: ClientStateCommon + ClientStateValidation<V>,
+{
+    let status = client_state_of_a_on_b.status(client_validation_ctx, client_id_on_b)?;
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive { status }.into());
+    }
+    client_state_of_a_on_b.validate_proof_height(msg.proof_height_on_a)?;
+
+    Ok(())
+}
+
+/// Verifies the proof that the channel end on A is open and points to the
+/// channel end on B.
+fn verify_chan_end_on_a<V, CS>(
+    client_validation_ctx: &V,
+    client_state_of_a_on_b: &CS,
+    consensus_state_of_a_on_b: &impl ConsensusState,
+    max_proof_size: usize,
+    msg: &MsgChannelOpenConfirm,
+    chan_end_on_b: &ChannelEnd,
+    conn_end_on_b: &ConnectionEnd,
+) -> Result<(), ContextError>
+where
+    CS: ClientStateCommon + ClientStateValidation<V>,
+{
+    let prefix_on_a = conn_end_on_b.counterparty().prefix();
+    let port_id_on_a = &chan_end_on_b.counterparty().port_id;
+    let chan_id_on_a = chan_end_on_b.counterparty().channel_id().ok_or(
+        ChannelError::MissingCounterpartyChannelId {
+            state: ChannelState::Open,
+        },
+    )?;
+    let conn_id_on_a = conn_end_on_b.counterparty().connection_id().ok_or(
+        ChannelError::UndefinedConnectionCounterparty {
+            connection_id: chan_end_on_b.connection_hops()[0].clone(),
+        },
+    )?;
+
+    let expected_chan_end_on_a = build_expected_counterparty_channel(
+        ChannelState::Open,
+        *chan_end_on_b.ordering(),
+        Counterparty::new(msg.port_id_on_b.clone(), Some(msg.chan_id_on_b.clone())),
+        conn_id_on_a.clone(),
+        chan_end_on_b.version.clone(),
+    )?;
+    let chan_end_path_on_a = ChannelEndPath::new(port_id_on_a, chan_id_on_a);
+
+    // Verify the proof for the channel state against the expected channel end.
+    // A counterparty channel id of None in not possible, and is checked in msg.
+    verify_membership_within(
+        client_validation_ctx,
+        client_state_of_a_on_b,
+        max_proof_size,
+        prefix_on_a,
+        &msg.proof_chan_end_on_a,
+        consensus_state_of_a_on_b.root(),
+        Path::ChannelEnd(chan_end_path_on_a),
+        expected_chan_end_on_a.encode_vec(),
+    )
+    .map_err(ChannelError::VerifyChannelFailed)?;
+
+    Ok(())
+}
+
+/// Returns the channel end on B, opened.
+fn opened_chan_end(chan_end_on_b: &ChannelEnd) -> ChannelEnd {
+    let mut chan_end_on_b = chan_end_on_b.clone();
+    chan_end_on_b.set_state(State::Open);
+
+    chan_end_on_b
+}
+
+fn log_fields(msg: &MsgChannelOpenConfirm) -> [(&'static str, String); 2] {
+    [
+        ("port_id", msg.port_id_on_b.to_string()),
+        ("channel_id", msg.chan_id_on_b.to_string()),
+    ]
+}
+
+/// Returns the events to emit and the messages to log once the channel end
+/// on B is opened.
+fn events_and_logs(
+    msg: &MsgChannelOpenConfirm,
+    chan_end_on_b: &ChannelEnd,
+    extras: ModuleExtras,
+) -> Result<(Vec<IbcEvent>, Vec<String>), ChannelError> {
+    let conn_id_on_b = chan_end_on_b.connection_hops[0].clone();
+    let port_id_on_a = chan_end_on_b.counterparty().port_id.clone();
+    let chan_id_on_a = chan_end_on_b
+        .counterparty()
+        .channel_id
+        .clone()
+        .ok_or(ChannelError::MissingCounterpartyChannelId { state: State::Open })?;
+
+    let core_event = IbcEvent::OpenConfirmChannel(OpenConfirm::new(
+        msg.port_id_on_b.clone(),
+        msg.chan_id_on_b.clone(),
+        port_id_on_a,
+        chan_id_on_a,
+        conn_id_on_b,
+    ));
+    let mut events = vec![IbcEvent::Message(MessageEvent::Channel), core_event];
+    events.extend(extras.events.into_iter().map(IbcEvent::Module));
+
+    Ok((events, extras.log))
+}
+
+/// Validates a `MsgChannelOpenConfirm` against a host with async storage.
+/// Performs the same checks as the synchronous handler.
+#[cfg(feature = "async")]
+pub async fn chan_open_confirm_validate_async<ValCtx>(
+    ctx_b: &ValCtx,
+    module: &dyn Module,
+    msg: MsgChannelOpenConfirm,
+) -> Result<(), ContextError>
+where
+    ValCtx: crate::core::AsyncValidationContext,
+{
+    validate_async(ctx_b, &msg).await?;
+
+    module.on_chan_open_confirm_validate(&msg.port_id_on_b, &msg.chan_id_on_b)?;
+
+    Ok(())
+}
+
+/// Executes a `MsgChannelOpenConfirm` against a host with async storage.
+/// Applies the same state changes and emits the same events as the
+/// synchronous handler.
+#[cfg(feature = "async")]
+pub async fn chan_open_confirm_execute_async<ExecCtx>(
+    ctx_b: &mut ExecCtx,
+    module: &mut dyn Module,
+    msg: MsgChannelOpenConfirm,
+) -> Result<(), ContextError>
+where
+    ExecCtx: crate::core::AsyncExecutionContext,
+{
+    let extras = module.on_chan_open_confirm_execute(&msg.port_id_on_b, &msg.chan_id_on_b)?;
+    let chan_end_path_on_b = ChannelEndPath::new(&msg.port_id_on_b, &msg.chan_id_on_b);
+    let chan_end_on_b = ctx_b.channel_end(&chan_end_path_on_b).await?;
+
+    // state changes
+    {
+        use crate::core::ics02_client::client_state::ClientStateExecution;
+
+        let conn_end_on_b = ctx_b
+            .connection_end(&chan_end_on_b.connection_hops()[0])
+            .await?;
+        let client_id_on_b = conn_end_on_b.client_id();
+        let client_state_of_a_on_b = ctx_b.client_state(client_id_on_b).await?;
+        client_state_of_a_on_b.update_state_on_verified_proofs(
+            ctx_b.get_client_execution_context(),
+            client_id_on_b,
+        )?;
+
+        ctx_b
+            .store_channel(&chan_end_path_on_b, opened_chan_end(&chan_end_on_b))
+            .await?;
+    }
+
+    // emit events and logs
+    {
+        ctx_b.log_structured("channel_open_confirm", &log_fields(&msg));
+
+        let (events, logs) = events_and_logs(&msg, &chan_end_on_b, extras)?;
+        ctx_b.emit_ibc_events(events);
+
+        for log_message in logs {
+            ctx_b.log_message(log_message);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+async fn validate_async<Ctx>(ctx_b: &Ctx, msg: &MsgChannelOpenConfirm) -> Result<(), ContextError>
+where
+    Ctx: crate::core::AsyncValidationContext,
+{
+    let granter = ctx_b.message_granter(&msg.signer);
+    crate::core::ics04_channel::handler::validate_signer_prefix(
+        ctx_b.signer_bech32_prefix(),
+        &msg.signer,
+        granter.as_ref(),
+    )?;
+    match granter {
+        Some(granter) => ctx_b.validate_signer_with_grant(&msg.signer, &granter)?,
+        None => ctx_b.validate_message_signer(&msg.signer)?,
+    }
+
+    // Unwrap the old channel end and validate it against the message.
+    let chan_end_path_on_b = ChannelEndPath::new(&msg.port_id_on_b, &msg.chan_id_on_b);
+    let chan_end_on_b = ctx_b.channel_end(&chan_end_path_on_b).await?;
+    verify_chan_end_on_b(&chan_end_on_b)?;
+
+    let conn_end_on_b = ctx_b
+        .connection_end(&chan_end_on_b.connection_hops()[0])
+        .await?;
+    conn_end_on_b.verify_state_matches(&ConnectionState::Open)?;
+
+    // Verify proofs
+    {
+        let client_id_on_b = conn_end_on_b.client_id();
+        let client_state_of_a_on_b = ctx_b.client_state(client_id_on_b).await?;
+        verify_client_of_a_on_b(
+            ctx_b.get_client_validation_context(),
+            client_id_on_b,
+            &client_state_of_a_on_b,
+            msg,
+        )?;
+
+        let client_cons_state_path_on_b =
+            ClientConsensusStatePath::new(client_id_on_b, &msg.proof_height_on_a);
+        let consensus_state_of_a_on_b = ctx_b.consensus_state(&client_cons_state_path_on_b).await?;
+        verify_chan_end_on_a(
+            ctx_b.get_client_validation_context(),
+            &client_state_of_a_on_b,
+            &consensus_state_of_a_on_b,
+            ctx_b.max_proof_size(),
+            msg,
+            &chan_end_on_b,
+            &conn_end_on_b,
         )?;
-        let chan_end_path_on_a = ChannelEndPath::new(port_id_on_a, chan_id_on_a);
-
-        // Verify the proof for the channel state against the expected channel end.
-        // A counterparty channel id of None in not possible, and is checked in msg.
-        client_state_of_a_on_b
-            .verify_membership(
-                prefix_on_a,
-                &msg.proof_chan_end_on_a,
-                consensus_state_of_a_on_b.root(),
-                Path::ChannelEnd(chan_end_path_on_a),
-                expected_chan_end_on_a.encode_vec(),
-            )
-            .map_err(ChannelError::VerifyChannelFailed)?;
     }
 
     Ok(())
@@ -183,22 +379,27 @@ mod tests {
     use crate::core::ics04_channel::msgs::chan_open_confirm::test_util::get_dummy_raw_msg_chan_open_confirm;
     use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
     use crate::core::ics04_channel::Version;
+    use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::core::ics23_commitment::error::CommitmentError;
     use crate::core::ics24_host::identifier::ChannelId;
-    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
-    use crate::core::router::ModuleId;
-    use crate::core::router::Router;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId, PortId};
     use crate::core::timestamp::ZERO_DURATION;
     use crate::Height;
 
+    use crate::clients::ics06_solomachine::client_state::test_util::{prove, public_key};
+    use crate::clients::ics06_solomachine::client_state::ClientState as SoloMachineClientState;
+    use crate::clients::ics06_solomachine::client_type as solomachine_client_type;
+    use crate::clients::ics06_solomachine::consensus_state::ConsensusState as SoloMachineConsensusState;
+    use crate::clients::ics09_localhost::client_type as localhost_client_type;
+    use crate::core::ics02_client::ClientExecutionContext;
+    use crate::core::ics24_host::path::ClientStatePath;
+    use crate::core::timestamp::Timestamp;
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::context::MockContext;
-    use crate::mock::router::MockRouter;
-    use crate::{applications::transfer::MODULE_ID_STR, test_utils::DummyTransferModule};
+    use crate::test_utils::{CallbackKind, DummyTransferModule, RecordingModule};
 
     pub struct Fixture {
         pub context: MockContext,
-        pub router: MockRouter,
-        pub module_id: ModuleId,
         pub msg: MsgChannelOpenConfirm,
         pub client_id_on_b: ClientId,
         pub conn_id_on_b: ConnectionId,
@@ -212,12 +413,6 @@ mod tests {
         let proof_height = 10;
         let context = MockContext::default();
 
-        let module_id: ModuleId = ModuleId::new(MODULE_ID_STR.to_string());
-        let mut router = MockRouter::default();
-        router
-            .add_route(module_id.clone(), DummyTransferModule::new())
-            .unwrap();
-
         let client_id_on_b = ClientId::new(mock_client_type(), 45).unwrap();
         let conn_id_on_b = ConnectionId::new(2);
         let conn_end_on_b = ConnectionEnd::new(
@@ -244,8 +439,6 @@ mod tests {
 
         Fixture {
             context,
-            router,
-            module_id,
             msg,
             client_id_on_b,
             conn_id_on_b,
@@ -315,6 +508,48 @@ mod tests {
         )
     }
 
+    #[rstest]
+    fn chan_open_confirm_fail_missing_counterparty_channel_id(fixture: Fixture) {
+        let Fixture {
+            context,
+            msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let chan_end_on_b = ChannelEnd::new(
+            State::TryOpen,
+            Order::Unordered,
+            Counterparty::new(msg.port_id_on_b.clone(), None),
+            vec![conn_id_on_b.clone()],
+            Version::default(),
+        )
+        .unwrap();
+        let context = context
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b)
+            .with_channel(
+                msg.port_id_on_b.clone(),
+                ChannelId::default(),
+                chan_end_on_b,
+            );
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(
+                    ChannelError::MissingCounterpartyChannelId { state: State::Open }
+                ))
+            ),
+            "Validation fails because the counterparty channel id is missing: {res:?}"
+        )
+    }
+
     #[rstest]
     fn chan_open_confirm_validate_happy_path(fixture: Fixture) {
         let Fixture {
@@ -342,12 +577,93 @@ mod tests {
         assert!(res.is_ok(), "Validation happy path")
     }
 
+    #[rstest]
+    fn chan_open_confirm_fail_proof_verification(fixture: Fixture) {
+        let Fixture {
+            context,
+            msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            chan_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let context = context
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b)
+            .with_channel(
+                msg.port_id_on_b.clone(),
+                ChannelId::default(),
+                chan_end_on_b,
+            )
+            .with_verification_result(false);
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(
+                    ChannelError::VerifyChannelFailed(ClientError::Ics23Verification(
+                        CommitmentError::VerificationFailure
+                    ))
+                ))
+            ),
+            "Validation fails because the channel end proof is rejected"
+        )
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    async fn chan_open_confirm_async_happy_path(fixture: Fixture) {
+        let Fixture {
+            context,
+            msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            chan_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let mut context = context
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b)
+            .with_channel(
+                msg.port_id_on_b.clone(),
+                ChannelId::default(),
+                chan_end_on_b,
+            );
+
+        let chan_end_path_on_b = ChannelEndPath::new(&msg.port_id_on_b, &msg.chan_id_on_b);
+        let mut module = RecordingModule::new(DummyTransferModule::new());
+
+        chan_open_confirm_validate_async(&context, &module, msg.clone())
+            .await
+            .unwrap();
+        chan_open_confirm_execute_async(&mut context, &mut module, msg)
+            .await
+            .unwrap();
+
+        assert_eq!(module.callbacks(), &[CallbackKind::OpenConfirm]);
+        assert_eq!(context.events.len(), 2);
+        assert!(matches!(context.events[1], IbcEvent::OpenConfirmChannel(_)));
+        assert_eq!(
+            ValidationContext::channel_end(&context, &chan_end_path_on_b)
+                .unwrap()
+                .state,
+            State::Open
+        );
+    }
+
     #[rstest]
     fn chan_open_confirm_execute_happy_path(fixture: Fixture) {
         let Fixture {
             context,
-            mut router,
-            module_id,
             msg,
             client_id_on_b,
             conn_id_on_b,
@@ -366,16 +682,333 @@ mod tests {
                 chan_end_on_b,
             );
 
-        let module = router.get_route_mut(&module_id).unwrap();
-        let res = chan_open_confirm_execute(&mut context, module, msg);
+        let port_id_on_b = msg.port_id_on_b.clone();
+        let chan_id_on_b = msg.chan_id_on_b.clone();
+
+        let mut module = RecordingModule::new(DummyTransferModule::new());
+        let res = chan_open_confirm_execute(&mut context, &mut module, msg);
 
         assert!(res.is_ok(), "Execution happy path");
+        assert_eq!(module.callbacks(), &[CallbackKind::OpenConfirm]);
 
         assert_eq!(context.events.len(), 2);
         assert!(matches!(
             context.events[0],
             IbcEvent::Message(MessageEvent::Channel)
         ));
-        assert!(matches!(context.events[1], IbcEvent::OpenConfirmChannel(_)));
+        let open_confirm = context.events[1].as_open_confirm_channel().unwrap();
+        assert_eq!(open_confirm.port_id_on_b(), &port_id_on_b);
+        assert_eq!(open_confirm.chan_id_on_b(), &chan_id_on_b);
+
+        assert_eq!(
+            context.structured_logs,
+            vec![(
+                "channel_open_confirm".to_string(),
+                vec![
+                    ("port_id".to_string(), port_id_on_b.to_string()),
+                    ("channel_id".to_string(), chan_id_on_b.to_string()),
+                ]
+            )]
+        );
+        assert_eq!(
+            context.logs,
+            vec![format!(
+                "channel_open_confirm port_id={port_id_on_b} channel_id={chan_id_on_b}"
+            )]
+        );
+    }
+
+    /// Sets up both ends of a channel on the same chain, connected through a
+    /// localhost client: end B is in `TryOpen`, and end A is in `chan_state_on_a`.
+    fn localhost_context(fixture: &Fixture, chan_state_on_a: State) -> MockContext {
+        let Fixture {
+            context,
+            msg,
+            conn_id_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let client_id = ClientId::new(localhost_client_type(), 0).unwrap();
+        let conn_id_on_a = ConnectionId::new(3);
+        let port_id_on_a = PortId::transfer();
+        let chan_id_on_a = ChannelId::new(1);
+
+        let conn_end = |counterparty_conn_id: &ConnectionId| {
+            ConnectionEnd::new(
+                ConnectionState::Open,
+                client_id.clone(),
+                ConnectionCounterparty::new(
+                    client_id.clone(),
+                    Some(counterparty_conn_id.clone()),
+                    context.commitment_prefix(),
+                ),
+                get_compatible_versions(),
+                ZERO_DURATION,
+            )
+            .unwrap()
+        };
+
+        let chan_end_on_a = ChannelEnd::new(
+            chan_state_on_a,
+            Order::Unordered,
+            Counterparty::new(msg.port_id_on_b.clone(), Some(msg.chan_id_on_b.clone())),
+            vec![conn_id_on_a.clone()],
+            Version::default(),
+        )
+        .unwrap();
+        let chan_end_on_b = ChannelEnd::new(
+            State::TryOpen,
+            Order::Unordered,
+            Counterparty::new(port_id_on_a.clone(), Some(chan_id_on_a.clone())),
+            vec![conn_id_on_b.clone()],
+            Version::default(),
+        )
+        .unwrap();
+
+        context
+            .clone()
+            .with_client_parametrized(
+                &client_id,
+                Height::new(0, *proof_height).unwrap(),
+                Some(localhost_client_type()),
+                None,
+            )
+            .with_connection(conn_id_on_a.clone(), conn_end(conn_id_on_b))
+            .with_connection(conn_id_on_b.clone(), conn_end(&conn_id_on_a))
+            .with_channel(port_id_on_a, chan_id_on_a, chan_end_on_a)
+            .with_channel(
+                msg.port_id_on_b.clone(),
+                msg.chan_id_on_b.clone(),
+                chan_end_on_b,
+            )
+    }
+
+    /// The events are emitted in a single batch
+    #[rstest]
+    fn chan_open_confirm_execute_emits_events_in_batch(fixture: Fixture) {
+        let Fixture {
+            context,
+            msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            chan_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let expected_events = vec![
+            IbcEvent::Message(MessageEvent::Channel),
+            IbcEvent::OpenConfirmChannel(OpenConfirm::new(
+                msg.port_id_on_b.clone(),
+                ChannelId::default(),
+                chan_end_on_b.counterparty().port_id().clone(),
+                chan_end_on_b.counterparty().channel_id().unwrap().clone(),
+                conn_id_on_b.clone(),
+            )),
+        ];
+        let mut context = context
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b)
+            .with_channel(
+                msg.port_id_on_b.clone(),
+                ChannelId::default(),
+                chan_end_on_b,
+            );
+
+        let mut module = RecordingModule::new(DummyTransferModule::new());
+        chan_open_confirm_execute(&mut context, &mut module, msg).unwrap();
+
+        assert_eq!(context.event_batches, vec![2]);
+        assert_eq!(context.events, expected_events);
+        let sequences: Vec<u64> = context
+            .sequenced_events
+            .iter()
+            .map(|event| event.sequence)
+            .collect();
+        assert_eq!(sequences, vec![0, 1]);
+    }
+
+    #[rstest]
+    fn chan_open_confirm_validate_localhost(fixture: Fixture) {
+        let context = localhost_context(&fixture, State::Open);
+
+        let res = validate(&context, &fixture.msg);
+
+        assert!(
+            res.is_ok(),
+            "Validation succeeds against the host's own store: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn chan_open_confirm_fail_localhost_counterparty_not_open(fixture: Fixture) {
+        let context = localhost_context(&fixture, State::TryOpen);
+
+        let res = validate(&context, &fixture.msg);
+
+        assert!(
+            res.is_err(),
+            "Validation fails because the counterparty channel stored on the host is not open"
+        )
+    }
+
+    fn custom_prefix() -> CommitmentPrefix {
+        CommitmentPrefix::try_from(b"custom-store".to_vec()).unwrap()
+    }
+
+    #[rstest]
+    fn chan_open_confirm_validate_localhost_custom_prefix(mut fixture: Fixture) {
+        fixture.context = fixture.context.with_commitment_prefix(custom_prefix());
+        let context = localhost_context(&fixture, State::Open);
+
+        let conn_end_on_b = context.connection_end(&fixture.conn_id_on_b).unwrap();
+        assert_eq!(conn_end_on_b.counterparty().prefix(), &custom_prefix());
+
+        let res = validate(&context, &fixture.msg);
+
+        assert!(
+            res.is_ok(),
+            "Validation succeeds when the counterparty prefix is the host's custom prefix: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn chan_open_confirm_fail_localhost_prefix_mismatch(fixture: Fixture) {
+        let context =
+            localhost_context(&fixture, State::Open).with_commitment_prefix(custom_prefix());
+
+        let res = validate(&context, &fixture.msg);
+
+        assert!(
+            res.is_err(),
+            "Validation fails because the counterparty prefix is not the host's prefix"
+        )
+    }
+
+    #[rstest]
+    fn chan_open_confirm_localhost_custom_prefix_opens_channel(mut fixture: Fixture) {
+        fixture.context = fixture.context.with_commitment_prefix(custom_prefix());
+        let mut context = localhost_context(&fixture, State::Open);
+        let msg = fixture.msg;
+
+        let mut module = DummyTransferModule::new();
+        let res = chan_open_confirm_validate(&context, &module, msg.clone());
+        assert!(res.is_ok(), "Validation succeeds: {res:?}");
+        let res = chan_open_confirm_execute(&mut context, &mut module, msg.clone());
+        assert!(res.is_ok(), "Execution succeeds: {res:?}");
+
+        let chan_end_on_b = context
+            .channel_end(&ChannelEndPath::new(&msg.port_id_on_b, &msg.chan_id_on_b))
+            .unwrap();
+        assert_eq!(chan_end_on_b.state, State::Open);
+
+        let open_confirm = context.events[1].as_open_confirm_channel().unwrap();
+        assert_eq!(open_confirm.port_id_on_a(), &PortId::transfer());
+        assert_eq!(open_confirm.chan_id_on_a(), &ChannelId::new(1));
+    }
+
+    /// The solo machine client moves to its next sequence once the handler
+    /// verified its proof, so that the proof cannot be used again.
+    #[rstest]
+    fn chan_open_confirm_solomachine_consumes_proof(fixture: Fixture) {
+        let Fixture {
+            context,
+            mut msg,
+            conn_id_on_b,
+            ..
+        } = fixture;
+
+        let key = ed25519_consensus::SigningKey::from([1; 32]);
+        let client_id = ClientId::new(solomachine_client_type(), 0).unwrap();
+        let consensus_state = SoloMachineConsensusState::new(
+            public_key(&key),
+            "wallet".to_string(),
+            Timestamp::from_nanoseconds(10).unwrap(),
+        );
+        let client_state = SoloMachineClientState::new(1, consensus_state.clone()).unwrap();
+
+        let conn_id_on_a = ConnectionId::new(3);
+        let port_id_on_a = PortId::transfer();
+        let chan_id_on_a = ChannelId::new(1);
+        let prefix_on_a = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let conn_end_on_b = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(conn_id_on_a.clone()),
+                prefix_on_a.clone(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+        let chan_end_on_b = ChannelEnd::new(
+            State::TryOpen,
+            Order::Unordered,
+            Counterparty::new(port_id_on_a.clone(), Some(chan_id_on_a.clone())),
+            vec![conn_id_on_b.clone()],
+            Version::default(),
+        )
+        .unwrap();
+        let chan_end_on_a = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(msg.port_id_on_b.clone(), Some(msg.chan_id_on_b.clone())),
+            vec![conn_id_on_a],
+            Version::default(),
+        )
+        .unwrap();
+
+        let mut context = context
+            .with_connection(conn_id_on_b, conn_end_on_b)
+            .with_channel(
+                msg.port_id_on_b.clone(),
+                msg.chan_id_on_b.clone(),
+                chan_end_on_b,
+            );
+        context
+            .store_client_state(
+                ClientStatePath::new(&client_id),
+                client_state.clone().into(),
+            )
+            .unwrap();
+        context
+            .store_consensus_state(
+                ClientConsensusStatePath::new(&client_id, &client_state.latest_height()),
+                consensus_state.into(),
+            )
+            .unwrap();
+
+        msg.proof_height_on_a = client_state.latest_height();
+        msg.proof_chan_end_on_a = prove(
+            &key,
+            &client_state,
+            &prefix_on_a,
+            ChannelEndPath::new(&port_id_on_a, &chan_id_on_a).into(),
+            chan_end_on_a.encode_vec(),
+        );
+
+        let mut module = DummyTransferModule::new();
+        let res = chan_open_confirm_validate(&context, &module, msg.clone());
+        assert!(res.is_ok(), "Validation succeeds: {res:?}");
+        let res = chan_open_confirm_execute(&mut context, &mut module, msg.clone());
+        assert!(res.is_ok(), "Execution succeeds: {res:?}");
+
+        let chan_end_on_b = context
+            .channel_end(&ChannelEndPath::new(&msg.port_id_on_b, &msg.chan_id_on_b))
+            .unwrap();
+        assert_eq!(chan_end_on_b.state, State::Open);
+
+        let new_client_state = context.client_state(&client_id).unwrap();
+        assert_eq!(new_client_state.latest_height(), Height::new(0, 2).unwrap());
+        assert!(
+            new_client_state
+                .validate_proof_height(msg.proof_height_on_a)
+                .is_err(),
+            "the proof height of the consumed proof is still accepted"
+        );
     }
 }
@@ -7,8 +7,8 @@ use crate::core::events::{IbcEvent, MessageEvent};
 use crate::core::ics02_client::client_state::ClientStateValidation;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
 use crate::core::ics04_channel::events::OpenInit;
+use crate::core::ics04_channel::handler::validate_channel_msg_signer;
 use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
-use crate::core::ics24_host::identifier::ChannelId;
 use crate::core::ics24_host::path::{ChannelEndPath, SeqAckPath, SeqRecvPath, SeqSendPath};
 use crate::core::router::Module;
 use crate::core::{ContextError, ExecutionContext, ValidationContext};
@@ -22,7 +22,7 @@ where
     ValCtx: ValidationContext,
 {
     validate(ctx_a, &msg)?;
-    let chan_id_on_a = ChannelId::new(ctx_a.channel_counter()?);
+    let chan_id_on_a = ctx_a.next_channel_identifier(&msg.port_id_on_a)?;
 
     module.on_chan_open_init_validate(
         msg.ordering,
@@ -44,7 +44,7 @@ pub(crate) fn chan_open_init_execute<ExecCtx>(
 where
     ExecCtx: ExecutionContext,
 {
-    let chan_id_on_a = ChannelId::new(ctx_a.channel_counter()?);
+    let chan_id_on_a = ctx_a.generate_channel_identifier(&msg.port_id_on_a)?;
     let (extras, version) = module.on_chan_open_init_execute(
         msg.ordering,
         &msg.connection_hops_on_a,
@@ -63,13 +63,11 @@ where
             msg.ordering,
             Counterparty::new(msg.port_id_on_b.clone(), None),
             msg.connection_hops_on_a.clone(),
-            msg.version_proposal.clone(),
+            version.clone(),
         )?;
         let chan_end_path_on_a = ChannelEndPath::new(&msg.port_id_on_a, &chan_id_on_a);
         ctx_a.store_channel(&chan_end_path_on_a, chan_end_on_a)?;
 
-        ctx_a.increase_channel_counter();
-
         // Initialize send, recv, and ack sequence numbers.
         let seq_send_path = SeqSendPath::new(&msg.port_id_on_a, &chan_id_on_a);
         ctx_a.store_next_sequence_send(&seq_send_path, 1.into())?;
@@ -83,9 +81,13 @@ where
 
     // emit events and logs
     {
-        ctx_a.log_message(format!(
-            "success: channel open init with channel identifier: {chan_id_on_a}"
-        ));
+        ctx_a.log_structured(
+            "channel_open_init",
+            &[
+                ("port_id", msg.port_id_on_a.to_string()),
+                ("channel_id", chan_id_on_a.to_string()),
+            ],
+        );
         let core_event = IbcEvent::OpenInitChannel(OpenInit::new(
             msg.port_id_on_a.clone(),
             chan_id_on_a.clone(),
@@ -112,9 +114,10 @@ fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelOpenInit) -> Result<(), ContextErr
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_a, &msg.signer)?;
 
     msg.verify_connection_hops_length()?;
+    msg.ordering.verify_known()?;
     // An IBC connection running on the local (host) chain should exist.
     let conn_end_on_a = ctx_a.connection_end(&msg.connection_hops_on_a[0])?;
 
@@ -149,9 +152,13 @@ mod tests {
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
     use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::Order;
+    use crate::core::ics04_channel::error::ChannelError;
     use crate::core::ics04_channel::handler::chan_open_init::validate;
     use crate::core::ics04_channel::msgs::chan_open_init::test_util::get_dummy_raw_msg_chan_open_init;
     use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::ChannelId;
     use crate::core::ics24_host::identifier::ClientId;
     use crate::core::ics24_host::identifier::ConnectionId;
 
@@ -160,7 +167,8 @@ mod tests {
     use crate::core::router::Router;
     use crate::mock::context::MockContext;
     use crate::mock::router::MockRouter;
-    use crate::test_utils::DummyTransferModule;
+    use crate::signer::SignerError;
+    use crate::test_utils::{DummyTransferModule, UpgradingVersionModule, UPGRADED_VERSION};
     use test_log::test;
 
     pub struct Fixture {
@@ -228,6 +236,61 @@ mod tests {
         assert!(res.is_ok(), "Validation succeeds; good parameters")
     }
 
+    #[rstest]
+    fn chan_open_init_validate_signer_bech32_prefix(fixture: Fixture) {
+        let Fixture { ctx, msg, .. } = fixture;
+
+        let res = validate(&ctx.clone().with_signer_bech32_prefix("cosmos"), &msg);
+        assert!(
+            res.is_ok(),
+            "Validation succeeds; signer has the host prefix"
+        );
+
+        let res = validate(&ctx.clone().with_signer_bech32_prefix("osmo"), &msg);
+        assert!(matches!(
+            res,
+            Err(ContextError::SignerError(
+                SignerError::WrongBech32Prefix { .. }
+            ))
+        ));
+
+        let msg = MsgChannelOpenInit {
+            signer: "not-a-bech32-address".to_string().into(),
+            ..msg
+        };
+        let res = validate(&ctx.with_signer_bech32_prefix("cosmos"), &msg);
+        assert!(matches!(
+            res,
+            Err(ContextError::SignerError(SignerError::InvalidBech32 { .. }))
+        ));
+    }
+
+    #[rstest]
+    fn chan_open_init_validate_unknown_ordering(fixture: Fixture) {
+        let Fixture { ctx, msg, .. } = fixture;
+
+        let mut raw_msg = get_dummy_raw_msg_chan_open_init(None);
+        if let Some(channel) = raw_msg.channel.as_mut() {
+            channel.ordering = 5;
+        }
+        assert!(matches!(
+            MsgChannelOpenInit::try_from(raw_msg),
+            Err(ChannelError::UnknownOrderType { .. })
+        ));
+
+        let msg = MsgChannelOpenInit {
+            ordering: Order::try_from(0).unwrap(),
+            ..msg
+        };
+        let res = validate(&ctx, &msg);
+        assert!(matches!(
+            res,
+            Err(ContextError::ChannelError(
+                ChannelError::UnknownOrderType { .. }
+            ))
+        ));
+    }
+
     #[rstest]
     fn chan_open_init_validate_counterparty_chan_id_set(fixture: Fixture) {
         let Fixture { ctx, .. } = fixture;
@@ -263,4 +326,65 @@ mod tests {
         ));
         assert!(matches!(ctx.events[1], IbcEvent::OpenInitChannel(_)));
     }
+
+    #[rstest]
+    fn chan_open_init_execute_stores_module_version(fixture: Fixture) {
+        let Fixture { mut ctx, msg, .. } = fixture;
+        let port_id_on_a = msg.port_id_on_a.clone();
+        let chan_id_on_a = ChannelId::new(ctx.channel_counter().unwrap());
+
+        let mut module = UpgradingVersionModule;
+        let res = chan_open_init_execute(&mut ctx, &mut module, msg);
+
+        assert!(
+            res.is_ok(),
+            "Execution success: module upgrades the version"
+        );
+
+        let chan_end_on_a = ctx
+            .channel_end(&ChannelEndPath::new(&port_id_on_a, &chan_id_on_a))
+            .unwrap();
+        assert_eq!(
+            chan_end_on_a.version(),
+            &Version::new(UPGRADED_VERSION.to_string())
+        );
+
+        let open_init = ctx.events[1].as_open_init_channel().unwrap();
+        assert_eq!(
+            open_init.version_on_a(),
+            &Version::new(UPGRADED_VERSION.to_string())
+        );
+    }
+
+    #[rstest]
+    fn chan_open_init_execute_custom_channel_id(fixture: Fixture) {
+        let Fixture {
+            ctx,
+            mut router,
+            module_id,
+            msg,
+        } = fixture;
+        let mut ctx = ctx.with_channel_id_strategy(|port_id, counter| {
+            format!("{port_id}-channel-{counter}").parse().unwrap()
+        });
+        let module = router.get_route_mut(&module_id).unwrap();
+        let port_id_on_a = msg.port_id_on_a.clone();
+        let expected_chan_id: ChannelId = format!("{port_id_on_a}-channel-0").parse().unwrap();
+
+        chan_open_init_validate(&ctx, module, msg.clone()).unwrap();
+        chan_open_init_execute(&mut ctx, module, msg).unwrap();
+
+        let open_init = ctx.events[1].as_open_init_channel().unwrap();
+        assert_eq!(open_init.chan_id_on_a(), &expected_chan_id);
+        assert!(ctx
+            .channel_end(&ChannelEndPath::new(&port_id_on_a, &expected_chan_id))
+            .is_ok());
+        assert_eq!(ctx.channel_counter().unwrap(), 1);
+        assert_eq!(
+            ctx.next_channel_identifier(&port_id_on_a).unwrap(),
+            format!("{port_id_on_a}-channel-1")
+                .parse::<ChannelId>()
+                .unwrap()
+        );
+    }
 }
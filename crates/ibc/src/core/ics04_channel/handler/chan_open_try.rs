@@ -7,13 +7,18 @@ use crate::core::events::{IbcEvent, MessageEvent};
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::State;
-use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State as ChannelState};
+use crate::core::ics04_channel::channel::{
+    build_expected_counterparty_channel, ChannelEnd, Counterparty, State as ChannelState,
+};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::OpenTry;
+use crate::core::ics04_channel::handler::{decode_unverified_proof, validate_channel_msg_signer};
 use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
-use crate::core::ics24_host::identifier::ChannelId;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{ChannelEndPath, ClientConsensusStatePath};
 use crate::core::ics24_host::path::{SeqAckPath, SeqRecvPath, SeqSendPath};
@@ -30,7 +35,7 @@ where
 {
     validate(ctx_b, &msg)?;
 
-    let chan_id_on_b = ChannelId::new(ctx_b.channel_counter()?);
+    let chan_id_on_b = ctx_b.next_channel_identifier(&msg.port_id_on_b)?;
 
     module.on_chan_open_try_validate(
         msg.ordering,
@@ -52,7 +57,7 @@ pub(crate) fn chan_open_try_execute<ExecCtx>(
 where
     ExecCtx: ExecutionContext,
 {
-    let chan_id_on_b = ChannelId::new(ctx_b.channel_counter()?);
+    let chan_id_on_b = ctx_b.generate_channel_identifier(&msg.port_id_on_b)?;
     let (extras, version) = module.on_chan_open_try_execute(
         msg.ordering,
         &msg.connection_hops_on_b,
@@ -66,6 +71,9 @@ where
 
     // state changes
     {
+        let conn_end_on_b = ctx_b.connection_end(&conn_id_on_b)?;
+        update_client_on_verified_proofs(ctx_b, conn_end_on_b.client_id())?;
+
         let chan_end_on_b = ChannelEnd::new(
             State::TryOpen,
             msg.ordering,
@@ -76,7 +84,6 @@ where
 
         let chan_end_path_on_b = ChannelEndPath::new(&msg.port_id_on_b, &chan_id_on_b);
         ctx_b.store_channel(&chan_end_path_on_b, chan_end_on_b)?;
-        ctx_b.increase_channel_counter();
 
         // Initialize send, recv, and ack sequence numbers.
         let seq_send_path = SeqSendPath::new(&msg.port_id_on_b, &chan_id_on_b);
@@ -91,9 +98,13 @@ where
 
     // emit events and logs
     {
-        ctx_b.log_message(format!(
-            "success: channel open try with channel identifier: {chan_id_on_b}"
-        ));
+        ctx_b.log_structured(
+            "channel_open_try",
+            &[
+                ("port_id", msg.port_id_on_b.to_string()),
+                ("channel_id", chan_id_on_b.to_string()),
+            ],
+        );
 
         let core_event = IbcEvent::OpenTryChannel(OpenTry::new(
             msg.port_id_on_b.clone(),
@@ -122,9 +133,10 @@ fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgChannelOpenTry) -> Result<(), ContextErro
 where
     Ctx: ValidationContext,
 {
-    ctx_b.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_b, &msg.signer)?;
 
     msg.verify_connection_hops_length()?;
+    msg.ordering.verify_known()?;
 
     let conn_end_on_b = ctx_b.connection_end(&msg.connection_hops_on_b[0])?;
 
@@ -160,45 +172,89 @@ where
             },
         )?;
 
-        let expected_chan_end_on_a = ChannelEnd::new(
+        let expected_chan_end_on_a = build_expected_counterparty_channel(
             ChannelState::Init,
             msg.ordering,
             Counterparty::new(msg.port_id_on_b.clone(), None),
-            vec![conn_id_on_a.clone()],
+            conn_id_on_a.clone(),
             msg.version_supported_on_a.clone(),
         )?;
         let chan_end_path_on_a = ChannelEndPath::new(&port_id_on_a, &chan_id_on_a);
 
+        verify_proven_channel_end(ctx_b, &msg.proof_chan_end_on_a, &expected_chan_end_on_a)?;
+
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-        client_state_of_a_on_b
-            .verify_membership(
-                prefix_on_a,
-                &msg.proof_chan_end_on_a,
-                consensus_state_of_a_on_b.root(),
-                Path::ChannelEnd(chan_end_path_on_a),
-                expected_chan_end_on_a.encode_vec(),
-            )
-            .map_err(ChannelError::VerifyChannelFailed)?;
+        verify_membership(
+            ctx_b,
+            &client_state_of_a_on_b,
+            prefix_on_a,
+            &msg.proof_chan_end_on_a,
+            consensus_state_of_a_on_b.root(),
+            Path::ChannelEnd(chan_end_path_on_a),
+            expected_chan_end_on_a.encode_vec(),
+        )
+        .map_err(ChannelError::VerifyChannelFailed)?;
     }
 
     Ok(())
 }
 
+/// Checks that the counterparty channel end attested by `proof_chan_end`, if
+/// any, is bound to the same connection and has the same ordering as
+/// `expected_chan_end`, so that a mismatch is reported as such rather than as
+/// a failed proof verification.
+pub(crate) fn verify_proven_channel_end<Ctx>(
+    ctx: &Ctx,
+    proof_chan_end: &CommitmentProofBytes,
+    expected_chan_end: &ChannelEnd,
+) -> Result<(), ChannelError>
+where
+    Ctx: ValidationContext,
+{
+    let proven_chan_end = match decode_unverified_proof(ctx, proof_chan_end)
+        .and_then(|proof| proof.value().map(<[u8]>::to_vec))
+        .and_then(|value| ChannelEnd::decode_vec(&value).ok())
+    {
+        Some(proven_chan_end) => proven_chan_end,
+        None => return Ok(()),
+    };
+
+    if let (Some(expected), Some(actual)) = (
+        expected_chan_end.connection_hops().first(),
+        proven_chan_end.connection_hops().first(),
+    ) {
+        if actual != expected {
+            return Err(ChannelError::MismatchedCounterpartyConnection {
+                expected: expected.clone(),
+                actual: actual.clone(),
+            });
+        }
+    }
+
+    expected_chan_end.verify_ordering_matches(proven_chan_end.ordering())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::*;
     use test_log::test;
 
+    use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+    use ibc_proto::ics23::commitment_proof::Proof;
+    use ibc_proto::ics23::{CommitmentProof, ExistenceProof};
+
     use crate::core::ics03_connection::connection::ConnectionEnd;
     use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
     use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::Order;
     use crate::core::ics04_channel::msgs::chan_open_try::test_util::get_dummy_raw_msg_chan_open_try;
     use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
-    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId};
     use crate::core::router::ModuleId;
     use crate::core::router::Router;
     use crate::core::timestamp::ZERO_DURATION;
@@ -208,7 +264,7 @@ mod tests {
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::context::MockContext;
     use crate::mock::router::MockRouter;
-    use crate::test_utils::DummyTransferModule;
+    use crate::test_utils::{DummyTransferModule, UpgradingVersionModule, UPGRADED_VERSION};
 
     pub struct Fixture {
         pub ctx: MockContext,
@@ -347,4 +403,192 @@ mod tests {
         ));
         assert!(matches!(ctx.events[1], IbcEvent::OpenTryChannel(_)));
     }
+
+    #[rstest]
+    fn chan_open_try_execute_stores_module_version(fixture: Fixture) {
+        let Fixture {
+            ctx,
+            mut msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let mut ctx = ctx
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b);
+
+        msg.version_supported_on_a = Version::default();
+        let port_id_on_b = msg.port_id_on_b.clone();
+        let chan_id_on_b = ChannelId::new(ctx.channel_counter().unwrap());
+
+        let mut module = UpgradingVersionModule;
+        let res = chan_open_try_execute(&mut ctx, &mut module, msg);
+
+        assert!(
+            res.is_ok(),
+            "Execution success: module upgrades the version"
+        );
+
+        let chan_end_on_b = ctx
+            .channel_end(&ChannelEndPath::new(&port_id_on_b, &chan_id_on_b))
+            .unwrap();
+        assert_eq!(
+            chan_end_on_b.version(),
+            &Version::new(UPGRADED_VERSION.to_string())
+        );
+
+        match &ctx.events[1] {
+            IbcEvent::OpenTryChannel(e) => {
+                assert_eq!(
+                    e.version_on_b(),
+                    &Version::new(UPGRADED_VERSION.to_string())
+                )
+            }
+            _ => panic!("expected an OpenTryChannel event"),
+        }
+    }
+
+    /// Returns a Merkle membership proof of a counterparty channel end bound
+    /// to `conn_id_on_a`.
+    fn proof_of_chan_end_on(
+        msg: &MsgChannelOpenTry,
+        conn_id_on_a: ConnectionId,
+    ) -> CommitmentProofBytes {
+        proof_of_chan_end_with_ordering(msg, conn_id_on_a, msg.ordering)
+    }
+
+    fn proof_of_chan_end_with_ordering(
+        msg: &MsgChannelOpenTry,
+        conn_id_on_a: ConnectionId,
+        ordering: Order,
+    ) -> CommitmentProofBytes {
+        let chan_end_on_a = ChannelEnd::new(
+            State::Init,
+            ordering,
+            Counterparty::new(msg.port_id_on_b.clone(), None),
+            vec![conn_id_on_a],
+            msg.version_supported_on_a.clone(),
+        )
+        .unwrap();
+        let proof = ExistenceProof {
+            value: chan_end_on_a.encode_vec(),
+            ..Default::default()
+        };
+
+        RawMerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Proof::Exist(proof)),
+            }],
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    #[rstest]
+    fn chan_open_try_validate_matching_counterparty_connection(fixture: Fixture) {
+        let Fixture {
+            ctx,
+            mut msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let conn_id_on_a = conn_end_on_b
+            .counterparty()
+            .connection_id()
+            .unwrap()
+            .clone();
+        msg.proof_chan_end_on_a = proof_of_chan_end_on(&msg, conn_id_on_a);
+        let ctx = ctx
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b);
+
+        let res = validate(&ctx, &msg);
+
+        assert!(
+            res.is_ok(),
+            "Validation succeeds for a matching counterparty connection. err: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn chan_open_try_fail_mismatched_counterparty_connection(fixture: Fixture) {
+        let Fixture {
+            ctx,
+            mut msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let expected_conn_id = conn_end_on_b
+            .counterparty()
+            .connection_id()
+            .unwrap()
+            .clone();
+        let misrouted_conn_id = ConnectionId::new(7);
+        msg.proof_chan_end_on_a = proof_of_chan_end_on(&msg, misrouted_conn_id.clone());
+        let ctx = ctx
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b);
+
+        let res = validate(&ctx, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(ChannelError::MismatchedCounterpartyConnection {
+                    ref expected,
+                    ref actual,
+                })) if expected == &expected_conn_id && actual == &misrouted_conn_id
+            ),
+            "Validation fails for a misrouted handshake. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn chan_open_try_fail_ordering_mismatch(fixture: Fixture) {
+        let Fixture {
+            ctx,
+            mut msg,
+            client_id_on_b,
+            conn_id_on_b,
+            conn_end_on_b,
+            proof_height,
+            ..
+        } = fixture;
+
+        let conn_id_on_a = conn_end_on_b
+            .counterparty()
+            .connection_id()
+            .unwrap()
+            .clone();
+        msg.ordering = Order::Ordered;
+        msg.proof_chan_end_on_a =
+            proof_of_chan_end_with_ordering(&msg, conn_id_on_a, Order::Unordered);
+        let ctx = ctx
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b);
+
+        let res = validate(&ctx, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(ChannelError::OrderingMismatch {
+                    local: Order::Ordered,
+                    counterparty: Order::Unordered,
+                }))
+            ),
+            "Validation fails when the counterparty end has another ordering. got: {res:?}"
+        )
+    }
 }
@@ -4,15 +4,21 @@ use crate::prelude::*;
 use crate::core::events::{IbcEvent, MessageEvent};
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
-use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::delay::verify_conn_delay_passed;
+use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics04_channel::channel::{Counterparty, Order, State as ChannelState};
-use crate::core::ics04_channel::commitment::{compute_ack_commitment, compute_packet_commitment};
+use crate::core::ics04_channel::commitment::{
+    compute_ack_commitment_with, compute_packet_commitment_with,
+};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::error::PacketError;
 use crate::core::ics04_channel::events::{ReceivePacket, WriteAcknowledgement};
+use crate::core::ics04_channel::handler::{decode_unverified_proof, validate_channel_msg_signer};
 use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
-use crate::core::ics04_channel::packet::Receipt;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{
     AckPath, ChannelEndPath, ClientConsensusStatePath, CommitmentPath, ReceiptPath, SeqRecvPath,
@@ -23,11 +29,19 @@ use crate::core::{ContextError, ExecutionContext, ValidationContext};
 
 pub(crate) fn recv_packet_validate<ValCtx>(
     ctx_b: &ValCtx,
+    module: &dyn Module,
     msg: MsgRecvPacket,
 ) -> Result<(), ContextError>
 where
     ValCtx: ValidationContext,
 {
+    if !module.accepts_packets() {
+        return Err(ChannelError::ModuleNotAcceptingPackets {
+            port_id: msg.packet.port_id_on_b,
+        }
+        .into());
+    }
+
     // Note: this contains the validation for `write_acknowledgement` as well.
     validate(ctx_b, &msg)
 
@@ -79,6 +93,9 @@ where
 
     // state changes
     {
+        let conn_end_on_b = ctx_b.connection_end(&chan_end_on_b.connection_hops()[0])?;
+        update_client_on_verified_proofs(ctx_b, conn_end_on_b.client_id())?;
+
         // `recvPacket` core handler state changes
         match chan_end_on_b.ordering {
             Order::Unordered => {
@@ -106,14 +123,35 @@ where
         // `writeAcknowledgement` handler state changes
         ctx_b.store_packet_acknowledgement(
             &ack_path_on_b,
-            compute_ack_commitment(&acknowledgement),
+            compute_ack_commitment_with(ctx_b.commitment_hasher(), acknowledgement.as_ref()),
+        )?;
+
+        let host_timestamp = ctx_b.host_timestamp()?;
+        ctx_b.store_last_recv_time(
+            &msg.packet.port_id_on_b,
+            &msg.packet.chan_id_on_b,
+            host_timestamp,
         )?;
     }
 
     // emit events and logs
     {
-        ctx_b.log_message("success: packet receive".to_string());
-        ctx_b.log_message("success: packet write acknowledgement".to_string());
+        ctx_b.log_structured(
+            "packet_receive",
+            &[
+                ("port_id", msg.packet.port_id_on_b.to_string()),
+                ("channel_id", msg.packet.chan_id_on_b.to_string()),
+                ("sequence", msg.packet.seq_on_a.to_string()),
+            ],
+        );
+        ctx_b.log_structured(
+            "packet_write_acknowledgement",
+            &[
+                ("port_id", msg.packet.port_id_on_b.to_string()),
+                ("channel_id", msg.packet.chan_id_on_b.to_string()),
+                ("sequence", msg.packet.seq_on_a.to_string()),
+            ],
+        );
 
         let conn_id_on_b = &chan_end_on_b.connection_hops()[0];
         let event = IbcEvent::ReceivePacket(ReceivePacket::new(
@@ -147,7 +185,9 @@ fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgRecvPacket) -> Result<(), ContextError>
 where
     Ctx: ValidationContext,
 {
-    ctx_b.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_b, &msg.signer)?;
+
+    ctx_b.validate_self_consistency()?;
 
     let chan_end_path_on_b =
         ChannelEndPath::new(&msg.packet.port_id_on_b, &msg.packet.chan_id_on_b);
@@ -155,6 +195,15 @@ where
 
     chan_end_on_b.verify_state_matches(&ChannelState::Open)?;
 
+    let max_packet_data_size = ctx_b.max_packet_data_size(&chan_end_path_on_b);
+    if msg.packet.data.len() > max_packet_data_size {
+        return Err(ChannelError::PacketDataTooLarge {
+            size: msg.packet.data.len(),
+            max: max_packet_data_size,
+        }
+        .into());
+    }
+
     let counterparty = Counterparty::new(
         msg.packet.port_id_on_a.clone(),
         Some(msg.packet.chan_id_on_a.clone()),
@@ -165,20 +214,30 @@ where
     let conn_id_on_b = &chan_end_on_b.connection_hops()[0];
     let conn_end_on_b = ctx_b.connection_end(conn_id_on_b)?;
 
-    conn_end_on_b.verify_state_matches(&ConnectionState::Open)?;
+    // The connection may have been closed under the channel since it opened.
+    if !conn_end_on_b.is_open() {
+        return Err(ConnectionError::ConnectionNotOpen {
+            connection_id: conn_id_on_b.clone(),
+        }
+        .into());
+    }
 
     let latest_height = ctx_b.host_height()?;
     if msg.packet.timeout_height_on_b.has_expired(latest_height) {
-        return Err(PacketError::LowPacketHeight {
-            chain_height: latest_height,
-            timeout_height: msg.packet.timeout_height_on_b,
+        return Err(PacketError::PacketTimeoutHeightElapsed {
+            packet_height: msg.packet.timeout_height_on_b,
+            host_height: latest_height,
         }
         .into());
     }
 
     let latest_timestamp = ctx_b.host_timestamp()?;
     if let Expiry::Expired = latest_timestamp.check_expiry(&msg.packet.timeout_timestamp_on_b) {
-        return Err(PacketError::LowPacketTimestamp.into());
+        return Err(PacketError::PacketTimeoutTimestampElapsed {
+            packet_ts: msg.packet.timeout_timestamp_on_b,
+            host_ts: latest_timestamp,
+        }
+        .into());
     }
 
     // Verify proofs
@@ -194,12 +253,14 @@ where
             }
         }
         client_state_of_a_on_b.validate_proof_height(msg.proof_height_on_a)?;
+        verify_commitment_sequence(ctx_b, &msg.proof_commitment_on_a, msg.packet.seq_on_a)?;
 
         let client_cons_state_path_on_b =
             ClientConsensusStatePath::new(client_id_on_b, &msg.proof_height_on_a);
         let consensus_state_of_a_on_b = ctx_b.consensus_state(&client_cons_state_path_on_b)?;
 
-        let expected_commitment_on_a = compute_packet_commitment(
+        let expected_commitment_on_a = compute_packet_commitment_with(
+            ctx_b.commitment_hasher(),
             &msg.packet.data,
             &msg.packet.timeout_height_on_b,
             &msg.packet.timeout_timestamp_on_b,
@@ -213,25 +274,30 @@ where
         verify_conn_delay_passed(ctx_b, msg.proof_height_on_a, &conn_end_on_b)?;
 
         // Verify the proof for the packet against the chain store.
-        client_state_of_a_on_b
-            .verify_membership(
-                conn_end_on_b.counterparty().prefix(),
-                &msg.proof_commitment_on_a,
-                consensus_state_of_a_on_b.root(),
-                Path::Commitment(commitment_path_on_a),
-                expected_commitment_on_a.into_vec(),
-            )
-            .map_err(|e| ChannelError::PacketVerificationFailed {
-                sequence: msg.packet.seq_on_a,
-                client_error: e,
-            })
-            .map_err(PacketError::Channel)?;
+        verify_membership(
+            ctx_b,
+            &client_state_of_a_on_b,
+            conn_end_on_b.counterparty().prefix(),
+            &msg.proof_commitment_on_a,
+            consensus_state_of_a_on_b.root(),
+            Path::Commitment(commitment_path_on_a),
+            expected_commitment_on_a.into_vec(),
+        )
+        .map_err(|e| ChannelError::PacketVerificationFailed {
+            sequence: msg.packet.seq_on_a,
+            client_error: e,
+        })
+        .map_err(PacketError::Channel)?;
     }
 
     if chan_end_on_b.order_matches(&Order::Ordered) {
         let seq_recv_path_on_b =
             SeqRecvPath::new(&msg.packet.port_id_on_b, &msg.packet.chan_id_on_b);
         let next_seq_recv = ctx_b.get_next_sequence_recv(&seq_recv_path_on_b)?;
+        // Packets on ordered channels must be received in order: a sequence
+        // beyond `next_seq_recv` means that an earlier packet is still
+        // missing. A sequence below it was already received, which
+        // `recv_packet_execute()` treats as a no-op.
         if msg.packet.seq_on_a > next_seq_recv {
             return Err(PacketError::InvalidPacketSequence {
                 given_sequence: msg.packet.seq_on_a,
@@ -266,6 +332,36 @@ where
     Ok(())
 }
 
+/// Checks that the commitment path proven by `proof_commitment` is the one
+/// of the packet sequence, so that a packet cannot be received under another
+/// sequence than the one it was committed under.
+///
+/// Proofs that are not Merkle membership proofs of a packet commitment are
+/// left to the client to verify.
+fn verify_commitment_sequence<Ctx>(
+    ctx_b: &Ctx,
+    proof_commitment: &CommitmentProofBytes,
+    packet_sequence: Sequence,
+) -> Result<(), PacketError>
+where
+    Ctx: ValidationContext,
+{
+    let proven_path = decode_unverified_proof(ctx_b, proof_commitment)
+        .and_then(|proof| proof.key().map(<[u8]>::to_vec))
+        .and_then(|key| String::from_utf8(key).ok())
+        .and_then(|key| key.parse::<Path>().ok());
+
+    match proven_path {
+        Some(Path::Commitment(path)) if path.sequence != packet_sequence => {
+            Err(PacketError::SequencePathMismatch {
+                packet_sequence,
+                proof_sequence: path.sequence,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 fn validate_write_acknowledgement<Ctx>(ctx_b: &Ctx, msg: &MsgRecvPacket) -> Result<(), ContextError>
 where
     Ctx: ValidationContext,
@@ -285,9 +381,15 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ics04_channel::commitment::compute_packet_commitment;
     use rstest::*;
     use test_log::test;
 
+    use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+    use ibc_proto::ics23::commitment_proof::Proof;
+    use ibc_proto::ics23::{CommitmentProof, ExistenceProof};
+
+    use crate::core::ics02_client::proof::DEFAULT_MAX_PROOF_SIZE;
     use crate::core::ics03_connection::connection::ConnectionEnd;
     use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
     use crate::core::ics03_connection::connection::State as ConnectionState;
@@ -295,7 +397,8 @@ mod tests {
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
     use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
     use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
-    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::packet::{Packet, Sequence, DEFAULT_MAX_PACKET_DATA_SIZE};
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::core::router::ModuleId;
@@ -308,6 +411,7 @@ mod tests {
     use crate::mock::ics18_relayer::context::RelayerContext;
     use crate::mock::router::MockRouter;
     use crate::test_utils::get_dummy_account_id;
+    use crate::test_utils::RecordingModule;
     use crate::{applications::transfer::MODULE_ID_STR, test_utils::DummyTransferModule};
 
     pub struct Fixture {
@@ -388,6 +492,119 @@ mod tests {
         )
     }
 
+    #[rstest]
+    fn recv_packet_fail_inconsistent_host_timestamp(fixture: Fixture) {
+        let Fixture { context, msg, .. } = fixture;
+        let context = context.with_host_timestamp(Timestamp::from_nanoseconds(1).unwrap());
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(
+                    ClientError::InconsistentHostTimestamp { .. }
+                ))
+            ),
+            "validation should fail on an inconsistent host. got: {res:?}"
+        )
+    }
+
+    /// Returns a context in which `fixture.msg` passes validation.
+    fn context_receiving(fixture: &Fixture) -> MockContext {
+        let packet = &fixture.msg.packet;
+        let mut context = fixture
+            .context
+            .clone()
+            .with_client(&ClientId::default(), fixture.client_height)
+            .with_connection(ConnectionId::default(), fixture.conn_end_on_b.clone())
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                fixture.chan_end_on_b.clone(),
+            )
+            .with_height(fixture.host_height)
+            .with_recv_sequence(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                packet.seq_on_a,
+            );
+
+        context
+            .store_update_time(
+                ClientId::default(),
+                fixture.client_height,
+                Timestamp::from_nanoseconds(1000).unwrap(),
+            )
+            .unwrap();
+        context
+            .store_update_height(
+                ClientId::default(),
+                fixture.client_height,
+                Height::new(0, 5).unwrap(),
+            )
+            .unwrap();
+        context
+    }
+
+    /// A Merkle proof whose existence proof is for the commitment of `sequence`.
+    fn proof_of_commitment_at(packet: &Packet, sequence: Sequence) -> CommitmentProofBytes {
+        let path = CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, sequence);
+        let proof = ExistenceProof {
+            key: path.to_string().into_bytes(),
+            value: compute_packet_commitment(
+                &packet.data,
+                &packet.timeout_height_on_b,
+                &packet.timeout_timestamp_on_b,
+            )
+            .into_vec(),
+            ..Default::default()
+        };
+
+        RawMerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Proof::Exist(proof)),
+            }],
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    #[rstest]
+    fn recv_packet_validate_proof_of_packet_sequence(fixture: Fixture) {
+        let context = context_receiving(&fixture);
+        let mut msg = fixture.msg;
+        msg.proof_commitment_on_a = proof_of_commitment_at(&msg.packet, msg.packet.seq_on_a);
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            res.is_ok(),
+            "validation should succeed for a proof of the packet sequence. err: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_proof_of_other_sequence(fixture: Fixture) {
+        let context = context_receiving(&fixture);
+        let mut msg = fixture.msg;
+        let other_sequence = msg.packet.seq_on_a.increment();
+        msg.proof_commitment_on_a = proof_of_commitment_at(&msg.packet, other_sequence);
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::SequencePathMismatch {
+                    packet_sequence,
+                    proof_sequence,
+                })) if packet_sequence == msg.packet.seq_on_a && proof_sequence == other_sequence
+            ),
+            "validation should fail for a proof of another sequence. got: {res:?}"
+        )
+    }
+
     #[rstest]
     fn recv_packet_validate_happy_path(fixture: Fixture) {
         let Fixture {
@@ -445,6 +662,298 @@ mod tests {
         )
     }
 
+    fn validate_with_data_size(fixture: Fixture, data_size: usize) -> Result<(), ContextError> {
+        validate_with_modified_msg(fixture, |msg| msg.packet.data = vec![b'a'; data_size])
+    }
+
+    fn validate_with_proof_size(fixture: Fixture, proof_size: usize) -> Result<(), ContextError> {
+        validate_with_modified_msg(fixture, |msg| {
+            msg.proof_commitment_on_a = vec![1; proof_size].try_into().unwrap()
+        })
+    }
+
+    fn validate_with_modified_msg(
+        fixture: Fixture,
+        modify: impl FnOnce(&mut MsgRecvPacket),
+    ) -> Result<(), ContextError> {
+        let Fixture {
+            context,
+            mut msg,
+            conn_end_on_b,
+            chan_end_on_b,
+            client_height,
+            host_height,
+            ..
+        } = fixture;
+
+        modify(&mut msg);
+
+        let packet = &msg.packet;
+        let mut context = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                chan_end_on_b,
+            )
+            .with_height(host_height);
+
+        context
+            .store_update_time(
+                ClientId::default(),
+                client_height,
+                Timestamp::from_nanoseconds(1000).unwrap(),
+            )
+            .unwrap();
+        context
+            .store_update_height(
+                ClientId::default(),
+                client_height,
+                Height::new(0, 5).unwrap(),
+            )
+            .unwrap();
+
+        validate(&context, &msg)
+    }
+
+    fn recv_packet_validate_with_module(
+        fixture: Fixture,
+        module: &dyn Module,
+    ) -> Result<(), ContextError> {
+        let Fixture {
+            context,
+            msg,
+            conn_end_on_b,
+            chan_end_on_b,
+            client_height,
+            host_height,
+            ..
+        } = fixture;
+
+        let packet = &msg.packet;
+        let mut context = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                chan_end_on_b,
+            )
+            .with_height(host_height);
+
+        context
+            .store_update_time(
+                ClientId::default(),
+                client_height,
+                Timestamp::from_nanoseconds(1000).unwrap(),
+            )
+            .unwrap();
+        context
+            .store_update_height(
+                ClientId::default(),
+                client_height,
+                Height::new(0, 5).unwrap(),
+            )
+            .unwrap();
+
+        recv_packet_validate(&context, module, msg)
+    }
+
+    #[rstest]
+    fn recv_packet_validate_module_accepts_packets(fixture: Fixture) {
+        let module = DummyTransferModule::new();
+
+        let res = recv_packet_validate_with_module(fixture, &module);
+
+        assert!(
+            res.is_ok(),
+            "validation should succeed for a module accepting packets. err: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_module_not_accepting_packets(fixture: Fixture) {
+        let port_id_on_b = fixture.msg.packet.port_id_on_b.clone();
+        let module = DummyTransferModule::new().with_accepts_packets(false);
+
+        let res = recv_packet_validate_with_module(fixture, &module);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(ChannelError::ModuleNotAcceptingPackets { ref port_id }))
+                    if port_id == &port_id_on_b
+            ),
+            "validation should fail for a degraded module. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_timeout_height_elapsed(fixture: Fixture) {
+        let packet_height = TimeoutHeight::At(Height::new(0, 1).unwrap());
+
+        let res = validate_with_modified_msg(fixture, |msg| {
+            msg.packet.timeout_height_on_b = packet_height;
+        });
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::PacketTimeoutHeightElapsed {
+                    packet_height: height,
+                    ..
+                })) if height == packet_height
+            ),
+            "validation should fail on the timeout height alone. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_timeout_timestamp_elapsed(fixture: Fixture) {
+        let packet_ts = Timestamp::from_nanoseconds(1).unwrap();
+
+        let res = validate_with_modified_msg(fixture, |msg| {
+            msg.packet.timeout_height_on_b = TimeoutHeight::Never;
+            msg.packet.timeout_timestamp_on_b = packet_ts;
+        });
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::PacketTimeoutTimestampElapsed {
+                    packet_ts: ts,
+                    ..
+                })) if ts == packet_ts
+            ),
+            "validation should fail on the timeout timestamp alone. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_validate_no_timeout_height(fixture: Fixture) {
+        let res = validate_with_modified_msg(fixture, |msg| {
+            msg.packet.timeout_height_on_b = TimeoutHeight::Never;
+            msg.packet.timeout_timestamp_on_b = Timestamp::from_nanoseconds(u64::MAX).unwrap();
+        });
+
+        assert!(
+            res.is_ok(),
+            "a packet without timeout height is only timed out by its timestamp. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_timestamp_elapsed_before_height(fixture: Fixture) {
+        let packet_ts = Timestamp::from_nanoseconds(1).unwrap();
+
+        let res = validate_with_modified_msg(fixture, |msg| {
+            msg.packet.timeout_height_on_b = TimeoutHeight::At(Height::new(0, u64::MAX).unwrap());
+            msg.packet.timeout_timestamp_on_b = packet_ts;
+        });
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::PacketTimeoutTimestampElapsed {
+                    packet_ts: ts,
+                    ..
+                })) if ts == packet_ts
+            ),
+            "validation should fail on the timeout timestamp. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_validate_max_data_size(fixture: Fixture) {
+        let res = validate_with_data_size(fixture, DEFAULT_MAX_PACKET_DATA_SIZE);
+
+        assert!(
+            res.is_ok(),
+            "validation should succeed for a packet at the size limit. err: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_data_too_large(fixture: Fixture) {
+        let res = validate_with_data_size(fixture, DEFAULT_MAX_PACKET_DATA_SIZE + 1);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ChannelError(ChannelError::PacketDataTooLarge { size, max }))
+                    if size == DEFAULT_MAX_PACKET_DATA_SIZE + 1 && max == DEFAULT_MAX_PACKET_DATA_SIZE
+            ),
+            "validation should fail for an oversized packet. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_validate_max_proof_size(fixture: Fixture) {
+        let res = validate_with_proof_size(fixture, DEFAULT_MAX_PROOF_SIZE);
+
+        assert!(
+            res.is_ok(),
+            "validation should succeed for a proof at the size limit. err: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_proof_too_large(fixture: Fixture) {
+        let res = validate_with_proof_size(fixture, DEFAULT_MAX_PROOF_SIZE + 1);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::Channel(
+                    ChannelError::PacketVerificationFailed {
+                        client_error: ClientError::ProofTooLarge { size, max },
+                        ..
+                    }
+                )))
+                    if size == DEFAULT_MAX_PROOF_SIZE + 1 && max == DEFAULT_MAX_PROOF_SIZE
+            ),
+            "validation should fail for an oversized proof. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn recv_packet_fail_connection_not_open(fixture: Fixture) {
+        let Fixture {
+            context,
+            msg,
+            mut conn_end_on_b,
+            chan_end_on_b,
+            client_height,
+            host_height,
+            ..
+        } = fixture;
+
+        conn_end_on_b.set_state(ConnectionState::TryOpen);
+
+        let packet = &msg.packet;
+        let context = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                chan_end_on_b,
+            )
+            .with_height(host_height);
+
+        let res = validate(&context, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ConnectionError(ConnectionError::ConnectionNotOpen { ref connection_id }))
+                    if connection_id == &ConnectionId::default()
+            ),
+            "validation should fail because the connection is not open. got: {res:?}"
+        )
+    }
+
     #[rstest]
     fn recv_packet_timeout_expired(fixture: Fixture) {
         let Fixture {
@@ -523,4 +1032,244 @@ mod tests {
         ));
         assert!(matches!(&ctx.events[3], &IbcEvent::WriteAcknowledgement(_)));
     }
+
+    #[rstest]
+    fn recv_packet_execute_records_last_recv_time(fixture: Fixture) {
+        let Fixture {
+            context,
+            mut router,
+            module_id,
+            msg,
+            conn_end_on_b,
+            chan_end_on_b,
+            client_height,
+            ..
+        } = fixture;
+        let packet = msg.packet.clone();
+        let mut ctx = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                chan_end_on_b,
+            );
+
+        assert_eq!(
+            ctx.last_recv_time(&packet.port_id_on_b, &packet.chan_id_on_b)
+                .unwrap(),
+            None
+        );
+
+        let module = router.get_route_mut(&module_id).unwrap();
+        recv_packet_execute(&mut ctx, module, msg.clone()).unwrap();
+
+        let first_recv_time = ctx.host_timestamp().unwrap();
+        assert_eq!(
+            ctx.last_recv_time(&packet.port_id_on_b, &packet.chan_id_on_b)
+                .unwrap(),
+            Some(first_recv_time)
+        );
+
+        ctx.advance_host_chain_height();
+        let mut next_msg = msg;
+        next_msg.packet.seq_on_a = packet.seq_on_a.increment();
+
+        let module = router.get_route_mut(&module_id).unwrap();
+        recv_packet_execute(&mut ctx, module, next_msg).unwrap();
+
+        let second_recv_time = ctx.host_timestamp().unwrap();
+        assert!(second_recv_time > first_recv_time);
+        assert_eq!(
+            ctx.last_recv_time(&packet.port_id_on_b, &packet.chan_id_on_b)
+                .unwrap(),
+            Some(second_recv_time)
+        );
+    }
+
+    #[rstest]
+    fn recv_packet_execute_unordered_emits_receive_packet(fixture: Fixture) {
+        let Fixture {
+            context,
+            mut router,
+            module_id,
+            msg,
+            conn_end_on_b,
+            chan_end_on_b,
+            client_height,
+            ..
+        } = fixture;
+        let packet = msg.packet.clone();
+        let mut ctx = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                chan_end_on_b,
+            );
+
+        let module = router.get_route_mut(&module_id).unwrap();
+        let res = recv_packet_execute(&mut ctx, module, msg);
+
+        assert!(res.is_ok(), "execution should succeed. err: {res:?}");
+
+        let receipt_path =
+            ReceiptPath::new(&packet.port_id_on_b, &packet.chan_id_on_b, packet.seq_on_a);
+        assert!(ctx.get_packet_receipt(&receipt_path).is_ok());
+
+        let event = ctx
+            .events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::ReceivePacket(event) => Some(event),
+                _ => None,
+            })
+            .expect("a receive packet event is emitted");
+        assert_eq!(event.seq_on_a(), &packet.seq_on_a);
+        assert_eq!(event.port_id_on_b(), &packet.port_id_on_b);
+        assert_eq!(event.chan_id_on_b(), &packet.chan_id_on_b);
+        assert_eq!(event.channel_ordering(), &Order::Unordered);
+        assert_eq!(event.conn_id_on_b(), &ConnectionId::default());
+    }
+
+    #[rstest]
+    fn recv_packet_execute_merged_extras_order(fixture: Fixture) {
+        let Fixture {
+            context,
+            msg,
+            conn_end_on_b,
+            chan_end_on_b,
+            client_height,
+            ..
+        } = fixture;
+        let mut ctx = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end_on_b);
+
+        let mut module =
+            RecordingModule::new(RecordingModule::new(DummyTransferModule::new()).named("inner"))
+                .named("middleware");
+        let res = recv_packet_execute(&mut ctx, &mut module, msg);
+
+        assert!(res.is_ok());
+
+        let module_events: Vec<&str> = ctx
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                IbcEvent::Module(event) => Some(event.kind.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(module_events, vec!["inner", "middleware"]);
+        assert!(matches!(ctx.events.last(), Some(IbcEvent::Module(_))));
+
+        assert_eq!(
+            ctx.logs[ctx.logs.len() - 2..],
+            ["inner".to_string(), "middleware".to_string()]
+        );
+    }
+
+    /// Sets up an ordered channel on which the next sequence to receive is
+    /// `next_seq_recv`.
+    fn ordered_channel_context(fixture: &Fixture, next_seq_recv: Sequence) -> MockContext {
+        let Fixture {
+            context,
+            msg,
+            conn_end_on_b,
+            chan_end_on_b,
+            client_height,
+            host_height,
+            ..
+        } = fixture;
+
+        let packet = &msg.packet;
+        let mut chan_end_on_b = chan_end_on_b.clone();
+        chan_end_on_b.ordering = Order::Ordered;
+
+        let mut context = context
+            .clone()
+            .with_client(&ClientId::default(), *client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b.clone())
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                chan_end_on_b,
+            )
+            .with_height(*host_height)
+            .with_recv_sequence(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                next_seq_recv,
+            );
+
+        context
+            .store_update_time(
+                ClientId::default(),
+                *client_height,
+                Timestamp::from_nanoseconds(1000).unwrap(),
+            )
+            .unwrap();
+        context
+            .store_update_height(
+                ClientId::default(),
+                *client_height,
+                Height::new(0, 5).unwrap(),
+            )
+            .unwrap();
+
+        context
+    }
+
+    fn msg_with_sequence(msg: &MsgRecvPacket, sequence: Sequence) -> MsgRecvPacket {
+        let mut msg = msg.clone();
+        msg.packet.seq_on_a = sequence;
+        msg
+    }
+
+    #[rstest]
+    fn recv_packet_ordered_in_sequence(fixture: Fixture) {
+        let mut context = ordered_channel_context(&fixture, 1.into());
+        let Fixture {
+            mut router,
+            module_id,
+            msg,
+            ..
+        } = fixture;
+
+        let msg_1 = msg_with_sequence(&msg, 1.into());
+        let res = validate(&context, &msg_1);
+        assert!(res.is_ok(), "packet 1 is the next one expected: {res:?}");
+
+        let module = router.get_route_mut(&module_id).unwrap();
+        recv_packet_execute(&mut context, module, msg_1).unwrap();
+
+        let msg_2 = msg_with_sequence(&msg, 2.into());
+        let res = validate(&context, &msg_2);
+        assert!(
+            res.is_ok(),
+            "packet 2 is the next one expected after receiving 1: {res:?}"
+        );
+    }
+
+    #[rstest]
+    fn recv_packet_ordered_sequence_gap(fixture: Fixture) {
+        let context = ordered_channel_context(&fixture, 1.into());
+
+        let msg_2 = msg_with_sequence(&fixture.msg, 2.into());
+        let res = validate(&context, &msg_2);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::InvalidPacketSequence {
+                    given_sequence,
+                    next_sequence,
+                })) if given_sequence == 2.into() && next_sequence == 1.into()
+            ),
+            "packet 2 cannot be received before packet 1: {res:?}"
+        );
+    }
 }
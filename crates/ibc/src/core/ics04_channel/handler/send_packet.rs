@@ -6,10 +6,13 @@ use crate::core::events::IbcEvent;
 use crate::core::events::MessageEvent;
 use crate::core::ics02_client::client_state::ClientStateCommon;
 use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics04_channel::channel::Counterparty;
-use crate::core::ics04_channel::commitment::compute_packet_commitment;
+use crate::core::ics04_channel::commitment::compute_packet_commitment_with;
 use crate::core::ics04_channel::context::SendPacketExecutionContext;
+use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::SendPacket;
+use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics04_channel::{
     context::SendPacketValidationContext, error::PacketError, packet::Packet,
 };
@@ -54,6 +57,15 @@ pub fn send_packet_validate(
 
     let conn_end_on_a = ctx_a.connection_end(conn_id_on_a)?;
 
+    // An open channel must still sit on an open connection, which may have
+    // been closed since the channel opened.
+    if chan_end_on_a.is_open() && !conn_end_on_a.is_open() {
+        return Err(ConnectionError::ConnectionNotOpen {
+            connection_id: conn_id_on_a.clone(),
+        }
+        .into());
+    }
+
     let client_id_on_a = conn_end_on_a.client_id();
 
     let client_state_of_b_on_a = ctx_a.client_state(client_id_on_a)?;
@@ -96,9 +108,26 @@ pub fn send_packet_validate(
         .into());
     }
 
+    next_sequence_send_after(packet, next_seq_send_on_a)?;
+
     Ok(())
 }
 
+/// Returns the send sequence following the given one on the channel of
+/// `packet`, failing rather than wrapping around to a sequence whose
+/// commitment may still be stored.
+fn next_sequence_send_after(
+    packet: &Packet,
+    next_seq_send_on_a: Sequence,
+) -> Result<Sequence, ChannelError> {
+    next_seq_send_on_a
+        .checked_increment()
+        .ok_or_else(|| ChannelError::SequenceOverflow {
+            port_id: packet.port_id_on_a.clone(),
+            channel_id: packet.chan_id_on_a.clone(),
+        })
+}
+
 /// Send the packet without any validation.
 ///
 /// A prior call to [`send_packet_validate`] MUST have succeeded.
@@ -110,12 +139,15 @@ pub fn send_packet_execute(
         let seq_send_path_on_a = SeqSendPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
         let next_seq_send_on_a = ctx_a.get_next_sequence_send(&seq_send_path_on_a)?;
 
-        ctx_a.store_next_sequence_send(&seq_send_path_on_a, next_seq_send_on_a.increment())?;
+        let next_seq_send_on_a = next_sequence_send_after(&packet, next_seq_send_on_a)?;
+
+        ctx_a.store_next_sequence_send(&seq_send_path_on_a, next_seq_send_on_a)?;
     }
 
     ctx_a.store_packet_commitment(
         &CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a),
-        compute_packet_commitment(
+        compute_packet_commitment_with(
+            ctx_a.commitment_hasher(),
             &packet.data,
             &packet.timeout_height_on_b,
             &packet.timeout_timestamp_on_b,
@@ -128,7 +160,14 @@ pub fn send_packet_execute(
         let chan_end_on_a = ctx_a.channel_end(&chan_end_path_on_a)?;
         let conn_id_on_a = &chan_end_on_a.connection_hops()[0];
 
-        ctx_a.log_message("success: packet send".to_string());
+        ctx_a.log_structured(
+            "packet_send",
+            &[
+                ("port_id", packet.port_id_on_a.to_string()),
+                ("channel_id", packet.chan_id_on_a.to_string()),
+                ("sequence", packet.seq_on_a.to_string()),
+            ],
+        );
         let event = IbcEvent::SendPacket(SendPacket::new(
             packet,
             chan_end_on_a.ordering,
@@ -156,6 +195,7 @@ mod tests {
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::version::get_compatible_versions;
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::commitment::compute_packet_commitment;
     use crate::core::ics04_channel::handler::send_packet::send_packet;
     use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::core::ics04_channel::packet::Packet;
@@ -164,6 +204,8 @@ mod tests {
     use crate::core::timestamp::Timestamp;
     use crate::core::timestamp::ZERO_DURATION;
     use crate::mock::context::MockContext;
+    use crate::test_utils::ReversedSha256Hasher;
+    use alloc::sync::Arc;
 
     #[test]
     fn send_packet_processing() {
@@ -229,6 +271,9 @@ mod tests {
 
         let client_height = Height::new(0, client_raw_height).unwrap();
 
+        let mut conn_end_on_a_not_open = conn_end_on_a.clone();
+        conn_end_on_a_not_open.set_state(ConnectionState::Init);
+
         let tests: Vec<Test> = vec![
             Test {
                 name: "Processing fails because no channel exists in the context".to_string(),
@@ -248,7 +293,7 @@ mod tests {
                         chan_end_on_a.clone(),
                     )
                     .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
-                packet,
+                packet: packet.clone(),
                 want_pass: true,
             },
             Test {
@@ -281,6 +326,22 @@ mod tests {
                 packet: packet_timeout_one_before_client_height,
                 want_pass: false,
             },
+            Test {
+                name: "Processing fails because the connection of the open channel is not open"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), conn_end_on_a_not_open)
+                    .with_channel(
+                        PortId::default(),
+                        ChannelId::default(),
+                        chan_end_on_a.clone(),
+                    )
+                    .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
+                packet: packet.clone(),
+                want_pass: false,
+            },
             Test {
                 name: "Packet timeout due to timestamp".to_string(),
                 ctx: context
@@ -331,4 +392,108 @@ mod tests {
             }
         }
     }
+
+    /// Returns a context with an open channel on top of an open connection,
+    /// whose next send sequence is `next_seq_send`.
+    fn open_channel_context(next_seq_send: Sequence) -> MockContext {
+        let chan_end_on_a = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::new("ics20-1".to_string()),
+        )
+        .unwrap();
+
+        let conn_end_on_a = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        MockContext::default()
+            .with_client(&ClientId::default(), Height::new(0, 5).unwrap())
+            .with_connection(ConnectionId::default(), conn_end_on_a)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end_on_a)
+            .with_send_sequence(PortId::default(), ChannelId::default(), next_seq_send)
+    }
+
+    /// Returns a packet with sequence `seq_on_a` to send on the channel of
+    /// [`open_channel_context`].
+    fn packet_to_send(seq_on_a: Sequence) -> Packet {
+        let timestamp_future = Timestamp::now().add(Duration::from_secs(10)).unwrap();
+        let mut packet: Packet = get_dummy_raw_packet(10, timestamp_future.nanoseconds())
+            .try_into()
+            .unwrap();
+        packet.seq_on_a = seq_on_a;
+        packet.data = vec![0];
+        packet
+    }
+
+    #[test]
+    fn send_packet_sequence_overflow() {
+        let packet = packet_to_send(u64::MAX.into());
+        let mut ctx = open_channel_context(u64::MAX.into());
+
+        let res = send_packet_validate(&ctx, &packet);
+        assert!(matches!(
+            res,
+            Err(ContextError::ChannelError(
+                ChannelError::SequenceOverflow { .. }
+            ))
+        ));
+
+        let res = send_packet_execute(&mut ctx, packet);
+        assert!(matches!(
+            res,
+            Err(ContextError::ChannelError(
+                ChannelError::SequenceOverflow { .. }
+            ))
+        ));
+
+        let seq_send_path = SeqSendPath::new(&PortId::default(), &ChannelId::default());
+        assert_eq!(
+            ctx.get_next_sequence_send(&seq_send_path).unwrap(),
+            Sequence::from(u64::MAX)
+        );
+        assert!(ctx.events.is_empty());
+    }
+
+    #[test]
+    fn send_packet_with_commitment_hasher() {
+        let packet = packet_to_send(1.into());
+        let mut ctx =
+            open_channel_context(1.into()).with_commitment_hasher(Arc::new(ReversedSha256Hasher));
+
+        send_packet(&mut ctx, packet.clone()).unwrap();
+
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+        let commitment =
+            crate::core::ValidationContext::get_packet_commitment(&ctx, &commitment_path).unwrap();
+        assert_eq!(
+            commitment,
+            compute_packet_commitment_with(
+                &ReversedSha256Hasher,
+                &packet.data,
+                &packet.timeout_height_on_b,
+                &packet.timeout_timestamp_on_b,
+            )
+        );
+        assert_ne!(
+            commitment,
+            compute_packet_commitment(
+                &packet.data,
+                &packet.timeout_height_on_b,
+                &packet.timeout_timestamp_on_b,
+            )
+        );
+    }
 }
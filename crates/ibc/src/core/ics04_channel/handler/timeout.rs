@@ -7,16 +7,23 @@ use crate::core::events::IbcEvent;
 use crate::core::events::MessageEvent;
 use crate::core::ics02_client::client_state::ClientStateCommon;
 use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::handler::update_client_on_verified_proofs;
+use crate::core::ics02_client::height::Height;
+use crate::core::ics02_client::proof::{verify_membership, verify_non_membership};
+use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::delay::verify_conn_delay_passed;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::channel::{Counterparty, Order};
-use crate::core::ics04_channel::commitment::compute_packet_commitment;
+use crate::core::ics04_channel::commitment::compute_packet_commitment_with;
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::error::PacketError;
 use crate::core::ics04_channel::events::ChannelClosed;
+use crate::core::ics04_channel::handler::{decode_unverified_proof, validate_channel_msg_signer};
 use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
 use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
 use crate::core::ics04_channel::{events::TimeoutPacket, handler::timeout_on_close};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics23_commitment::merkle::ProofKind;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{
     ChannelEndPath, ClientConsensusStatePath, CommitmentPath, ReceiptPath, SeqRecvPath,
@@ -90,6 +97,9 @@ where
 
     // apply state changes
     let chan_end_on_a = {
+        let conn_end_on_a = ctx_a.connection_end(&chan_end_on_a.connection_hops()[0])?;
+        update_client_on_verified_proofs(ctx_a, conn_end_on_a.client_id())?;
+
         let commitment_path_on_a = CommitmentPath {
             port_id: packet.port_id_on_a.clone(),
             channel_id: packet.chan_id_on_a.clone(),
@@ -110,7 +120,14 @@ where
 
     // emit events and logs
     {
-        ctx_a.log_message("success: packet timeout".to_string());
+        ctx_a.log_structured(
+            "packet_timeout",
+            &[
+                ("port_id", packet.port_id_on_a.to_string()),
+                ("channel_id", packet.chan_id_on_a.to_string()),
+                ("sequence", packet.seq_on_a.to_string()),
+            ],
+        );
 
         if let Order::Ordered = chan_end_on_a.ordering {
             let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
@@ -143,7 +160,9 @@ fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgTimeout) -> Result<(), ContextError>
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_a, &msg.signer)?;
+
+    ctx_a.validate_self_consistency()?;
 
     let chan_end_on_a = ctx_a.channel_end(&ChannelEndPath::new(
         &msg.packet.port_id_on_a,
@@ -178,7 +197,8 @@ where
         Err(_) => return Ok(()),
     };
 
-    let expected_commitment_on_a = compute_packet_commitment(
+    let expected_commitment_on_a = compute_packet_commitment_with(
+        ctx_a.commitment_hasher(),
         &msg.packet.data,
         &msg.packet.timeout_height_on_b,
         &msg.packet.timeout_timestamp_on_b,
@@ -222,7 +242,9 @@ where
 
         verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, &conn_end_on_a)?;
 
-        let next_seq_recv_verification_result = if chan_end_on_a.order_matches(&Order::Ordered) {
+        verify_unreceived_proof_kind(ctx_a, chan_end_on_a.ordering, &msg.proof_unreceived_on_b)?;
+
+        if chan_end_on_a.order_matches(&Order::Ordered) {
             if msg.packet.seq_on_a < msg.next_seq_recv_on_b {
                 return Err(PacketError::InvalidPacketSequence {
                     given_sequence: msg.packet.seq_on_a,
@@ -240,13 +262,20 @@ where
                     sequence: msg.packet.seq_on_a,
                 })?;
 
-            client_state_of_b_on_a.verify_membership(
+            verify_membership(
+                ctx_a,
+                &client_state_of_b_on_a,
                 conn_end_on_a.counterparty().prefix(),
                 &msg.proof_unreceived_on_b,
                 consensus_state_of_b_on_a.root(),
                 Path::SeqRecv(seq_recv_path_on_b),
                 value,
             )
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.next_seq_recv_on_b,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
         } else {
             let receipt_path_on_b = ReceiptPath::new(
                 &msg.packet.port_id_on_b,
@@ -254,20 +283,99 @@ where
                 msg.packet.seq_on_a,
             );
 
-            client_state_of_b_on_a.verify_non_membership(
-                conn_end_on_a.counterparty().prefix(),
+            verify_packet_receipt_absence(
+                ctx_a,
+                &conn_end_on_a,
+                msg.proof_height_on_b,
                 &msg.proof_unreceived_on_b,
-                consensus_state_of_b_on_a.root(),
-                Path::Receipt(receipt_path_on_b),
-            )
-        };
-        next_seq_recv_verification_result
-            .map_err(|e| ChannelError::PacketVerificationFailed {
-                sequence: msg.next_seq_recv_on_b,
-                client_error: e,
-            })
-            .map_err(PacketError::Channel)?;
+                &receipt_path_on_b,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `proof_unreceived_on_b` is of the kind checked when timing out
+/// a packet on a channel with the given `ordering`: a membership proof of the
+/// next sequence to receive for ordered channels, and a non-membership proof
+/// of the packet receipt otherwise.
+///
+/// Proofs that are not Merkle proofs are left to the client to verify.
+pub(crate) fn verify_unreceived_proof_kind<Ctx>(
+    ctx_a: &Ctx,
+    ordering: Order,
+    proof_unreceived_on_b: &CommitmentProofBytes,
+) -> Result<(), PacketError>
+where
+    Ctx: ValidationContext,
+{
+    let expected = match ordering {
+        Order::Ordered => ProofKind::Membership,
+        Order::Unordered | Order::None => ProofKind::NonMembership,
+    };
+
+    let actual =
+        decode_unverified_proof(ctx_a, proof_unreceived_on_b).and_then(|proof| proof.kind());
+
+    match actual {
+        Some(actual) if actual != expected => Err(PacketError::MismatchedTimeoutProof {
+            ordering,
+            expected,
+            actual,
+        }),
+        _ => Ok(()),
     }
+}
+
+/// Verifies that no packet receipt is stored on the counterparty at
+/// `receipt_path_on_b`, i.e. that the packet was never received on an
+/// unordered channel.
+///
+/// The proof is checked against the consensus state at `proof_height_on_b`
+/// of the client of `conn_end_on_a`, which must be active, under the
+/// counterparty's commitment prefix. This is the check performed by the
+/// timeout handlers for unordered channels, exposed so that timeout messages
+/// can be pre-validated.
+pub fn verify_packet_receipt_absence<Ctx>(
+    ctx_a: &Ctx,
+    conn_end_on_a: &ConnectionEnd,
+    proof_height_on_b: Height,
+    proof_unreceived_on_b: &CommitmentProofBytes,
+    receipt_path_on_b: &ReceiptPath,
+) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let client_id_on_a = conn_end_on_a.client_id();
+    let client_state_of_b_on_a = ctx_a.client_state(client_id_on_a)?;
+
+    {
+        let status =
+            client_state_of_b_on_a.status(ctx_a.get_client_validation_context(), client_id_on_a)?;
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive { status }.into());
+        }
+    }
+    client_state_of_b_on_a.validate_proof_height(proof_height_on_b)?;
+
+    let client_cons_state_path_on_a =
+        ClientConsensusStatePath::new(client_id_on_a, &proof_height_on_b);
+    let consensus_state_of_b_on_a = ctx_a.consensus_state(&client_cons_state_path_on_a)?;
+
+    verify_non_membership(
+        ctx_a,
+        &client_state_of_b_on_a,
+        conn_end_on_a.counterparty().prefix(),
+        proof_unreceived_on_b,
+        consensus_state_of_b_on_a.root(),
+        Path::Receipt(receipt_path_on_b.clone()),
+    )
+    .map_err(|e| ChannelError::PacketVerificationFailed {
+        sequence: receipt_path_on_b.sequence,
+        client_error: e,
+    })
+    .map_err(PacketError::Channel)?;
 
     Ok(())
 }
@@ -277,7 +385,10 @@ mod tests {
     use super::*;
     use rstest::*;
 
+    use crate::core::ics04_channel::commitment::compute_packet_commitment;
+
     use crate::core::ics02_client::height::Height;
+    use crate::core::ics02_client::ClientExecutionContext;
     use crate::core::ics03_connection::connection::ConnectionEnd;
     use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
     use crate::core::ics03_connection::connection::State as ConnectionState;
@@ -287,14 +398,19 @@ mod tests {
     use crate::core::ics04_channel::handler::timeout::validate;
     use crate::core::ics04_channel::msgs::timeout::test_util::get_dummy_raw_msg_timeout;
     use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+    use crate::core::ics04_channel::packet::Receipt;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::core::router::ModuleId;
     use crate::core::router::Router;
     use crate::core::timestamp::Timestamp;
     use crate::core::timestamp::ZERO_DURATION;
+    use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+    use ibc_proto::ics23::commitment_proof::Proof;
+    use ibc_proto::ics23::{CommitmentProof, ExistenceProof, NonExistenceProof};
 
     use crate::applications::transfer::MODULE_ID_STR;
+    use crate::clients::ics09_localhost::client_type as localhost_client_type;
     use crate::mock::context::MockContext;
     use crate::mock::router::MockRouter;
     use crate::test_utils::DummyTransferModule;
@@ -612,6 +728,131 @@ mod tests {
         assert!(res.is_ok(), "Good parameters for unordered channels")
     }
 
+    /// Returns a Merkle proof whose proof for the key itself is of `kind`.
+    fn merkle_proof_of_kind(kind: ProofKind) -> CommitmentProofBytes {
+        let proof = match kind {
+            ProofKind::Membership => Proof::Exist(ExistenceProof::default()),
+            ProofKind::NonMembership => Proof::Nonexist(NonExistenceProof::default()),
+        };
+        RawMerkleProof {
+            proofs: vec![CommitmentProof { proof: Some(proof) }],
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    /// Validates timing out the fixture's packet on `chan_end_on_a` with a
+    /// proof of unreceipt of the given `kind`.
+    fn validate_with_proof_kind(
+        fixture: Fixture,
+        chan_end_on_a: ChannelEnd,
+        kind: ProofKind,
+    ) -> Result<(), ContextError> {
+        let Fixture {
+            ctx,
+            mut msg,
+            conn_end_on_a,
+            packet_commitment,
+            client_height,
+            ..
+        } = fixture;
+
+        msg.proof_unreceived_on_b = merkle_proof_of_kind(kind);
+        let packet = msg.packet.clone();
+
+        let mut ctx = ctx
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_a)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end_on_a)
+            .with_packet_commitment(
+                packet.port_id_on_a,
+                packet.chan_id_on_a,
+                packet.seq_on_a,
+                packet_commitment,
+            );
+
+        ctx.store_update_time(
+            ClientId::default(),
+            client_height,
+            Timestamp::from_nanoseconds(1000).unwrap(),
+        )
+        .unwrap();
+        ctx.store_update_height(
+            ClientId::default(),
+            client_height,
+            Height::new(0, 5).unwrap(),
+        )
+        .unwrap();
+
+        validate(&ctx, &msg)
+    }
+
+    #[rstest]
+    fn timeout_ordered_channel_membership_proof(fixture: Fixture) {
+        let chan_end_on_a = fixture.chan_end_on_a_ordered.clone();
+
+        let res = validate_with_proof_kind(fixture, chan_end_on_a, ProofKind::Membership);
+
+        assert!(
+            res.is_ok(),
+            "Validation succeeds with a proof of the next sequence to receive. err: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn timeout_ordered_channel_fail_non_membership_proof(fixture: Fixture) {
+        let chan_end_on_a = fixture.chan_end_on_a_ordered.clone();
+
+        let res = validate_with_proof_kind(fixture, chan_end_on_a, ProofKind::NonMembership);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(
+                    PacketError::MismatchedTimeoutProof {
+                        ordering: Order::Ordered,
+                        expected: ProofKind::Membership,
+                        actual: ProofKind::NonMembership,
+                    }
+                ))
+            ),
+            "Validation fails with a proof of receipt absence on an ordered channel. got: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn timeout_unordered_channel_non_membership_proof(fixture: Fixture) {
+        let chan_end_on_a = fixture.chan_end_on_a_unordered.clone();
+
+        let res = validate_with_proof_kind(fixture, chan_end_on_a, ProofKind::NonMembership);
+
+        assert!(
+            res.is_ok(),
+            "Validation succeeds with a proof of receipt absence. err: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn timeout_unordered_channel_fail_membership_proof(fixture: Fixture) {
+        let chan_end_on_a = fixture.chan_end_on_a_unordered.clone();
+
+        let res = validate_with_proof_kind(fixture, chan_end_on_a, ProofKind::Membership);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(
+                    PacketError::MismatchedTimeoutProof {
+                        ordering: Order::Unordered,
+                        expected: ProofKind::NonMembership,
+                        actual: ProofKind::Membership,
+                    }
+                ))
+            ),
+            "Validation fails with a membership proof on an unordered channel. got: {res:?}"
+        )
+    }
+
     #[rstest]
     fn timeout_unordered_chan_execute(fixture: Fixture) {
         let Fixture {
@@ -696,4 +937,213 @@ mod tests {
         ));
         assert!(matches!(ctx.events[3], IbcEvent::ChannelClosed(_)));
     }
+
+    /// Builds chain A with a client tracking the latest block of chain B.
+    fn ctx_tracking_latest_block_of(
+        ctx_b: &MockContext,
+        msg: &MsgTimeout,
+        conn_end_on_a: ConnectionEnd,
+        chan_end_on_a: ChannelEnd,
+    ) -> MockContext {
+        let height_of_b = ctx_b.latest_height();
+        let consensus_state_of_b = ctx_b.host_consensus_state(&height_of_b).unwrap();
+        let packet_commitment = compute_packet_commitment(
+            &msg.packet.data,
+            &msg.packet.timeout_height_on_b,
+            &msg.packet.timeout_timestamp_on_b,
+        );
+
+        let mut ctx_a = MockContext::default()
+            .with_client(&ClientId::default(), height_of_b)
+            .with_connection(ConnectionId::default(), conn_end_on_a)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end_on_a)
+            .with_packet_commitment(
+                msg.packet.port_id_on_a.clone(),
+                msg.packet.chan_id_on_a.clone(),
+                msg.packet.seq_on_a,
+                packet_commitment,
+            );
+        // Keep A's clock ahead of B's latest block, so that the client is not
+        // considered to be tracking a block from the future.
+        ctx_a.advance_host_time(
+            ctx_b
+                .host_timestamp()
+                .unwrap()
+                .duration_since(&ctx_a.host_timestamp().unwrap())
+                .unwrap_or(ZERO_DURATION),
+        );
+        ctx_a
+            .store_consensus_state(
+                ClientConsensusStatePath::new(&ClientId::default(), &height_of_b),
+                consensus_state_of_b,
+            )
+            .unwrap();
+        ctx_a
+            .store_update_time(
+                ClientId::default(),
+                height_of_b,
+                Timestamp::from_nanoseconds(1000).unwrap(),
+            )
+            .unwrap();
+        ctx_a
+            .store_update_height(ClientId::default(), height_of_b, Height::new(0, 1).unwrap())
+            .unwrap();
+
+        ctx_a
+    }
+
+    #[rstest]
+    fn timeout_succeeds_after_counterparty_time_advances(fixture: Fixture) {
+        let Fixture {
+            conn_end_on_a,
+            chan_end_on_a_unordered,
+            ..
+        } = fixture;
+
+        let mut ctx_b = MockContext::default();
+        let deadline =
+            (ctx_b.host_timestamp().unwrap() + core::time::Duration::from_secs(3600)).unwrap();
+
+        let msg_at = |proof_height: Height| {
+            MsgTimeout::try_from(get_dummy_raw_msg_timeout(
+                proof_height.revision_height(),
+                0,
+                deadline.nanoseconds(),
+            ))
+            .unwrap()
+        };
+
+        let msg = msg_at(ctx_b.latest_height());
+        let ctx_a = ctx_tracking_latest_block_of(
+            &ctx_b,
+            &msg,
+            conn_end_on_a.clone(),
+            chan_end_on_a_unordered.clone(),
+        );
+        let res = validate(&ctx_a, &msg);
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(
+                    PacketError::PacketTimeoutNotReached { .. }
+                ))
+            ),
+            "Timeout should fail before chain B reaches the deadline: {res:?}"
+        );
+
+        ctx_b.advance_host_time(core::time::Duration::from_secs(2 * 3600));
+
+        let msg = msg_at(ctx_b.latest_height());
+        let ctx_a =
+            ctx_tracking_latest_block_of(&ctx_b, &msg, conn_end_on_a, chan_end_on_a_unordered);
+        let res = validate(&ctx_a, &msg);
+
+        assert!(
+            res.is_ok(),
+            "Timeout should succeed once chain B is past the deadline: {res:?}"
+        );
+    }
+
+    /// Builds a context with a connection whose client tracks the host
+    /// itself, so that non-membership is checked against receipts stored in
+    /// the context.
+    fn localhost_ctx(msg: &MsgTimeout) -> (MockContext, ConnectionEnd, ReceiptPath) {
+        let client_id = ClientId::new(localhost_client_type(), 0).unwrap();
+        let ctx = MockContext::default().with_client_parametrized(
+            &client_id,
+            msg.proof_height_on_b,
+            Some(localhost_client_type()),
+            None,
+        );
+        let conn_end_on_a = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id,
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                ValidationContext::commitment_prefix(&ctx),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+        let receipt_path_on_b = ReceiptPath::new(
+            &msg.packet.port_id_on_b,
+            &msg.packet.chan_id_on_b,
+            msg.packet.seq_on_a,
+        );
+
+        (ctx, conn_end_on_a, receipt_path_on_b)
+    }
+
+    #[rstest]
+    fn verify_packet_receipt_absence_unreceived(fixture: Fixture) {
+        let Fixture { msg, .. } = fixture;
+        let (ctx, conn_end_on_a, receipt_path_on_b) = localhost_ctx(&msg);
+
+        let res = verify_packet_receipt_absence(
+            &ctx,
+            &conn_end_on_a,
+            msg.proof_height_on_b,
+            &msg.proof_unreceived_on_b,
+            &receipt_path_on_b,
+        );
+
+        assert!(
+            res.is_ok(),
+            "Absence should be verified for a sequence that was never received: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn verify_packet_receipt_absence_fail_spoofed_receipt(fixture: Fixture) {
+        let Fixture { msg, .. } = fixture;
+        let (mut ctx, conn_end_on_a, receipt_path_on_b) = localhost_ctx(&msg);
+        ctx.store_packet_receipt(&receipt_path_on_b, Receipt::Ok)
+            .unwrap();
+
+        // the relayer claims the receipt is absent while it is present
+        let res = verify_packet_receipt_absence(
+            &ctx,
+            &conn_end_on_a,
+            msg.proof_height_on_b,
+            &msg.proof_unreceived_on_b,
+            &receipt_path_on_b,
+        );
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::PacketError(PacketError::Channel(
+                    ChannelError::PacketVerificationFailed { .. }
+                )))
+            ),
+            "Absence should not be verified once a receipt is stored: {res:?}"
+        )
+    }
+
+    #[rstest]
+    fn verify_packet_receipt_absence_fail_proof_height(fixture: Fixture) {
+        let Fixture { msg, .. } = fixture;
+        let (ctx, conn_end_on_a, receipt_path_on_b) = localhost_ctx(&msg);
+
+        let res = verify_packet_receipt_absence(
+            &ctx,
+            &conn_end_on_a,
+            msg.proof_height_on_b.increment(),
+            &msg.proof_unreceived_on_b,
+            &receipt_path_on_b,
+        );
+
+        assert!(
+            matches!(
+                res,
+                Err(ContextError::ClientError(
+                    ClientError::InvalidProofHeight { .. }
+                ))
+            ),
+            "Absence should not be verified above the latest client height: {res:?}"
+        )
+    }
 }
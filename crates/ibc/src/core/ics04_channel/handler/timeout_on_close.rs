@@ -5,11 +5,16 @@ use prost::Message;
 
 use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::proof::verify_membership;
 use crate::core::ics03_connection::delay::verify_conn_delay_passed;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
-use crate::core::ics04_channel::commitment::compute_packet_commitment;
+use crate::core::ics04_channel::commitment::compute_packet_commitment_with;
 use crate::core::ics04_channel::error::{ChannelError, PacketError};
+use crate::core::ics04_channel::handler::timeout::{
+    verify_packet_receipt_absence, verify_unreceived_proof_kind,
+};
+use crate::core::ics04_channel::handler::validate_channel_msg_signer;
 use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{
@@ -21,7 +26,9 @@ pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgTimeoutOnClose) -> Result<(), Context
 where
     Ctx: ValidationContext,
 {
-    ctx_a.validate_message_signer(&msg.signer)?;
+    validate_channel_msg_signer(ctx_a, &msg.signer)?;
+
+    ctx_a.validate_self_consistency()?;
 
     let packet = &msg.packet;
     let chan_end_path_on_a = ChannelEndPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
@@ -51,7 +58,8 @@ where
         Err(_) => return Ok(()),
     };
 
-    let expected_commitment_on_a = compute_packet_commitment(
+    let expected_commitment_on_a = compute_packet_commitment_with(
+        ctx_a.commitment_hasher(),
         &packet.data,
         &packet.timeout_height_on_b,
         &packet.timeout_timestamp_on_b,
@@ -85,10 +93,15 @@ where
         let consensus_state_of_b_on_a = ctx_a.consensus_state(&client_cons_state_path_on_a)?;
         let prefix_on_b = conn_end_on_a.counterparty().prefix();
         let port_id_on_b = chan_end_on_a.counterparty().port_id.clone();
-        let chan_id_on_b = chan_end_on_a
-            .counterparty()
-            .channel_id()
-            .ok_or(PacketError::Channel(ChannelError::MissingCounterparty))?;
+        let chan_id_on_b =
+            chan_end_on_a
+                .counterparty()
+                .channel_id()
+                .ok_or(PacketError::Channel(
+                    ChannelError::MissingCounterpartyChannelId {
+                        state: *chan_end_on_a.state(),
+                    },
+                ))?;
         let conn_id_on_b = conn_end_on_a.counterparty().connection_id().ok_or(
             PacketError::UndefinedConnectionCounterparty {
                 connection_id: chan_end_on_a.connection_hops()[0].clone(),
@@ -111,20 +124,23 @@ where
 
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-        client_state_of_b_on_a
-            .verify_membership(
-                prefix_on_b,
-                &msg.proof_unreceived_on_b,
-                consensus_state_of_b_on_a.root(),
-                Path::ChannelEnd(chan_end_path_on_b),
-                expected_chan_end_on_b.encode_vec(),
-            )
-            .map_err(ChannelError::VerifyChannelFailed)
-            .map_err(PacketError::Channel)?;
+        verify_membership(
+            ctx_a,
+            &client_state_of_b_on_a,
+            prefix_on_b,
+            &msg.proof_close_on_b,
+            consensus_state_of_b_on_a.root(),
+            Path::ChannelEnd(chan_end_path_on_b),
+            expected_chan_end_on_b.encode_vec(),
+        )
+        .map_err(ChannelError::VerifyChannelFailed)
+        .map_err(PacketError::Channel)?;
 
         verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, &conn_end_on_a)?;
 
-        let next_seq_recv_verification_result = if chan_end_on_a.order_matches(&Order::Ordered) {
+        verify_unreceived_proof_kind(ctx_a, chan_end_on_a.ordering, &msg.proof_unreceived_on_b)?;
+
+        if chan_end_on_a.order_matches(&Order::Ordered) {
             if packet.seq_on_a < msg.next_seq_recv_on_b {
                 return Err(PacketError::InvalidPacketSequence {
                     given_sequence: packet.seq_on_a,
@@ -141,13 +157,20 @@ where
                 }
             })?;
 
-            client_state_of_b_on_a.verify_membership(
+            verify_membership(
+                ctx_a,
+                &client_state_of_b_on_a,
                 conn_end_on_a.counterparty().prefix(),
                 &msg.proof_unreceived_on_b,
                 consensus_state_of_b_on_a.root(),
                 Path::SeqRecv(seq_recv_path_on_b),
                 value,
             )
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.next_seq_recv_on_b,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
         } else {
             let receipt_path_on_b = ReceiptPath::new(
                 &msg.packet.port_id_on_b,
@@ -155,19 +178,14 @@ where
                 msg.packet.seq_on_a,
             );
 
-            client_state_of_b_on_a.verify_non_membership(
-                conn_end_on_a.counterparty().prefix(),
+            verify_packet_receipt_absence(
+                ctx_a,
+                &conn_end_on_a,
+                msg.proof_height_on_b,
                 &msg.proof_unreceived_on_b,
-                consensus_state_of_b_on_a.root(),
-                Path::Receipt(receipt_path_on_b),
-            )
-        };
-        next_seq_recv_verification_result
-            .map_err(|e| ChannelError::PacketVerificationFailed {
-                sequence: msg.next_seq_recv_on_b,
-                client_error: e,
-            })
-            .map_err(PacketError::Channel)?;
+                &receipt_path_on_b,
+            )?;
+        }
     };
 
     Ok(())
@@ -7,6 +7,7 @@ pub mod error;
 pub mod events;
 
 pub(crate) mod handler;
+pub use handler::timeout::verify_packet_receipt_absence;
 pub mod msgs;
 pub mod packet;
 pub mod timeout;
@@ -28,6 +28,29 @@ pub struct MsgRecvPacket {
     pub signer: Signer,
 }
 
+impl MsgRecvPacket {
+    /// Builds a `MsgRecvPacket` from a packet and the proof of its commitment
+    /// on the sending chain, rejecting packets that fail
+    /// [`Packet::validate_basic`].
+    ///
+    /// The proof height is always set, since a [`Height`] cannot be zero.
+    pub fn build(
+        packet: Packet,
+        proof_commitment_on_a: CommitmentProofBytes,
+        proof_height_on_a: Height,
+        signer: Signer,
+    ) -> Result<Self, PacketError> {
+        packet.validate_basic()?;
+
+        Ok(Self {
+            packet,
+            proof_commitment_on_a,
+            proof_height_on_a,
+            signer,
+        })
+    }
+}
+
 impl Msg for MsgRecvPacket {
     type Raw = RawMsgRecvPacket;
 
@@ -132,7 +155,11 @@ mod test {
     use crate::core::ics04_channel::error::PacketError;
     use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
     use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
-    use crate::test_utils::get_dummy_bech32_account;
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::core::ics04_channel::packet::{Packet, Sequence};
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::test_utils::{get_dummy_account_id, get_dummy_bech32_account, get_dummy_proof};
+    use crate::Height;
 
     #[test]
     fn msg_recv_packet_try_from_raw() {
@@ -199,4 +226,55 @@ mod test {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    #[test]
+    fn build_valid_msg() {
+        let packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+        let proof_height = Height::new(0, 20).unwrap();
+
+        let msg = MsgRecvPacket::build(
+            packet.clone(),
+            get_dummy_proof().try_into().unwrap(),
+            proof_height,
+            get_dummy_account_id(),
+        )
+        .unwrap();
+
+        assert_eq!(msg.packet, packet);
+        assert_eq!(msg.proof_height_on_a, proof_height);
+
+        let msg_back = MsgRecvPacket::try_from(RawMsgRecvPacket::from(msg.clone())).unwrap();
+        assert_eq!(msg, msg_back);
+    }
+
+    #[test]
+    fn build_rejects_malformed_packet() {
+        let packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+        let build = |packet: Packet| {
+            MsgRecvPacket::build(
+                packet,
+                get_dummy_proof().try_into().unwrap(),
+                Height::new(0, 20).unwrap(),
+                get_dummy_account_id(),
+            )
+        };
+
+        let res = build(Packet {
+            seq_on_a: Sequence::from(0),
+            ..packet.clone()
+        });
+        assert!(matches!(res, Err(PacketError::ZeroPacketSequence)));
+
+        let res = build(Packet {
+            data: Vec::new(),
+            ..packet.clone()
+        });
+        assert!(matches!(res, Err(PacketError::ZeroPacketData)));
+
+        let res = build(Packet {
+            timeout_height_on_b: TimeoutHeight::Never,
+            ..packet
+        });
+        assert!(matches!(res, Err(PacketError::MissingTimeout)));
+    }
 }
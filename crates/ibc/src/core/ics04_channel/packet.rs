@@ -2,6 +2,7 @@
 
 use crate::prelude::*;
 
+use alloc::collections::BTreeSet;
 use core::str::FromStr;
 
 use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
@@ -12,6 +13,9 @@ use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::core::timestamp::{Expiry::Expired, Timestamp};
 use crate::Height;
 
+/// Default maximum size in bytes of the data of a received packet (256 KiB).
+pub const DEFAULT_MAX_PACKET_DATA_SIZE: usize = 256 * 1024;
+
 /// Enumeration of proof carrying ICS4 message, helper for relayer.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PacketMsgType {
@@ -91,6 +95,12 @@ impl Sequence {
     pub fn increment(&self) -> Sequence {
         Sequence(self.0 + 1)
     }
+
+    /// Returns the sequence following this one, or `None` if it would
+    /// overflow.
+    pub fn checked_increment(&self) -> Option<Sequence> {
+        self.0.checked_add(1).map(Sequence)
+    }
 }
 
 impl From<u64> for Sequence {
@@ -205,6 +215,71 @@ impl Packet {
 
         height_timed_out || timestamp_timed_out
     }
+
+    /// Checks the stateless validity conditions of a packet: the sequence
+    /// is non-zero, the data is non-empty and at least one of the timeout
+    /// height and the timeout timestamp is set.
+    pub fn validate_basic(&self) -> Result<(), PacketError> {
+        if self.seq_on_a.is_zero() {
+            return Err(PacketError::ZeroPacketSequence);
+        }
+
+        if self.data.is_empty() {
+            return Err(PacketError::ZeroPacketData);
+        }
+
+        // Packet timeout height and packet timeout timestamp cannot both be unset.
+        if !self.timeout_height_on_b.is_set() && !self.timeout_timestamp_on_b.is_set() {
+            return Err(PacketError::MissingTimeout);
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether `packet` is timed out on the destination chain at
+/// `host_height` and `host_timestamp`, as the timeout handler checks against
+/// the destination chain's consensus state.
+///
+/// This needs no context, e.g. for relayers to select the packets to time
+/// out before querying proofs. Packets with neither a timeout height nor a
+/// timeout timestamp never time out.
+pub fn is_packet_timed_out(
+    packet: &Packet,
+    host_height: Height,
+    host_timestamp: Timestamp,
+) -> bool {
+    packet.timed_out(&host_timestamp, host_height)
+}
+
+/// Returns the sequences of the packets committed on an unordered channel that
+/// the counterparty has not received, given the sequences of the receipts it
+/// stored. The sequences are returned in the order of `commitments`.
+///
+/// This needs no context, e.g. for relayers to select the packets to relay
+/// from the results of commitment and receipt queries.
+pub fn unreceived_packets(commitments: &[Sequence], received: &[Sequence]) -> Vec<Sequence> {
+    let received: BTreeSet<&Sequence> = received.iter().collect();
+    commitments
+        .iter()
+        .filter(|seq| !received.contains(seq))
+        .copied()
+        .collect()
+}
+
+/// Returns the sequences of the packets committed on an ordered channel that
+/// the counterparty has not received, i.e. those at or after its
+/// `next_sequence_recv`. The sequences are returned in the order of
+/// `commitments`.
+pub fn unreceived_packets_ordered(
+    commitments: &[Sequence],
+    next_sequence_recv: Sequence,
+) -> Vec<Sequence> {
+    commitments
+        .iter()
+        .filter(|seq| **seq >= next_sequence_recv)
+        .copied()
+        .collect()
 }
 
 /// Custom debug output to omit the packet data
@@ -228,14 +303,6 @@ impl TryFrom<RawPacket> for Packet {
     type Error = PacketError;
 
     fn try_from(raw_pkt: RawPacket) -> Result<Self, Self::Error> {
-        if Sequence::from(raw_pkt.sequence).is_zero() {
-            return Err(PacketError::ZeroPacketSequence);
-        }
-
-        if raw_pkt.data.is_empty() {
-            return Err(PacketError::ZeroPacketData);
-        }
-
         // Note: ibc-go currently (July 2022) incorrectly treats the timeout
         // heights `{revision_number : >0, revision_height: 0}` as valid
         // timeouts. However, heights with `revision_height == 0` are invalid in
@@ -252,12 +319,7 @@ impl TryFrom<RawPacket> for Packet {
         let timeout_timestamp_on_b = Timestamp::from_nanoseconds(raw_pkt.timeout_timestamp)
             .map_err(PacketError::InvalidPacketTimestamp)?;
 
-        // Packet timeout height and packet timeout timestamp cannot both be unset.
-        if !packet_timeout_height.is_set() && !timeout_timestamp_on_b.is_set() {
-            return Err(PacketError::MissingTimeout);
-        }
-
-        Ok(Packet {
+        let packet = Packet {
             seq_on_a: Sequence::from(raw_pkt.sequence),
             port_id_on_a: raw_pkt.source_port.parse()?,
             chan_id_on_a: raw_pkt.source_channel.parse()?,
@@ -266,7 +328,11 @@ impl TryFrom<RawPacket> for Packet {
             data: raw_pkt.data,
             timeout_height_on_b: packet_timeout_height,
             timeout_timestamp_on_b,
-        })
+        };
+
+        packet.validate_basic()?;
+
+        Ok(packet)
     }
 }
 
@@ -321,7 +387,12 @@ mod tests {
     use ibc_proto::ibc::core::client::v1::Height as RawHeight;
 
     use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
-    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::packet::{
+        is_packet_timed_out, unreceived_packets, unreceived_packets_ordered, Packet, Sequence,
+    };
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::timestamp::Timestamp;
+    use crate::Height;
 
     #[test]
     fn packet_try_from_raw() {
@@ -503,4 +574,118 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    fn height(revision_height: u64) -> Height {
+        Height::new(0, revision_height).unwrap()
+    }
+
+    fn timestamp(nanoseconds: u64) -> Timestamp {
+        Timestamp::from_nanoseconds(nanoseconds).unwrap()
+    }
+
+    #[test]
+    fn packet_timed_out_at_height() {
+        let packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+
+        assert!(!is_packet_timed_out(
+            &packet,
+            height(10),
+            timestamp(u64::MAX)
+        ));
+        assert!(is_packet_timed_out(&packet, height(11), timestamp(1)));
+    }
+
+    #[test]
+    fn packet_timed_out_at_timestamp() {
+        let packet = Packet::try_from(get_dummy_raw_packet(0, 1000)).unwrap();
+
+        assert!(!is_packet_timed_out(
+            &packet,
+            height(u64::MAX),
+            timestamp(1000)
+        ));
+        assert!(is_packet_timed_out(&packet, height(1), timestamp(1001)));
+    }
+
+    #[test]
+    fn packet_without_timeout_never_times_out() {
+        let mut packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+        packet.timeout_height_on_b = TimeoutHeight::Never;
+        packet.timeout_timestamp_on_b = Timestamp::none();
+
+        assert!(!is_packet_timed_out(
+            &packet,
+            height(u64::MAX),
+            timestamp(u64::MAX)
+        ));
+    }
+
+    /// A zero timeout height means "no timeout height" in both directions of
+    /// the proto conversion
+    #[test]
+    fn packet_zero_timeout_height_roundtrip() {
+        let raw_packet = get_dummy_raw_packet(0, 1000);
+        assert_eq!(
+            raw_packet.timeout_height,
+            Some(RawHeight {
+                revision_number: 0,
+                revision_height: 0,
+            })
+        );
+
+        let packet = Packet::try_from(raw_packet.clone()).unwrap();
+        assert_eq!(packet.timeout_height_on_b, TimeoutHeight::Never);
+        assert_eq!(RawPacket::from(packet), raw_packet);
+
+        let raw_packet = RawPacket {
+            timeout_height: None,
+            ..raw_packet
+        };
+        let packet = Packet::try_from(raw_packet).unwrap();
+        assert_eq!(packet.timeout_height_on_b, TimeoutHeight::Never);
+    }
+
+    #[test]
+    fn packet_timed_out_with_both_timeouts_set() {
+        let packet = Packet::try_from(get_dummy_raw_packet(10, 1000)).unwrap();
+
+        assert!(!is_packet_timed_out(&packet, height(10), timestamp(1000)));
+        assert!(is_packet_timed_out(&packet, height(11), timestamp(1000)));
+        assert!(is_packet_timed_out(&packet, height(10), timestamp(1001)));
+    }
+
+    fn seqs(nrs: &[u64]) -> Vec<Sequence> {
+        nrs.iter().copied().map(Sequence::from).collect()
+    }
+
+    #[test]
+    fn unreceived_packets_unordered() {
+        assert_eq!(
+            unreceived_packets(&seqs(&[1, 2, 3, 5, 8]), &seqs(&[2, 5, 6])),
+            seqs(&[1, 3, 8])
+        );
+        assert_eq!(
+            unreceived_packets(&seqs(&[1, 2]), &seqs(&[1, 2])),
+            seqs(&[])
+        );
+        assert_eq!(unreceived_packets(&seqs(&[1, 2]), &[]), seqs(&[1, 2]));
+        assert_eq!(unreceived_packets(&[], &seqs(&[1, 2])), seqs(&[]));
+    }
+
+    #[test]
+    fn unreceived_packets_ordered_channel() {
+        assert_eq!(
+            unreceived_packets_ordered(&seqs(&[3, 4, 5, 6]), 5.into()),
+            seqs(&[5, 6])
+        );
+        assert_eq!(
+            unreceived_packets_ordered(&seqs(&[3, 4]), 5.into()),
+            seqs(&[])
+        );
+        assert_eq!(
+            unreceived_packets_ordered(&seqs(&[3, 4]), 1.into()),
+            seqs(&[3, 4])
+        );
+        assert_eq!(unreceived_packets_ordered(&[], 1.into()), seqs(&[]));
+    }
 }
@@ -48,6 +48,47 @@ impl Version {
         &self.0
     }
 
+    /// Builds the version of an application channel, wrapped in the JSON
+    /// metadata `{"fee_version":...,"app_version":...}` of fee-enabled
+    /// (ICS-29) channels when a `fee` version is given.
+    #[cfg(feature = "serde")]
+    pub fn new_app_version(app: &str, fee: Option<&str>) -> Self {
+        match fee {
+            Some(fee) => {
+                let metadata = FeeMetadata {
+                    fee_version: fee.to_string(),
+                    app_version: app.to_string(),
+                };
+                Self::new(
+                    serde_json::to_string(&metadata)
+                        .expect("serializing strings to JSON never fails"),
+                )
+            }
+            None => Self::new(app.to_string()),
+        }
+    }
+
+    /// Returns the version of the application, unwrapping it from the fee
+    /// metadata if this is the version of a fee-enabled channel.
+    #[cfg(feature = "serde")]
+    pub fn app_version(&self) -> Version {
+        match self.fee_metadata() {
+            Some(metadata) => Self::new(metadata.app_version),
+            None => self.clone(),
+        }
+    }
+
+    /// Returns the fee version if this is the version of a fee-enabled channel.
+    #[cfg(feature = "serde")]
+    pub fn fee_version(&self) -> Option<String> {
+        self.fee_metadata().map(|metadata| metadata.fee_version)
+    }
+
+    #[cfg(feature = "serde")]
+    fn fee_metadata(&self) -> Option<FeeMetadata> {
+        serde_json::from_str(&self.0).ok()
+    }
+
     pub fn verify_is_expected(&self, expected: Version) -> Result<(), ChannelError> {
         if self != &expected {
             return Err(ChannelError::VersionNotSupported {
@@ -59,6 +100,15 @@ impl Version {
     }
 }
 
+/// The version metadata of fee-enabled channels, as defined by ICS-29.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FeeMetadata {
+    fee_version: String,
+    app_version: String,
+}
+
 impl From<String> for Version {
     fn from(s: String) -> Self {
         Self::new(s)
@@ -85,3 +135,39 @@ impl Display for Version {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_version_roundtrip() {
+        let version = Version::new_app_version("ics20-1", Some("ics29-1"));
+
+        assert_eq!(
+            version.as_str(),
+            r#"{"fee_version":"ics29-1","app_version":"ics20-1"}"#
+        );
+        assert_eq!(version.app_version(), Version::new("ics20-1".to_string()));
+        assert_eq!(version.fee_version(), Some("ics29-1".to_string()));
+        assert_eq!(
+            Version::new_app_version(
+                version.app_version().as_str(),
+                version.fee_version().as_deref()
+            ),
+            version
+        );
+    }
+
+    #[test]
+    fn app_version_without_fee() {
+        let version = Version::new_app_version("ics20-1", None);
+
+        assert_eq!(version, Version::new("ics20-1".to_string()));
+        assert_eq!(version.app_version(), version);
+        assert_eq!(version.fee_version(), None);
+
+        let other_json = Version::new(r#"{"app_version":"ics20-1"}"#.to_string());
+        assert_eq!(other_json.app_version(), other_json);
+    }
+}
@@ -72,6 +72,12 @@ pub struct CommitmentProofBytes {
     bytes: Vec<u8>,
 }
 
+impl CommitmentProofBytes {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
 impl fmt::Debug for CommitmentProofBytes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let hex = Hex::upper_case()
@@ -151,6 +157,24 @@ pub struct CommitmentPrefix {
 }
 
 impl CommitmentPrefix {
+    /// Builds a commitment prefix out of the given bytes, which must not be
+    /// empty.
+    pub fn new(bytes: Vec<u8>) -> Result<Self, CommitmentError> {
+        if bytes.is_empty() {
+            return Err(CommitmentError::EmptyCommitmentPrefix);
+        }
+        Ok(Self { bytes })
+    }
+
+    /// Checks that the prefix is not empty, which may not be the case for
+    /// prefixes built with `Default` or deserialized with serde.
+    pub fn verify_not_empty(&self) -> Result<(), CommitmentError> {
+        if self.bytes.is_empty() {
+            return Err(CommitmentError::EmptyCommitmentPrefix);
+        }
+        Ok(())
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
@@ -164,11 +188,7 @@ impl TryFrom<Vec<u8>> for CommitmentPrefix {
     type Error = CommitmentError;
 
     fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
-        if bytes.is_empty() {
-            Err(Self::Error::EmptyCommitmentPrefix)
-        } else {
-            Ok(Self { bytes })
-        }
+        Self::new(bytes)
     }
 }
 
@@ -192,6 +212,31 @@ impl serde::Serialize for CommitmentPrefix {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitment_prefix_rejects_empty_bytes() {
+        assert!(matches!(
+            CommitmentPrefix::new(vec![]),
+            Err(CommitmentError::EmptyCommitmentPrefix)
+        ));
+        assert!(matches!(
+            CommitmentPrefix::try_from(vec![]),
+            Err(CommitmentError::EmptyCommitmentPrefix)
+        ));
+        assert!(matches!(
+            CommitmentPrefix::default().verify_not_empty(),
+            Err(CommitmentError::EmptyCommitmentPrefix)
+        ));
+
+        let prefix = CommitmentPrefix::new(b"ibc".to_vec()).unwrap();
+        assert_eq!(prefix.as_bytes(), b"ibc");
+        assert!(prefix.verify_not_empty().is_ok());
+    }
+}
+
 #[cfg(test)]
 pub mod test_util {
     use super::CommitmentProofBytes;
@@ -15,6 +15,8 @@ use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot
 use crate::core::ics23_commitment::error::CommitmentError;
 use crate::core::ics23_commitment::specs::ProofSpecs;
 
+use displaydoc::Display;
+
 pub fn apply_prefix(prefix: &CommitmentPrefix, mut path: Vec<String>) -> MerklePath {
     let mut key_path: Vec<String> = vec![format!("{prefix:?}")];
     key_path.append(&mut path);
@@ -53,7 +55,47 @@ impl From<MerkleProof> for RawMerkleProof {
     }
 }
 
+/// What a Merkle proof attests about the key it is checked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum ProofKind {
+    /// membership
+    Membership,
+    /// non-membership
+    NonMembership,
+}
+
 impl MerkleProof {
+    /// Returns whether this proof attests to the presence or the absence of
+    /// its key, as told by the proof for the key itself, i.e. the first one.
+    ///
+    /// Returns `None` if that proof is neither an existence nor a
+    /// non-existence proof.
+    pub fn kind(&self) -> Option<ProofKind> {
+        match self.proofs.first().and_then(|proof| proof.proof.as_ref()) {
+            Some(Proof::Exist(_)) => Some(ProofKind::Membership),
+            Some(Proof::Nonexist(_)) => Some(ProofKind::NonMembership),
+            _ => None,
+        }
+    }
+
+    /// Returns the value attested by the proof for the key itself, i.e. the
+    /// first one, if that proof is an existence proof.
+    pub fn value(&self) -> Option<&[u8]> {
+        match self.proofs.first().and_then(|proof| proof.proof.as_ref()) {
+            Some(Proof::Exist(proof)) => Some(&proof.value),
+            _ => None,
+        }
+    }
+
+    /// Returns the key of the proof for the key itself, i.e. the first one, if
+    /// that proof is an existence proof.
+    pub fn key(&self) -> Option<&[u8]> {
+        match self.proofs.first().and_then(|proof| proof.proof.as_ref()) {
+            Some(Proof::Exist(proof)) => Some(&proof.key),
+            _ => None,
+        }
+    }
+
     pub fn verify_membership(
         &self,
         specs: &ProofSpecs,
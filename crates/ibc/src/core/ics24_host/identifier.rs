@@ -507,6 +507,8 @@ pub enum IdentifierError {
     },
     /// identifier `{id}` must only contain alphanumeric characters or `.`, `_`, `+`, `-`, `#`, - `[`, `]`, `<`, `>`
     InvalidCharacter { id: String },
+    /// identifier `{id}` has a numeric suffix that overflows `u64`
+    OverflowingSuffix { id: String },
     /// identifier prefix `{prefix}` is invalid
     InvalidPrefix { prefix: String },
     /// identifier cannot be empty
@@ -549,4 +551,37 @@ mod tests {
         assert!(ChainId::from_str("/chainA-1").is_err());
         assert!(ChainId::from_str("chainA-1-").is_err());
     }
+
+    #[test]
+    fn test_connection_id_overflowing_suffix() {
+        assert!(ConnectionId::from_str(&format!("connection-{}", u64::MAX)).is_ok());
+        assert!(matches!(
+            ConnectionId::from_str("connection-99999999999999999999"),
+            Err(IdentifierError::OverflowingSuffix { .. })
+        ));
+    }
+
+    #[test]
+    fn test_client_id_overflowing_suffix() {
+        assert!(ClientId::from_str(&format!("07-tendermint-{}", u64::MAX)).is_ok());
+        assert!(matches!(
+            ClientId::from_str("07-tendermint-18446744073709551616"),
+            Err(IdentifierError::OverflowingSuffix { .. })
+        ));
+    }
+
+    #[test]
+    fn test_identifier_too_long() {
+        let too_long = format!("connection-{}", "1".repeat(54));
+        assert!(matches!(
+            ConnectionId::from_str(&too_long),
+            Err(IdentifierError::InvalidLength { length: 65, .. })
+        ));
+
+        let too_long = format!("07-tendermint-{}", "1".repeat(51));
+        assert!(matches!(
+            ClientId::from_str(&too_long),
+            Err(IdentifierError::InvalidLength { length: 65, .. })
+        ));
+    }
 }
@@ -79,6 +79,21 @@ pub fn validate_prefix_length(
     Ok(())
 }
 
+/// Checks that a numeric suffix following the last "-" of the identifier, if
+/// present, fits in a `u64`, since identifiers are formed from a `u64` counter.
+pub fn validate_numeric_suffix(id: &str) -> Result<(), Error> {
+    if let Some((_, suffix)) = id.rsplit_once('-') {
+        if !suffix.is_empty()
+            && suffix.bytes().all(|b| b.is_ascii_digit())
+            && suffix.parse::<u64>().is_err()
+        {
+            return Err(Error::OverflowingSuffix { id: id.into() });
+        }
+    }
+
+    Ok(())
+}
+
 /// Default validator function for the Client types.
 pub fn validate_client_type(id: &str) -> Result<(), Error> {
     validate_identifier_chars(id)?;
@@ -88,19 +103,21 @@ pub fn validate_client_type(id: &str) -> Result<(), Error> {
 /// Default validator function for Client identifiers.
 ///
 /// A valid client identifier must be between 9-64 characters as specified in
-/// the ICS-24 spec.
+/// the ICS-24 spec, and its numeric suffix, if any, must fit in a `u64`.
 pub fn validate_client_identifier(id: &str) -> Result<(), Error> {
     validate_identifier_chars(id)?;
-    validate_identifier_length(id, 9, 64)
+    validate_identifier_length(id, 9, 64)?;
+    validate_numeric_suffix(id)
 }
 
 /// Default validator function for Connection identifiers.
 ///
 /// A valid connection identifier must be between 10-64 characters as specified
-/// in the ICS-24 spec.
+/// in the ICS-24 spec, and its numeric suffix, if any, must fit in a `u64`.
 pub fn validate_connection_identifier(id: &str) -> Result<(), Error> {
     validate_identifier_chars(id)?;
-    validate_identifier_length(id, 10, 64)
+    validate_identifier_length(id, 10, 64)?;
+    validate_numeric_suffix(id)
 }
 
 /// Default validator function for Port identifiers.
@@ -32,19 +32,33 @@ pub mod router;
 pub mod events;
 pub mod timestamp;
 
+#[cfg(feature = "async")]
+mod async_context;
 mod context;
+pub(crate) mod forward;
 mod handler;
 mod msgs;
+mod trace;
 
 pub use handler::dispatch;
 pub use handler::execute;
 pub use handler::validate;
+pub use handler::HostHeightGuard;
 
+pub use trace::{dispatch_trace, ExecutionTrace, TraceEntry};
+
+#[cfg(feature = "async")]
+pub use async_context::*;
 pub use context::*;
 
+pub use msgs::relayer_of;
 pub use msgs::Msg;
 pub use msgs::MsgEnvelope;
 
+#[cfg(feature = "async")]
+pub use ics04_channel::handler::chan_open_confirm::{
+    chan_open_confirm_execute_async, chan_open_confirm_validate_async,
+};
 pub use ics04_channel::handler::send_packet::{
     send_packet, send_packet_execute, send_packet_validate,
 };
@@ -4,7 +4,7 @@ use ibc_proto::google::protobuf::Any;
 
 use crate::core::context::RouterError;
 use crate::core::ics02_client::msgs::{
-    create_client, misbehaviour, update_client, upgrade_client, ClientMsg,
+    create_client, misbehaviour, update_client, update_client_batch, upgrade_client, ClientMsg,
 };
 use crate::core::ics03_connection::msgs::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try, ConnectionMsg,
@@ -13,6 +13,7 @@ use crate::core::ics04_channel::msgs::{
     acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
     chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close, ChannelMsg, PacketMsg,
 };
+use crate::signer::Signer;
 use ibc_proto::protobuf::Protobuf;
 
 /// Trait to be implemented by all IBC messages
@@ -27,10 +28,12 @@ pub trait Msg: Clone {
         prost::Message::encode_to_vec(&raw_msg)
     }
 
-    fn to_any(self) -> Any {
+    /// Encodes this message as a `prost_types::Any`, which [`MsgEnvelope`]
+    /// decodes back through its `TryFrom<Any>` implementation.
+    fn to_any(&self) -> Any {
         Any {
             type_url: self.type_url(),
-            value: self.get_sign_bytes(),
+            value: self.clone().get_sign_bytes(),
         }
     }
 }
@@ -61,6 +64,14 @@ impl TryFrom<Any> for MsgEnvelope {
                     .map_err(RouterError::MalformedMessageBytes)?;
                 Ok(MsgEnvelope::Client(ClientMsg::UpdateClient(domain_msg)))
             }
+            update_client_batch::TYPE_URL => {
+                let domain_msg =
+                    update_client_batch::MsgUpdateClientBatch::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Client(ClientMsg::UpdateClientBatch(
+                    domain_msg,
+                )))
+            }
             upgrade_client::TYPE_URL => {
                 let domain_msg = upgrade_client::MsgUpgradeClient::decode_vec(&any_msg.value)
                     .map_err(RouterError::MalformedMessageBytes)?;
@@ -157,3 +168,239 @@ impl TryFrom<Any> for MsgEnvelope {
         }
     }
 }
+
+/// Returns the relayer that submitted a packet message, i.e. its signer, for
+/// instance so that ICS-29 fees can be paid out to it.
+///
+/// Returns `None` for client, connection and channel handshake messages,
+/// which do not relay packets.
+pub fn relayer_of(msg: &MsgEnvelope) -> Option<&Signer> {
+    match msg {
+        MsgEnvelope::Packet(PacketMsg::Recv(msg)) => Some(&msg.signer),
+        MsgEnvelope::Packet(PacketMsg::Ack(msg)) => Some(&msg.signer),
+        MsgEnvelope::Packet(PacketMsg::Timeout(msg)) => Some(&msg.signer),
+        MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(msg)) => Some(&msg.signer),
+        MsgEnvelope::Client(_) | MsgEnvelope::Connection(_) | MsgEnvelope::Channel(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::fmt::Debug;
+
+    use crate::core::ics02_client::height::Height;
+    use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
+    use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+    use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+    use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
+    use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+    use crate::core::ics04_channel::msgs::chan_close_confirm::test_util::get_dummy_raw_msg_chan_close_confirm;
+    use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+    use crate::core::ics04_channel::msgs::chan_close_init::test_util::get_dummy_raw_msg_chan_close_init;
+    use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+    use crate::core::ics04_channel::msgs::chan_open_ack::test_util::get_dummy_raw_msg_chan_open_ack;
+    use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::test_util::get_dummy_raw_msg_chan_open_confirm;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+    use crate::core::ics04_channel::msgs::chan_open_init::test_util::get_dummy_raw_msg_chan_open_init;
+    use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::core::ics04_channel::msgs::chan_open_try::test_util::get_dummy_raw_msg_chan_open_try;
+    use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+    use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
+    use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use crate::core::ics04_channel::msgs::timeout::test_util::get_dummy_raw_msg_timeout;
+    use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+    use crate::core::ics04_channel::msgs::timeout_on_close::test_util::get_dummy_raw_msg_timeout_on_close;
+    use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+    use crate::core::ics24_host::identifier::ClientId;
+    use crate::mock::client_state::MockClientState;
+    use crate::mock::consensus_state::MockConsensusState;
+    use crate::mock::header::MockHeader;
+    use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
+    use crate::test_utils::get_dummy_account_id;
+
+    /// Checks that `msg` is encoded under the Cosmos SDK `type_url`, that
+    /// `MsgEnvelope` accepts the encoding, and that it decodes back to `msg`.
+    fn assert_any_round_trip<M>(msg: M, type_url: &str)
+    where
+        M: Msg + TryFrom<<M as Msg>::Raw> + PartialEq + Debug,
+        <M as Msg>::Raw: Default,
+        <M as TryFrom<<M as Msg>::Raw>>::Error: Debug,
+    {
+        let any = msg.to_any();
+
+        assert_eq!(any.type_url, type_url);
+        assert!(MsgEnvelope::try_from(any.clone()).is_ok());
+
+        let raw = <<M as Msg>::Raw as prost::Message>::decode(any.value.as_slice()).unwrap();
+        assert_eq!(M::try_from(raw).unwrap(), msg);
+    }
+
+    #[test]
+    fn test_client_msgs_any_round_trip() {
+        let height = Height::new(0, 10).unwrap();
+        let header = MockHeader::new(height);
+
+        assert_any_round_trip(
+            MsgCreateClient::new(
+                MockClientState::new(header).into(),
+                MockConsensusState::new(header).into(),
+                get_dummy_account_id(),
+            ),
+            "/ibc.core.client.v1.MsgCreateClient",
+        );
+        assert_any_round_trip(
+            MsgUpdateClient::new(ClientId::default(), header.into(), get_dummy_account_id()),
+            "/ibc.core.client.v1.MsgUpdateClient",
+        );
+        assert_any_round_trip(
+            MsgUpgradeClient::new_dummy(height),
+            "/ibc.core.client.v1.MsgUpgradeClient",
+        );
+        assert_any_round_trip(
+            MsgSubmitMisbehaviour {
+                client_id: ClientId::default(),
+                misbehaviour: MockMisbehaviour {
+                    client_id: ClientId::default(),
+                    header1: header,
+                    header2: header,
+                }
+                .into(),
+                signer: get_dummy_account_id(),
+            },
+            "/ibc.core.client.v1.MsgSubmitMisbehaviour",
+        );
+    }
+
+    #[test]
+    fn test_connection_msgs_any_round_trip() {
+        assert_any_round_trip(
+            MsgConnectionOpenInit::new_dummy(),
+            "/ibc.core.connection.v1.MsgConnectionOpenInit",
+        );
+        assert_any_round_trip(
+            MsgConnectionOpenTry::new_dummy(10, 34),
+            "/ibc.core.connection.v1.MsgConnectionOpenTry",
+        );
+        assert_any_round_trip(
+            MsgConnectionOpenAck::new_dummy(10, 34),
+            "/ibc.core.connection.v1.MsgConnectionOpenAck",
+        );
+        assert_any_round_trip(
+            MsgConnectionOpenConfirm::new_dummy(),
+            "/ibc.core.connection.v1.MsgConnectionOpenConfirm",
+        );
+    }
+
+    #[test]
+    fn test_channel_msgs_any_round_trip() {
+        let proof_height = 10;
+
+        assert_any_round_trip(
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init(None)).unwrap(),
+            "/ibc.core.channel.v1.MsgChannelOpenInit",
+        );
+        assert_any_round_trip(
+            MsgChannelOpenTry::try_from(get_dummy_raw_msg_chan_open_try(proof_height)).unwrap(),
+            "/ibc.core.channel.v1.MsgChannelOpenTry",
+        );
+        assert_any_round_trip(
+            MsgChannelOpenAck::try_from(get_dummy_raw_msg_chan_open_ack(proof_height)).unwrap(),
+            "/ibc.core.channel.v1.MsgChannelOpenAck",
+        );
+        assert_any_round_trip(
+            MsgChannelOpenConfirm::try_from(get_dummy_raw_msg_chan_open_confirm(proof_height))
+                .unwrap(),
+            "/ibc.core.channel.v1.MsgChannelOpenConfirm",
+        );
+        assert_any_round_trip(
+            MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap(),
+            "/ibc.core.channel.v1.MsgChannelCloseInit",
+        );
+        assert_any_round_trip(
+            MsgChannelCloseConfirm::try_from(get_dummy_raw_msg_chan_close_confirm(proof_height))
+                .unwrap(),
+            "/ibc.core.channel.v1.MsgChannelCloseConfirm",
+        );
+    }
+
+    #[test]
+    fn test_packet_msgs_any_round_trip() {
+        let proof_height = 10;
+
+        assert_any_round_trip(
+            MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(proof_height)).unwrap(),
+            "/ibc.core.channel.v1.MsgRecvPacket",
+        );
+        assert_any_round_trip(
+            MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(proof_height)).unwrap(),
+            "/ibc.core.channel.v1.MsgAcknowledgement",
+        );
+        assert_any_round_trip(
+            MsgTimeout::try_from(get_dummy_raw_msg_timeout(proof_height, 5, 0)).unwrap(),
+            "/ibc.core.channel.v1.MsgTimeout",
+        );
+        assert_any_round_trip(
+            MsgTimeoutOnClose::try_from(get_dummy_raw_msg_timeout_on_close(proof_height, 5))
+                .unwrap(),
+            "/ibc.core.channel.v1.MsgTimeoutOnClose",
+        );
+    }
+
+    #[test]
+    fn test_relayer_of_packet_msgs() {
+        let proof_height = 10;
+        let relayer = Signer::from("cosmos1relayer".to_string());
+
+        let mut recv =
+            MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(proof_height)).unwrap();
+        recv.signer = relayer.clone();
+        let mut ack =
+            MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(proof_height)).unwrap();
+        ack.signer = relayer.clone();
+        let mut timeout =
+            MsgTimeout::try_from(get_dummy_raw_msg_timeout(proof_height, 5, 0)).unwrap();
+        timeout.signer = relayer.clone();
+        let mut timeout_on_close =
+            MsgTimeoutOnClose::try_from(get_dummy_raw_msg_timeout_on_close(proof_height, 5))
+                .unwrap();
+        timeout_on_close.signer = relayer.clone();
+
+        let msgs = vec![
+            MsgEnvelope::Packet(PacketMsg::Recv(recv)),
+            MsgEnvelope::Packet(PacketMsg::Ack(ack)),
+            MsgEnvelope::Packet(PacketMsg::Timeout(timeout)),
+            MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(timeout_on_close)),
+        ];
+
+        for msg in &msgs {
+            assert_eq!(relayer_of(msg), Some(&relayer), "{msg:?}");
+        }
+    }
+
+    #[test]
+    fn test_relayer_of_non_packet_msgs() {
+        let msgs = vec![
+            MsgEnvelope::Client(ClientMsg::UpdateClient(MsgUpdateClient::new(
+                ClientId::default(),
+                MockHeader::new(Height::new(0, 10).unwrap()).into(),
+                get_dummy_account_id(),
+            ))),
+            MsgEnvelope::Connection(ConnectionMsg::OpenInit(MsgConnectionOpenInit::new_dummy())),
+            MsgEnvelope::Channel(ChannelMsg::CloseInit(
+                MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap(),
+            )),
+        ];
+
+        for msg in &msgs {
+            assert_eq!(relayer_of(msg), None, "{msg:?}");
+        }
+    }
+}
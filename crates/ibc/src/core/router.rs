@@ -26,6 +26,37 @@ pub trait Router {
     fn lookup_module(&self, port_id: &PortId) -> Option<ModuleId>;
 }
 
+/// A capability over a port, as defined in ICS-05, held by the module the
+/// port is bound to.
+///
+/// Only the holder of a port's capability may drive the channel handshakes
+/// on that port, see [`ExecutionContext::claim_capability`] and
+/// [`ValidationContext::authenticate_capability`].
+///
+/// [`ExecutionContext::claim_capability`]: crate::core::ExecutionContext::claim_capability
+/// [`ValidationContext::authenticate_capability`]: crate::core::ValidationContext::authenticate_capability
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capability {
+    port_id: PortId,
+    module_id: ModuleId,
+}
+
+impl Capability {
+    /// Capabilities are only created by the core handlers and contexts, so
+    /// that modules cannot forge them.
+    pub(crate) fn new(port_id: PortId, module_id: ModuleId) -> Self {
+        Self { port_id, module_id }
+    }
+
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn module_id(&self) -> &ModuleId {
+        &self.module_id
+    }
+}
+
 /// Module name, internal to the chain.
 ///
 /// That is, the IBC protocol never exposes this name. Note that this is
@@ -92,6 +123,16 @@ impl ModuleExtras {
             log: Vec::new(),
         }
     }
+
+    /// Appends the events and log messages of `other` after those of `self`,
+    /// preserving their order.
+    ///
+    /// This is useful for middleware, which combines the extras of the module it
+    /// wraps with its own.
+    pub fn merge(&mut self, other: ModuleExtras) {
+        self.events.extend(other.events);
+        self.log.extend(other.log);
+    }
 }
 
 /// The trait that defines an IBC application
@@ -202,6 +243,15 @@ pub trait Module: Debug {
         Ok(ModuleExtras::empty())
     }
 
+    /// Whether the module accepts newly received packets.
+    ///
+    /// A module in a degraded state (e.g. under maintenance) may return
+    /// `false` to reject `MsgRecvPacket`s, while still processing the
+    /// acknowledgements and timeouts of the packets it has sent.
+    fn accepts_packets(&self) -> bool {
+        true
+    }
+
     // Note: no `on_recv_packet_validate()`
     // the `onRecvPacket` callback always succeeds
     // if any error occurs, than an "error acknowledgement"
@@ -242,4 +292,626 @@ pub trait Module: Debug {
         packet: &Packet,
         relayer: &Signer,
     ) -> (ModuleExtras, Result<(), PacketError>);
+
+    /// Returns the version to pass to the layers outside of this module,
+    /// given the version negotiated by the layers inside of it, when this
+    /// module is a middleware of a [`ModuleStack`]. Defaults to `version`.
+    fn wrap_version(&self, version: Version) -> Version {
+        version
+    }
+
+    /// Returns the acknowledgement to pass to the layers outside of this
+    /// module, given the acknowledgement of `packet` written by the layers
+    /// inside of it, when this module is a middleware of a [`ModuleStack`].
+    /// Defaults to `acknowledgement`.
+    fn wrap_acknowledgement(
+        &mut self,
+        _packet: &Packet,
+        acknowledgement: Acknowledgement,
+    ) -> Acknowledgement {
+        acknowledgement
+    }
+}
+
+/// A base module wrapped in an ordered list of middleware modules, e.g. fee
+/// middleware wrapping ICA middleware wrapping the token transfer module.
+///
+/// Each callback of the stack is threaded through all of its layers:
+///
+/// - the channel handshake callbacks and `on_recv_packet_execute` are called
+///   from the outermost middleware to the innermost one, and then on the base
+///   module;
+/// - the acknowledgement and timeout callbacks are called on the base module
+///   first, and then from the innermost middleware to the outermost one.
+///
+/// The opening handshake callbacks of a layer receive the version returned by
+/// the layer outside of it, the outermost one receiving the version given to
+/// the stack. The version returned by the base module is then passed to
+/// [`Module::wrap_version`] from the innermost middleware to the outermost
+/// one, and the stack returns the version of the outermost middleware.
+///
+/// Likewise, the acknowledgement of a received packet is the one returned by
+/// the base module, passed to [`Module::wrap_acknowledgement`] from the
+/// innermost middleware to the outermost one. The acknowledgements returned
+/// by the `on_recv_packet_execute` callbacks of the middlewares are ignored.
+///
+/// The first layer to fail aborts the callback, without calling the remaining
+/// layers. The extras of all the layers are merged in call order.
+#[derive(Debug)]
+pub struct ModuleStack {
+    /// Outermost first.
+    middlewares: Vec<Box<dyn Module>>,
+    base: Box<dyn Module>,
+}
+
+impl ModuleStack {
+    pub fn new(base: impl Module + 'static) -> Self {
+        Self {
+            middlewares: Vec::new(),
+            base: Box::new(base),
+        }
+    }
+
+    /// Wraps the stack in `middleware`, which becomes its outermost layer.
+    pub fn with_middleware(mut self, middleware: impl Module + 'static) -> Self {
+        self.middlewares.insert(0, Box::new(middleware));
+        self
+    }
+
+    /// Calls `f` on every layer from the outermost one to the base module.
+    fn validate_outer_to_inner<E>(
+        &self,
+        f: impl Fn(&dyn Module) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for middleware in &self.middlewares {
+            f(middleware.as_ref())?;
+        }
+        f(self.base.as_ref())
+    }
+
+    /// Calls `f` on every layer from the outermost one to the base module,
+    /// passing each layer the version returned by the previous one, and
+    /// returns the version of the base module wrapped by every middleware.
+    fn negotiate_version(
+        &self,
+        version: &Version,
+        f: impl Fn(&dyn Module, &Version) -> Result<Version, ChannelError>,
+    ) -> Result<Version, ChannelError> {
+        let mut version = version.clone();
+        for middleware in &self.middlewares {
+            version = f(middleware.as_ref(), &version)?;
+        }
+        Ok(self.wrap_version(f(self.base.as_ref(), &version)?))
+    }
+
+    /// Passes `version` to every middleware from the innermost one to the
+    /// outermost one.
+    fn wrap_version(&self, version: Version) -> Version {
+        self.middlewares
+            .iter()
+            .rev()
+            .fold(version, |version, middleware| {
+                middleware.wrap_version(version)
+            })
+    }
+
+    /// Calls `f` on every layer from the base module to the outermost one.
+    fn validate_inner_to_outer<E>(
+        &self,
+        f: impl Fn(&dyn Module) -> Result<(), E>,
+    ) -> Result<(), E> {
+        f(self.base.as_ref())?;
+        for middleware in self.middlewares.iter().rev() {
+            f(middleware.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Calls `f` on every layer from the outermost one to the base module,
+    /// returning the merged extras.
+    fn execute_outer_to_inner(
+        &mut self,
+        mut f: impl FnMut(&mut dyn Module) -> Result<ModuleExtras, ChannelError>,
+    ) -> Result<ModuleExtras, ChannelError> {
+        let mut extras = ModuleExtras::empty();
+        for middleware in &mut self.middlewares {
+            extras.merge(f(middleware.as_mut())?);
+        }
+        extras.merge(f(self.base.as_mut())?);
+        Ok(extras)
+    }
+
+    /// Like [`Self::negotiate_version`], also returning the merged extras.
+    fn execute_negotiate_version(
+        &mut self,
+        version: &Version,
+        mut f: impl FnMut(&mut dyn Module, &Version) -> Result<(ModuleExtras, Version), ChannelError>,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        let mut extras = ModuleExtras::empty();
+        let mut version = version.clone();
+        for middleware in &mut self.middlewares {
+            let (middleware_extras, middleware_version) = f(middleware.as_mut(), &version)?;
+            extras.merge(middleware_extras);
+            version = middleware_version;
+        }
+        let (base_extras, base_version) = f(self.base.as_mut(), &version)?;
+        extras.merge(base_extras);
+        Ok((extras, self.wrap_version(base_version)))
+    }
+
+    /// Calls `f` on every layer from the base module to the outermost one,
+    /// returning the merged extras.
+    fn execute_inner_to_outer(
+        &mut self,
+        mut f: impl FnMut(&mut dyn Module) -> (ModuleExtras, Result<(), PacketError>),
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        let (mut extras, res) = f(self.base.as_mut());
+        if res.is_err() {
+            return (extras, res);
+        }
+        for middleware in self.middlewares.iter_mut().rev() {
+            let (middleware_extras, res) = f(middleware.as_mut());
+            extras.merge(middleware_extras);
+            if res.is_err() {
+                return (extras, res);
+            }
+        }
+        (extras, Ok(()))
+    }
+}
+
+impl Module for ModuleStack {
+    fn on_chan_open_init_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        self.negotiate_version(version, |module, version| {
+            module.on_chan_open_init_validate(
+                order,
+                connection_hops,
+                port_id,
+                channel_id,
+                counterparty,
+                version,
+            )
+        })
+    }
+
+    fn on_chan_open_init_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        self.execute_negotiate_version(version, |module, version| {
+            module.on_chan_open_init_execute(
+                order,
+                connection_hops,
+                port_id,
+                channel_id,
+                counterparty,
+                version,
+            )
+        })
+    }
+
+    fn on_chan_open_try_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        self.negotiate_version(counterparty_version, |module, counterparty_version| {
+            module.on_chan_open_try_validate(
+                order,
+                connection_hops,
+                port_id,
+                channel_id,
+                counterparty,
+                counterparty_version,
+            )
+        })
+    }
+
+    fn on_chan_open_try_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        self.execute_negotiate_version(counterparty_version, |module, counterparty_version| {
+            module.on_chan_open_try_execute(
+                order,
+                connection_hops,
+                port_id,
+                channel_id,
+                counterparty,
+                counterparty_version,
+            )
+        })
+    }
+
+    fn on_chan_open_ack_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        self.validate_outer_to_inner(|module| {
+            module.on_chan_open_ack_validate(port_id, channel_id, counterparty_version)
+        })
+    }
+
+    fn on_chan_open_ack_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.execute_outer_to_inner(|module| {
+            module.on_chan_open_ack_execute(port_id, channel_id, counterparty_version)
+        })
+    }
+
+    fn on_chan_open_confirm_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.validate_outer_to_inner(|module| {
+            module.on_chan_open_confirm_validate(port_id, channel_id)
+        })
+    }
+
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.execute_outer_to_inner(|module| {
+            module.on_chan_open_confirm_execute(port_id, channel_id)
+        })
+    }
+
+    fn on_chan_close_init_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.validate_outer_to_inner(|module| {
+            module.on_chan_close_init_validate(port_id, channel_id)
+        })
+    }
+
+    fn on_chan_close_init_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.execute_outer_to_inner(|module| module.on_chan_close_init_execute(port_id, channel_id))
+    }
+
+    fn on_chan_close_confirm_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.validate_outer_to_inner(|module| {
+            module.on_chan_close_confirm_validate(port_id, channel_id)
+        })
+    }
+
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.execute_outer_to_inner(|module| {
+            module.on_chan_close_confirm_execute(port_id, channel_id)
+        })
+    }
+
+    /// A stack accepts packets only if all of its layers do.
+    fn accepts_packets(&self) -> bool {
+        self.middlewares
+            .iter()
+            .all(|middleware| middleware.accepts_packets())
+            && self.base.accepts_packets()
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        let mut extras = ModuleExtras::empty();
+        for middleware in &mut self.middlewares {
+            let (middleware_extras, _) = middleware.on_recv_packet_execute(packet, relayer);
+            extras.merge(middleware_extras);
+        }
+        let (base_extras, acknowledgement) = self.base.on_recv_packet_execute(packet, relayer);
+        extras.merge(base_extras);
+
+        let acknowledgement = self
+            .middlewares
+            .iter_mut()
+            .rev()
+            .fold(acknowledgement, |acknowledgement, middleware| {
+                middleware.wrap_acknowledgement(packet, acknowledgement)
+            });
+        (extras, acknowledgement)
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        self.validate_inner_to_outer(|module| {
+            module.on_acknowledgement_packet_validate(packet, acknowledgement, relayer)
+        })
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        self.execute_inner_to_outer(|module| {
+            module.on_acknowledgement_packet_execute(packet, acknowledgement, relayer)
+        })
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        self.validate_inner_to_outer(|module| module.on_timeout_packet_validate(packet, relayer))
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        self.execute_inner_to_outer(|module| module.on_timeout_packet_execute(packet, relayer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::test_utils::get_dummy_account_id;
+
+    type CallLog = Rc<RefCell<Vec<(&'static str, &'static str)>>>;
+
+    /// A layer recording its packet callbacks, along with its name, in a log
+    /// shared by all the layers of a stack, and appending its name to the
+    /// versions and acknowledgements passing through it.
+    #[derive(Debug)]
+    struct Layer {
+        name: &'static str,
+        log: CallLog,
+    }
+
+    impl Layer {
+        fn record(&self, callback: &'static str) {
+            self.log.borrow_mut().push((self.name, callback));
+        }
+
+        fn negotiate(&self, version: &Version) -> Version {
+            Version::new(format!("{version}>{}", self.name))
+        }
+    }
+
+    impl Module for Layer {
+        fn on_chan_open_init_validate(
+            &self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            version: &Version,
+        ) -> Result<Version, ChannelError> {
+            Ok(self.negotiate(version))
+        }
+
+        fn on_chan_open_init_execute(
+            &mut self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            version: &Version,
+        ) -> Result<(ModuleExtras, Version), ChannelError> {
+            Ok((ModuleExtras::empty(), self.negotiate(version)))
+        }
+
+        fn on_chan_open_try_validate(
+            &self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            counterparty_version: &Version,
+        ) -> Result<Version, ChannelError> {
+            Ok(self.negotiate(counterparty_version))
+        }
+
+        fn on_chan_open_try_execute(
+            &mut self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            counterparty_version: &Version,
+        ) -> Result<(ModuleExtras, Version), ChannelError> {
+            Ok((ModuleExtras::empty(), self.negotiate(counterparty_version)))
+        }
+
+        fn on_recv_packet_execute(
+            &mut self,
+            _packet: &Packet,
+            _relayer: &Signer,
+        ) -> (ModuleExtras, Acknowledgement) {
+            self.record("recv");
+            let mut extras = ModuleExtras::empty();
+            extras.log.push(self.name.to_string());
+            (
+                extras,
+                Acknowledgement::try_from(self.name.as_bytes().to_vec()).unwrap(),
+            )
+        }
+
+        fn on_acknowledgement_packet_validate(
+            &self,
+            _packet: &Packet,
+            _acknowledgement: &Acknowledgement,
+            _relayer: &Signer,
+        ) -> Result<(), PacketError> {
+            Ok(())
+        }
+
+        fn on_acknowledgement_packet_execute(
+            &mut self,
+            _packet: &Packet,
+            _acknowledgement: &Acknowledgement,
+            _relayer: &Signer,
+        ) -> (ModuleExtras, Result<(), PacketError>) {
+            self.record("ack");
+            (ModuleExtras::empty(), Ok(()))
+        }
+
+        fn on_timeout_packet_validate(
+            &self,
+            _packet: &Packet,
+            _relayer: &Signer,
+        ) -> Result<(), PacketError> {
+            Ok(())
+        }
+
+        fn on_timeout_packet_execute(
+            &mut self,
+            _packet: &Packet,
+            _relayer: &Signer,
+        ) -> (ModuleExtras, Result<(), PacketError>) {
+            self.record("timeout");
+            (ModuleExtras::empty(), Ok(()))
+        }
+
+        fn wrap_version(&self, version: Version) -> Version {
+            Version::new(format!("{version}<{}", self.name))
+        }
+
+        fn wrap_acknowledgement(
+            &mut self,
+            _packet: &Packet,
+            acknowledgement: Acknowledgement,
+        ) -> Acknowledgement {
+            let mut bytes = acknowledgement.as_bytes().to_vec();
+            bytes.extend_from_slice(format!("+{}", self.name).as_bytes());
+            Acknowledgement::try_from(bytes).unwrap()
+        }
+    }
+
+    #[test]
+    fn module_stack_version_threading() {
+        let layer = |name| Layer {
+            name,
+            log: CallLog::default(),
+        };
+        let mut stack = ModuleStack::new(layer("base"))
+            .with_middleware(layer("inner"))
+            .with_middleware(layer("outer"));
+
+        let counterparty = Counterparty::new(PortId::transfer(), None);
+        let version = Version::new("v".to_string());
+        let expected = Version::new("v>outer>inner>base<inner<outer".to_string());
+
+        let res = stack.on_chan_open_init_validate(
+            Order::Unordered,
+            &[ConnectionId::default()],
+            &PortId::transfer(),
+            &ChannelId::default(),
+            &counterparty,
+            &version,
+        );
+        assert_eq!(res.unwrap(), expected);
+
+        let res = stack.on_chan_open_try_execute(
+            Order::Unordered,
+            &[ConnectionId::default()],
+            &PortId::transfer(),
+            &ChannelId::default(),
+            &counterparty,
+            &version,
+        );
+        assert_eq!(res.unwrap().1, expected);
+    }
+
+    #[test]
+    fn module_stack_call_order() {
+        let log = CallLog::default();
+        let layer = |name| Layer {
+            name,
+            log: log.clone(),
+        };
+        let mut stack = ModuleStack::new(layer("base"))
+            .with_middleware(layer("inner"))
+            .with_middleware(layer("outer"));
+
+        let packet = Packet::try_from(get_dummy_raw_packet(10, 0)).unwrap();
+        let relayer = get_dummy_account_id();
+
+        let (extras, acknowledgement) = stack.on_recv_packet_execute(&packet, &relayer);
+        assert_eq!(acknowledgement.as_bytes(), b"base+inner+outer");
+        assert_eq!(extras.log, vec!["outer", "inner", "base"]);
+        assert_eq!(
+            log.take(),
+            vec![("outer", "recv"), ("inner", "recv"), ("base", "recv")]
+        );
+
+        let (_, res) = stack.on_acknowledgement_packet_execute(&packet, &acknowledgement, &relayer);
+        assert!(res.is_ok());
+        assert_eq!(
+            log.take(),
+            vec![("base", "ack"), ("inner", "ack"), ("outer", "ack")]
+        );
+
+        let (_, res) = stack.on_timeout_packet_execute(&packet, &relayer);
+        assert!(res.is_ok());
+        assert_eq!(
+            log.take(),
+            vec![
+                ("base", "timeout"),
+                ("inner", "timeout"),
+                ("outer", "timeout")
+            ]
+        );
+    }
 }
@@ -0,0 +1,650 @@
+//! Records the host interactions of a message dispatch as a deterministic trace.
+//!
+//! Two implementations processing the same message against the same state
+//! are expected to produce the same [`ExecutionTrace`], which makes traces
+//! suitable as golden fixtures for conformance and replay testing.
+
+use core::cell::RefCell;
+use core::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+
+use crate::core::events::IbcEvent;
+use crate::core::forward::forward_context;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{
+    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentPath, ConnectionPath, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
+};
+use crate::core::router::{Capability, Router};
+use crate::core::timestamp::Timestamp;
+use crate::core::{dispatch, ContextError, ExecutionContext, MsgEnvelope, ValidationContext};
+use crate::prelude::*;
+use crate::signer::Signer;
+use crate::Height;
+
+/// A single host interaction recorded while dispatching a message.
+///
+/// Store accesses are identified by their ICS-24 path where one exists, and
+/// by the ibc-go store key otherwise. Values are deliberately left out, since
+/// their encoding (and e.g. the host timestamp) is implementation specific.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceEntry {
+    /// A read of the host state at the given key
+    Read(String),
+    /// A write of the host state at the given key
+    Write(String),
+    /// A check delegated to the host, such as validating the message signer
+    Verify(String),
+    /// An emitted IBC event, identified by its type
+    Event(String),
+}
+
+/// The ordered host interactions of a message dispatch, together with its outcome.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    pub entries: Vec<TraceEntry>,
+    /// The error that aborted the dispatch, if any
+    pub error: Option<String>,
+}
+
+/// Dispatches `msg` like [`dispatch`], recording every interaction with `ctx` in order.
+///
+/// Only the core [`ValidationContext`] and [`ExecutionContext`] methods are
+/// recorded. Light client verification and client state writes go through the
+/// client contexts of the host, and module callbacks through the `router`, so
+/// neither shows up in the trace.
+pub fn dispatch_trace<Ctx>(
+    ctx: &mut Ctx,
+    router: &mut impl Router,
+    msg: MsgEnvelope,
+) -> ExecutionTrace
+where
+    Ctx: ExecutionContext,
+{
+    let mut tracing_ctx = TracingContext {
+        inner: ctx,
+        entries: RefCell::new(Vec::new()),
+    };
+
+    let error = dispatch(&mut tracing_ctx, router, msg)
+        .err()
+        .map(|e| e.to_string());
+
+    ExecutionTrace {
+        entries: tracing_ctx.entries.into_inner(),
+        error,
+    }
+}
+
+/// Context wrapper which records every call made to the inner context.
+struct TracingContext<'a, Ctx> {
+    inner: &'a mut Ctx,
+    entries: RefCell<Vec<TraceEntry>>,
+}
+
+impl<'a, Ctx> TracingContext<'a, Ctx> {
+    fn record(&self, entry: TraceEntry) {
+        self.entries.borrow_mut().push(entry);
+    }
+
+    fn read(&self, key: impl ToString) {
+        self.record(TraceEntry::Read(key.to_string()));
+    }
+
+    fn write(&self, key: impl ToString) {
+        self.record(TraceEntry::Write(key.to_string()));
+    }
+}
+
+impl<'a, Ctx> ValidationContext for TracingContext<'a, Ctx>
+where
+    Ctx: ValidationContext,
+{
+    type ClientValidationContext = Ctx::ClientValidationContext;
+    type E = Ctx::E;
+    type AnyConsensusState = Ctx::AnyConsensusState;
+    type AnyClientState = Ctx::AnyClientState;
+
+    forward_context!(
+        inner;
+        get_client_validation_context,
+        decode_client_state,
+        validate_self_consistency,
+        get_compatible_versions,
+        pick_version,
+        max_packet_data_size,
+        max_proof_size,
+        commitment_hasher,
+        signer_bech32_prefix,
+        message_granter,
+        allowed_client_types,
+        last_recv_time,
+        telemetry,
+    );
+
+    fn client_state(&self, client_id: &ClientId) -> Result<Self::AnyClientState, ContextError> {
+        self.read(ClientStatePath::new(client_id));
+        self.inner.client_state(client_id)
+    }
+
+    fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Self::AnyConsensusState, ContextError> {
+        self.read(client_cons_state_path);
+        self.inner.consensus_state(client_cons_state_path)
+    }
+
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ContextError> {
+        self.read(format!("clients/{client_id}/consensusStates"));
+        self.inner.consensus_state_heights(client_id)
+    }
+
+    fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Timestamp, ContextError> {
+        self.read(format!(
+            "clients/{client_id}/consensusStates/{height}/processedTime"
+        ));
+        self.inner.client_update_time(client_id, height)
+    }
+
+    fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Height, ContextError> {
+        self.read(format!(
+            "clients/{client_id}/consensusStates/{height}/processedHeight"
+        ));
+        self.inner.client_update_height(client_id, height)
+    }
+
+    fn host_height(&self) -> Result<Height, ContextError> {
+        self.read("host/height");
+        self.inner.host_height()
+    }
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        self.read("host/timestamp");
+        self.inner.host_timestamp()
+    }
+
+    fn host_consensus_state(
+        &self,
+        height: &Height,
+    ) -> Result<Self::AnyConsensusState, ContextError> {
+        self.read(format!("host/consensusStates/{height}"));
+        self.inner.host_consensus_state(height)
+    }
+
+    fn client_counter(&self) -> Result<u64, ContextError> {
+        self.read("nextClientSequence");
+        self.inner.client_counter()
+    }
+
+    fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError> {
+        self.read(ConnectionPath::new(conn_id));
+        self.inner.connection_end(conn_id)
+    }
+
+    fn validate_self_client(
+        &self,
+        client_state_of_host_on_counterparty: Any,
+    ) -> Result<(), ContextError> {
+        self.record(TraceEntry::Verify("selfClient".to_string()));
+        self.inner
+            .validate_self_client(client_state_of_host_on_counterparty)
+    }
+
+    fn commitment_prefix(&self) -> CommitmentPrefix {
+        self.read("commitmentPrefix");
+        self.inner.commitment_prefix()
+    }
+
+    fn connection_counter(&self) -> Result<u64, ContextError> {
+        self.read("nextConnectionSequence");
+        self.inner.connection_counter()
+    }
+
+    fn channel_end(&self, channel_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError> {
+        self.read(channel_end_path);
+        self.inner.channel_end(channel_end_path)
+    }
+
+    fn get_next_sequence_send(
+        &self,
+        seq_send_path: &SeqSendPath,
+    ) -> Result<Sequence, ContextError> {
+        self.read(seq_send_path);
+        self.inner.get_next_sequence_send(seq_send_path)
+    }
+
+    fn get_next_sequence_recv(
+        &self,
+        seq_recv_path: &SeqRecvPath,
+    ) -> Result<Sequence, ContextError> {
+        self.read(seq_recv_path);
+        self.inner.get_next_sequence_recv(seq_recv_path)
+    }
+
+    fn get_next_sequence_ack(&self, seq_ack_path: &SeqAckPath) -> Result<Sequence, ContextError> {
+        self.read(seq_ack_path);
+        self.inner.get_next_sequence_ack(seq_ack_path)
+    }
+
+    fn get_next_sequences(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(Sequence, Sequence, Sequence), ContextError> {
+        self.read(SeqSendPath::new(port_id, channel_id));
+        self.read(SeqRecvPath::new(port_id, channel_id));
+        self.read(SeqAckPath::new(port_id, channel_id));
+        self.inner.get_next_sequences(port_id, channel_id)
+    }
+
+    fn get_packet_commitment(
+        &self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<PacketCommitment, ContextError> {
+        self.read(commitment_path);
+        self.inner.get_packet_commitment(commitment_path)
+    }
+
+    fn get_packet_receipt(&self, receipt_path: &ReceiptPath) -> Result<Receipt, ContextError> {
+        self.read(receipt_path);
+        self.inner.get_packet_receipt(receipt_path)
+    }
+
+    fn get_packet_acknowledgement(
+        &self,
+        ack_path: &AckPath,
+    ) -> Result<AcknowledgementCommitment, ContextError> {
+        self.read(ack_path);
+        self.inner.get_packet_acknowledgement(ack_path)
+    }
+
+    fn packet_commitments(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<Vec<(Sequence, PacketCommitment)>, ContextError> {
+        let ChannelEndPath(port_id, channel_id) = channel_end_path;
+        self.read(format!(
+            "commitments/ports/{port_id}/channels/{channel_id}/sequences"
+        ));
+        self.inner.packet_commitments(channel_end_path)
+    }
+
+    fn packet_acknowledgements(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: &[Sequence],
+    ) -> Result<Vec<(Sequence, AcknowledgementCommitment)>, ContextError> {
+        let ChannelEndPath(port_id, channel_id) = channel_end_path;
+        self.read(format!(
+            "acks/ports/{port_id}/channels/{channel_id}/sequences"
+        ));
+        self.inner
+            .packet_acknowledgements(channel_end_path, sequences)
+    }
+
+    fn channel_counter(&self) -> Result<u64, ContextError> {
+        self.read("nextChannelSequence");
+        self.inner.channel_counter()
+    }
+
+    fn next_channel_identifier(&self, port_id: &PortId) -> Result<ChannelId, ContextError> {
+        self.read("nextChannelSequence");
+        self.inner.next_channel_identifier(port_id)
+    }
+
+    fn max_expected_time_per_block(&self) -> Duration {
+        self.read("maxExpectedTimePerBlock");
+        self.inner.max_expected_time_per_block()
+    }
+
+    fn block_delay(&self, delay_period_time: &Duration) -> u64 {
+        self.read("maxExpectedTimePerBlock");
+        self.inner.block_delay(delay_period_time)
+    }
+
+    fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError> {
+        self.record(TraceEntry::Verify("messageSigner".to_string()));
+        self.inner.validate_message_signer(signer)
+    }
+
+    fn validate_signer_with_grant(
+        &self,
+        signer: &Signer,
+        granter: &Signer,
+    ) -> Result<(), ContextError> {
+        self.record(TraceEntry::Verify("messageSigner".to_string()));
+        self.inner.validate_signer_with_grant(signer, granter)
+    }
+
+    fn authenticate_capability(&self, capability: &Capability) -> Result<(), ContextError> {
+        self.record(TraceEntry::Verify("portCapability".to_string()));
+        self.inner.authenticate_capability(capability)
+    }
+}
+
+impl<'a, Ctx> ExecutionContext for TracingContext<'a, Ctx>
+where
+    Ctx: ExecutionContext,
+{
+    forward_context!(
+        inner;
+        get_client_execution_context,
+        log_message,
+        log_structured,
+        claim_capability,
+        store_last_recv_time,
+    );
+
+    fn increase_client_counter(&mut self) -> Result<u64, ContextError> {
+        self.write("nextClientSequence");
+        self.inner.increase_client_counter()
+    }
+
+    fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        self.write(format!(
+            "clients/{client_id}/consensusStates/{height}/processedTime"
+        ));
+        self.inner.store_update_time(client_id, height, timestamp)
+    }
+
+    fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), ContextError> {
+        self.write(format!(
+            "clients/{client_id}/consensusStates/{height}/processedHeight"
+        ));
+        self.inner
+            .store_update_height(client_id, height, host_height)
+    }
+
+    fn delete_consensus_state(
+        &mut self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<(), ContextError> {
+        self.write(client_cons_state_path);
+        self.inner.delete_consensus_state(client_cons_state_path)
+    }
+
+    fn delete_update_time(
+        &mut self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<(), ContextError> {
+        self.write(format!(
+            "clients/{client_id}/consensusStates/{height}/processedTime"
+        ));
+        self.inner.delete_update_time(client_id, height)
+    }
+
+    fn delete_update_height(
+        &mut self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<(), ContextError> {
+        self.write(format!(
+            "clients/{client_id}/consensusStates/{height}/processedHeight"
+        ));
+        self.inner.delete_update_height(client_id, height)
+    }
+
+    fn prune_consensus_states(
+        &mut self,
+        client_id: &ClientId,
+        before_height: &Height,
+    ) -> Result<usize, ContextError> {
+        self.write(format!("clients/{client_id}/consensusStates"));
+        self.inner.prune_consensus_states(client_id, before_height)
+    }
+
+    fn store_connection(
+        &mut self,
+        connection_path: &ConnectionPath,
+        connection_end: ConnectionEnd,
+    ) -> Result<(), ContextError> {
+        self.write(connection_path);
+        self.inner.store_connection(connection_path, connection_end)
+    }
+
+    fn store_connection_to_client(
+        &mut self,
+        client_connection_path: &ClientConnectionPath,
+        conn_id: ConnectionId,
+    ) -> Result<(), ContextError> {
+        self.write(client_connection_path);
+        self.inner
+            .store_connection_to_client(client_connection_path, conn_id)
+    }
+
+    fn increase_connection_counter(&mut self) -> Result<u64, ContextError> {
+        self.write("nextConnectionSequence");
+        self.inner.increase_connection_counter()
+    }
+
+    fn store_packet_commitment(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        commitment: PacketCommitment,
+    ) -> Result<(), ContextError> {
+        self.write(commitment_path);
+        self.inner
+            .store_packet_commitment(commitment_path, commitment)
+    }
+
+    fn delete_packet_commitment(
+        &mut self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<(), ContextError> {
+        self.write(commitment_path);
+        self.inner.delete_packet_commitment(commitment_path)
+    }
+
+    fn store_packet_receipt(
+        &mut self,
+        receipt_path: &ReceiptPath,
+        receipt: Receipt,
+    ) -> Result<(), ContextError> {
+        self.write(receipt_path);
+        self.inner.store_packet_receipt(receipt_path, receipt)
+    }
+
+    fn store_packet_acknowledgement(
+        &mut self,
+        ack_path: &AckPath,
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<(), ContextError> {
+        self.write(ack_path);
+        self.inner
+            .store_packet_acknowledgement(ack_path, ack_commitment)
+    }
+
+    fn delete_packet_acknowledgement(&mut self, ack_path: &AckPath) -> Result<(), ContextError> {
+        self.write(ack_path);
+        self.inner.delete_packet_acknowledgement(ack_path)
+    }
+
+    fn store_channel(
+        &mut self,
+        channel_end_path: &ChannelEndPath,
+        channel_end: ChannelEnd,
+    ) -> Result<(), ContextError> {
+        self.write(channel_end_path);
+        self.inner.store_channel(channel_end_path, channel_end)
+    }
+
+    fn store_next_sequence_send(
+        &mut self,
+        seq_send_path: &SeqSendPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        self.write(seq_send_path);
+        self.inner.store_next_sequence_send(seq_send_path, seq)
+    }
+
+    fn store_next_sequence_recv(
+        &mut self,
+        seq_recv_path: &SeqRecvPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        self.write(seq_recv_path);
+        self.inner.store_next_sequence_recv(seq_recv_path, seq)
+    }
+
+    fn store_next_sequence_ack(
+        &mut self,
+        seq_ack_path: &SeqAckPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        self.write(seq_ack_path);
+        self.inner.store_next_sequence_ack(seq_ack_path, seq)
+    }
+
+    fn increase_channel_counter(&mut self) {
+        self.write("nextChannelSequence");
+        self.inner.increase_channel_counter()
+    }
+
+    fn generate_channel_identifier(&mut self, port_id: &PortId) -> Result<ChannelId, ContextError> {
+        self.write("nextChannelSequence");
+        self.inner.generate_channel_identifier(port_id)
+    }
+
+    fn emit_ibc_event(&mut self, event: IbcEvent) {
+        self.record(TraceEntry::Event(event.event_type().to_string()));
+        self.inner.emit_ibc_event(event)
+    }
+
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>) {
+        for event in events.iter() {
+            self.record(TraceEntry::Event(event.event_type().to_string()));
+        }
+        self.inner.emit_ibc_events(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::ics03_connection::connection::{
+        Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{Counterparty, Order, State};
+    use crate::core::ics04_channel::msgs::chan_open_confirm::test_util::get_dummy_raw_msg_chan_open_confirm;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics04_channel::Version;
+    use crate::core::router::ModuleId;
+    use crate::core::timestamp::ZERO_DURATION;
+    use crate::mock::client_state::client_type as mock_client_type;
+    use crate::mock::context::MockContext;
+    use crate::mock::router::MockRouter;
+    use crate::{applications::transfer::MODULE_ID_STR, test_utils::DummyTransferModule};
+
+    #[test]
+    fn test_dispatch_trace_chan_open_confirm() {
+        let proof_height = 10;
+        let client_id_on_b = ClientId::new(mock_client_type(), 45).unwrap();
+        let conn_id_on_b = ConnectionId::new(2);
+        let conn_end_on_b = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id_on_b.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty(Some(0))).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        let msg =
+            MsgChannelOpenConfirm::try_from(get_dummy_raw_msg_chan_open_confirm(proof_height))
+                .unwrap();
+
+        let chan_end_on_b = ChannelEnd::new(
+            State::TryOpen,
+            Order::Unordered,
+            Counterparty::new(msg.port_id_on_b.clone(), Some(ChannelId::default())),
+            vec![conn_id_on_b.clone()],
+            Version::default(),
+        )
+        .unwrap();
+
+        let mut context = MockContext::default()
+            .with_client(&client_id_on_b, Height::new(0, proof_height).unwrap())
+            .with_connection(conn_id_on_b, conn_end_on_b)
+            .with_channel(
+                msg.port_id_on_b.clone(),
+                ChannelId::default(),
+                chan_end_on_b,
+            );
+
+        let module_id = ModuleId::new(MODULE_ID_STR.to_string());
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .bind_port(msg.port_id_on_b.clone(), module_id)
+            .unwrap();
+
+        let channel_end = ChannelEndPath::new(&msg.port_id_on_b, &ChannelId::default()).to_string();
+
+        let trace = dispatch_trace(
+            &mut context,
+            &mut router,
+            MsgEnvelope::Channel(ChannelMsg::OpenConfirm(msg)),
+        );
+
+        let read = |key: &str| TraceEntry::Read(key.to_string());
+        let write = |key: &str| TraceEntry::Write(key.to_string());
+        let event = |kind: &str| TraceEntry::Event(kind.to_string());
+
+        let expected = ExecutionTrace {
+            entries: vec![
+                // validation
+                TraceEntry::Verify("portCapability".to_string()),
+                TraceEntry::Verify("messageSigner".to_string()),
+                read(&channel_end),
+                read("connections/connection-2"),
+                read("clients/9999-mock-45/clientState"),
+                read("clients/9999-mock-45/consensusStates/0-10"),
+                // execution
+                TraceEntry::Verify("portCapability".to_string()),
+                read(&channel_end),
+                read("connections/connection-2"),
+                read("clients/9999-mock-45/clientState"),
+                write(&channel_end),
+                event("message"),
+                event("channel_open_confirm"),
+            ],
+            error: None,
+        };
+
+        assert_eq!(trace, expected);
+    }
+}
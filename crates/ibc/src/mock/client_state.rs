@@ -20,6 +20,7 @@ use crate::core::ics02_client::ClientExecutionContext;
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
+use crate::core::ics23_commitment::error::CommitmentError;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::path::Path;
 use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath};
@@ -151,6 +152,13 @@ pub trait MockClientContext {
 
     /// Returns the current timestamp of the local chain.
     fn host_timestamp(&self) -> Result<Timestamp, ContextError>;
+
+    /// Returns whether the mock client accepts the proofs it is given, which
+    /// it does not otherwise check. Contexts return `false` to exercise the
+    /// proof verification failures of the handlers.
+    fn proof_verification_result(&self) -> bool {
+        true
+    }
 }
 
 impl ClientStateCommon for MockClientState {
@@ -178,6 +186,10 @@ impl ClientStateCommon for MockClientState {
         Ok(())
     }
 
+    fn header_height(&self, header: Any) -> Result<Height, ClientError> {
+        Ok(MockHeader::try_from(header)?.height())
+    }
+
     fn verify_upgrade_client(
         &self,
         upgraded_client_state: Any,
@@ -309,6 +321,46 @@ where
 
         Ok(Status::Active)
     }
+
+    fn verify_membership_with_context(
+        &self,
+        ctx: &ClientValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        verify_proof_result(ctx)?;
+
+        self.verify_membership(prefix, proof, root, path, value)
+    }
+
+    fn verify_non_membership_with_context(
+        &self,
+        ctx: &ClientValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        verify_proof_result(ctx)?;
+
+        self.verify_non_membership(prefix, proof, root, path)
+    }
+}
+
+fn verify_proof_result<Ctx>(ctx: &Ctx) -> Result<(), ClientError>
+where
+    Ctx: MockClientContext,
+{
+    if !ctx.proof_verification_result() {
+        return Err(ClientError::Ics23Verification(
+            CommitmentError::VerificationFailure,
+        ));
+    }
+
+    Ok(())
 }
 
 impl<E> ClientStateExecution<E> for MockClientState
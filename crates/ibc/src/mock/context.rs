@@ -1,8 +1,14 @@
 //! Implementation of a global context mock. Used in testing handlers of all IBC modules.
 
+#[cfg(feature = "async")]
+mod async_context;
 mod clients;
+mod state_diff;
+
+pub use state_diff::StateDiff;
 
 use crate::clients::ics07_tendermint::TENDERMINT_CLIENT_TYPE;
+use crate::clients::ics09_localhost::LOCALHOST_CLIENT_TYPE;
 use crate::core::ics24_host::path::{
     AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, CommitmentPath,
     ConnectionPath, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
@@ -22,13 +28,21 @@ use parking_lot::Mutex;
 use ibc_proto::google::protobuf::Any;
 use tracing::debug;
 
+use crate::clients::ics06_solomachine::client_state::ClientState as SoloMachineClientState;
+use crate::clients::ics06_solomachine::client_state::SOLOMACHINE_CLIENT_STATE_TYPE_URL;
+use crate::clients::ics06_solomachine::consensus_state::ConsensusState as SoloMachineConsensusState;
+use crate::clients::ics06_solomachine::consensus_state::SOLOMACHINE_CONSENSUS_STATE_TYPE_URL;
 use crate::clients::ics07_tendermint::client_state::ClientState as TmClientState;
 use crate::clients::ics07_tendermint::client_state::TENDERMINT_CLIENT_STATE_TYPE_URL;
 use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
 use crate::clients::ics07_tendermint::consensus_state::TENDERMINT_CONSENSUS_STATE_TYPE_URL;
+use crate::clients::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use crate::clients::ics09_localhost::client_state::LOCALHOST_CLIENT_STATE_TYPE_URL;
+use crate::clients::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
+use crate::clients::ics09_localhost::consensus_state::LOCALHOST_CONSENSUS_STATE_TYPE_URL;
 
 use crate::core::dispatch;
-use crate::core::events::IbcEvent;
+use crate::core::events::{EventSequencer, IbcEvent, SequencedEvent};
 use crate::core::ics02_client::client_state::ClientState;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::consensus_state::ConsensusState;
@@ -36,15 +50,20 @@ use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics04_channel::channel::ChannelEnd;
-use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::commitment::{
+    AcknowledgementCommitment, Hasher, PacketCommitment, Sha256Hasher,
+};
 use crate::core::ics04_channel::error::{ChannelError, PacketError};
 use crate::core::ics04_channel::packet::{Receipt, Sequence};
 use crate::core::ics23_commitment::commitment::CommitmentPrefix;
 use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
-use crate::core::router::Router;
+use crate::core::router::{Capability, ModuleId, Router};
 use crate::core::timestamp::Timestamp;
+use crate::core::{
+    structured_log_string, validate_host_timestamp_consistency, ExecutionContext, MsgEnvelope,
+    NoopTelemetry, Telemetry,
+};
 use crate::core::{ContextError, ValidationContext};
-use crate::core::{ExecutionContext, MsgEnvelope};
 use crate::mock::client_state::{client_type as mock_client_type, MockClientState};
 use crate::mock::consensus_state::MockConsensusState;
 use crate::mock::header::MockHeader;
@@ -66,6 +85,8 @@ pub const DEFAULT_BLOCK_TIME_SECS: u64 = 3;
 #[mock]
 pub enum AnyClientState {
     Tendermint(TmClientState),
+    Localhost(LocalhostClientState),
+    SoloMachine(SoloMachineClientState),
     Mock(MockClientState),
 }
 
@@ -77,6 +98,10 @@ impl TryFrom<Any> for AnyClientState {
     fn try_from(raw: Any) -> Result<Self, Self::Error> {
         if raw.type_url == TENDERMINT_CLIENT_STATE_TYPE_URL {
             TmClientState::try_from(raw).map(Into::into)
+        } else if raw.type_url == LOCALHOST_CLIENT_STATE_TYPE_URL {
+            LocalhostClientState::try_from(raw).map(Into::into)
+        } else if raw.type_url == SOLOMACHINE_CLIENT_STATE_TYPE_URL {
+            SoloMachineClientState::try_from(raw).map(Into::into)
         } else if raw.type_url == MOCK_CLIENT_STATE_TYPE_URL {
             MockClientState::try_from(raw).map(Into::into)
         } else {
@@ -91,6 +116,8 @@ impl From<AnyClientState> for Any {
     fn from(host_client_state: AnyClientState) -> Self {
         match host_client_state {
             AnyClientState::Tendermint(cs) => cs.into(),
+            AnyClientState::Localhost(cs) => cs.into(),
+            AnyClientState::SoloMachine(cs) => cs.into(),
             AnyClientState::Mock(cs) => cs.into(),
         }
     }
@@ -99,6 +126,8 @@ impl From<AnyClientState> for Any {
 #[derive(Debug, Clone, From, TryInto, PartialEq, ConsensusState)]
 pub enum AnyConsensusState {
     Tendermint(TmConsensusState),
+    Localhost(LocalhostConsensusState),
+    SoloMachine(SoloMachineConsensusState),
     Mock(MockConsensusState),
 }
 
@@ -110,6 +139,10 @@ impl TryFrom<Any> for AnyConsensusState {
     fn try_from(raw: Any) -> Result<Self, Self::Error> {
         if raw.type_url == TENDERMINT_CONSENSUS_STATE_TYPE_URL {
             TmConsensusState::try_from(raw).map(Into::into)
+        } else if raw.type_url == LOCALHOST_CONSENSUS_STATE_TYPE_URL {
+            LocalhostConsensusState::try_from(raw).map(Into::into)
+        } else if raw.type_url == SOLOMACHINE_CONSENSUS_STATE_TYPE_URL {
+            SoloMachineConsensusState::try_from(raw).map(Into::into)
         } else if raw.type_url == MOCK_CONSENSUS_STATE_TYPE_URL {
             MockConsensusState::try_from(raw).map(Into::into)
         } else {
@@ -124,6 +157,8 @@ impl From<AnyConsensusState> for Any {
     fn from(host_consensus_state: AnyConsensusState) -> Self {
         match host_consensus_state {
             AnyConsensusState::Tendermint(cs) => cs.into(),
+            AnyConsensusState::Localhost(cs) => cs.into(),
+            AnyConsensusState::SoloMachine(cs) => cs.into(),
             AnyConsensusState::Mock(cs) => cs.into(),
         }
     }
@@ -190,6 +225,12 @@ pub struct MockIbcStore {
 
     // Used by unordered channel
     pub packet_receipt: PortChannelIdMap<BTreeMap<Sequence, Receipt>>,
+
+    /// The module holding the capability of each port that has been claimed.
+    pub port_capabilities: BTreeMap<PortId, ModuleId>,
+
+    /// The host timestamp at which a packet was last received on each channel.
+    pub last_recv_time: PortChannelIdMap<Timestamp>,
 }
 
 /// A context implementing the dependencies necessary for testing any IBC module.
@@ -216,7 +257,73 @@ pub struct MockContext {
 
     pub events: Vec<IbcEvent>,
 
+    /// The emitted events along with their sequence numbers within the block
+    /// they were emitted in.
+    pub sequenced_events: Vec<SequencedEvent>,
+
+    /// The number of events of each batch emitted with `emit_ibc_events`.
+    pub event_batches: Vec<usize>,
+
+    event_sequencer: EventSequencer,
+
     pub logs: Vec<String>,
+
+    /// The key and fields of every structured log, which are also recorded
+    /// in string form in `logs`.
+    pub structured_logs: Vec<(String, Vec<(String, String)>)>,
+
+    /// Client types that may be created; empty to allow all of them.
+    allowed_client_types: Vec<ClientType>,
+
+    /// The prefix under which the host commits its IBC state.
+    commitment_prefix: CommitmentPrefix,
+
+    /// A host timestamp reported regardless of the latest block, if any.
+    pinned_host_timestamp: Option<Timestamp>,
+
+    /// The `(granter, grantee)` pairs of accounts for which the grantee may sign
+    /// messages on behalf of the granter.
+    signer_grants: Vec<(Signer, Signer)>,
+
+    /// The `(signer, granter)` pairs of accounts for which the signer signs
+    /// the processed messages on behalf of the granter.
+    message_granters: Vec<(Signer, Signer)>,
+
+    /// Derives the identifier of a channel opened on a port from the channel
+    /// counter, if channel identifiers are not allocated sequentially.
+    channel_id_strategy: Option<fn(&PortId, u64) -> ChannelId>,
+
+    /// Whether the mock client accepts the proofs it is given.
+    proof_verification_result: bool,
+
+    /// The bech32 prefix that message signers must use, if any.
+    signer_bech32_prefix: Option<String>,
+
+    /// The telemetry that handler results are reported to, if any.
+    telemetry: Option<MockTelemetry>,
+
+    /// The hash function commitments are computed with, if not SHA256.
+    commitment_hasher: Option<MockHasher>,
+}
+
+/// A shared [`Telemetry`], kept by the mock context to be cloned with it.
+#[derive(Clone)]
+struct MockTelemetry(Arc<dyn Telemetry + Send + Sync>);
+
+impl Debug for MockTelemetry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("MockTelemetry")
+    }
+}
+
+/// A shared commitment [`Hasher`], kept by the mock context to be cloned with it.
+#[derive(Clone)]
+struct MockHasher(Arc<dyn Hasher + Send + Sync>);
+
+impl Debug for MockHasher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("MockHasher")
+    }
 }
 
 /// Returns a MockContext with bare minimum initialization: no clients, no connections and no channels are
@@ -250,7 +357,21 @@ impl Clone for MockContext {
             block_time: self.block_time,
             ibc_store,
             events: self.events.clone(),
+            sequenced_events: self.sequenced_events.clone(),
+            event_batches: self.event_batches.clone(),
+            event_sequencer: self.event_sequencer.clone(),
             logs: self.logs.clone(),
+            structured_logs: self.structured_logs.clone(),
+            allowed_client_types: self.allowed_client_types.clone(),
+            commitment_prefix: self.commitment_prefix.clone(),
+            pinned_host_timestamp: self.pinned_host_timestamp,
+            signer_grants: self.signer_grants.clone(),
+            message_granters: self.message_granters.clone(),
+            channel_id_strategy: self.channel_id_strategy,
+            proof_verification_result: self.proof_verification_result,
+            signer_bech32_prefix: self.signer_bech32_prefix.clone(),
+            telemetry: self.telemetry.clone(),
+            commitment_hasher: self.commitment_hasher.clone(),
         }
     }
 }
@@ -312,7 +433,21 @@ impl MockContext {
             block_time,
             ibc_store: Arc::new(Mutex::new(MockIbcStore::default())),
             events: Vec::new(),
+            sequenced_events: Vec::new(),
+            event_batches: Vec::new(),
+            event_sequencer: EventSequencer::new(),
             logs: Vec::new(),
+            structured_logs: Vec::new(),
+            allowed_client_types: Vec::new(),
+            commitment_prefix: CommitmentPrefix::try_from(b"mock".to_vec()).expect("Never fails"),
+            pinned_host_timestamp: None,
+            signer_grants: Vec::new(),
+            message_granters: Vec::new(),
+            channel_id_strategy: None,
+            proof_verification_result: true,
+            signer_bech32_prefix: None,
+            telemetry: None,
+            commitment_hasher: None,
         }
     }
 
@@ -379,6 +514,11 @@ impl MockContext {
 
             // Return the tuple.
             (Some(client_state), light_block.into())
+        } else if client_type.as_str() == LOCALHOST_CLIENT_TYPE {
+            (
+                Some(LocalhostClientState::new(client_chain_id, client_state_height).into()),
+                LocalhostConsensusState::new(Timestamp::now()).into(),
+            )
         } else {
             panic!("unknown client type")
         };
@@ -579,6 +719,86 @@ impl MockContext {
         }
     }
 
+    /// Restricts the client types that may be created on this context.
+    pub fn with_allowed_client_types(self, allowed_client_types: Vec<ClientType>) -> Self {
+        Self {
+            allowed_client_types,
+            ..self
+        }
+    }
+
+    /// Sets the prefix under which the host commits its IBC state.
+    pub fn with_commitment_prefix(self, commitment_prefix: CommitmentPrefix) -> Self {
+        Self {
+            commitment_prefix,
+            ..self
+        }
+    }
+
+    /// Reports the outcome of the handlers run by `execute` to `telemetry`.
+    pub fn with_telemetry(self, telemetry: Arc<dyn Telemetry + Send + Sync>) -> Self {
+        Self {
+            telemetry: Some(MockTelemetry(telemetry)),
+            ..self
+        }
+    }
+
+    /// Computes packet and acknowledgement commitments with `hasher`.
+    pub fn with_commitment_hasher(self, hasher: Arc<dyn Hasher + Send + Sync>) -> Self {
+        Self {
+            commitment_hasher: Some(MockHasher(hasher)),
+            ..self
+        }
+    }
+
+    /// Makes `host_timestamp` return `timestamp` instead of following the latest block, e.g.
+    /// to simulate a host whose timestamp is inconsistent with its consensus state.
+    pub fn with_host_timestamp(self, timestamp: Timestamp) -> Self {
+        Self {
+            pinned_host_timestamp: Some(timestamp),
+            ..self
+        }
+    }
+
+    /// Lets `grantee` sign messages on behalf of `granter`.
+    pub fn with_signer_grant(mut self, granter: Signer, grantee: Signer) -> Self {
+        self.signer_grants.push((granter, grantee));
+        self
+    }
+
+    /// Processes the messages of `signer` as signed on behalf of `granter`.
+    pub fn with_message_granter(mut self, signer: Signer, granter: Signer) -> Self {
+        self.message_granters.push((signer, granter));
+        self
+    }
+
+    /// Allocates the identifier of the channels opened on a port with
+    /// `strategy`, passing it the channel counter.
+    pub fn with_channel_id_strategy(self, strategy: fn(&PortId, u64) -> ChannelId) -> Self {
+        Self {
+            channel_id_strategy: Some(strategy),
+            ..self
+        }
+    }
+
+    /// Makes the proof verification of mock clients succeed or fail, e.g. to
+    /// exercise the proof verification failures of the handlers.
+    pub fn with_verification_result(self, result: bool) -> Self {
+        Self {
+            proof_verification_result: result,
+            ..self
+        }
+    }
+
+    /// Requires the signers of IBC messages to be bech32 addresses with the
+    /// given prefix.
+    pub fn with_signer_bech32_prefix(self, prefix: &str) -> Self {
+        Self {
+            signer_bech32_prefix: Some(prefix.to_string()),
+            ..self
+        }
+    }
+
     pub fn with_packet_commitment(
         self,
         port_id: PortId,
@@ -613,15 +833,25 @@ impl MockContext {
 
     /// Triggers the advancing of the host chain, by extending the history of blocks (or headers).
     pub fn advance_host_chain_height(&mut self) {
+        self.advance_host_time(self.block_time);
+    }
+
+    /// Advances the host chain by `n` blocks, each one `block_time` after the previous one.
+    pub fn advance_height_by(&mut self, n: u64) {
+        for _ in 0..n {
+            self.advance_host_chain_height();
+        }
+    }
+
+    /// Advances the host chain by a single block whose timestamp is `duration` past the latest
+    /// block. This moves both the host height and timestamp, e.g. to let packet timeouts elapse.
+    pub fn advance_host_time(&mut self, duration: Duration) {
         let latest_block = self.history.last().expect("history cannot be empty");
         let new_block = HostBlock::generate_block(
             self.host_chain_id.clone(),
             self.host_chain_type,
             latest_block.height().increment().revision_height(),
-            latest_block
-                .timestamp()
-                .add(self.block_time)
-                .expect("Never fails"),
+            latest_block.timestamp().add(duration).expect("Never fails"),
         );
 
         // Append the new header at the tip of the history.
@@ -760,6 +990,10 @@ impl ValidationContext for MockContext {
         if let Ok(client_state) = TmClientState::try_from(client_state.clone()) {
             client_state.validate().map_err(ClientError::from)?;
             Ok(client_state.into())
+        } else if let Ok(client_state) = LocalhostClientState::try_from(client_state.clone()) {
+            Ok(client_state.into())
+        } else if let Ok(client_state) = SoloMachineClientState::try_from(client_state.clone()) {
+            Ok(client_state.into())
         } else if let Ok(client_state) = MockClientState::try_from(client_state.clone()) {
             Ok(client_state.into())
         } else {
@@ -792,11 +1026,25 @@ impl ValidationContext for MockContext {
         .map_err(ContextError::ClientError)
     }
 
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ContextError> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .clients
+            .get(client_id)
+            .map(|client_record| client_record.consensus_states.keys().cloned().collect())
+            .unwrap_or_default())
+    }
+
     fn host_height(&self) -> Result<Height, ContextError> {
         Ok(self.latest_height())
     }
 
     fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        if let Some(timestamp) = self.pinned_host_timestamp {
+            return Ok(timestamp);
+        }
+
         Ok(self
             .history
             .last()
@@ -815,6 +1063,10 @@ impl ValidationContext for MockContext {
         .map_err(ContextError::ConnectionError)
     }
 
+    fn validate_self_consistency(&self) -> Result<(), ContextError> {
+        validate_host_timestamp_consistency(self)
+    }
+
     fn client_counter(&self) -> Result<u64, ContextError> {
         Ok(self.ibc_store.lock().client_ids_counter)
     }
@@ -875,7 +1127,7 @@ impl ValidationContext for MockContext {
     }
 
     fn commitment_prefix(&self) -> CommitmentPrefix {
-        CommitmentPrefix::try_from(b"mock".to_vec()).expect("Never fails")
+        self.commitment_prefix.clone()
     }
 
     fn connection_counter(&self) -> Result<u64, ContextError> {
@@ -968,6 +1220,41 @@ impl ValidationContext for MockContext {
         .map_err(ContextError::PacketError)
     }
 
+    fn get_next_sequences(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(Sequence, Sequence, Sequence), ContextError> {
+        let ibc_store = self.ibc_store.lock();
+        let next_sequence = |sequences: &PortChannelIdMap<Sequence>| {
+            sequences
+                .get(port_id)
+                .and_then(|map| map.get(channel_id))
+                .copied()
+        };
+
+        let send = next_sequence(&ibc_store.next_sequence_send).ok_or_else(|| {
+            PacketError::MissingNextSendSeq {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+            }
+        })?;
+        let recv = next_sequence(&ibc_store.next_sequence_recv).ok_or_else(|| {
+            PacketError::MissingNextRecvSeq {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+            }
+        })?;
+        let ack = next_sequence(&ibc_store.next_sequence_ack).ok_or_else(|| {
+            PacketError::MissingNextAckSeq {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+            }
+        })?;
+
+        Ok((send, recv, ack))
+    }
+
     fn get_packet_commitment(
         &self,
         commitment_path: &CommitmentPath,
@@ -1031,6 +1318,48 @@ impl ValidationContext for MockContext {
         .map_err(ContextError::PacketError)
     }
 
+    fn packet_commitments(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<Vec<(Sequence, PacketCommitment)>, ContextError> {
+        let ChannelEndPath(port_id, channel_id) = channel_end_path;
+
+        Ok(self
+            .ibc_store
+            .lock()
+            .packet_commitment
+            .get(port_id)
+            .and_then(|map| map.get(channel_id))
+            .map(|map| {
+                map.iter()
+                    .map(|(seq, commitment)| (*seq, commitment.clone()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn packet_acknowledgements(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: &[Sequence],
+    ) -> Result<Vec<(Sequence, AcknowledgementCommitment)>, ContextError> {
+        let ChannelEndPath(port_id, channel_id) = channel_end_path;
+
+        Ok(self
+            .ibc_store
+            .lock()
+            .packet_acknowledgement
+            .get(port_id)
+            .and_then(|map| map.get(channel_id))
+            .map(|map| {
+                map.iter()
+                    .filter(|(seq, _)| sequences.is_empty() || sequences.contains(seq))
+                    .map(|(seq, ack)| (*seq, ack.clone()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
     fn client_update_time(
         &self,
         client_id: &ClientId,
@@ -1075,17 +1404,110 @@ impl ValidationContext for MockContext {
         Ok(self.ibc_store.lock().channel_ids_counter)
     }
 
+    fn next_channel_identifier(&self, port_id: &PortId) -> Result<ChannelId, ContextError> {
+        let counter = self.channel_counter()?;
+
+        Ok(match self.channel_id_strategy {
+            Some(strategy) => strategy(port_id, counter),
+            None => ChannelId::new(counter),
+        })
+    }
+
     fn max_expected_time_per_block(&self) -> Duration {
         self.block_time
     }
 
+    fn commitment_hasher(&self) -> &dyn Hasher {
+        match &self.commitment_hasher {
+            Some(MockHasher(hasher)) => hasher.as_ref(),
+            None => &Sha256Hasher,
+        }
+    }
+
     fn validate_message_signer(&self, _signer: &Signer) -> Result<(), ContextError> {
         Ok(())
     }
 
+    fn signer_bech32_prefix(&self) -> Option<String> {
+        self.signer_bech32_prefix.clone()
+    }
+
+    fn message_granter(&self, signer: &Signer) -> Option<Signer> {
+        self.message_granters
+            .iter()
+            .find(|(grantee, _)| grantee == signer)
+            .map(|(_, granter)| granter.clone())
+    }
+
+    fn validate_signer_with_grant(
+        &self,
+        signer: &Signer,
+        granter: &Signer,
+    ) -> Result<(), ContextError> {
+        self.validate_message_signer(signer)?;
+
+        let granted = self
+            .signer_grants
+            .iter()
+            .any(|(grant_granter, grantee)| grant_granter == granter && grantee == signer);
+        if !granted {
+            return Err(ChannelError::MissingSignerGrant {
+                signer: signer.clone(),
+                granter: granter.clone(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    fn allowed_client_types(&self) -> Vec<ClientType> {
+        self.allowed_client_types.clone()
+    }
+
+    /// Ports whose capability has not been claimed are not restricted.
+    fn authenticate_capability(&self, capability: &Capability) -> Result<(), ContextError> {
+        match self
+            .ibc_store
+            .lock()
+            .port_capabilities
+            .get(capability.port_id())
+        {
+            Some(module_id) if module_id != capability.module_id() => {
+                Err(ChannelError::CapabilityNotOwned {
+                    port_id: capability.port_id().clone(),
+                    module_id: capability.module_id().clone(),
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn last_recv_time(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Option<Timestamp>, ContextError> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .last_recv_time
+            .get(port_id)
+            .and_then(|map| map.get(channel_id))
+            .copied())
+    }
+
     fn get_client_validation_context(&self) -> &Self::ClientValidationContext {
         self
     }
+
+    fn telemetry(&self) -> &dyn Telemetry {
+        match &self.telemetry {
+            Some(MockTelemetry(telemetry)) => telemetry.as_ref(),
+            None => &NoopTelemetry,
+        }
+    }
 }
 
 impl ExecutionContext for MockContext {
@@ -1093,8 +1515,11 @@ impl ExecutionContext for MockContext {
         self
     }
 
-    fn increase_client_counter(&mut self) {
-        self.ibc_store.lock().client_ids_counter += 1
+    fn increase_client_counter(&mut self) -> Result<u64, ContextError> {
+        let mut ibc_store = self.ibc_store.lock();
+        let previous = ibc_store.client_ids_counter;
+        ibc_store.client_ids_counter = previous.saturating_add(1);
+        Ok(previous)
     }
 
     fn store_update_time(
@@ -1125,6 +1550,45 @@ impl ExecutionContext for MockContext {
         Ok(())
     }
 
+    fn delete_consensus_state(
+        &mut self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(client_cons_state_path.epoch, client_cons_state_path.height)?;
+        self.ibc_store
+            .lock()
+            .clients
+            .get_mut(&client_cons_state_path.client_id)
+            .and_then(|client_record| client_record.consensus_states.remove(&height));
+        Ok(())
+    }
+
+    fn delete_update_time(
+        &mut self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<(), ContextError> {
+        let _ = self
+            .ibc_store
+            .lock()
+            .client_processed_times
+            .remove(&(client_id.clone(), *height));
+        Ok(())
+    }
+
+    fn delete_update_height(
+        &mut self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<(), ContextError> {
+        let _ = self
+            .ibc_store
+            .lock()
+            .client_processed_heights
+            .remove(&(client_id.clone(), *height));
+        Ok(())
+    }
+
     fn store_connection(
         &mut self,
         connection_path: &ConnectionPath,
@@ -1151,8 +1615,11 @@ impl ExecutionContext for MockContext {
         Ok(())
     }
 
-    fn increase_connection_counter(&mut self) {
-        self.ibc_store.lock().connection_ids_counter += 1;
+    fn increase_connection_counter(&mut self) -> Result<u64, ContextError> {
+        let mut ibc_store = self.ibc_store.lock();
+        let previous = ibc_store.connection_ids_counter;
+        ibc_store.connection_ids_counter = previous.saturating_add(1);
+        Ok(previous)
     }
 
     fn store_packet_commitment(
@@ -1307,12 +1774,69 @@ impl ExecutionContext for MockContext {
     }
 
     fn emit_ibc_event(&mut self, event: IbcEvent) {
-        self.events.push(event);
+        let sequence = self.event_sequencer.next_sequence(self.latest_height());
+        self.events.push(event.clone());
+        self.sequenced_events
+            .push(SequencedEvent { sequence, event });
+    }
+
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>) {
+        self.event_batches.push(events.len());
+        for event in events {
+            self.emit_ibc_event(event)
+        }
     }
 
     fn log_message(&mut self, message: String) {
         self.logs.push(message);
     }
+
+    fn log_structured(&mut self, key: &str, fields: &[(&str, String)]) {
+        self.structured_logs.push((
+            key.to_string(),
+            fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        ));
+        self.log_message(structured_log_string(key, fields));
+    }
+
+    fn claim_capability(
+        &mut self,
+        port_id: PortId,
+        module_id: ModuleId,
+    ) -> Result<Capability, ContextError> {
+        let mut ibc_store = self.ibc_store.lock();
+        match ibc_store.port_capabilities.get(&port_id) {
+            Some(owner) if owner != &module_id => Err(ChannelError::CapabilityAlreadyClaimed {
+                port_id,
+                module_id: owner.clone(),
+            }
+            .into()),
+            _ => {
+                ibc_store
+                    .port_capabilities
+                    .insert(port_id.clone(), module_id.clone());
+                Ok(Capability::new(port_id, module_id))
+            }
+        }
+    }
+
+    fn store_last_recv_time(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        self.ibc_store
+            .lock()
+            .last_recv_time
+            .entry(port_id.clone())
+            .or_default()
+            .insert(channel_id.clone(), timestamp);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1702,4 +2226,442 @@ mod tests {
             on_recv_packet_result("barmodule"),
         ];
     }
+
+    #[test]
+    fn test_advance_host_time_and_height() {
+        let mut ctx = MockContext::default();
+        let height = ctx.host_height().unwrap();
+        let timestamp = ctx.host_timestamp().unwrap();
+
+        ctx.advance_host_time(Duration::from_secs(3600));
+        assert_eq!(ctx.host_height().unwrap(), height.increment());
+        assert_eq!(
+            ctx.host_timestamp().unwrap(),
+            (timestamp + Duration::from_secs(3600)).unwrap()
+        );
+        assert!(ctx.host_consensus_state(&height.increment()).is_ok());
+
+        ctx.advance_height_by(3);
+        assert_eq!(ctx.host_height().unwrap(), height.add(4));
+        assert!(ctx.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_self_consistency() {
+        let ctx = MockContext::default();
+        assert!(ctx.validate_self_consistency().is_ok());
+
+        let tm_ctx = MockContext::new(
+            ChainId::new("mockgaia", 1).unwrap(),
+            HostType::SyntheticTendermint,
+            5,
+            Height::new(1, 5).unwrap(),
+        );
+        assert!(tm_ctx.validate_self_consistency().is_ok());
+
+        let host_height = ctx.host_height().unwrap();
+        let consensus_timestamp = ctx.host_consensus_state(&host_height).unwrap().timestamp();
+        let inconsistent_ctx = ctx.with_host_timestamp(Timestamp::from_nanoseconds(1).unwrap());
+
+        assert!(matches!(
+            inconsistent_ctx.validate_self_consistency(),
+            Err(ContextError::ClientError(ClientError::InconsistentHostTimestamp {
+                height,
+                consensus_timestamp: timestamp,
+                ..
+            })) if height == host_height && timestamp == consensus_timestamp
+        ));
+    }
+
+    fn event_sequences(ctx: &MockContext) -> Vec<u64> {
+        ctx.sequenced_events
+            .iter()
+            .map(|event| event.sequence)
+            .collect()
+    }
+
+    #[test]
+    fn test_event_sequences_per_block() {
+        use crate::core::events::{MessageEvent, EVENT_SEQUENCE_ATTRIBUTE_KEY};
+        use tendermint::abci;
+
+        let mut ctx = MockContext::default();
+
+        // Identical events within a block get distinct sequence numbers.
+        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel));
+        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel));
+        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel));
+        assert_eq!(event_sequences(&ctx), vec![0, 1, 2]);
+
+        ctx.advance_host_chain_height();
+        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel));
+        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel));
+        assert_eq!(event_sequences(&ctx), vec![0, 1, 2, 0, 1]);
+
+        let sequences: Vec<String> = ctx
+            .sequenced_events
+            .into_iter()
+            .map(|event| {
+                let abci_event = abci::Event::try_from(event).unwrap();
+                abci_event
+                    .attributes
+                    .into_iter()
+                    .find(|attr| attr.key == EVENT_SEQUENCE_ATTRIBUTE_KEY)
+                    .unwrap()
+                    .value
+            })
+            .collect();
+        assert_eq!(sequences, vec!["0", "1", "2", "0", "1"]);
+    }
+
+    #[test]
+    fn test_get_packet_receipt() {
+        let mut ctx = MockContext::default();
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::default();
+
+        let received_path = ReceiptPath::new(&port_id, &channel_id, 1.into());
+        ctx.store_packet_receipt(&received_path, Receipt::Ok)
+            .unwrap();
+
+        assert!(matches!(
+            ctx.get_packet_receipt(&received_path),
+            Ok(Receipt::Ok)
+        ));
+
+        let unreceived_path = ReceiptPath::new(&port_id, &channel_id, 2.into());
+        assert!(matches!(
+            ctx.get_packet_receipt(&unreceived_path),
+            Err(ContextError::PacketError(PacketError::PacketReceiptNotFound { sequence }))
+                if sequence == 2.into()
+        ));
+    }
+
+    #[test]
+    fn test_next_and_prev_consensus_state() {
+        use crate::clients::ics07_tendermint::ValidationContext as TmValidationContext;
+
+        let client_id = ClientId::default();
+        let height = |h| Height::new(0, h).expect("Never fails");
+        let consensus_states: BTreeMap<Height, AnyConsensusState> = [2, 4, 6]
+            .into_iter()
+            .map(|h| {
+                let cs = MockConsensusState::new(MockHeader::new(height(h)));
+                (height(h), cs.into())
+            })
+            .collect();
+        let consensus_state = |h| consensus_states[&height(h)].clone();
+
+        let ctx = MockContext::default();
+        ctx.ibc_store.lock().clients.insert(
+            client_id.clone(),
+            MockClientRecord {
+                client_state: Some(MockClientState::new(MockHeader::new(height(6))).into()),
+                consensus_states: consensus_states.clone(),
+            },
+        );
+
+        let next = |h| ctx.next_consensus_state(&client_id, &height(h)).unwrap();
+        let prev = |h| ctx.prev_consensus_state(&client_id, &height(h)).unwrap();
+
+        assert_eq!(next(1), Some(consensus_state(2)));
+        assert_eq!(next(2), Some(consensus_state(4)));
+        assert_eq!(next(3), Some(consensus_state(4)));
+        assert_eq!(next(6), None);
+
+        assert_eq!(prev(7), Some(consensus_state(6)));
+        assert_eq!(prev(6), Some(consensus_state(4)));
+        assert_eq!(prev(5), Some(consensus_state(4)));
+        assert_eq!(prev(2), None);
+
+        assert!(ctx
+            .next_consensus_state(&ClientId::new(mock_client_type(), 42).unwrap(), &height(1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_prune_consensus_states() {
+        let client_id = ClientId::default();
+        let height = |h| Height::new(0, h).expect("Never fails");
+        let consensus_states: BTreeMap<Height, AnyConsensusState> = [1, 2, 3, 4, 5]
+            .into_iter()
+            .map(|h| {
+                let cs = MockConsensusState::new(MockHeader::new(height(h)));
+                (height(h), cs.into())
+            })
+            .collect();
+
+        let mut ctx = MockContext::default();
+        ctx.ibc_store.lock().clients.insert(
+            client_id.clone(),
+            MockClientRecord {
+                client_state: Some(MockClientState::new(MockHeader::new(height(5))).into()),
+                consensus_states,
+            },
+        );
+        for h in [1, 2, 3, 4, 5] {
+            ctx.store_update_time(client_id.clone(), height(h), Timestamp::now())
+                .unwrap();
+            ctx.store_update_height(client_id.clone(), height(h), height(10 + h))
+                .unwrap();
+        }
+
+        assert_eq!(
+            ctx.consensus_state_heights(&client_id).unwrap(),
+            vec![height(1), height(2), height(3), height(4), height(5)]
+        );
+
+        assert_eq!(
+            ctx.prune_consensus_states(&client_id, &height(4)).unwrap(),
+            3
+        );
+        assert_eq!(
+            ctx.consensus_state_heights(&client_id).unwrap(),
+            vec![height(4), height(5)]
+        );
+        assert!(ctx
+            .consensus_state(&ClientConsensusStatePath::new(&client_id, &height(3)))
+            .is_err());
+        assert!(ctx
+            .consensus_state(&ClientConsensusStatePath::new(&client_id, &height(4)))
+            .is_ok());
+        assert!(ctx.client_update_time(&client_id, &height(3)).is_err());
+        assert!(ctx.client_update_height(&client_id, &height(3)).is_err());
+        assert!(ctx.client_update_time(&client_id, &height(4)).is_ok());
+        assert!(ctx.client_update_height(&client_id, &height(4)).is_ok());
+
+        assert_eq!(
+            ctx.prune_consensus_states(&client_id, &height(4)).unwrap(),
+            0
+        );
+
+        // The consensus state at the latest height of the client is kept,
+        // whatever the cutoff.
+        assert_eq!(
+            ctx.prune_consensus_states(&client_id, &height(10)).unwrap(),
+            1
+        );
+        assert_eq!(
+            ctx.consensus_state_heights(&client_id).unwrap(),
+            vec![height(5)]
+        );
+        assert!(ctx.client_update_time(&client_id, &height(5)).is_ok());
+    }
+
+    #[test]
+    fn test_packet_commitments_and_acknowledgements() {
+        let mut ctx = MockContext::default();
+        let port_id = PortId::transfer();
+        let chan_id = ChannelId::new(0);
+        let other_chan_id = ChannelId::new(1);
+        let chan_end_path = ChannelEndPath::new(&port_id, &chan_id);
+
+        let commitment = |seq: u64| PacketCommitment::from(vec![seq as u8]);
+        let ack = |seq: u64| AcknowledgementCommitment::from(vec![seq as u8]);
+
+        for seq in [3, 1, 2] {
+            ctx.store_packet_commitment(
+                &CommitmentPath::new(&port_id, &chan_id, seq.into()),
+                commitment(seq),
+            )
+            .unwrap();
+        }
+        for seq in [1, 3] {
+            ctx.store_packet_acknowledgement(
+                &AckPath::new(&port_id, &chan_id, seq.into()),
+                ack(seq),
+            )
+            .unwrap();
+        }
+        ctx.store_packet_commitment(
+            &CommitmentPath::new(&port_id, &other_chan_id, 4.into()),
+            commitment(4),
+        )
+        .unwrap();
+
+        assert_eq!(
+            ctx.packet_commitments(&chan_end_path).unwrap(),
+            vec![
+                (1.into(), commitment(1)),
+                (2.into(), commitment(2)),
+                (3.into(), commitment(3)),
+            ]
+        );
+        assert_eq!(
+            ctx.packet_acknowledgements(&chan_end_path, &[]).unwrap(),
+            vec![(1.into(), ack(1)), (3.into(), ack(3))]
+        );
+        assert_eq!(
+            ctx.packet_acknowledgements(&chan_end_path, &[2.into(), 3.into()])
+                .unwrap(),
+            vec![(3.into(), ack(3))]
+        );
+        assert!(ctx
+            .packet_commitments(&ChannelEndPath::new(&port_id, &ChannelId::new(2)))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_packet_enumeration_ignores_insertion_order() {
+        let port_id = PortId::transfer();
+        let chan_id = ChannelId::new(0);
+        let chan_end_path = ChannelEndPath::new(&port_id, &chan_id);
+
+        let ctx_with = |seqs: [u64; 4]| {
+            let mut ctx = MockContext::default();
+            for seq in seqs {
+                ctx.store_packet_commitment(
+                    &CommitmentPath::new(&port_id, &chan_id, seq.into()),
+                    PacketCommitment::from(vec![seq as u8]),
+                )
+                .unwrap();
+                ctx.store_packet_acknowledgement(
+                    &AckPath::new(&port_id, &chan_id, seq.into()),
+                    AcknowledgementCommitment::from(vec![seq as u8]),
+                )
+                .unwrap();
+            }
+            ctx
+        };
+
+        let ctx_a = ctx_with([1, 2, 3, 4]);
+        let ctx_b = ctx_with([4, 2, 1, 3]);
+
+        assert_eq!(
+            ctx_a.packet_commitments(&chan_end_path).unwrap(),
+            ctx_b.packet_commitments(&chan_end_path).unwrap()
+        );
+        assert_eq!(
+            ctx_a.packet_acknowledgements(&chan_end_path, &[]).unwrap(),
+            ctx_b.packet_acknowledgements(&chan_end_path, &[]).unwrap()
+        );
+        assert_eq!(
+            ctx_a
+                .packet_acknowledgements(&chan_end_path, &[3.into(), 1.into()])
+                .unwrap(),
+            ctx_b
+                .packet_acknowledgements(&chan_end_path, &[1.into(), 3.into()])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_next_sequences() {
+        let port_id = PortId::transfer();
+        let chan_id = ChannelId::new(0);
+        let seq_send_path = SeqSendPath::new(&port_id, &chan_id);
+        let seq_recv_path = SeqRecvPath::new(&port_id, &chan_id);
+        let seq_ack_path = SeqAckPath::new(&port_id, &chan_id);
+
+        let mut ctx = MockContext::default()
+            .with_send_sequence(port_id.clone(), chan_id.clone(), 1.into())
+            .with_recv_sequence(port_id.clone(), chan_id.clone(), 1.into());
+
+        assert!(matches!(
+            ctx.get_next_sequences(&port_id, &chan_id),
+            Err(ContextError::PacketError(
+                PacketError::MissingNextAckSeq { .. }
+            ))
+        ));
+
+        ctx = ctx.with_ack_sequence(port_id.clone(), chan_id.clone(), 1.into());
+        for _ in 0..3 {
+            let next_seq_send = ctx.get_next_sequence_send(&seq_send_path).unwrap();
+            ctx.store_next_sequence_send(&seq_send_path, next_seq_send.increment())
+                .unwrap();
+        }
+        let next_seq_recv = ctx.get_next_sequence_recv(&seq_recv_path).unwrap();
+        ctx.store_next_sequence_recv(&seq_recv_path, next_seq_recv.increment())
+            .unwrap();
+
+        assert_eq!(
+            ctx.get_next_sequences(&port_id, &chan_id).unwrap(),
+            (
+                ctx.get_next_sequence_send(&seq_send_path).unwrap(),
+                ctx.get_next_sequence_recv(&seq_recv_path).unwrap(),
+                ctx.get_next_sequence_ack(&seq_ack_path).unwrap(),
+            )
+        );
+        assert_eq!(
+            ctx.get_next_sequences(&port_id, &chan_id).unwrap(),
+            (4.into(), 2.into(), 1.into())
+        );
+    }
+
+    #[test]
+    fn test_diff_channel_open() {
+        use crate::applications::transfer::MODULE_ID_STR;
+        use crate::clients::ics07_tendermint::client_type as tm_client_type;
+        use crate::core::ics03_connection::connection::State as ConnectionState;
+        use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+        use crate::core::ics03_connection::version::get_compatible_versions;
+        use crate::core::ics04_channel::channel::State as ChannelState;
+        use crate::core::ics04_channel::msgs::chan_open_init::test_util::get_dummy_raw_msg_chan_open_init;
+        use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+        use crate::core::ics04_channel::msgs::ChannelMsg;
+        use crate::core::ics24_host::path::Path;
+        use crate::test_utils::DummyTransferModule;
+
+        let msg_conn_init = MsgConnectionOpenInit::new_dummy();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            msg_conn_init.client_id_on_a.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        )
+        .unwrap();
+        let ctx_before = MockContext::default()
+            .with_client(
+                &ClientId::new(tm_client_type(), 0).unwrap(),
+                Height::new(0, 10).unwrap(),
+            )
+            .with_connection(ConnectionId::default(), conn_end);
+
+        let module_id = ModuleId::new(MODULE_ID_STR.to_string());
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+        router.bind_port(PortId::default(), module_id).unwrap();
+
+        let msg = MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init(None)).unwrap();
+        let mut ctx_after = ctx_before.clone();
+        ctx_after
+            .deliver(&mut router, MsgEnvelope::Channel(ChannelMsg::OpenInit(msg)))
+            .unwrap();
+
+        assert!(ctx_before.diff(&ctx_before.clone()).is_empty());
+
+        let port_id = PortId::default();
+        let chan_id = ChannelId::new(0);
+        let opened: Vec<Path> = vec![
+            ChannelEndPath::new(&port_id, &chan_id).into(),
+            SeqSendPath::new(&port_id, &chan_id).into(),
+            SeqRecvPath::new(&port_id, &chan_id).into(),
+            SeqAckPath::new(&port_id, &chan_id).into(),
+        ];
+
+        let diff = ctx_before.diff(&ctx_after);
+        assert_eq!(diff.added, opened);
+        assert!(diff.changed.is_empty());
+        assert!(diff.removed.is_empty());
+
+        let diff = ctx_after.diff(&ctx_before);
+        assert!(diff.added.is_empty());
+        assert!(diff.changed.is_empty());
+        assert_eq!(diff.removed, opened);
+
+        let chan_end_path = ChannelEndPath::new(&port_id, &chan_id);
+        let mut ctx_open = ctx_after.clone();
+        let mut chan_end = ctx_open.channel_end(&chan_end_path).unwrap();
+        chan_end.set_state(ChannelState::Open);
+        ctx_open.store_channel(&chan_end_path, chan_end).unwrap();
+
+        let diff = ctx_after.diff(&ctx_open);
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.changed, vec![Path::from(chan_end_path)]);
+        assert!(diff.removed.is_empty());
+    }
 }
@@ -0,0 +1,106 @@
+//! Implementation of the async host context traits for the [`MockContext`],
+//! forwarding to its synchronous implementation.
+use crate::prelude::*;
+
+use async_trait::async_trait;
+
+use crate::core::events::IbcEvent;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::core::ics24_host::path::{ChannelEndPath, ClientConsensusStatePath};
+use crate::core::{
+    AsyncExecutionContext, AsyncValidationContext, ContextError, ExecutionContext,
+    ValidationContext,
+};
+use crate::mock::context::{AnyClientState, AnyConsensusState, MockContext};
+use crate::signer::Signer;
+
+#[async_trait]
+impl AsyncValidationContext for MockContext {
+    type ClientValidationContext = Self;
+    type E = Self;
+    type AnyConsensusState = AnyConsensusState;
+    type AnyClientState = AnyClientState;
+
+    fn get_client_validation_context(&self) -> &Self::ClientValidationContext {
+        self
+    }
+
+    async fn client_state(&self, client_id: &ClientId) -> Result<AnyClientState, ContextError> {
+        ValidationContext::client_state(self, client_id)
+    }
+
+    async fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<AnyConsensusState, ContextError> {
+        ValidationContext::consensus_state(self, client_cons_state_path)
+    }
+
+    async fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError> {
+        ValidationContext::connection_end(self, conn_id)
+    }
+
+    async fn channel_end(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<ChannelEnd, ContextError> {
+        ValidationContext::channel_end(self, channel_end_path)
+    }
+
+    fn max_proof_size(&self) -> usize {
+        ValidationContext::max_proof_size(self)
+    }
+
+    fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError> {
+        ValidationContext::validate_message_signer(self, signer)
+    }
+
+    fn message_granter(&self, signer: &Signer) -> Option<Signer> {
+        ValidationContext::message_granter(self, signer)
+    }
+
+    fn validate_signer_with_grant(
+        &self,
+        signer: &Signer,
+        granter: &Signer,
+    ) -> Result<(), ContextError> {
+        ValidationContext::validate_signer_with_grant(self, signer, granter)
+    }
+
+    fn signer_bech32_prefix(&self) -> Option<String> {
+        ValidationContext::signer_bech32_prefix(self)
+    }
+}
+
+#[async_trait]
+impl AsyncExecutionContext for MockContext {
+    fn get_client_execution_context(&mut self) -> &mut Self::E {
+        self
+    }
+
+    async fn store_channel(
+        &mut self,
+        channel_end_path: &ChannelEndPath,
+        channel_end: ChannelEnd,
+    ) -> Result<(), ContextError> {
+        ExecutionContext::store_channel(self, channel_end_path, channel_end)
+    }
+
+    fn emit_ibc_event(&mut self, event: IbcEvent) {
+        ExecutionContext::emit_ibc_event(self, event)
+    }
+
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>) {
+        ExecutionContext::emit_ibc_events(self, events)
+    }
+
+    fn log_message(&mut self, message: String) {
+        ExecutionContext::log_message(self, message)
+    }
+
+    fn log_structured(&mut self, key: &str, fields: &[(&str, String)]) {
+        ExecutionContext::log_structured(self, key, fields)
+    }
+}
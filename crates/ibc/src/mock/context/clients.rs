@@ -1,20 +1,34 @@
 //! Client context implementations for `MockContext`
 
+use core::ops::Bound;
+
 use crate::mock::client_state::MockClientContext;
 use crate::prelude::*;
 
 use super::{AnyClientState, AnyConsensusState, MockClientRecord, MockContext};
 use crate::clients::ics07_tendermint::CommonContext as TmCommonContext;
 use crate::clients::ics07_tendermint::ValidationContext as TmValidationContext;
+use crate::clients::ics09_localhost::ExecutionContext as LocalhostExecutionContext;
+use crate::clients::ics09_localhost::ValidationContext as LocalhostValidationContext;
+use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
 use crate::core::ics02_client::ClientExecutionContext;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::path::ClientConsensusStatePath;
 use crate::core::ics24_host::path::ClientStatePath;
+use crate::core::ics24_host::path::Path;
+use crate::core::ics24_host::path::{
+    ChannelEndPath, ConnectionPath, SeqAckPath, SeqRecvPath, SeqSendPath,
+};
 use crate::core::timestamp::Timestamp;
 use crate::core::ContextError;
 use crate::core::ValidationContext;
 use crate::Height;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
 
 impl MockClientContext for MockContext {
     type ConversionError = &'static str;
@@ -30,6 +44,10 @@ impl MockClientContext for MockContext {
     fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
         ValidationContext::host_timestamp(self)
     }
+
+    fn proof_verification_result(&self) -> bool {
+        self.proof_verification_result
+    }
 }
 
 impl TmCommonContext for MockContext {
@@ -63,24 +81,13 @@ impl TmValidationContext for MockContext {
                     client_id: client_id.clone(),
                 })?;
 
-        // Get the consensus state heights and sort them in ascending order.
-        let mut heights: Vec<Height> = client_record.consensus_states.keys().cloned().collect();
-        heights.sort();
-
-        // Search for next state.
-        for h in heights {
-            if h > *height {
-                // unwrap should never happen, as the consensus state for h must exist
-                return Ok(Some(
-                    client_record
-                        .consensus_states
-                        .get(&h)
-                        .expect("Never fails")
-                        .clone(),
-                ));
-            }
-        }
-        Ok(None)
+        // Consensus states are keyed by height, so the first entry strictly
+        // above `height` is the next state.
+        Ok(client_record
+            .consensus_states
+            .range((Bound::Excluded(*height), Bound::Unbounded))
+            .next()
+            .map(|(_, cs)| cs.clone()))
     }
 
     fn prev_consensus_state(
@@ -97,24 +104,99 @@ impl TmValidationContext for MockContext {
                     client_id: client_id.clone(),
                 })?;
 
-        // Get the consensus state heights and sort them in descending order.
-        let mut heights: Vec<Height> = client_record.consensus_states.keys().cloned().collect();
-        heights.sort_by(|a, b| b.cmp(a));
-
-        // Search for previous state.
-        for h in heights {
-            if h < *height {
-                // unwrap should never happen, as the consensus state for h must exist
-                return Ok(Some(
-                    client_record
-                        .consensus_states
-                        .get(&h)
-                        .expect("Never fails")
-                        .clone(),
-                ));
-            }
+        // Consensus states are keyed by height, so the last entry strictly
+        // below `height` is the previous state.
+        Ok(client_record
+            .consensus_states
+            .range(..*height)
+            .next_back()
+            .map(|(_, cs)| cs.clone()))
+    }
+}
+
+impl LocalhostValidationContext for MockContext {
+    fn host_commitment_prefix(&self) -> CommitmentPrefix {
+        ValidationContext::commitment_prefix(self)
+    }
+
+    fn host_commitment(&self, path: &Path) -> Result<Option<Vec<u8>>, ContextError> {
+        fn encode_sequence(sequence: &Sequence) -> Vec<u8> {
+            u64::from(*sequence).encode_to_vec()
         }
-        Ok(None)
+
+        let ibc_store = self.ibc_store.lock();
+
+        let commitment = match path {
+            Path::ClientState(ClientStatePath(client_id)) => ibc_store
+                .clients
+                .get(client_id)
+                .and_then(|record| record.client_state.clone())
+                .map(|client_state| Protobuf::<Any>::encode_vec(&client_state)),
+            Path::ClientConsensusState(path) => ibc_store
+                .clients
+                .get(&path.client_id)
+                .and_then(|record| {
+                    let height = Height::new(path.epoch, path.height).ok()?;
+                    record.consensus_states.get(&height)
+                })
+                .map(ConsensusState::encode_vec),
+            Path::Connection(ConnectionPath(conn_id)) => ibc_store
+                .connections
+                .get(conn_id)
+                .map(|conn_end| conn_end.encode_vec()),
+            Path::ChannelEnd(ChannelEndPath(port_id, chan_id)) => ibc_store
+                .channels
+                .get(port_id)
+                .and_then(|map| map.get(chan_id))
+                .map(|chan_end| chan_end.encode_vec()),
+            Path::SeqSend(SeqSendPath(port_id, chan_id)) => ibc_store
+                .next_sequence_send
+                .get(port_id)
+                .and_then(|map| map.get(chan_id))
+                .map(encode_sequence),
+            Path::SeqRecv(SeqRecvPath(port_id, chan_id)) => ibc_store
+                .next_sequence_recv
+                .get(port_id)
+                .and_then(|map| map.get(chan_id))
+                .map(encode_sequence),
+            Path::SeqAck(SeqAckPath(port_id, chan_id)) => ibc_store
+                .next_sequence_ack
+                .get(port_id)
+                .and_then(|map| map.get(chan_id))
+                .map(encode_sequence),
+            Path::Commitment(path) => ibc_store
+                .packet_commitment
+                .get(&path.port_id)
+                .and_then(|map| map.get(&path.channel_id))
+                .and_then(|map| map.get(&path.sequence))
+                .map(|commitment| commitment.clone().into_vec()),
+            Path::Ack(path) => ibc_store
+                .packet_acknowledgement
+                .get(&path.port_id)
+                .and_then(|map| map.get(&path.channel_id))
+                .and_then(|map| map.get(&path.sequence))
+                .map(|ack_commitment| ack_commitment.clone().into_vec()),
+            // Receipts carry no value; only their presence matters.
+            Path::Receipt(path) => ibc_store
+                .packet_receipt
+                .get(&path.port_id)
+                .and_then(|map| map.get(&path.channel_id))
+                .and_then(|map| map.get(&path.sequence))
+                .map(|_| vec![1]),
+            Path::ClientConnection(_) | Path::Ports(_) | Path::UpgradeClient(_) => None,
+        };
+
+        Ok(commitment)
+    }
+}
+
+impl LocalhostExecutionContext for MockContext {
+    fn host_height(&self) -> Result<Height, ContextError> {
+        ValidationContext::host_height(self)
+    }
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        ValidationContext::host_timestamp(self)
     }
 }
 
@@ -0,0 +1,161 @@
+//! Diffing of the IBC state of two `MockContext`s
+
+use alloc::collections::btree_map::BTreeMap;
+
+use crate::prelude::*;
+
+use super::{MockContext, PortChannelIdMap};
+use crate::clients::ics09_localhost::ValidationContext as LocalhostValidationContext;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::path::{
+    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentPath, ConnectionPath, Path, PortPath, ReceiptPath, SeqAckPath, SeqRecvPath,
+    SeqSendPath,
+};
+
+/// The paths of the IBC state that differ between two contexts, each in
+/// ascending order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Paths only present in the other context.
+    pub added: Vec<Path>,
+    /// Paths present in both contexts, holding different values.
+    pub changed: Vec<Path>,
+    /// Paths only present in this context.
+    pub removed: Vec<Path>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl MockContext {
+    /// Returns how the IBC state of `other` differs from the state of this
+    /// context, e.g. to inspect the effect of a simulated upgrade.
+    ///
+    /// Values are compared in their committed encoding; client connections
+    /// and port capabilities, which are not committed, are compared by
+    /// identifier.
+    pub fn diff(&self, other: &MockContext) -> StateDiff {
+        let before = self.state_snapshot();
+        let mut after = other.state_snapshot();
+        let mut diff = StateDiff::default();
+
+        for (path, value) in before {
+            match after.remove(&path) {
+                Some(other_value) if other_value != value => diff.changed.push(path),
+                Some(_) => {}
+                None => diff.removed.push(path),
+            }
+        }
+        diff.added = after.into_keys().collect();
+
+        diff
+    }
+
+    /// Returns the value stored under every path of the IBC state.
+    fn state_snapshot(&self) -> BTreeMap<Path, Vec<u8>> {
+        let (paths, mut snapshot) = {
+            let ibc_store = self.ibc_store.lock();
+            let mut paths: Vec<Path> = Vec::new();
+            let mut snapshot = BTreeMap::new();
+
+            for (client_id, record) in &ibc_store.clients {
+                if record.client_state.is_some() {
+                    paths.push(ClientStatePath::new(client_id).into());
+                }
+                paths.extend(
+                    record
+                        .consensus_states
+                        .keys()
+                        .map(|height| ClientConsensusStatePath::new(client_id, height).into()),
+                );
+            }
+            paths.extend(
+                ibc_store
+                    .connections
+                    .keys()
+                    .map(|conn_id| ConnectionPath::new(conn_id).into()),
+            );
+            paths.extend(channel_paths(&ibc_store.channels, ChannelEndPath::new));
+            paths.extend(channel_paths(
+                &ibc_store.next_sequence_send,
+                SeqSendPath::new,
+            ));
+            paths.extend(channel_paths(
+                &ibc_store.next_sequence_recv,
+                SeqRecvPath::new,
+            ));
+            paths.extend(channel_paths(&ibc_store.next_sequence_ack, SeqAckPath::new));
+            paths.extend(sequence_paths(
+                &ibc_store.packet_commitment,
+                CommitmentPath::new,
+            ));
+            paths.extend(sequence_paths(
+                &ibc_store.packet_acknowledgement,
+                AckPath::new,
+            ));
+            paths.extend(sequence_paths(&ibc_store.packet_receipt, ReceiptPath::new));
+
+            for (client_id, conn_id) in &ibc_store.client_connections {
+                snapshot.insert(
+                    ClientConnectionPath::new(client_id).into(),
+                    conn_id.as_str().as_bytes().to_vec(),
+                );
+            }
+            for (port_id, module_id) in &ibc_store.port_capabilities {
+                snapshot.insert(
+                    PortPath(port_id.clone()).into(),
+                    module_id.to_string().into_bytes(),
+                );
+            }
+
+            (paths, snapshot)
+        };
+
+        for path in paths {
+            if let Ok(Some(value)) = self.host_commitment(&path) {
+                snapshot.insert(path, value);
+            }
+        }
+
+        snapshot
+    }
+}
+
+fn channel_paths<V, P>(
+    map: &PortChannelIdMap<V>,
+    path: impl Fn(&PortId, &ChannelId) -> P,
+) -> Vec<Path>
+where
+    P: Into<Path>,
+{
+    let mut paths = Vec::new();
+    for (port_id, channels) in map {
+        for chan_id in channels.keys() {
+            paths.push(path(port_id, chan_id).into());
+        }
+    }
+    paths
+}
+
+fn sequence_paths<V, P>(
+    map: &PortChannelIdMap<BTreeMap<Sequence, V>>,
+    path: impl Fn(&PortId, &ChannelId, Sequence) -> P,
+) -> Vec<Path>
+where
+    P: Into<Path>,
+{
+    let mut paths = Vec::new();
+    for (port_id, channels) in map {
+        for (chan_id, sequences) in channels {
+            for sequence in sequences.keys() {
+                paths.push(path(port_id, chan_id, *sequence).into());
+            }
+        }
+    }
+    paths
+}
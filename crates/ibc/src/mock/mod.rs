@@ -16,3 +16,5 @@ pub mod ics18_relayer;
 pub mod misbehaviour;
 #[cfg(any(test, feature = "mocks"))]
 pub mod router;
+#[cfg(any(test, feature = "mocks"))]
+pub mod verifying;
@@ -2,13 +2,14 @@ use crate::{core::ics24_host::identifier::PortId, prelude::*};
 use alloc::{collections::BTreeMap, sync::Arc};
 
 use crate::core::router::{Module, ModuleId, Router};
+use crate::core::RouterError;
 
 #[derive(Default)]
 pub struct MockRouter {
     router: BTreeMap<ModuleId, Arc<dyn Module>>,
 
     /// Maps ports to the the module that owns it
-    pub port_to_module: BTreeMap<PortId, ModuleId>,
+    port_to_module: BTreeMap<PortId, ModuleId>,
 }
 
 impl MockRouter {
@@ -23,8 +24,26 @@ impl MockRouter {
         }
     }
 
-    pub fn scope_port_to_module(&mut self, port_id: PortId, module_id: ModuleId) {
-        self.port_to_module.insert(port_id, module_id);
+    /// Binds `port_id` to the module routed at `module_id`, so that
+    /// [`Router::lookup_module`] resolves the port to it. Fails if the module
+    /// has no route, or if the port is already bound to another module.
+    pub fn bind_port(&mut self, port_id: PortId, module_id: ModuleId) -> Result<(), RouterError> {
+        if !self.router.contains_key(&module_id) {
+            return Err(RouterError::ModuleNotFound);
+        }
+
+        match self.port_to_module.get(&port_id) {
+            Some(bound_module_id) if bound_module_id != &module_id => {
+                Err(RouterError::PortAlreadyBound {
+                    port_id,
+                    module_id: bound_module_id.clone(),
+                })
+            }
+            _ => {
+                self.port_to_module.insert(port_id, module_id);
+                Ok(())
+            }
+        }
     }
 }
 
@@ -52,3 +71,44 @@ impl Router for MockRouter {
         self.port_to_module.get(port_id).cloned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::applications::transfer::MODULE_ID_STR;
+    use crate::test_utils::DummyTransferModule;
+
+    #[test]
+    fn test_bind_port() {
+        let module_id = ModuleId::new(MODULE_ID_STR.to_string());
+        let other_module_id = ModuleId::new("othermodule".to_string());
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .add_route(other_module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+
+        assert!(matches!(
+            router.bind_port(PortId::default(), ModuleId::new("unrouted".to_string())),
+            Err(RouterError::ModuleNotFound)
+        ));
+
+        router
+            .bind_port(PortId::default(), module_id.clone())
+            .unwrap();
+        // Binding the port again to the same module is a no-op.
+        router
+            .bind_port(PortId::default(), module_id.clone())
+            .unwrap();
+
+        let res = router.bind_port(PortId::default(), other_module_id);
+        assert!(matches!(
+            res,
+            Err(RouterError::PortAlreadyBound { module_id: ref bound, .. }) if bound == &module_id
+        ));
+        assert_eq!(router.lookup_module(&PortId::default()), Some(module_id));
+    }
+}
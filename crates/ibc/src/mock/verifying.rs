@@ -0,0 +1,350 @@
+//! A context wrapper which checks stored packet commitments against the
+//! packets they were computed from.
+//!
+//! This is an auditing tool for tests: it catches a host store that corrupts
+//! or mixes up packet commitments, and is not meant to be used in production.
+
+use alloc::collections::BTreeMap;
+
+use crate::core::events::IbcEvent;
+use crate::core::forward::forward_context;
+use crate::core::ics04_channel::commitment::{compute_packet_commitment_with, PacketCommitment};
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics24_host::path::{ChannelEndPath, CommitmentPath};
+use crate::core::{ContextError, ExecutionContext, ValidationContext};
+use crate::prelude::*;
+
+/// Wraps a context and, whenever a packet commitment is read, recomputes it
+/// from the packet it was stored for and asserts that both are equal.
+///
+/// Packets are cached as they are sent through the wrapper, from their
+/// `SendPacket` events, and can also be cached explicitly with
+/// [`VerifyingContext::cache_packet`]. Commitments of packets that are not
+/// cached are returned unchecked.
+pub struct VerifyingContext<Ctx> {
+    inner: Ctx,
+    packets: BTreeMap<CommitmentPath, Packet>,
+}
+
+impl<Ctx> VerifyingContext<Ctx> {
+    pub fn new(inner: Ctx) -> Self {
+        Self {
+            inner,
+            packets: BTreeMap::new(),
+        }
+    }
+
+    /// Caches `packet`, so that reads of its commitment are verified.
+    pub fn cache_packet(&mut self, packet: Packet) {
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+        self.packets.insert(commitment_path, packet);
+    }
+
+    /// Caches the packet of `event` if it is a `SendPacket` event.
+    fn cache_sent_packet(&mut self, event: &IbcEvent) {
+        if let IbcEvent::SendPacket(send_packet) = event {
+            self.cache_packet(Packet {
+                seq_on_a: *send_packet.seq_on_a(),
+                port_id_on_a: send_packet.port_id_on_a().clone(),
+                chan_id_on_a: send_packet.chan_id_on_a().clone(),
+                port_id_on_b: send_packet.port_id_on_b().clone(),
+                chan_id_on_b: send_packet.chan_id_on_b().clone(),
+                data: send_packet.packet_data().to_vec(),
+                timeout_height_on_b: *send_packet.timeout_height_on_b(),
+                timeout_timestamp_on_b: *send_packet.timeout_timestamp_on_b(),
+            });
+        }
+    }
+
+    pub fn inner(&self) -> &Ctx {
+        &self.inner
+    }
+
+    /// Gives direct access to the wrapped context, e.g. to tamper with its
+    /// store. Changes made through it are not verified.
+    pub fn inner_mut(&mut self) -> &mut Ctx {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> Ctx {
+        self.inner
+    }
+}
+
+impl<Ctx> VerifyingContext<Ctx>
+where
+    Ctx: ValidationContext,
+{
+    /// Panics if `commitment` differs from the commitment of the packet
+    /// cached for `commitment_path`.
+    fn verify_commitment(&self, commitment_path: &CommitmentPath, commitment: &PacketCommitment) {
+        if let Some(packet) = self.packets.get(commitment_path) {
+            let expected_commitment = compute_packet_commitment_with(
+                self.inner.commitment_hasher(),
+                &packet.data,
+                &packet.timeout_height_on_b,
+                &packet.timeout_timestamp_on_b,
+            );
+            assert_eq!(
+                commitment, &expected_commitment,
+                "stored packet commitment at `{commitment_path}` does not match its packet"
+            );
+        }
+    }
+}
+
+impl<Ctx> ValidationContext for VerifyingContext<Ctx>
+where
+    Ctx: ValidationContext,
+{
+    type ClientValidationContext = Ctx::ClientValidationContext;
+    type E = Ctx::E;
+    type AnyConsensusState = Ctx::AnyConsensusState;
+    type AnyClientState = Ctx::AnyClientState;
+
+    forward_context!(
+        inner;
+        get_client_validation_context,
+        client_state,
+        decode_client_state,
+        consensus_state,
+        consensus_state_heights,
+        client_update_time,
+        client_update_height,
+        host_height,
+        host_timestamp,
+        host_consensus_state,
+        validate_self_consistency,
+        client_counter,
+        connection_end,
+        validate_self_client,
+        commitment_prefix,
+        connection_counter,
+        get_compatible_versions,
+        pick_version,
+        channel_end,
+        get_next_sequence_send,
+        get_next_sequence_recv,
+        get_next_sequence_ack,
+        get_next_sequences,
+        get_packet_receipt,
+        get_packet_acknowledgement,
+        packet_acknowledgements,
+        channel_counter,
+        next_channel_identifier,
+        max_expected_time_per_block,
+        max_packet_data_size,
+        max_proof_size,
+        commitment_hasher,
+        block_delay,
+        validate_message_signer,
+        signer_bech32_prefix,
+        message_granter,
+        validate_signer_with_grant,
+        allowed_client_types,
+        authenticate_capability,
+        last_recv_time,
+        telemetry,
+    );
+
+    fn get_packet_commitment(
+        &self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<PacketCommitment, ContextError> {
+        let commitment = self.inner.get_packet_commitment(commitment_path)?;
+        self.verify_commitment(commitment_path, &commitment);
+        Ok(commitment)
+    }
+
+    fn packet_commitments(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<Vec<(Sequence, PacketCommitment)>, ContextError> {
+        let ChannelEndPath(port_id, channel_id) = channel_end_path;
+        let commitments = self.inner.packet_commitments(channel_end_path)?;
+        for (sequence, commitment) in commitments.iter() {
+            let commitment_path = CommitmentPath::new(port_id, channel_id, *sequence);
+            self.verify_commitment(&commitment_path, commitment);
+        }
+        Ok(commitments)
+    }
+}
+
+impl<Ctx> ExecutionContext for VerifyingContext<Ctx>
+where
+    Ctx: ExecutionContext,
+{
+    forward_context!(
+        inner;
+        get_client_execution_context,
+        increase_client_counter,
+        store_update_time,
+        store_update_height,
+        delete_consensus_state,
+        delete_update_time,
+        delete_update_height,
+        prune_consensus_states,
+        store_connection,
+        store_connection_to_client,
+        increase_connection_counter,
+        store_packet_commitment,
+        store_packet_receipt,
+        store_packet_acknowledgement,
+        delete_packet_acknowledgement,
+        store_channel,
+        store_next_sequence_send,
+        store_next_sequence_recv,
+        store_next_sequence_ack,
+        increase_channel_counter,
+        generate_channel_identifier,
+        log_message,
+        log_structured,
+        claim_capability,
+        store_last_recv_time,
+    );
+
+    fn delete_packet_commitment(
+        &mut self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<(), ContextError> {
+        self.packets.remove(commitment_path);
+        self.inner.delete_packet_commitment(commitment_path)
+    }
+
+    fn emit_ibc_event(&mut self, event: IbcEvent) {
+        self.cache_sent_packet(&event);
+        self.inner.emit_ibc_event(event)
+    }
+
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>) {
+        for event in events.iter() {
+            self.cache_sent_packet(event);
+        }
+        self.inner.emit_ibc_events(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::ops::Add;
+    use core::time::Duration;
+
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::handler::send_packet::send_packet;
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::timestamp::Timestamp;
+    use crate::core::timestamp::ZERO_DURATION;
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    /// Sends a packet through a `VerifyingContext`, returning the context
+    /// along with the path of the stored commitment.
+    fn send_verified_packet() -> (VerifyingContext<MockContext>, CommitmentPath) {
+        let chan_end_on_a = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::new("ics20-1".to_string()),
+        )
+        .unwrap();
+        let conn_end_on_a = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+
+        let ctx = MockContext::default()
+            .with_client(&ClientId::default(), Height::new(0, 5).unwrap())
+            .with_connection(ConnectionId::default(), conn_end_on_a)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end_on_a)
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into());
+
+        let timestamp_future = Timestamp::now().add(Duration::from_secs(10)).unwrap();
+        let mut packet: Packet = get_dummy_raw_packet(10, timestamp_future.nanoseconds())
+            .try_into()
+            .unwrap();
+        packet.seq_on_a = 1.into();
+        packet.data = vec![0];
+
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+
+        let mut ctx = VerifyingContext::new(ctx);
+        send_packet(&mut ctx, packet).unwrap();
+
+        (ctx, commitment_path)
+    }
+
+    #[test]
+    fn verifying_context_reads_intact_commitment() {
+        let (ctx, commitment_path) = send_verified_packet();
+
+        let commitment = ctx.get_packet_commitment(&commitment_path).unwrap();
+        assert_eq!(
+            commitment,
+            ctx.inner().get_packet_commitment(&commitment_path).unwrap()
+        );
+
+        assert!(ctx
+            .packet_commitments(&ChannelEndPath::new(
+                &commitment_path.port_id,
+                &commitment_path.channel_id,
+            ))
+            .is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match its packet")]
+    fn verifying_context_detects_corrupted_commitment() {
+        let (mut ctx, commitment_path) = send_verified_packet();
+
+        ctx.inner_mut()
+            .store_packet_commitment(&commitment_path, vec![0xff].into())
+            .unwrap();
+
+        let _ = ctx.get_packet_commitment(&commitment_path);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match its packet")]
+    fn verifying_context_detects_corrupted_commitment_in_channel_listing() {
+        let (mut ctx, commitment_path) = send_verified_packet();
+
+        ctx.inner_mut()
+            .store_packet_commitment(&commitment_path, vec![0xff].into())
+            .unwrap();
+
+        let _ = ctx.packet_commitments(&ChannelEndPath::new(
+            &commitment_path.port_id,
+            &commitment_path.channel_id,
+        ));
+    }
+
+    #[test]
+    fn verifying_context_forwards_event_batches() {
+        let (mut ctx, _) = send_verified_packet();
+        let events = ctx.inner().events.clone();
+
+        ctx.emit_ibc_events(events.clone());
+
+        assert_eq!(ctx.inner().event_batches, vec![events.len()]);
+        assert_eq!(ctx.inner().events[events.len()..], events[..]);
+    }
+}
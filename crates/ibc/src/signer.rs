@@ -1,6 +1,22 @@
 use crate::prelude::*;
 
 use derive_more::Display;
+use subtle_encoding::bech32;
+
+#[derive(displaydoc::Display, Debug)]
+pub enum SignerError {
+    /// signer `{signer}` is not a valid bech32 address
+    InvalidBech32 { signer: Signer },
+    /// signer `{signer}` has bech32 prefix `{actual}` instead of `{expected}`
+    WrongBech32Prefix {
+        signer: Signer,
+        expected: String,
+        actual: String,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignerError {}
 
 /// Represents the address of the signer of the current transaction
 #[cfg_attr(
@@ -20,6 +36,25 @@ use derive_more::Display;
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
 pub struct Signer(String);
 
+impl Signer {
+    /// Checks that the signer is a valid bech32 address with the
+    /// `expected_prefix` human-readable part.
+    pub fn validate_bech32(&self, expected_prefix: &str) -> Result<(), SignerError> {
+        let (prefix, _) = bech32::decode(&self.0).map_err(|_| SignerError::InvalidBech32 {
+            signer: self.clone(),
+        })?;
+        if prefix != expected_prefix {
+            return Err(SignerError::WrongBech32Prefix {
+                signer: self.clone(),
+                expected: expected_prefix.to_string(),
+                actual: prefix,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 impl From<String> for Signer {
     fn from(s: String) -> Self {
         Self(s)
@@ -31,3 +66,25 @@ impl AsRef<str> for Signer {
         self.0.as_str()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_bech32() {
+        let signer = Signer::from("cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng".to_string());
+        assert!(signer.validate_bech32("cosmos").is_ok());
+
+        assert!(matches!(
+            signer.validate_bech32("osmo"),
+            Err(SignerError::WrongBech32Prefix { actual, .. }) if actual == "cosmos"
+        ));
+
+        let garbage = Signer::from("not-a-bech32-address".to_string());
+        assert!(matches!(
+            garbage.validate_bech32("cosmos"),
+            Err(SignerError::InvalidBech32 { .. })
+        ));
+    }
+}
@@ -1,13 +1,16 @@
-use subtle_encoding::bech32;
 use tendermint::{block, consensus, evidence, public_key::Algorithm};
 
 use crate::applications::transfer::context::{
-    cosmos_adr028_escrow_address, TokenTransferExecutionContext, TokenTransferValidationContext,
+    get_escrow_address, TokenTransferExecutionContext, TokenTransferValidationContext,
 };
 use crate::applications::transfer::error::TokenTransferError;
-use crate::applications::transfer::PrefixedCoin;
+use crate::applications::transfer::{
+    Amount, DenomMetadata, EscrowConservationContext, PrefixedCoin, PrefixedDenom, VERSION,
+};
+use crate::core::events::ModuleEvent;
 use crate::core::ics04_channel::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::channel::{Counterparty, Order};
+use crate::core::ics04_channel::commitment::{Hasher, Sha256Hasher};
 use crate::core::ics04_channel::error::{ChannelError, PacketError};
 use crate::core::ics04_channel::packet::Packet;
 use crate::core::ics04_channel::Version;
@@ -15,6 +18,7 @@ use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::core::router::{Module, ModuleExtras};
 use crate::prelude::*;
 use crate::signer::Signer;
+use alloc::collections::BTreeMap;
 
 // Needed in mocks.
 pub fn default_consensus_params() -> consensus::Params {
@@ -53,14 +57,134 @@ pub fn get_dummy_bech32_account() -> String {
 }
 
 pub fn get_dummy_transfer_module() -> DummyTransferModule {
-    DummyTransferModule
+    DummyTransferModule::new()
 }
 #[derive(Debug)]
-pub struct DummyTransferModule;
+pub struct DummyTransferModule {
+    supported_versions: Vec<Version>,
+    escrow_balances: BTreeMap<(PortId, ChannelId, PrefixedDenom), Amount>,
+    denom_metadata: BTreeMap<PrefixedDenom, DenomMetadata>,
+    accepts_packets: bool,
+    channel_versions: BTreeMap<(PortId, ChannelId), Version>,
+    minted_balances: BTreeMap<(Signer, PrefixedDenom), Amount>,
+    sent_balances: BTreeMap<(Signer, PrefixedDenom), Amount>,
+    funded_balances: BTreeMap<(Signer, PrefixedDenom), Amount>,
+    denom_traces: BTreeMap<String, PrefixedDenom>,
+}
 
 impl DummyTransferModule {
     pub fn new() -> Self {
-        Self
+        Self {
+            supported_versions: vec![Version::new(VERSION.to_string())],
+            escrow_balances: BTreeMap::new(),
+            denom_metadata: BTreeMap::new(),
+            accepts_packets: true,
+            channel_versions: BTreeMap::new(),
+            minted_balances: BTreeMap::new(),
+            sent_balances: BTreeMap::new(),
+            funded_balances: BTreeMap::new(),
+            denom_traces: BTreeMap::new(),
+        }
+    }
+
+    /// Funds `account` with `coin`, which it may then send. Accounts that are
+    /// not funded with a denomination may send any amount of it.
+    pub fn with_funds(mut self, account: Signer, coin: PrefixedCoin) -> Self {
+        self.funded_balances
+            .insert((account, coin.denom), coin.amount);
+        self
+    }
+
+    /// Registers the denomination trace `denom` under its hash.
+    pub fn with_denom_trace(mut self, denom: PrefixedDenom) -> Self {
+        self.denom_traces.insert(denom_hash(&denom), denom);
+        self
+    }
+
+    /// Replaces the transfer versions this module negotiates with.
+    pub fn with_supported_versions(self, supported_versions: Vec<Version>) -> Self {
+        Self {
+            supported_versions,
+            ..self
+        }
+    }
+
+    /// Sets the amount of `denom` held in escrow for the given channel.
+    pub fn with_escrow_balance(
+        mut self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        denom: PrefixedDenom,
+        amount: Amount,
+    ) -> Self {
+        self.escrow_balances
+            .insert((port_id, channel_id, denom), amount);
+        self
+    }
+
+    /// Sets the version negotiated for the given channel, which determines the
+    /// encoding of the packet data sent and received on it. Channels default
+    /// to `ics20-1`.
+    pub fn with_channel_version(
+        mut self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        version: Version,
+    ) -> Self {
+        self.channel_versions.insert((port_id, channel_id), version);
+        self
+    }
+
+    /// Sets whether this module accepts newly received packets.
+    pub fn with_accepts_packets(self, accepts_packets: bool) -> Self {
+        Self {
+            accepts_packets,
+            ..self
+        }
+    }
+
+    /// Returns the amount of `denom` minted to `account` and not burned since.
+    pub fn minted_balance(&self, account: &Signer, denom: &PrefixedDenom) -> Amount {
+        self.minted_balances
+            .get(&(account.clone(), denom.clone()))
+            .copied()
+            .unwrap_or_else(|| 0u64.into())
+    }
+
+    /// Returns the funded balance of `account` once `coin` is sent, if it is
+    /// funded with the denomination of `coin`, failing if the account holds
+    /// less than `coin`.
+    fn funds_after_send(
+        &self,
+        account: &Signer,
+        coin: &PrefixedCoin,
+    ) -> Result<Option<Amount>, TokenTransferError> {
+        self.funded_balances
+            .get(&(account.clone(), coin.denom.clone()))
+            .map(|funds| {
+                funds.checked_sub(coin.amount).ok_or_else(|| {
+                    TokenTransferError::InsufficientFunds {
+                        denom: coin.denom.clone(),
+                        amount: coin.amount,
+                    }
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns the minted balance of `account` once `coin` is burned, failing
+    /// if the account holds less than `coin`.
+    fn balance_after_burn(
+        &self,
+        account: &Signer,
+        coin: &PrefixedCoin,
+    ) -> Result<Amount, TokenTransferError> {
+        self.minted_balance(account, &coin.denom)
+            .checked_sub(coin.amount)
+            .ok_or_else(|| TokenTransferError::InsufficientFunds {
+                denom: coin.denom.clone(),
+                amount: coin.amount,
+            })
     }
 }
 
@@ -71,6 +195,10 @@ impl Default for DummyTransferModule {
 }
 
 impl Module for DummyTransferModule {
+    fn accepts_packets(&self) -> bool {
+        self.accepts_packets
+    }
+
     fn on_chan_open_init_validate(
         &self,
         _order: Order,
@@ -177,8 +305,27 @@ impl TokenTransferValidationContext for DummyTransferModule {
         port_id: &PortId,
         channel_id: &ChannelId,
     ) -> Result<Self::AccountId, TokenTransferError> {
-        let addr = cosmos_adr028_escrow_address(port_id, channel_id);
-        Ok(bech32::encode("cosmos", addr).into())
+        Ok(get_escrow_address("cosmos", port_id, channel_id))
+    }
+
+    fn get_escrow_balance(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        denom: &PrefixedDenom,
+    ) -> Result<Amount, TokenTransferError> {
+        Ok(self
+            .escrow_balances
+            .get(&(port_id.clone(), channel_id.clone(), denom.clone()))
+            .copied()
+            .unwrap_or_else(|| 0u64.into()))
+    }
+
+    fn get_denom_metadata(
+        &self,
+        denom: &PrefixedDenom,
+    ) -> Result<Option<DenomMetadata>, TokenTransferError> {
+        Ok(self.denom_metadata.get(denom).cloned())
     }
 
     fn can_send_coins(&self) -> Result<(), TokenTransferError> {
@@ -191,11 +338,11 @@ impl TokenTransferValidationContext for DummyTransferModule {
 
     fn send_coins_validate(
         &self,
-        _from_account: &Self::AccountId,
+        from_account: &Self::AccountId,
         _to_account: &Self::AccountId,
-        _coin: &PrefixedCoin,
+        coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError> {
-        Ok(())
+        self.funds_after_send(from_account, coin).map(|_| ())
     }
 
     fn mint_coins_validate(
@@ -208,36 +355,569 @@ impl TokenTransferValidationContext for DummyTransferModule {
 
     fn burn_coins_validate(
         &self,
-        _account: &Self::AccountId,
-        _coin: &PrefixedCoin,
+        account: &Self::AccountId,
+        coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError> {
-        Ok(())
+        self.balance_after_burn(account, coin).map(|_| ())
+    }
+
+    fn denom_hash_string(&self, denom: &PrefixedDenom) -> Option<String> {
+        Some(denom_hash(denom))
+    }
+
+    fn denom_trace(&self, denom_hash: &str) -> Option<PrefixedDenom> {
+        self.denom_traces.get(denom_hash).cloned()
+    }
+
+    fn denom_traces(&self) -> Vec<PrefixedDenom> {
+        self.denom_traces.values().cloned().collect()
+    }
+
+    fn escrowed_denoms(&self, port_id: &PortId, channel_id: &ChannelId) -> Vec<PrefixedDenom> {
+        self.escrow_balances
+            .iter()
+            .filter(|((port, channel, _), amount)| {
+                port == port_id && channel == channel_id && !amount.is_zero()
+            })
+            .map(|((_, _, denom), _)| denom.clone())
+            .collect()
+    }
+
+    fn supported_versions(&self) -> Vec<Version> {
+        self.supported_versions.clone()
+    }
+
+    fn channel_version(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Version, TokenTransferError> {
+        Ok(self
+            .channel_versions
+            .get(&(port_id.clone(), channel_id.clone()))
+            .cloned()
+            .unwrap_or_else(|| Version::new(VERSION.to_string())))
+    }
+}
+
+/// Only the channels with an escrow balance stored are checked, as escrow
+/// accounts cannot be mapped back to their channel.
+impl EscrowConservationContext for DummyTransferModule {
+    fn escrow_channels(&self) -> Vec<(PortId, ChannelId)> {
+        let mut channels: Vec<(PortId, ChannelId)> = self
+            .escrow_balances
+            .keys()
+            .map(|(port_id, channel_id, _)| (port_id.clone(), channel_id.clone()))
+            .collect();
+        channels.dedup();
+        channels
+    }
+
+    fn account_coins(&self, account: &Self::AccountId) -> Vec<PrefixedCoin> {
+        self.sent_balances
+            .iter()
+            .filter(|((holder, _), _)| holder == account)
+            .map(|((_, denom), amount)| PrefixedCoin {
+                denom: denom.clone(),
+                amount: *amount,
+            })
+            .collect()
     }
 }
 
 impl TokenTransferExecutionContext for DummyTransferModule {
+    /// Only the accounts funded with [`DummyTransferModule::with_funds`] are
+    /// debited, while the coins received are tracked for every account.
     fn send_coins_execute(
         &mut self,
-        _from_account: &Self::AccountId,
-        _to_account: &Self::AccountId,
-        _coin: &PrefixedCoin,
+        from_account: &Self::AccountId,
+        to_account: &Self::AccountId,
+        coin: &PrefixedCoin,
+    ) -> Result<(), TokenTransferError> {
+        if let Some(funds) = self.funds_after_send(from_account, coin)? {
+            self.funded_balances
+                .insert((from_account.clone(), coin.denom.clone()), funds);
+        }
+
+        let balance = self
+            .sent_balances
+            .entry((to_account.clone(), coin.denom.clone()))
+            .or_insert_with(|| 0u64.into());
+        *balance = balance
+            .as_ref()
+            .saturating_add(*coin.amount.as_ref())
+            .into();
+        Ok(())
+    }
+
+    fn store_escrow_balance(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        denom: &PrefixedDenom,
+        amount: Amount,
     ) -> Result<(), TokenTransferError> {
+        self.escrow_balances
+            .insert((port_id.clone(), channel_id.clone(), denom.clone()), amount);
+        Ok(())
+    }
+
+    fn store_denom_metadata(&mut self, metadata: DenomMetadata) -> Result<(), TokenTransferError> {
+        self.denom_metadata.insert(metadata.base.clone(), metadata);
         Ok(())
     }
 
     fn mint_coins_execute(
         &mut self,
-        _account: &Self::AccountId,
-        _coin: &PrefixedCoin,
+        account: &Self::AccountId,
+        coin: &PrefixedCoin,
     ) -> Result<(), TokenTransferError> {
+        let balance = self.minted_balance(account, &coin.denom);
+        self.minted_balances.insert(
+            (account.clone(), coin.denom.clone()),
+            balance
+                .as_ref()
+                .saturating_add(*coin.amount.as_ref())
+                .into(),
+        );
         Ok(())
     }
 
     fn burn_coins_execute(
         &mut self,
-        _account: &Self::AccountId,
-        _coin: &PrefixedCoin,
+        account: &Self::AccountId,
+        coin: &PrefixedCoin,
+    ) -> Result<(), TokenTransferError> {
+        let balance = self.balance_after_burn(account, coin)?;
+        self.minted_balances
+            .insert((account.clone(), coin.denom.clone()), balance);
+        Ok(())
+    }
+
+    fn store_denom_trace(&mut self, denom: PrefixedDenom) -> Result<(), TokenTransferError> {
+        self.denom_traces.insert(denom_hash(&denom), denom);
+        Ok(())
+    }
+
+    fn delete_denom_trace(&mut self, denom: &PrefixedDenom) -> Result<(), TokenTransferError> {
+        self.denom_traces.remove(&denom_hash(denom));
+        Ok(())
+    }
+
+    fn migrate_denom_balances(
+        &mut self,
+        old_denom: &PrefixedDenom,
+        new_denom: &PrefixedDenom,
     ) -> Result<(), TokenTransferError> {
+        let accounts: Vec<Signer> = self
+            .minted_balances
+            .keys()
+            .filter(|(_, denom)| denom == old_denom)
+            .map(|(account, _)| account.clone())
+            .collect();
+        for account in accounts {
+            if let Some(amount) = self
+                .minted_balances
+                .remove(&(account.clone(), old_denom.clone()))
+            {
+                self.minted_balances
+                    .insert((account, new_denom.clone()), amount);
+            }
+        }
         Ok(())
     }
 }
+
+/// Returns the hash under which the SDK registers the trace of `denom`, i.e.
+/// the upper-case hex encoded SHA256 digest of its full path.
+fn denom_hash(denom: &PrefixedDenom) -> String {
+    use sha2::Digest;
+
+    let digest = sha2::Sha256::digest(denom.to_string().as_bytes());
+    String::from_utf8(subtle_encoding::hex::encode_upper(digest))
+        .expect("hex encoding is always valid UTF-8")
+}
+
+/// A module callback executed by a [`RecordingModule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallbackKind {
+    OpenInit,
+    OpenTry,
+    OpenAck,
+    OpenConfirm,
+    CloseInit,
+    CloseConfirm,
+    RecvPacket,
+    AcknowledgementPacket,
+    TimeoutPacket,
+}
+
+/// Hashes with SHA256, then reverses the digest, to tell its commitments
+/// apart from the default ones.
+pub struct ReversedSha256Hasher;
+
+impl Hasher for ReversedSha256Hasher {
+    fn hash(&self, data: &[u8]) -> Vec<u8> {
+        let mut digest = Sha256Hasher.hash(data);
+        digest.reverse();
+        digest
+    }
+}
+
+/// A module that negotiates [`UPGRADED_VERSION`] instead of the version it is
+/// proposed, during both `ChanOpenInit` and `ChanOpenTry`.
+#[derive(Debug)]
+pub struct UpgradingVersionModule;
+
+pub const UPGRADED_VERSION: &str = "ics20-2";
+
+impl Module for UpgradingVersionModule {
+    fn on_chan_open_init_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Ok(Version::new(UPGRADED_VERSION.to_string()))
+    }
+
+    fn on_chan_open_init_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Ok((
+            ModuleExtras::empty(),
+            Version::new(UPGRADED_VERSION.to_string()),
+        ))
+    }
+
+    fn on_chan_open_try_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Ok(Version::new(UPGRADED_VERSION.to_string()))
+    }
+
+    fn on_chan_open_try_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        _counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Ok((
+            ModuleExtras::empty(),
+            Version::new(UPGRADED_VERSION.to_string()),
+        ))
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        (
+            ModuleExtras::empty(),
+            Acknowledgement::try_from(vec![1u8]).expect("Never fails"),
+        )
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (ModuleExtras::empty(), Ok(()))
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        (ModuleExtras::empty(), Ok(()))
+    }
+}
+
+/// Wraps a module and records the execute callbacks invoked on it, in order,
+/// while delegating all callbacks to the wrapped module.
+#[derive(Debug)]
+pub struct RecordingModule<M: Module> {
+    inner: M,
+    callbacks: Vec<CallbackKind>,
+    name: Option<&'static str>,
+}
+
+impl<M: Module> RecordingModule<M> {
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            callbacks: Vec::new(),
+            name: None,
+        }
+    }
+
+    /// Makes the module merge an event and a log entry named `name` after the
+    /// extras of the wrapped module when receiving a packet, like a
+    /// middleware would.
+    pub fn named(self, name: &'static str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    /// Returns the execute callbacks invoked so far, in order.
+    pub fn callbacks(&self) -> &[CallbackKind] {
+        &self.callbacks
+    }
+}
+
+impl<M: Module> Module for RecordingModule<M> {
+    fn on_chan_open_init_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        self.inner.on_chan_open_init_validate(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+        )
+    }
+
+    fn on_chan_open_init_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        self.callbacks.push(CallbackKind::OpenInit);
+        self.inner.on_chan_open_init_execute(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+        )
+    }
+
+    fn on_chan_open_try_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        self.inner.on_chan_open_try_validate(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            counterparty_version,
+        )
+    }
+
+    fn on_chan_open_try_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        self.callbacks.push(CallbackKind::OpenTry);
+        self.inner.on_chan_open_try_execute(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            counterparty_version,
+        )
+    }
+
+    fn on_chan_open_ack_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        self.inner
+            .on_chan_open_ack_validate(port_id, channel_id, counterparty_version)
+    }
+
+    fn on_chan_open_ack_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.callbacks.push(CallbackKind::OpenAck);
+        self.inner
+            .on_chan_open_ack_execute(port_id, channel_id, counterparty_version)
+    }
+
+    fn on_chan_open_confirm_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.inner
+            .on_chan_open_confirm_validate(port_id, channel_id)
+    }
+
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.callbacks.push(CallbackKind::OpenConfirm);
+        self.inner.on_chan_open_confirm_execute(port_id, channel_id)
+    }
+
+    fn on_chan_close_init_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.inner.on_chan_close_init_validate(port_id, channel_id)
+    }
+
+    fn on_chan_close_init_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.callbacks.push(CallbackKind::CloseInit);
+        self.inner.on_chan_close_init_execute(port_id, channel_id)
+    }
+
+    fn on_chan_close_confirm_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.inner
+            .on_chan_close_confirm_validate(port_id, channel_id)
+    }
+
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.callbacks.push(CallbackKind::CloseConfirm);
+        self.inner
+            .on_chan_close_confirm_execute(port_id, channel_id)
+    }
+
+    fn accepts_packets(&self) -> bool {
+        self.inner.accepts_packets()
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        self.callbacks.push(CallbackKind::RecvPacket);
+        let (mut extras, acknowledgement) = self.inner.on_recv_packet_execute(packet, relayer);
+
+        if let Some(name) = self.name {
+            extras.events.push(ModuleEvent {
+                kind: name.to_string(),
+                attributes: vec![],
+            });
+            extras.log.push(name.to_string());
+        }
+
+        (extras, acknowledgement)
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        self.inner
+            .on_acknowledgement_packet_validate(packet, acknowledgement, relayer)
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        self.callbacks.push(CallbackKind::AcknowledgementPacket);
+        self.inner
+            .on_acknowledgement_packet_execute(packet, acknowledgement, relayer)
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        self.inner.on_timeout_packet_validate(packet, relayer)
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        self.callbacks.push(CallbackKind::TimeoutPacket);
+        self.inner.on_timeout_packet_execute(packet, relayer)
+    }
+}